@@ -6,7 +6,7 @@ use tokio::sync::mpsc;
 use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
-use engine::types::SearchLimit;
+use engine::prelude::SearchLimit;
 
 use crate::engine::EngineManager;
 
@@ -19,7 +19,11 @@ struct ClientMessage {
     #[serde(default)]
     fen: String,
     #[serde(default)]
+    moves: Vec<String>,
+    #[serde(default)]
     limit: Option<SearchLimit>,
+    #[serde(default, rename = "searchMoves")]
+    search_moves: Option<Vec<String>>,
     #[serde(default)]
     uci_move: String,
 }
@@ -108,10 +112,13 @@ async fn handle_client_message(
             let fen = msg.fen;
             let limit = msg.limit.ok_or_else(|| anyhow::anyhow!("Missing limit"))?;
 
-            info!("Analyzing position: {} with limit {:?}", fen, limit);
+            info!(
+                "Analyzing position: {} moves {:?} with limit {:?}",
+                fen, msg.moves, limit
+            );
 
             // Analyze position
-            engine.analyze(id, fen, limit, tx)?;
+            engine.analyze(id, fen, msg.moves, limit, msg.search_moves, tx)?;
         }
         "stop" => {
             info!("Stopping analysis: {}", msg.id);
@@ -1,17 +1,15 @@
 use anyhow::Result;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
-use engine::EngineImpl;
-use engine::types::{BestMove, EngineOptions, SearchInfo, SearchLimit};
+use engine::prelude::{BestMove, EngineImpl, EngineOptions, SearchInfo, SearchLimit, StopHandle};
 
 use crate::connection::ServerMessage;
 
 pub struct EngineManager {
     engine: Arc<Mutex<EngineImpl>>,
-    stop_flag: Arc<AtomicBool>,
+    current_stop: Option<StopHandle>,
     _tx: mpsc::UnboundedSender<ServerMessage>,
     current_id: Option<String>,
 }
@@ -26,14 +24,13 @@ impl EngineManager {
             skill_level: None,
             multi_pv: None,
             use_tablebases: None,
+            chess960: None,
         };
-        let engine_impl = EngineImpl::new_with(opts);
-        let stop_flag = engine_impl.stop_flag();
-        let engine = Arc::new(Mutex::new(engine_impl));
+        let engine = Arc::new(Mutex::new(EngineImpl::new_with(opts)));
 
         Self {
             engine,
-            stop_flag,
+            current_stop: None,
             _tx: tx,
             current_id: None,
         }
@@ -43,12 +40,23 @@ impl EngineManager {
         &mut self,
         id: String,
         fen: String,
+        moves: Vec<String>,
         limit: SearchLimit,
+        search_moves: Option<Vec<String>>,
         tx: mpsc::UnboundedSender<ServerMessage>,
     ) -> Result<()> {
         // Store current analysis ID
         self.current_id = Some(id.clone());
 
+        // Each analysis gets its own stop handle, created before the thread
+        // starts so a stop that arrives early still reaches this search
+        // (and never a later one). A search still running from a previous
+        // analyze is stopped so it releases the engine.
+        let stop = StopHandle::new();
+        if let Some(old) = self.current_stop.replace(stop.clone()) {
+            old.stop();
+        }
+
         // Clone Arc for the thread
         let engine = Arc::clone(&self.engine);
         let callback_id = id.clone();
@@ -63,7 +71,7 @@ impl EngineManager {
             // Lock engine and set position
             {
                 let mut eng = engine.lock().unwrap();
-                eng.position(&fen, &[]);
+                eng.position(&fen, &moves);
                 info!("Position set for id: {}", callback_id);
             }
 
@@ -71,7 +79,8 @@ impl EngineManager {
             info!("Starting analysis for id: {}", callback_id);
             let mut best: BestMove = {
                 let mut eng = engine.lock().unwrap();
-                eng.analyze(limit, |info: SearchInfo| {
+                eng.use_stop_handle(stop);
+                eng.analyze_with_search_moves(limit, search_moves.as_deref(), |info: SearchInfo| {
                     // Send SearchInfo to WebSocket
                     let msg = ServerMessage {
                         msg_type: "searchInfo".to_string(),
@@ -110,8 +119,10 @@ impl EngineManager {
     }
 
     pub fn stop(&mut self) {
-        // Set stop flag directly without locking (non-blocking)
-        self.stop_flag.store(true, Ordering::Relaxed);
+        // Stop the current analysis without locking (non-blocking)
+        if let Some(stop) = self.current_stop.take() {
+            stop.stop();
+        }
         self.current_id = None;
     }
 
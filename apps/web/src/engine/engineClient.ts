@@ -127,6 +127,7 @@ function remoteAnalyze(req: AnalyzeRequest, onEvent: EventHandler): StopHandler
     id: req.id,
     fen: req.fen,
     limit: req.limit,
+    searchMoves: req.searchMoves,
   });
 
   // Return cleanup function
@@ -125,7 +125,7 @@ function handleAnalyze(req: AnalyzeRequest): void {
 
     // Start analysis (no callback - WASM doesn't support SearchInfo streaming)
     // The analyze method will return when search is complete
-    const result = wasmEngine.analyze(limit);
+    const result = wasmEngine.analyze(limit, req.searchMoves);
 
     logger.log('[Worker] Search completed, raw result:', result);
     logger.log('[Worker] Result type:', typeof result);
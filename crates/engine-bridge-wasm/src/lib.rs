@@ -1,7 +1,5 @@
-use engine::{
-    types::{BestMove, EngineOptions, SearchInfo, SearchLimit},
-    EngineImpl,
-};
+use engine::prelude::{BestMove, EngineImpl, EngineOptions, SearchInfo, SearchLimit};
+use serde::Serialize;
 use std::cell::RefCell;
 use wasm_bindgen::prelude::*;
 
@@ -11,6 +9,12 @@ pub fn init_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// 64 random bits from `Math.random`, 32 per call.
+fn random_seed() -> u64 {
+    let bits = || (js_sys::Math::random() * (1u64 << 32) as f64) as u64;
+    (bits() << 32) | bits()
+}
+
 #[wasm_bindgen]
 pub struct WasmEngine {
     inner: RefCell<EngineImpl>,
@@ -21,8 +25,11 @@ impl WasmEngine {
     #[wasm_bindgen(constructor)]
     pub fn new(opts_js: JsValue) -> Result<WasmEngine, JsValue> {
         let opts: EngineOptions = serde_wasm_bindgen::from_value(opts_js)?;
+        let mut engine = EngineImpl::new_with(opts);
+        // The engine's own seed is the same on every load in wasm
+        engine.set_option("MoveVarietySeed", &random_seed().to_string());
         Ok(WasmEngine {
-            inner: RefCell::new(EngineImpl::new_with(opts)),
+            inner: RefCell::new(engine),
         })
     }
 
@@ -33,18 +40,142 @@ impl WasmEngine {
         Ok(())
     }
 
+    /// Analyze the current position.
+    ///
+    /// `search_moves_js` is an optional array of UCI moves restricting the
+    /// root search ("go searchmoves"); pass undefined to search every move.
     #[wasm_bindgen(js_name = "analyze")]
-    pub fn analyze(&self, limit_js: JsValue) -> Result<JsValue, JsValue> {
+    pub fn analyze(&self, limit_js: JsValue, search_moves_js: JsValue) -> Result<JsValue, JsValue> {
         let limit: SearchLimit = serde_wasm_bindgen::from_value(limit_js)?;
+        let search_moves: Option<Vec<String>> = serde_wasm_bindgen::from_value(search_moves_js)?;
 
         // Use RefCell to avoid aliasing issues - borrow happens inside this scope
-        let best: BestMove = self.inner.borrow_mut().analyze(limit, |_info: SearchInfo| {
-            // No-op callback - SearchInfo streaming not supported in WASM
-        });
+        let best: BestMove = self.inner.borrow_mut().analyze_with_search_moves(
+            limit,
+            search_moves.as_deref(),
+            |_info: SearchInfo| {
+                // No-op callback - SearchInfo streaming not supported in WASM
+            },
+        );
 
         Ok(serde_wasm_bindgen::to_value(&best)?)
     }
 
+    /// Classify every move of a game as best/good/inaccuracy/mistake/blunder.
+    ///
+    /// `moves_js` is an array of UCI moves played from `fen`. Returns an array
+    /// of move assessments, one per move.
+    #[wasm_bindgen(js_name = "analyzeGame")]
+    pub fn analyze_game(
+        &self,
+        fen: String,
+        moves_js: JsValue,
+        limit_per_move_js: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let moves: Vec<String> = serde_wasm_bindgen::from_value(moves_js)?;
+        let limit: SearchLimit = serde_wasm_bindgen::from_value(limit_per_move_js)?;
+
+        let assessments = self
+            .inner
+            .borrow_mut()
+            .analyze_game(&fen, &moves, limit, |_, _| {})
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        Ok(serde_wasm_bindgen::to_value(&assessments)?)
+    }
+
+    /// Evaluate an array of FENs one after another with a single limit.
+    ///
+    /// Returns an array of `{fen, bestMove, score, depth}` entries; a FEN
+    /// that fails to parse yields an entry with `error` set instead of
+    /// failing the batch. The current position is left unchanged.
+    #[wasm_bindgen(js_name = "evaluateBatch")]
+    pub fn evaluate_batch(&self, fens_js: JsValue, limit_js: JsValue) -> Result<JsValue, JsValue> {
+        self.run_batch(fens_js, limit_js, |_, _| {})
+    }
+
+    /// Like `evaluateBatch`, calling `progress(done, total)` after each
+    /// position.
+    #[wasm_bindgen(js_name = "evaluateBatchWithProgress")]
+    pub fn evaluate_batch_with_progress(
+        &self,
+        fens_js: JsValue,
+        limit_js: JsValue,
+        progress: &js_sys::Function,
+    ) -> Result<JsValue, JsValue> {
+        self.run_batch(fens_js, limit_js, |done, total| {
+            // A throwing callback should not abort the batch
+            let _ = progress.call2(
+                &JsValue::NULL,
+                &JsValue::from(done as u32),
+                &JsValue::from(total as u32),
+            );
+        })
+    }
+
+    /// Classify one move against the engine's best move at `depth`, as
+    /// "excellent", "best", "good", "inaccuracy", "mistake" or "blunder".
+    #[wasm_bindgen(js_name = "moveClassification")]
+    pub fn move_classification(
+        &self,
+        fen: &str,
+        uci_move: &str,
+        depth: u32,
+    ) -> Result<JsValue, JsValue> {
+        let classification = self
+            .inner
+            .borrow_mut()
+            .move_classification(fen, uci_move, depth)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&classification)?)
+    }
+
+    /// Load evaluation parameters from a JSON string (an empty string
+    /// restores the defaults). Fails listing unknown or missing fields.
+    #[wasm_bindgen(js_name = "setEvalParams")]
+    pub fn set_eval_params(&self, json: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_eval_params_json(json)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Resize the transposition table to `mb` megabytes, dropping its
+    /// entries. Small tables (1-8 MB) keep memory low on mobile browsers at
+    /// the cost of more nodes on longer searches: at depth 10, Kiwipete takes
+    /// about 3.1M nodes with 1 MB against 2.0M with 64 MB. At depth 8 the
+    /// difference is within the noise of move ordering.
+    #[wasm_bindgen(js_name = "setHashSize")]
+    pub fn set_hash_size(&self, mb: u32) {
+        self.inner.borrow_mut().set_hash_size(mb);
+    }
+
+    /// Set a named engine option, e.g. `setOption("MoveVariety", "30")` for
+    /// casual games that don't always repeat the same opening moves, plus
+    /// `setOption("MoveVarietySeed", "1")` to make them reproducible.
+    #[wasm_bindgen(js_name = "setOption")]
+    pub fn set_option(&self, name: &str, value: &str) {
+        self.inner.borrow_mut().set_option(name, value);
+    }
+
+    /// Bytes allocated for the transposition table (0 before the first search)
+    #[wasm_bindgen(js_name = "ttBytes")]
+    pub fn tt_bytes(&self) -> usize {
+        self.inner.borrow().tt_bytes()
+    }
+
+    /// Engine name, version and build information (EngineAbout object)
+    #[wasm_bindgen(js_name = "version")]
+    pub fn version(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.inner.borrow().about())?)
+    }
+
+    /// Bytes used by the pawn hash table
+    #[wasm_bindgen(js_name = "pawnHashBytes")]
+    pub fn pawn_hash_bytes(&self) -> usize {
+        self.inner.borrow().pawn_hash_bytes()
+    }
+
     #[wasm_bindgen(js_name = "stop")]
     pub fn stop(&self) {
         self.inner.borrow_mut().stop();
@@ -78,6 +209,113 @@ impl WasmEngine {
             .collect()
     }
 
+    /// Get all legal moves for a position with capture/castle/promotion
+    /// metadata and SAN (array of MoveInfo objects)
+    #[wasm_bindgen(js_name = "legalMovesDetailed")]
+    pub fn legal_moves_detailed(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let moves = self.inner.borrow().legal_moves_detailed(fen);
+        Ok(serde_wasm_bindgen::to_value(&moves)?)
+    }
+
+    /// Same as `legalMovesDetailed`
+    #[wasm_bindgen(js_name = "legalMovesWithInfo")]
+    pub fn legal_moves_with_info(&self, fen: &str) -> Result<JsValue, JsValue> {
+        self.legal_moves_detailed(fen)
+    }
+
+    /// Get legal moves grouped by origin square (object keyed by square)
+    #[wasm_bindgen(js_name = "legalMovesBySquare")]
+    pub fn legal_moves_by_square(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let moves = self.inner.borrow().legal_moves_by_square(fen);
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        Ok(moves.serialize(&serializer)?)
+    }
+
+    /// Get hanging pieces for both sides with their en-prise values
+    /// (PositionHints object)
+    #[wasm_bindgen(js_name = "positionHints")]
+    pub fn position_hints(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let hints = self
+            .inner
+            .borrow()
+            .position_hints(fen)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&hints)?)
+    }
+
+    /// Get the squares attacked by a color ("w"/"b" or "white"/"black") as
+    /// an array of algebraic squares
+    #[wasm_bindgen(js_name = "attackedSquares")]
+    pub fn attacked_squares(&self, fen: &str, color: &str) -> Result<JsValue, JsValue> {
+        let squares = self
+            .inner
+            .borrow()
+            .attacked_squares(fen, color)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&squares)?)
+    }
+
+    /// Get every piece on the board as an array of { square, piece } objects
+    /// (FEN piece characters)
+    #[wasm_bindgen(js_name = "piecesList")]
+    pub fn pieces_list(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let pieces = self
+            .inner
+            .borrow()
+            .pieces_list(fen)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&pieces)?)
+    }
+
+    /// Get the squares that differ between two FENs as an array of
+    /// { square, before, after } objects (null for an empty square)
+    #[wasm_bindgen(js_name = "fenDiff")]
+    pub fn fen_diff(&self, fen_a: &str, fen_b: &str) -> Result<JsValue, JsValue> {
+        let changes = self
+            .inner
+            .borrow()
+            .fen_diff(fen_a, fen_b)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&changes)?)
+    }
+
+    /// Replay an array of UCI moves from `fen` as an array of
+    /// { uci, san, fenAfter, scoreAtNode } objects, one per move
+    #[wasm_bindgen(js_name = "expandPv")]
+    pub fn expand_pv(&self, fen: &str, moves_js: JsValue) -> Result<JsValue, JsValue> {
+        let moves: Vec<String> = serde_wasm_bindgen::from_value(moves_js)?;
+        let nodes = self
+            .inner
+            .borrow()
+            .expand_pv(fen, &moves)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&nodes)?)
+    }
+
+    /// Get whether the side to move is in check, the checking pieces and the
+    /// king square (CheckInfo object)
+    #[wasm_bindgen(js_name = "checkInfo")]
+    pub fn check_info(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let info = self
+            .inner
+            .borrow()
+            .check_info(fen)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&info)?)
+    }
+
+    /// Get the pinned pieces of the side to move with their pinners and pin
+    /// rays (array of PinInfo objects)
+    #[wasm_bindgen(js_name = "pinnedPieces")]
+    pub fn pinned_pieces(&self, fen: &str) -> Result<JsValue, JsValue> {
+        let pins = self
+            .inner
+            .borrow()
+            .pinned_pieces(fen)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&pins)?)
+    }
+
     /// Check if position is game over (returns [is_over, status])
     /// Status can be "checkmate", "stalemate", or null if not over
     #[wasm_bindgen(js_name = "isGameOver")]
@@ -90,3 +328,22 @@ impl WasmEngine {
         serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
     }
 }
+
+impl WasmEngine {
+    fn run_batch(
+        &self,
+        fens_js: JsValue,
+        limit_js: JsValue,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<JsValue, JsValue> {
+        let fens: Vec<String> = serde_wasm_bindgen::from_value(fens_js)?;
+        let limit: SearchLimit = serde_wasm_bindgen::from_value(limit_js)?;
+        let fens: Vec<&str> = fens.iter().map(String::as_str).collect();
+
+        let results = self
+            .inner
+            .borrow_mut()
+            .analyze_batch(&fens, limit, progress);
+        Ok(serde_wasm_bindgen::to_value(&results)?)
+    }
+}
@@ -0,0 +1,211 @@
+//! Run with `wasm-pack test --node crates/engine-bridge-wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use engine::prelude::{BestMove, EngineOptions, SearchLimit};
+use engine_bridge_wasm::WasmEngine;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+const FEN: &str = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+
+fn engine(hash_size_mb: u32) -> WasmEngine {
+    let opts = EngineOptions {
+        hash_size_mb,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    };
+    WasmEngine::new(serde_wasm_bindgen::to_value(&opts).unwrap()).unwrap()
+}
+
+fn analyze(eng: &WasmEngine, depth: u32) -> BestMove {
+    analyze_fen(eng, FEN, depth)
+}
+
+fn analyze_fen(eng: &WasmEngine, fen: &str, depth: u32) -> BestMove {
+    eng.position(
+        fen.to_string(),
+        serde_wasm_bindgen::to_value(&Vec::<String>::new()).unwrap(),
+    )
+    .unwrap();
+    let limit = serde_wasm_bindgen::to_value(&SearchLimit::Depth { depth }).unwrap();
+    let best = eng.analyze(limit, JsValue::UNDEFINED).unwrap();
+    serde_wasm_bindgen::from_value(best).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn one_megabyte_engine_plays_legal_moves() {
+    let eng = engine(1);
+    let best = analyze(&eng, 8);
+
+    assert!(eng.is_move_legal(FEN, &best.best), "{}", best.best);
+    assert!(eng.tt_bytes() <= 1024 * 1024);
+    assert!(eng.pawn_hash_bytes() > 0);
+}
+
+#[wasm_bindgen_test]
+fn deep_capture_chains_fit_the_wasm_stack() {
+    // Batteries on both sides of d5: long capture sequences in quiescence
+    let fen = "3qk3/3r4/1n1rbn2/2bp4/5N2/1BNR4/3R4/3QK3 w - - 0 1";
+    let eng = engine(1);
+    let best = analyze_fen(&eng, fen, 10);
+
+    assert!(eng.is_move_legal(fen, &best.best), "{}", best.best);
+    assert!(best.seldepth.unwrap_or(0) <= engine::search::MAX_PLY as u32);
+}
+
+#[wasm_bindgen_test]
+fn set_hash_size_resizes_table() {
+    let eng = engine(8);
+    analyze(&eng, 4);
+    assert!(eng.tt_bytes() > 1024 * 1024);
+
+    eng.set_hash_size(1);
+    assert_eq!(eng.tt_bytes(), 0);
+    analyze(&eng, 4);
+    assert!(eng.tt_bytes() <= 1024 * 1024);
+}
+
+#[wasm_bindgen_test]
+fn set_option_seeds_move_variety() {
+    let moves: Vec<String> = (0..2)
+        .map(|_| {
+            let eng = engine(1);
+            eng.set_option("MoveVariety", "50");
+            eng.set_option("MoveVarietySeed", "7");
+            analyze(&eng, 4).best
+        })
+        .collect();
+
+    assert!(engine(1).is_move_legal(FEN, &moves[0]), "{}", moves[0]);
+    assert_eq!(moves[0], moves[1]);
+}
+
+#[wasm_bindgen_test]
+fn position_hints_reports_hanging_pieces() {
+    let eng = engine(1);
+    let hints = eng
+        .position_hints("4k3/8/8/4p3/3N4/8/8/3RK3 w - - 0 1")
+        .unwrap();
+    let hints: engine::types::PositionHints = serde_wasm_bindgen::from_value(hints).unwrap();
+
+    assert_eq!(hints.white_hanging.len(), 1);
+    assert_eq!(hints.white_hanging[0].square, "d4");
+    assert!(hints.black_hanging.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn check_info_reports_double_check() {
+    // Nd6+ uncovers the e1 rook: knight and rook both check the e8 king
+    let eng = engine(1);
+    let info = eng.check_info("4k3/8/3N4/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    let info: engine::types::CheckInfo = serde_wasm_bindgen::from_value(info).unwrap();
+
+    assert!(info.in_check);
+    assert_eq!(info.king.as_deref(), Some("e8"));
+    assert_eq!(info.checkers, vec!["e1", "d6"]);
+}
+
+#[wasm_bindgen_test]
+fn pinned_pieces_reports_absolute_pin() {
+    // The e2 knight is pinned to the e1 king by the e5 rook
+    let eng = engine(1);
+    let pins = eng
+        .pinned_pieces("4k3/8/8/4r3/8/8/4N3/4K3 w - - 0 1")
+        .unwrap();
+    let pins: Vec<engine::types::PinInfo> = serde_wasm_bindgen::from_value(pins).unwrap();
+
+    assert_eq!(pins.len(), 1);
+    assert_eq!(pins[0].square, "e2");
+    assert_eq!(pins[0].pinner, "e5");
+    assert_eq!(pins[0].ray, vec!["e2", "e3", "e4", "e5"]);
+
+    let attacked = eng
+        .attacked_squares("4k3/8/8/4r3/8/8/4N3/4K3 w - - 0 1", "b")
+        .unwrap();
+    let attacked: Vec<String> = serde_wasm_bindgen::from_value(attacked).unwrap();
+    assert!(attacked.iter().any(|sq| sq == "e2"));
+    assert!(!attacked.iter().any(|sq| sq == "e1"));
+}
+
+#[wasm_bindgen_test]
+fn pieces_list_reports_every_piece() {
+    let eng = engine(1);
+    let pieces = eng.pieces_list("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    let pieces: Vec<engine::types::PieceOnSquare> = serde_wasm_bindgen::from_value(pieces).unwrap();
+
+    let pieces: Vec<_> = pieces
+        .iter()
+        .map(|p| (p.square.as_str(), p.piece))
+        .collect();
+    assert_eq!(pieces, vec![("e2", 'P'), ("e1", 'K'), ("e8", 'k')]);
+}
+
+#[wasm_bindgen_test]
+fn fen_diff_reports_castling_squares() {
+    let eng = engine(1);
+    let changes = eng
+        .fen_diff(
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1",
+        )
+        .unwrap();
+    let changes: Vec<engine::types::SquareDiff> = serde_wasm_bindgen::from_value(changes).unwrap();
+
+    let squares: Vec<_> = changes.iter().map(|c| c.square.as_str()).collect();
+    assert_eq!(squares, vec!["e1", "f1", "g1", "h1"]);
+    assert_eq!((changes[0].before, changes[0].after), (Some('K'), None));
+    assert!(eng.fen_diff("startpos", "startpos").is_err());
+}
+
+#[wasm_bindgen_test]
+fn expand_pv_returns_san_and_fens() {
+    let eng = engine(1);
+    let moves = serde_wasm_bindgen::to_value(&["e1g1", "e8e7", "b7b8q"]).unwrap();
+    let nodes = eng
+        .expand_pv("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1", moves)
+        .unwrap();
+    let nodes: Vec<engine::types::PvNode> = serde_wasm_bindgen::from_value(nodes).unwrap();
+
+    let sans: Vec<_> = nodes.iter().map(|n| n.san.as_str()).collect();
+    assert_eq!(sans, vec!["O-O", "Ke7", "b8=Q"]);
+    assert_eq!(nodes[2].fen_after, "1Q6/4k3/8/8/8/8/8/5RK1 b - - 0 2");
+}
+
+#[wasm_bindgen_test]
+fn move_classification_flags_hung_queen() {
+    // 1. e4 e5 2. Nf3 Qg5?? hangs the queen to the knight
+    let eng = engine(1);
+    let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+    let blunder = eng.move_classification(fen, "d8g5", 4).unwrap();
+    assert_eq!(blunder.as_string().as_deref(), Some("blunder"));
+    assert!(eng.move_classification(fen, "e2e4", 4).is_err());
+}
+
+#[wasm_bindgen_test]
+fn version_reports_engine_about() {
+    let eng = engine(1);
+    let about: engine::version::EngineAbout =
+        serde_wasm_bindgen::from_value(eng.version().unwrap()).unwrap();
+    assert_eq!(about, engine::version::EngineAbout::current());
+    assert_eq!(about.name, engine::version::NAME);
+}
+
+#[wasm_bindgen_test]
+fn evaluate_batch_reports_bad_fens_inline() {
+    let eng = engine(1);
+    let fens = serde_wasm_bindgen::to_value(&["6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1", "not a fen"])
+        .unwrap();
+    let limit = serde_wasm_bindgen::to_value(&SearchLimit::Depth { depth: 3 }).unwrap();
+    let results = eng.evaluate_batch(fens, limit).unwrap();
+    let results: Vec<engine::types::BatchEvaluation> =
+        serde_wasm_bindgen::from_value(results).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].best_move, "a1a8");
+    assert!(results[0].error.is_none());
+    assert!(results[1].error.is_some());
+}
@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use engine::board::Board;
+use engine::io::{parse_fen, parse_fen_batch, ToFen};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// 10,000 FENs from random games, as an analysis pipeline would see them.
+fn random_fens() -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut fens = Vec::with_capacity(10_000);
+    while fens.len() < 10_000 {
+        let mut board = Board::startpos();
+        for _ in 0..60 {
+            let moves = board.generate_legal_moves();
+            if moves.is_empty() || fens.len() == 10_000 {
+                break;
+            }
+            board.make_move(*moves.get(rng.gen_range(0..moves.len())));
+            fens.push(board.to_fen());
+        }
+    }
+    fens
+}
+
+fn bench_fen_batch(c: &mut Criterion) {
+    let owned = random_fens();
+    let fens: Vec<&str> = owned.iter().map(String::as_str).collect();
+    let mut group = c.benchmark_group("parse_10k_fens");
+
+    group.bench_function("parse_fen", |b| {
+        b.iter(|| {
+            fens.iter()
+                .map(|fen| parse_fen(black_box(fen)))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parse_fen_batch", |b| {
+        b.iter(|| parse_fen_batch(black_box(&fens)))
+    });
+    #[cfg(feature = "parallel")]
+    group.bench_function("parse_fen_batch_rayon", |b| {
+        b.iter(|| engine::io::parse_fen_batch_rayon(black_box(&fens)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_fen_batch);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use engine::io::parse_fen;
+use engine::movelist::MoveList;
+use engine::piece::PieceType;
+
+const FENS: [&str; 3] = [
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "2r1r1k1/pp1q1ppp/2n1b3/3pP3/3P4/2PB1N2/P4PPP/R2Q1RK1 w - - 0 1",
+    "rnbqkb1r/pp1p1pPp/8/2p1pP2/1P1P4/3P3P/P1P1P3/RNBQKBNR w KQkq e6 0 1",
+];
+
+/// The quiescence capture filter: captures other than queen promotions.
+fn is_qsearch_capture(m: &engine::Move) -> bool {
+    m.is_capture() && m.promotion_piece() != Some(PieceType::Queen)
+}
+
+fn bench_capture_filter(c: &mut Criterion) {
+    let lists: Vec<MoveList> = FENS
+        .iter()
+        .map(|fen| parse_fen(fen).unwrap().generate_legal_moves())
+        .collect();
+    let mut group = c.benchmark_group("qsearch_capture_filter");
+
+    group.bench_function("copy", |b| {
+        b.iter(|| {
+            lists
+                .iter()
+                .map(|moves| {
+                    let moves = black_box(moves.clone());
+                    let mut captures = MoveList::new();
+                    for m in moves.iter().filter(|m| is_qsearch_capture(m)) {
+                        captures.push(*m);
+                    }
+                    captures.len()
+                })
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("retain", |b| {
+        b.iter(|| {
+            lists
+                .iter()
+                .map(|moves| {
+                    let mut captures = black_box(moves.clone());
+                    captures.retain(is_qsearch_capture);
+                    captures.len()
+                })
+                .sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_capture_filter);
+criterion_main!(benches);
@@ -1,9 +1,25 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use engine::{
+    eval::Evaluator,
+    io::parse_fen,
+    search::Searcher,
     types::{EngineOptions, SearchLimit},
     EngineImpl,
 };
 
+/// Standard test positions (kiwipete, perft position 3, a quiet middlegame).
+const POSITIONS: [(&str, &str); 3] = [
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    ),
+    ("position3", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"),
+    (
+        "middlegame",
+        "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+    ),
+];
+
 fn bench_iterative(c: &mut Criterion) {
     c.bench_function("engine_iterative_depth_4", |b| {
         b.iter(|| {
@@ -14,6 +30,7 @@ fn bench_iterative(c: &mut Criterion) {
                 skill_level: None,
                 multi_pv: Some(1),
                 use_tablebases: None,
+                chess960: None,
             });
             eng.position("startpos", &[]);
             let _ = eng.analyze(SearchLimit::Depth { depth: 4 }, |_| {});
@@ -21,5 +38,97 @@ fn bench_iterative(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_iterative);
+fn engine_options() -> EngineOptions {
+    EngineOptions {
+        hash_size_mb: 64,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    }
+}
+
+/// One short analysis per request, as engine-server does: a new engine per
+/// request against one engine reset with `new_game` between requests.
+fn bench_engine_reuse(c: &mut Criterion) {
+    let (_, fen) = POSITIONS[2];
+    let mut group = c.benchmark_group("engine_request_depth_4");
+
+    group.bench_function("new_engine", |b| {
+        b.iter(|| {
+            let mut eng = EngineImpl::new_with(engine_options());
+            eng.position(fen, &[]);
+            eng.analyze(SearchLimit::Depth { depth: 4 }, |_| {})
+        });
+    });
+
+    let mut eng = EngineImpl::new_with(engine_options());
+    group.bench_function("reused_engine", |b| {
+        b.iter(|| {
+            eng.new_game();
+            eng.position(fen, &[]);
+            eng.analyze(SearchLimit::Depth { depth: 4 }, |_| {})
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_depth_8(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_depth_8");
+    group.sample_size(10);
+
+    for (name, fen) in POSITIONS {
+        let board = parse_fen(fen).unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut searcher = Searcher::new();
+                searcher.search(&board, 8)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Static evaluation with a warm pawn hash against one that never hits.
+fn bench_pawn_hash(c: &mut Criterion) {
+    let boards: Vec<_> = POSITIONS
+        .iter()
+        .map(|(_, fen)| parse_fen(fen).unwrap())
+        .collect();
+    let mut group = c.benchmark_group("pawn_hash");
+
+    let mut cached = Evaluator::new();
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            boards
+                .iter()
+                .map(|board| cached.evaluate(board))
+                .sum::<i32>()
+        });
+    });
+
+    let mut uncached = Evaluator::with_pawn_hash_bytes(0);
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            boards
+                .iter()
+                .map(|board| uncached.evaluate(board))
+                .sum::<i32>()
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_iterative,
+    bench_engine_reuse,
+    bench_depth_8,
+    bench_pawn_hash
+);
 criterion_main!(benches);
@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use engine::io::parse_fen;
+use engine::movegen::generate_moves;
+use engine::r#move::Move;
+use engine::search::see::{see_beats_threshold, see_value};
+
+const FENS: [&str; 3] = [
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "2r1r1k1/pp1q1ppp/2n1b3/3pP3/3P4/2PB1N2/P4PPP/R2Q1RK1 w - - 0 1",
+    "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1",
+];
+
+fn captures() -> Vec<(engine::board::Board, Vec<Move>)> {
+    FENS.iter()
+        .map(|fen| {
+            let board = parse_fen(fen).unwrap();
+            let moves = generate_moves(&board)
+                .iter()
+                .filter(|m| m.is_capture())
+                .copied()
+                .collect();
+            (board, moves)
+        })
+        .collect()
+}
+
+fn see_full_value(c: &mut Criterion) {
+    let positions = captures();
+    c.bench_function("see_value >= 0", |b| {
+        b.iter(|| {
+            positions
+                .iter()
+                .flat_map(|(board, moves)| moves.iter().map(move |m| (board, *m)))
+                .filter(|(board, m)| see_value(black_box(board), *m) >= 0)
+                .count()
+        })
+    });
+}
+
+fn see_threshold(c: &mut Criterion) {
+    let positions = captures();
+    c.bench_function("see_beats_threshold 0", |b| {
+        b.iter(|| {
+            positions
+                .iter()
+                .flat_map(|(board, moves)| moves.iter().map(move |m| (board, *m)))
+                .filter(|(board, m)| see_beats_threshold(black_box(board), *m, 0))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, see_full_value, see_threshold);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+//! Captures the git commit the engine is built from, for `version::GIT_HASH`.
+//!
+//! Outside a git checkout (a crates.io tarball, a Docker build without
+//! `.git`) nothing is set and the hash is reported as unknown.
+
+use std::process::Command;
+
+fn main() {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output();
+    if let Ok(output) = output {
+        let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !hash.is_empty() {
+            println!("cargo:rustc-env=ENGINE_GIT_HASH={}", hash);
+        }
+    }
+
+    // Rebuild when HEAD moves; the paths are relative to this crate.
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -5,9 +5,7 @@
 //! - Making and unmaking moves
 //! - Checking board state
 
-use engine::board::Board;
-use engine::r#move::{Move, MoveFlags};
-use engine::square::Square;
+use engine::prelude::*;
 
 fn main() {
     println!("=== Chess Engine Core - Basic Usage ===\n");
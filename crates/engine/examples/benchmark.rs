@@ -0,0 +1,30 @@
+//! Standard benchmark runner.
+//!
+//! Usage: `cargo run --release --example benchmark -- [depth] [--json]`
+//!
+//! Searches the built-in benchmark positions to `depth` (default 12) and
+//! prints the per-position results and totals, or the report as JSON for
+//! comparing total nodes against a stored baseline in CI.
+
+use engine::EngineImpl;
+
+const DEFAULT_DEPTH: u32 = 12;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let json = args.iter().any(|a| a == "--json");
+    let depth = match args.iter().find(|a| *a != "--json") {
+        Some(arg) => arg.parse().unwrap_or_else(|_| {
+            eprintln!("Invalid depth: {}", arg);
+            std::process::exit(2);
+        }),
+        None => DEFAULT_DEPTH,
+    };
+
+    let report = EngineImpl::default().benchmark(depth);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{}", report);
+    }
+}
@@ -2,8 +2,7 @@
 //!
 //! Demonstrates how to parse FEN strings and convert boards back to FEN.
 
-use engine::board::Board;
-use engine::io::{parse_fen, ToFen};
+use engine::prelude::*;
 
 fn main() {
     println!("=== FEN Parsing Examples ===\n");
@@ -2,8 +2,7 @@
 //!
 //! Demonstrates how to generate and display legal moves for various positions.
 
-use engine::board::Board;
-use engine::io::parse_fen;
+use engine::prelude::*;
 
 fn main() {
     println!("=== Move Generation Examples ===\n");
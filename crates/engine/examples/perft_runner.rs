@@ -2,26 +2,40 @@
 //!
 //! Demonstrates how to use the perft functions to validate move generation
 //! and measure performance.
+//!
+//! Usage: `cargo run --release --example perft_runner -- [--threads N]`
 
 use engine::board::Board;
 use engine::io::parse_fen;
-use engine::perft::{perft, perft_divide};
+use engine::perft::{perft_divide, perft_parallel};
 use std::time::Instant;
 
 fn main() {
-    println!("=== Perft Runner ===\n");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let threads = match args.iter().position(|a| a == "--threads") {
+        Some(i) => match args.get(i + 1).and_then(|n| n.parse().ok()) {
+            Some(n) => n,
+            None => {
+                eprintln!("--threads needs a thread count");
+                std::process::exit(2);
+            }
+        },
+        None => 1,
+    };
+
+    println!("=== Perft Runner ({} threads) ===\n", threads);
 
     // Test starting position
     println!("Testing starting position...");
     let board = Board::startpos();
-    run_perft(&board, 5);
+    run_perft(&board, 5, threads);
     println!();
 
     // Test Kiwipete position (complex midgame)
     println!("Testing Kiwipete position...");
     let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
     let board = parse_fen(fen).unwrap();
-    run_perft(&board, 4);
+    run_perft(&board, 4, threads);
     println!();
 
     // Show move breakdown for depth 2
@@ -38,10 +52,10 @@ fn main() {
     println!("Total: {}", total);
 }
 
-fn run_perft(board: &Board, max_depth: u32) {
+fn run_perft(board: &Board, max_depth: u32, threads: usize) {
     for depth in 1..=max_depth {
         let start = Instant::now();
-        let nodes = perft(board, depth);
+        let nodes = perft_parallel(board, depth, threads);
         let elapsed = start.elapsed();
 
         let nps = (nodes as f64 / elapsed.as_secs_f64()) as u64;
@@ -0,0 +1,157 @@
+//! Play the engine against itself at a fixed node budget.
+//!
+//! Each game starts from one of a few short openings, played with both
+//! colours. Games end by mate, stalemate, threefold repetition, the
+//! fifty-move rule or a move cap, or are adjudicated: a side resigns, or
+//! both sides agree a draw, as decided by an [`Adjudicator`] per side.
+//!
+//! Usage: selfplay [games] [nodes] [--no-adjudication]
+
+use engine::adjudication::{Adjudication, AdjudicationOptions, Adjudicator};
+use engine::board::Board;
+use engine::io::setup_position;
+use engine::piece::Color;
+use engine::search::Searcher;
+use std::env;
+
+/// Opening lines, in UCI moves from the start position.
+const OPENINGS: [&[&str]; 4] = [
+    &["e2e4", "e7e5", "g1f3", "b8c6"],
+    &["d2d4", "d7d5", "c2c4", "e7e6"],
+    &["e2e4", "c7c5", "g1f3", "d7d6"],
+    &["c2c4", "g8f6", "b1c3", "e7e5"],
+];
+
+/// Games still running after this many plies are called drawn.
+const MAX_PLIES: u32 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+struct GameResult {
+    outcome: Outcome,
+    reason: &'static str,
+    plies: u32,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let adjudicate = !args.iter().any(|a| a == "--no-adjudication");
+    let mut numbers = args.iter().filter_map(|a| a.parse::<u64>().ok());
+    let games = numbers.next().unwrap_or(8) as usize;
+    let nodes = numbers.next().unwrap_or(20_000);
+    let adjudication = adjudicate.then(AdjudicationOptions::default);
+
+    println!(
+        "Self-play: {} games at {} nodes per move, adjudication {}",
+        games,
+        nodes,
+        if adjudicate { "on" } else { "off" }
+    );
+
+    let mut searchers = [Searcher::with_tt_size(16), Searcher::with_tt_size(16)];
+    let (mut wins, mut draws, mut losses, mut adjudicated) = (0, 0, 0, 0);
+    for game in 0..games {
+        let opening = OPENINGS[(game / 2) % OPENINGS.len()];
+        // Engine "A" (searchers[0]) takes white in even games
+        let a_is_white = game % 2 == 0;
+        let [a, b] = &mut searchers;
+        let (white, black) = if a_is_white { (a, b) } else { (b, a) };
+        let result = play_game(white, black, opening, nodes, adjudication);
+
+        match (result.outcome, a_is_white) {
+            (Outcome::Draw, _) => draws += 1,
+            (Outcome::WhiteWins, true) | (Outcome::BlackWins, false) => wins += 1,
+            _ => losses += 1,
+        }
+        if matches!(result.reason, "resignation" | "draw agreed") {
+            adjudicated += 1;
+        }
+        println!(
+            "Game {:3}: {:?} by {} after {} plies",
+            game + 1,
+            result.outcome,
+            result.reason,
+            result.plies
+        );
+    }
+
+    println!("===========================");
+    println!("Engine A: +{} ={} -{}", wins, draws, losses);
+    println!("Adjudicated: {}/{}", adjudicated, games);
+}
+
+/// Play one game from `opening`, with each side searching `nodes` nodes
+/// per move.
+fn play_game(
+    white: &mut Searcher,
+    black: &mut Searcher,
+    opening: &[&str],
+    nodes: u64,
+    adjudication: Option<AdjudicationOptions>,
+) -> GameResult {
+    let mut board: Board = setup_position("startpos", opening).expect("valid opening");
+    white.reset_for_new_game();
+    black.reset_for_new_game();
+    let mut adjudicators =
+        adjudication.map(|opts| [Adjudicator::new(opts), Adjudicator::new(opts)]);
+    // Each side's latest verdict; a draw needs both sides to agree
+    let mut verdicts: [Option<Adjudication>; 2] = [None, None];
+    let mut history = vec![board.hash()];
+    let mut plies = 0;
+
+    let result = |outcome, reason, plies| GameResult {
+        outcome,
+        reason,
+        plies,
+    };
+    loop {
+        let side = board.side_to_move();
+        let winner_if_lost = match side {
+            Color::White => Outcome::BlackWins,
+            Color::Black => Outcome::WhiteWins,
+        };
+        if board.generate_legal_moves().is_empty() {
+            return if board.is_in_check() {
+                result(winner_if_lost, "checkmate", plies)
+            } else {
+                result(Outcome::Draw, "stalemate", plies)
+            };
+        }
+        if board.halfmove_clock() >= 100 {
+            return result(Outcome::Draw, "fifty-move rule", plies);
+        }
+        if history.iter().filter(|&&h| h == board.hash()).count() >= 3 {
+            return result(Outcome::Draw, "repetition", plies);
+        }
+        if plies >= MAX_PLIES {
+            return result(Outcome::Draw, "move cap", plies);
+        }
+
+        let searcher = match side {
+            Color::White => &mut *white,
+            Color::Black => &mut *black,
+        };
+        searcher.set_game_history(history[..history.len() - 1].to_vec());
+        let search = searcher.search_fixed_nodes(&board, nodes);
+
+        if let Some(adjudicators) = adjudicators.as_mut() {
+            let verdict = adjudicators[side as usize].update(search.score, board.fullmove_number());
+            verdicts[side as usize] = verdict;
+            if verdict == Some(Adjudication::Resign) {
+                return result(winner_if_lost, "resignation", plies);
+            }
+            if verdicts == [Some(Adjudication::DrawAgreed); 2] {
+                return result(Outcome::Draw, "draw agreed", plies);
+            }
+        }
+
+        board.make_move(search.best_move);
+        history.push(board.hash());
+        plies += 1;
+    }
+}
@@ -24,6 +24,7 @@
 ///   cargo run --example tactical_test_runner -- positions/wacnew.epd --depth 8 --verbose
 
 use engine::io::{load_epd_file, EpdTestPosition, ToFen};
+use engine::io::pgn::{parse_result_from_board, GameResult};
 use engine::search::core::Searcher;
 use engine::board::Board;
 use engine::movegen::generate_moves;
@@ -132,6 +133,7 @@ fn test_position(board: &Board, epd: &EpdTestPosition, config: &TestConfig, sear
 
     // Run the search to get engine's best move
     let depth = config.depth.unwrap_or(10);
+    searcher.reset_for_new_game();
     let result = searcher.search(board, depth as u32);
 
     let elapsed = start_time.elapsed();
@@ -163,8 +165,8 @@ fn test_position(board: &Board, epd: &EpdTestPosition, config: &TestConfig, sear
                     let mut test_board = board.clone();
                     test_board.make_move(*mv);
 
-                    let mut test_searcher = Searcher::new();
-                    let test_result = test_searcher.search(&test_board, (depth - 1).max(1) as u32);
+                    searcher.reset_for_new_game();
+                    let test_result = searcher.search(&test_board, (depth - 1).max(1) as u32);
                     let expected_score = -test_result.score; // Negate because opponent's view
 
                     // Keep track of best expected score
@@ -436,6 +438,13 @@ fn main() {
             println!("\nTesting: {}", epd.id);
         }
 
+        // Positions that are already decided have nothing to search
+        let game_result = parse_result_from_board(&epd.board);
+        if game_result != GameResult::Unknown {
+            println!("\nSkipping {}: game already over ({})", epd.id, game_result);
+            continue;
+        }
+
         let result = test_position(&epd.board, epd, &config, &mut searcher);
 
         print_result(&result, config.verbose);
@@ -1,9 +1,7 @@
 /// Standard UCI interface using stdin/stdout for testing with cutechess/fastchess
-use engine::board::Board;
-use engine::io::parse_fen;
-use engine::movegen::generate_moves;
-use engine::search::core::Searcher;
-use engine::search_params;
+use engine::io::{parse_position, play_uci_moves};
+use engine::prelude::*;
+use engine::uci::parse_uci_position;
 use std::io::{self, BufRead, Write};
 
 /// Get parameter bounds for UCI option reporting.
@@ -12,6 +10,9 @@ fn get_param_bounds(param_name: &str) -> (i32, i32) {
         "lmr_base_reduction" => (1, 4),
         "lmr_move_threshold" => (2, 10),
         "lmr_depth_threshold" => (2, 8),
+        "lmr_log_table" => (0, 1),
+        "lmr_history_adjust" => (0, 1),
+        "lmr_history_divisor" => (2000, 32000),
         "null_move_r" => (2, 4),
         "null_move_min_depth" => (2, 5),
         "futility_margin_d1" => (50, 200),
@@ -32,9 +33,12 @@ fn get_param_bounds(param_name: &str) -> (i32, i32) {
         "iid_depth_reduction" => (1, 4),
         "iir_depth_reduction" => (1, 3),
         "iid_min_depth" => (2, 6),
+        "iid_cut_depth_reduction" => (2, 6),
+        "iid_cut_min_depth" => (4, 10),
         "singular_margin" => (50, 200),
         "singular_depth_reduction" => (2, 5),
         "singular_min_depth" => (6, 12),
+        "bad_captures_before_quiets" => (0, 1),
         "king_safety_divisor" => (6, 20),
         _ => (0, 1000), // Default bounds
     }
@@ -45,7 +49,10 @@ fn main() {
     let mut stdout = io::stdout();
     let mut board = Board::default();
     let mut searcher = Searcher::new();
+    // Hashes of the positions before the current one, for repetitions
+    let mut history: Vec<u64> = Vec::new();
     let mut fixed_depth: Option<u32> = None; // For limiting strength via depth
+    let mut chess960 = false; // UCI_Chess960: castling as king takes rook
 
     for line in stdin.lock().lines() {
         let line = match line {
@@ -64,11 +71,11 @@ fn main() {
 
         match parts[0] {
             "uci" => {
-                writeln!(stdout, "id name ChessAI v1.0").unwrap();
-                writeln!(stdout, "id author Claude & Victor").unwrap();
+                writeln!(stdout, "id name {}", engine::version::id_name()).unwrap();
+                writeln!(stdout, "id author {}", engine::version::AUTHOR).unwrap();
 
                 // Report tunable parameters as UCI options
-                for param_name in search_params::SearchParams::param_names() {
+                for param_name in SearchParams::param_names() {
                     if let Ok(value) = search_params::get_param(param_name) {
                         // Determine sensible min/max based on parameter name
                         let (min, max) = get_param_bounds(param_name);
@@ -79,6 +86,7 @@ fn main() {
 
                 // Add FixedDepth option for strength limitation
                 writeln!(stdout, "option name FixedDepth type spin default 0 min 0 max 20").unwrap();
+                writeln!(stdout, "option name UCI_Chess960 type check default false").unwrap();
 
                 writeln!(stdout, "uciok").unwrap();
                 stdout.flush().unwrap();
@@ -92,48 +100,28 @@ fn main() {
             "ucinewgame" => {
                 board = Board::default();
                 searcher = Searcher::new();
+                history.clear();
             }
 
             "position" => {
-                if parts.len() < 2 {
-                    continue;
-                }
-
-                if parts[1] == "startpos" {
-                    board = Board::default();
-
-                    // Apply moves if provided
-                    if parts.len() > 2 && parts[2] == "moves" {
-                        for move_str in &parts[3..] {
-                            let moves = generate_moves(&board);
-                            let mv_uci_strings: Vec<String> = moves.iter().map(|m| m.to_uci()).collect();
-                            if let Some(idx) = mv_uci_strings.iter().position(|uci| uci == *move_str) {
-                                board.make_move(moves[idx]);
-                            }
-                        }
+                // Same parsing as the library's UCI handler, so castling,
+                // en passant and promotions resolve identically
+                let (spec, moves) = match parse_uci_position(&line) {
+                    Ok(position) => position,
+                    Err(e) => {
+                        writeln!(stdout, "info string {}", e).unwrap();
+                        continue;
                     }
-                } else if parts[1] == "fen" {
-                    // Find where "moves" starts (if present)
-                    let moves_idx = parts.iter().position(|&p| p == "moves");
-                    let fen_end = moves_idx.unwrap_or(parts.len());
-
-                    // Reconstruct FEN (parts 2 through fen_end)
-                    let fen = parts[2..fen_end].join(" ");
-
-                    if let Ok(new_board) = parse_fen(&fen) {
+                };
+                match parse_position(&spec) {
+                    Ok(new_board) => {
                         board = new_board;
-
-                        // Apply moves if provided
-                        if let Some(idx) = moves_idx {
-                            for move_str in &parts[idx + 1..] {
-                                let moves = generate_moves(&board);
-                                let mv_uci_strings: Vec<String> = moves.iter().map(|m| m.to_uci()).collect();
-                                if let Some(mv_idx) = mv_uci_strings.iter().position(|uci| uci == *move_str) {
-                                    board.make_move(moves[mv_idx]);
-                                }
-                            }
+                        history.clear();
+                        if let Err(e) = play_uci_moves(&mut board, &moves, &mut history) {
+                            writeln!(stdout, "info string {}", e).unwrap();
                         }
                     }
+                    Err(e) => writeln!(stdout, "info string invalid position: {}", e).unwrap(),
                 }
             }
 
@@ -235,20 +223,18 @@ fn main() {
                     };
                 }
 
-                // Search
-                let start = std::time::Instant::now();
-                let result = searcher.search(&board, depth);
-                let elapsed_ms = start.elapsed().as_millis() as u64;
-
-                // Output info with score
-                let score_cp = result.score;
-                let nps = if elapsed_ms > 0 { result.nodes * 1000 / elapsed_ms } else { 0 };
-                writeln!(stdout, "info depth {} score cp {} nodes {} nps {} time {}",
-                    result.depth, score_cp, result.nodes, nps, elapsed_ms
-                ).unwrap();
+                // Search, with an info line per iteration (and per failed
+                // aspiration window, marked as a bound)
+                searcher.set_game_history(history.clone());
+                let result = searcher.search_with_limit_callback(&board, depth, TimeControl::Infinite, |info| {
+                    writeln!(stdout, "info depth {} score {} nodes {} nps {} time {}",
+                        info.depth, info.score.to_uci(info.bound), info.nodes, info.nps, info.time_ms
+                    ).unwrap();
+                    stdout.flush().unwrap();
+                });
 
                 // Output best move
-                writeln!(stdout, "bestmove {}", result.best_move.to_uci()).unwrap();
+                writeln!(stdout, "bestmove {}", result.best_move.to_uci_in(chess960)).unwrap();
                 stdout.flush().unwrap();
             }
 
@@ -262,6 +248,10 @@ fn main() {
                         if let Ok(value) = parts[4].parse::<u32>() {
                             fixed_depth = if value > 0 { Some(value) } else { None };
                         }
+                    } else if param_name == "UCI_Chess960" {
+                        if let Ok(value) = parts[4].parse::<bool>() {
+                            chess960 = value;
+                        }
                     } else if let Ok(value) = parts[4].parse::<i32>() {
                         if let Err(e) = search_params::set_param(param_name, value) {
                             eprintln!("Error setting parameter {}: {}", param_name, e);
@@ -272,7 +262,6 @@ fn main() {
 
             "d" | "display" => {
                 // Debug command - show board
-                use engine::io::ToFen;
                 eprintln!("{}", board.to_fen());
             }
 
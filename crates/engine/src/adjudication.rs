@@ -0,0 +1,233 @@
+//! Resign and draw adjudication
+//!
+//! Decides when an engine playing a game should give up or agree a draw,
+//! from the score of each of its searches. Both decisions need the score to
+//! stay past the threshold for several moves in a row, so one bad search
+//! (or one blunder by the opponent that the engine has not yet punished)
+//! never ends a game on its own:
+//!
+//! - **Resign** once the engine's own score has been at or below
+//!   `-resign_score` for `resign_moves` consecutive moves.
+//! - **Draw** once the score has stayed within `draw_score` of zero for
+//!   `draw_moves` consecutive moves, counting only moves from
+//!   `draw_move_number` on.
+//!
+//! Setting `resign_moves` or `draw_moves` to zero turns that rule off; a
+//! game host that wants no adjudication at all simply has no
+//! [`Adjudicator`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::MATE_SCORE;
+use crate::types::Score;
+
+/// Thresholds for an [`Adjudicator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AdjudicationOptions {
+    /// Centipawns behind at which a move counts toward resigning
+    pub resign_score: i32,
+    /// Consecutive losing moves before resigning; 0 never resigns
+    pub resign_moves: u32,
+    /// Largest absolute score, in centipawns, that counts toward a draw
+    pub draw_score: i32,
+    /// Consecutive level moves before agreeing a draw; 0 never agrees
+    pub draw_moves: u32,
+    /// First move number counted toward a draw
+    pub draw_move_number: u32,
+}
+
+impl Default for AdjudicationOptions {
+    fn default() -> Self {
+        Self {
+            resign_score: 700,
+            resign_moves: 4,
+            draw_score: 10,
+            draw_moves: 8,
+            draw_move_number: 40,
+        }
+    }
+}
+
+/// How an adjudicated game ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Adjudication {
+    /// The engine resigns
+    Resign,
+    /// The engine offers (or accepts) a draw
+    DrawAgreed,
+}
+
+/// Tracks one side's scores over a game and decides when it should resign
+/// or agree a draw.
+#[derive(Debug, Clone)]
+pub struct Adjudicator {
+    options: AdjudicationOptions,
+    /// Consecutive moves scored at or below `-resign_score`
+    losing_moves: u32,
+    /// Consecutive counted moves scored within `draw_score` of zero
+    level_moves: u32,
+}
+
+impl Adjudicator {
+    pub fn new(options: AdjudicationOptions) -> Self {
+        Self {
+            options,
+            losing_moves: 0,
+            level_moves: 0,
+        }
+    }
+
+    pub fn options(&self) -> &AdjudicationOptions {
+        &self.options
+    }
+
+    /// Forget the scores seen so far, e.g. for a new game.
+    pub fn reset(&mut self) {
+        self.losing_moves = 0;
+        self.level_moves = 0;
+    }
+
+    /// Record the score of the engine's search for `move_number` (from the
+    /// engine's point of view) and return how the game should end, if it
+    /// should.
+    ///
+    /// A score that misses a threshold restarts that rule's count.
+    pub fn update(&mut self, score_cp: i32, move_number: u32) -> Option<Adjudication> {
+        let opts = &self.options;
+
+        if score_cp <= -opts.resign_score {
+            self.losing_moves += 1;
+        } else {
+            self.losing_moves = 0;
+        }
+        if move_number >= opts.draw_move_number && score_cp.abs() <= opts.draw_score {
+            self.level_moves += 1;
+        } else {
+            self.level_moves = 0;
+        }
+
+        if opts.resign_moves > 0 && self.losing_moves >= opts.resign_moves {
+            Some(Adjudication::Resign)
+        } else if opts.draw_moves > 0 && self.level_moves >= opts.draw_moves {
+            Some(Adjudication::DrawAgreed)
+        } else {
+            None
+        }
+    }
+
+    /// [`update`](Self::update) with a reported [`Score`], a mate counting
+    /// as the search's mate score.
+    pub fn update_score(&mut self, score: &Score, move_number: u32) -> Option<Adjudication> {
+        let score_cp = match *score {
+            Score::Cp { value } => value,
+            Score::Mate { plies } if plies > 0 => MATE_SCORE - plies,
+            Score::Mate { plies } => -MATE_SCORE - plies,
+        };
+        self.update(score_cp, move_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjudicator() -> Adjudicator {
+        Adjudicator::new(AdjudicationOptions {
+            resign_score: 500,
+            resign_moves: 3,
+            draw_score: 20,
+            draw_moves: 4,
+            draw_move_number: 30,
+        })
+    }
+
+    #[test]
+    fn single_bad_score_does_not_resign() {
+        let mut adj = adjudicator();
+        assert_eq!(adj.update(-900, 20), None);
+        assert_eq!(adj.update(-900, 21), None);
+        // Back above the threshold: the count starts over
+        assert_eq!(adj.update(-100, 22), None);
+        assert_eq!(adj.update(-900, 23), None);
+        assert_eq!(adj.update(-900, 24), None);
+        assert_eq!(adj.update(-900, 25), Some(Adjudication::Resign));
+    }
+
+    #[test]
+    fn resign_threshold_is_inclusive() {
+        let mut adj = adjudicator();
+        assert_eq!(adj.update(-499, 10), None);
+        assert_eq!(adj.update(-500, 11), None);
+        assert_eq!(adj.update(-500, 12), None);
+        assert_eq!(adj.update(-500, 13), Some(Adjudication::Resign));
+    }
+
+    #[test]
+    fn draw_needs_level_scores_after_minimum_move() {
+        let mut adj = adjudicator();
+        for move_number in 20..30 {
+            assert_eq!(adj.update(0, move_number), None);
+        }
+        assert_eq!(adj.update(5, 30), None);
+        assert_eq!(adj.update(-20, 31), None);
+        // One unbalanced score restarts the count
+        assert_eq!(adj.update(35, 32), None);
+        assert_eq!(adj.update(0, 33), None);
+        assert_eq!(adj.update(0, 34), None);
+        assert_eq!(adj.update(0, 35), None);
+        assert_eq!(adj.update(10, 36), Some(Adjudication::DrawAgreed));
+    }
+
+    #[test]
+    fn zero_move_counts_disable_rules() {
+        let mut adj = Adjudicator::new(AdjudicationOptions {
+            resign_moves: 0,
+            draw_moves: 0,
+            draw_move_number: 0,
+            ..AdjudicationOptions::default()
+        });
+        for move_number in 1..100 {
+            assert_eq!(adj.update(-30_000, move_number), None);
+            assert_eq!(adj.update(0, move_number), None);
+        }
+    }
+
+    #[test]
+    fn mate_scores_count_toward_resigning() {
+        let mut adj = adjudicator();
+        let mated = Score::Mate { plies: -7 };
+        assert_eq!(adj.update_score(&mated, 40), None);
+        assert_eq!(adj.update_score(&Score::Cp { value: -300 }, 41), None);
+        assert_eq!(adj.update_score(&mated, 42), None);
+        assert_eq!(adj.update_score(&mated, 43), None);
+        assert_eq!(adj.update_score(&mated, 44), Some(Adjudication::Resign));
+        assert_eq!(
+            Adjudicator::new(AdjudicationOptions::default())
+                .update_score(&Score::Mate { plies: 3 }, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn reset_clears_counts() {
+        let mut adj = adjudicator();
+        adj.update(-900, 1);
+        adj.update(-900, 2);
+        adj.reset();
+        assert_eq!(adj.update(-900, 3), None);
+    }
+
+    #[test]
+    fn options_deserialize_with_defaults() {
+        let opts: AdjudicationOptions = serde_json::from_str(r#"{"resignMoves":6}"#).unwrap();
+        assert_eq!(
+            opts,
+            AdjudicationOptions {
+                resign_moves: 6,
+                ..AdjudicationOptions::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Post-game analysis
+//!
+//! Searches every position of a finished game and compares the move that was
+//! played against the engine's best move. The difference in evaluation (from
+//! the mover's point of view) classifies each move:
+//!
+//! | Loss         | Classification |
+//! | ------------ | -------------- |
+//! | > 200 cp     | Blunder        |
+//! | > 100 cp     | Mistake        |
+//! | > 50 cp      | Inaccuracy     |
+//! | < -50 cp     | Excellent      |
+//! | otherwise    | Good / Best    |
+//!
+//! A played move can only beat the engine's choice because its score comes
+//! from searching the position after it, one ply deeper than the root
+//! search saw it: a tactic just past the root search's horizon.
+//!
+//! Mate scores get their own rules: walking into a forced mate or throwing
+//! away a forced mate is a blunder, regardless of centipawn arithmetic.
+//!
+//! A single [`Searcher`] is used for the whole game so the transposition
+//! table carries over from one position to the next.
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::io::parse_position;
+use crate::r#move::Move;
+use crate::search::{Searcher, MATE_SCORE, MAX_DEPTH, MAX_SKILL_LEVEL};
+use crate::types::{Score, SearchLimit};
+
+/// Classification thresholds (centipawns lost by the played move).
+mod thresholds {
+    pub const INACCURACY: i32 = 50;
+    pub const MISTAKE: i32 = 100;
+    pub const BLUNDER: i32 = 200;
+
+    /// A missed mate is only a mistake if the played move still wins this much.
+    pub const STILL_WINNING: i32 = 300;
+}
+
+/// A game to analyze: the starting position and the moves played from it.
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub start: Board,
+    pub moves: Vec<Move>,
+}
+
+impl Game {
+    /// Create a game without moves.
+    pub fn new(start: Board) -> Self {
+        Self {
+            start,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Build a game from a position (see [`parse_position`]) and moves in UCI
+    /// notation.
+    ///
+    /// Fails on an invalid FEN or the first illegal move.
+    pub fn from_uci<S: AsRef<str>>(fen: &str, moves: &[S]) -> Result<Self, String> {
+        let start = parse_position(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        start
+            .check_searchable()
+            .map_err(|e| format!("Position cannot be searched: {}", e))?;
+
+        let mut board = start.clone();
+        let mut game = Self::new(start);
+        for (ply, uci) in moves.iter().map(AsRef::as_ref).enumerate() {
+            let m = Move::from_uci(&board, uci)
+                .filter(|&m| board.make_move_if_legal(m).is_some())
+                .ok_or_else(|| format!("Illegal move at ply {}: {}", ply, uci))?;
+            game.moves.push(m);
+        }
+
+        Ok(game)
+    }
+}
+
+/// How good a played move was compared to the engine's choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoveClassification {
+    /// Scored more than 50 cp above the engine's best move
+    Excellent,
+    /// The engine's best move was played
+    Best,
+    /// Lost at most 50 cp
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+/// Assessment of a single move of a game.
+///
+/// Scores are from the perspective of the side that played the move.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveAssessment {
+    /// Index of the move in the game (0 = first move)
+    pub ply: usize,
+    /// Move that was played (UCI)
+    pub played: String,
+    /// Engine's best move (UCI)
+    pub best: String,
+    pub played_score: Score,
+    pub best_score: Score,
+    pub classification: MoveClassification,
+    /// Principal variation of the best line (UCI)
+    pub pv: Vec<String>,
+}
+
+/// Analyze every move of a game with `limit` per position.
+pub fn analyze_game(game: &Game, limit: SearchLimit) -> Vec<MoveAssessment> {
+    analyze_game_with_progress(&mut Searcher::new(), game, limit, |_, _| {})
+}
+
+/// Analyze a game with an existing searcher, reporting progress.
+///
+/// `progress(done, total)` is called after each analyzed move. The searcher
+/// keeps its transposition table; its book, contempt and skill settings are
+/// overridden for the analysis and the book and contempt are restored after.
+///
+/// Stopping the searcher (through its [`Searcher::stop_handle`]) ends the
+/// analysis early with the moves assessed so far; the move whose search was
+/// cut short is left out.
+pub fn analyze_game_with_progress<F>(
+    searcher: &mut Searcher,
+    game: &Game,
+    limit: SearchLimit,
+    mut progress: F,
+) -> Vec<MoveAssessment>
+where
+    F: FnMut(usize, usize),
+{
+    let (max_depth, time_control) = crate::search_bounds(limit);
+    let contempt = searcher.contempt();
+    let use_book = searcher.use_book();
+    searcher.set_use_book(false);
+    searcher.set_contempt(0);
+    searcher.set_skill_level(MAX_SKILL_LEVEL);
+
+    let total = game.moves.len();
+    let mut assessments = Vec::with_capacity(total);
+    let mut board = game.start.clone();
+    let stop = searcher.stop_handle();
+    let mut current = searcher.search_with_limit(&board, max_depth, time_control.clone());
+
+    for (ply, &played) in game.moves.iter().enumerate() {
+        if stop.is_stopped() {
+            break;
+        }
+        board.make_move(played);
+        let next = searcher.search_with_limit(&board, max_depth, time_control.clone());
+        if stop.is_stopped() {
+            break;
+        }
+
+        let best_score = current.score;
+        let played_score = if played == current.best_move {
+            best_score
+        } else {
+            parent_score(next.score)
+        };
+
+        let classification = if played == current.best_move {
+            MoveClassification::Best
+        } else {
+            classify(best_score, played_score)
+        };
+
+        assessments.push(MoveAssessment {
+            ply,
+            played: played.to_uci(),
+            best: current.best_move.to_uci(),
+            played_score: searcher.score_to_protocol(played_score),
+            best_score: searcher.score_to_protocol(best_score),
+            classification,
+            pv: current.pv.iter().map(|m| m.to_uci()).collect(),
+        });
+        progress(ply + 1, total);
+
+        current = next;
+    }
+
+    searcher.set_use_book(use_book);
+    searcher.set_contempt(contempt);
+    assessments
+}
+
+/// Whether a score is a forced mate for either side.
+#[inline]
+fn is_mate_score(score: i32) -> bool {
+    score.abs() >= MATE_SCORE - MAX_DEPTH as i32
+}
+
+/// Convert a child position's root score to the parent's point of view,
+/// adding the extra ply to mate distances.
+fn parent_score(child: i32) -> i32 {
+    if !is_mate_score(child) {
+        -child
+    } else if child > 0 {
+        -child + 1
+    } else {
+        -child - 1
+    }
+}
+
+/// Classify a move that is not the engine's best move.
+///
+/// Both scores are from the mover's point of view.
+pub fn classify(best_score: i32, played_score: i32) -> MoveClassification {
+    let best_mates = is_mate_score(best_score) && best_score > 0;
+    let played_mates = is_mate_score(played_score) && played_score > 0;
+    let played_mated = is_mate_score(played_score) && played_score < 0;
+    let best_mated = is_mate_score(best_score) && best_score < 0;
+
+    if played_mated && !best_mated {
+        // Walked into a forced mate
+        return MoveClassification::Blunder;
+    }
+    if best_mates && !played_mates {
+        // Missed a forced mate
+        return if played_score >= thresholds::STILL_WINNING {
+            MoveClassification::Mistake
+        } else {
+            MoveClassification::Blunder
+        };
+    }
+    if best_mates || best_mated {
+        // A slower mate, or a faster loss in a lost position
+        return MoveClassification::Good;
+    }
+
+    let loss = best_score - played_score;
+    if loss < -thresholds::INACCURACY {
+        MoveClassification::Excellent
+    } else if loss > thresholds::BLUNDER {
+        MoveClassification::Blunder
+    } else if loss > thresholds::MISTAKE {
+        MoveClassification::Mistake
+    } else if loss > thresholds::INACCURACY {
+        MoveClassification::Inaccuracy
+    } else {
+        MoveClassification::Good
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_from_uci() {
+        let game = Game::from_uci("startpos", &["e2e4", "e7e5"]).unwrap();
+        assert_eq!(game.moves.len(), 2);
+        assert_eq!(game.moves[1].to_uci(), "e7e5");
+
+        assert!(Game::from_uci("startpos", &["e2e5"]).is_err());
+        assert!(Game::from_uci("not a fen", &["e2e4"]).is_err());
+    }
+
+    #[test]
+    fn test_analysis_restores_book_setting() {
+        let game = Game::from_uci("startpos", &["e2e4", "e7e5"]).unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let limit = SearchLimit::Depth { depth: 2 };
+        analyze_game_with_progress(&mut searcher, &game, limit, |_, _| {});
+        assert!(!searcher.use_book());
+    }
+
+    #[test]
+    fn test_stop_ends_analysis_early() {
+        let moves = ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6"];
+        let game = Game::from_uci("startpos", &moves).unwrap();
+        let mut searcher = Searcher::new();
+        let stop = crate::search::StopHandle::new();
+        searcher.set_stop_handle(stop.clone());
+
+        let limit = SearchLimit::Depth { depth: 3 };
+        let assessments = analyze_game_with_progress(&mut searcher, &game, limit, |done, _| {
+            if done == 2 {
+                stop.stop();
+            }
+        });
+        assert_eq!(assessments.len(), 2);
+    }
+
+    #[test]
+    fn test_classify_centipawn_loss() {
+        assert_eq!(classify(50, 20), MoveClassification::Good);
+        assert_eq!(classify(50, -10), MoveClassification::Inaccuracy);
+        assert_eq!(classify(50, -100), MoveClassification::Mistake);
+        assert_eq!(classify(50, -400), MoveClassification::Blunder);
+        assert_eq!(classify(50, 90), MoveClassification::Good);
+        assert_eq!(classify(50, 120), MoveClassification::Excellent);
+    }
+
+    #[test]
+    fn test_classify_mate_rules() {
+        // Walking into a mate from an equal position
+        assert_eq!(classify(0, -MATE_SCORE + 3), MoveClassification::Blunder);
+        // Missing a mate but staying clearly winning
+        assert_eq!(classify(MATE_SCORE - 5, 900), MoveClassification::Mistake);
+        // Missing a mate and throwing the win away
+        assert_eq!(classify(MATE_SCORE - 5, 0), MoveClassification::Blunder);
+        // A slower mate is fine
+        assert_eq!(
+            classify(MATE_SCORE - 3, MATE_SCORE - 7),
+            MoveClassification::Good
+        );
+    }
+
+    #[test]
+    fn test_parent_score_adds_mate_ply() {
+        assert_eq!(parent_score(-MATE_SCORE), MATE_SCORE - 1);
+        assert_eq!(parent_score(MATE_SCORE - 2), -MATE_SCORE + 3);
+        assert_eq!(parent_score(35), -35);
+    }
+
+    #[test]
+    fn test_hung_queen_is_blunder() {
+        // 1. e4 e5 2. Nf3 Qg5?? 3. Nxg5
+        let game = Game::from_uci("startpos", &["e2e4", "e7e5", "g1f3", "d8g5", "f3g5"]).unwrap();
+        let assessments = analyze_game(&game, SearchLimit::Depth { depth: 4 });
+
+        assert_eq!(assessments.len(), 5);
+        for assessment in &assessments[..3] {
+            assert_ne!(assessment.classification, MoveClassification::Blunder);
+        }
+        assert_eq!(assessments[3].ply, 3);
+        assert_eq!(assessments[3].played, "d8g5");
+        assert_eq!(assessments[3].classification, MoveClassification::Blunder);
+        assert_eq!(assessments[4].classification, MoveClassification::Best);
+    }
+}
@@ -71,6 +71,15 @@ pub fn pawn_attacks(square: Square, color: Color) -> Bitboard {
     PAWN_ATTACKS[color.index()][square.index() as usize]
 }
 
+/// Get all squares attacked by a set of pawns of the given color
+#[inline]
+pub fn pawn_attacks_bb(pawns: Bitboard, color: Color) -> Bitboard {
+    match color {
+        Color::White => pawns.shift_north_east() | pawns.shift_north_west(),
+        Color::Black => pawns.shift_south_east() | pawns.shift_south_west(),
+    }
+}
+
 // =============================================================================
 // KNIGHT ATTACKS
 // =============================================================================
@@ -283,6 +292,133 @@ pub fn queen_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
     bishop_attacks(square, occupancy) | rook_attacks(square, occupancy)
 }
 
+// =============================================================================
+// X-RAY ATTACKS
+// =============================================================================
+
+/// Returns the squares a rook on `square` would additionally attack if the
+/// first `blockers` hit on each ray were removed.
+///
+/// Only the squares revealed behind those blockers are returned, which makes
+/// this the building block for pin and discovered-check detection.
+///
+/// # Example
+/// ```
+/// use engine::attacks::xray_rook_attacks;
+/// use engine::bitboard::Bitboard;
+/// use engine::square::Square;
+///
+/// // Rook on a1, own piece on a2, enemy piece on a7
+/// let occupancy = Bitboard::from_square(Square::A2) | Bitboard::from_square(Square::A7);
+/// let xray = xray_rook_attacks(Square::A1, occupancy, Bitboard::from_square(Square::A2));
+/// assert!(xray.contains(Square::A7));
+/// assert!(!xray.contains(Square::A2));
+/// ```
+#[inline]
+pub fn xray_rook_attacks(square: Square, occupancy: Bitboard, blockers: Bitboard) -> Bitboard {
+    let attacks = rook_attacks(square, occupancy);
+    let blockers = blockers & attacks;
+    attacks ^ rook_attacks(square, occupancy ^ blockers)
+}
+
+/// Returns the squares a bishop on `square` would additionally attack if the
+/// first `blockers` hit on each ray were removed.
+///
+/// See [`xray_rook_attacks`].
+#[inline]
+pub fn xray_bishop_attacks(square: Square, occupancy: Bitboard, blockers: Bitboard) -> Bitboard {
+    let attacks = bishop_attacks(square, occupancy);
+    let blockers = blockers & attacks;
+    attacks ^ bishop_attacks(square, occupancy ^ blockers)
+}
+
+// =============================================================================
+// RAYS
+// =============================================================================
+
+/// Squares strictly between two aligned squares.
+/// Index: [from][to], empty when the squares don't share a line.
+static BETWEEN: Lazy<Box<[[Bitboard; 64]; 64]>> = Lazy::new(|| {
+    let mut between = Box::new([[Bitboard::EMPTY; 64]; 64]);
+
+    for from_idx in 0..64 {
+        let from = Square::new(from_idx as u8);
+        for &(df, dr) in BISHOP_DIRECTIONS.iter().chain(ROOK_DIRECTIONS.iter()) {
+            let mut ray = Bitboard::EMPTY;
+            let mut file = from.file() as i8 + df;
+            let mut rank = from.rank() as i8 + dr;
+
+            while (0..8).contains(&file) && (0..8).contains(&rank) {
+                let to = Square::from_coords(file as u8, rank as u8);
+                between[from_idx][to.index() as usize] = ray;
+                ray = ray.set(to);
+
+                file += df;
+                rank += dr;
+            }
+        }
+    }
+
+    between
+});
+
+/// Returns the squares strictly between two squares on the same rank, file
+/// or diagonal, or an empty bitboard if they are not aligned.
+///
+/// # Example
+/// ```
+/// use engine::attacks::ray_between;
+/// use engine::square::Square;
+///
+/// let ray = ray_between(Square::A1, Square::D4);
+/// assert_eq!(ray.count(), 2); // B2, C3
+/// assert!(ray_between(Square::A1, Square::from_coords(1, 2)).is_empty()); // B3
+/// ```
+#[inline(always)]
+pub fn ray_between(from: Square, to: Square) -> Bitboard {
+    BETWEEN[from.index() as usize][to.index() as usize]
+}
+
+/// Whole lines through two aligned squares, edge to edge.
+/// Index: [a][b], empty when the squares don't share a line.
+static LINE: Lazy<Box<[[Bitboard; 64]; 64]>> = Lazy::new(|| {
+    let mut line = Box::new([[Bitboard::EMPTY; 64]; 64]);
+
+    for a_idx in 0..64 {
+        let a = Square::new(a_idx as u8);
+        for &(df, dr) in BISHOP_DIRECTIONS.iter().chain(ROOK_DIRECTIONS.iter()) {
+            let full = sliding_attacks(a, Bitboard::EMPTY, &[(df, dr), (-df, -dr)]).set(a);
+            for b in sliding_attacks(a, Bitboard::EMPTY, &[(df, dr)]) {
+                line[a_idx][b.index() as usize] = full;
+            }
+        }
+    }
+
+    line
+});
+
+/// Returns the whole rank, file or diagonal through two squares, including
+/// both of them, or an empty bitboard if they are not aligned.
+///
+/// Unlike [`ray_between`], the line runs on past both squares to the board
+/// edges, so a piece pinned to its king stays pinned exactly when it moves
+/// within `line_through(king, piece)`.
+///
+/// # Example
+/// ```
+/// use engine::attacks::line_through;
+/// use engine::square::Square;
+///
+/// let line = line_through(Square::D4, Square::E5);
+/// assert_eq!(line.count(), 8); // a1-h8
+/// assert!(line.contains(Square::A1) && line.contains(Square::H8));
+/// assert!(line_through(Square::A1, Square::from_coords(1, 2)).is_empty()); // B3
+/// ```
+#[inline(always)]
+pub fn line_through(a: Square, b: Square) -> Bitboard {
+    LINE[a.index() as usize][b.index() as usize]
+}
+
 // =============================================================================
 // INITIALIZATION
 // =============================================================================
@@ -295,6 +431,8 @@ pub fn init() {
     Lazy::force(&PAWN_ATTACKS);
     Lazy::force(&KNIGHT_ATTACKS);
     Lazy::force(&KING_ATTACKS);
+    Lazy::force(&BETWEEN);
+    Lazy::force(&LINE);
 }
 
 // =============================================================================
@@ -336,6 +474,24 @@ mod tests {
         assert!(attacks.contains(Square::from_coords(6, 5))); // G6
     }
 
+    #[test]
+    fn test_pawn_attacks_bb_matches_per_square() {
+        // Every pawn on the board at once, including both edge files
+        for color in [Color::White, Color::Black] {
+            let pawns = Bitboard::RANK_2 | Bitboard::RANK_7 | Bitboard::FILE_A | Bitboard::FILE_H;
+            let mut expected = Bitboard::EMPTY;
+            for sq in pawns {
+                expected |= pawn_attacks(sq, color);
+            }
+            assert_eq!(pawn_attacks_bb(pawns, color), expected);
+        }
+
+        assert_eq!(
+            pawn_attacks_bb(Bitboard::EMPTY, Color::White),
+            Bitboard::EMPTY
+        );
+    }
+
     #[test]
     fn test_knight_attacks_center() {
         // E4 = coords(4, 3)
@@ -465,6 +621,80 @@ mod tests {
         assert_eq!(attacks.count(), 27); // Queen in center on empty board
     }
 
+    #[test]
+    fn test_xray_attacks() {
+        let d4 = Square::from_coords(3, 3);
+        let d6 = Square::from_coords(3, 5);
+        let f6 = Square::from_coords(5, 5);
+        let b2 = Square::from_coords(1, 1);
+
+        // Rook on d1 sees through d4 to d6, but no further
+        let occupancy = Bitboard::from_square(d4) | Bitboard::from_square(d6);
+        let xray = xray_rook_attacks(Square::D1, occupancy, Bitboard::from_square(d4));
+        assert_eq!(xray.count(), 2); // D5, D6
+        assert!(xray.contains(d6));
+        assert!(!xray.contains(d4));
+
+        // Blockers that are not hit first reveal nothing
+        let xray = xray_rook_attacks(Square::D1, occupancy, Bitboard::from_square(d6));
+        assert!(xray.is_empty());
+
+        // Bishop on b2 through d4 to f6
+        let occupancy = Bitboard::from_square(d4) | Bitboard::from_square(f6);
+        let xray = xray_bishop_attacks(b2, occupancy, Bitboard::from_square(d4));
+        assert_eq!(xray.count(), 2); // E5, F6
+        assert!(xray.contains(f6));
+        assert!(xray_bishop_attacks(b2, occupancy, Bitboard::EMPTY).is_empty());
+    }
+
+    #[test]
+    fn test_ray_between() {
+        // Same file
+        let ray = ray_between(Square::E2, Square::E7);
+        assert_eq!(ray.count(), 4);
+        assert!(ray.contains(Square::E4));
+        assert!(!ray.contains(Square::E2));
+        assert!(!ray.contains(Square::E7));
+
+        // Diagonal, both directions
+        assert_eq!(
+            ray_between(Square::H8, Square::A1),
+            ray_between(Square::A1, Square::H8)
+        );
+        assert_eq!(ray_between(Square::A1, Square::H8).count(), 6);
+
+        // Adjacent and unaligned squares
+        assert!(ray_between(Square::E4, Square::E5).is_empty());
+        assert!(ray_between(Square::E4, Square::from_coords(5, 5)).is_empty());
+        assert!(ray_between(Square::E4, Square::E4).is_empty());
+    }
+
+    #[test]
+    fn test_line_through() {
+        // Rank and file, in both orders
+        assert_eq!(line_through(Square::A2, Square::H2), Bitboard::RANK_2);
+        assert_eq!(line_through(Square::H2, Square::D2), Bitboard::RANK_2);
+        assert_eq!(line_through(Square::E2, Square::E7), Bitboard::FILE_E);
+        assert_eq!(line_through(Square::E8, Square::E1), Bitboard::FILE_E);
+
+        // Diagonal and anti-diagonal
+        let diagonal = line_through(Square::D4, Square::E5);
+        assert_eq!(diagonal, Bitboard::new(0x8040_2010_0804_0201));
+        assert_eq!(line_through(Square::H8, Square::A1), diagonal);
+        let anti_diagonal = line_through(Square::A8, Square::D5);
+        assert_eq!(anti_diagonal, Bitboard::new(0x0102_0408_1020_4080));
+        assert_eq!(line_through(Square::H1, Square::E4), anti_diagonal);
+
+        // A short diagonal stops at the edges
+        let short = line_through(Square::A7, Square::B8);
+        assert_eq!(short.count(), 2);
+
+        // Unaligned and identical squares
+        assert!(line_through(Square::E4, Square::from_coords(5, 5)).is_empty());
+        assert!(line_through(Square::G1, Square::F3).is_empty());
+        assert!(line_through(Square::E4, Square::E4).is_empty());
+    }
+
     #[test]
     fn test_init() {
         // Just ensure it doesn't panic
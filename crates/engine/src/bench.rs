@@ -0,0 +1,141 @@
+//! Standard benchmark
+//!
+//! Searches a fixed set of positions to a fixed depth and reports the nodes
+//! and time spent on each, see [`crate::EngineImpl::benchmark`]. With the same
+//! build, depth and hash size the node counts are reproducible, so CI can
+//! compare the total against a stored baseline to catch search regressions,
+//! while the nodes per second track raw speed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::STARTPOS_FEN;
+use crate::perft;
+
+/// Positions searched by [`crate::EngineImpl::benchmark`]: the six
+/// Chessprogramming Wiki perft positions from [`crate::perft`], two
+/// middlegames, an opening and a pawn endgame.
+pub const BENCH_POSITIONS: &[&str] = &[
+    STARTPOS_FEN,
+    perft::KIWIPETE_FEN,
+    perft::POSITION3_FEN,
+    perft::POSITION4_FEN,
+    perft::POSITION5_FEN,
+    perft::POSITION6_FEN,
+    "4rrk1/pp1n3p/3q2pQ/2p1pb2/2PP4/2P3N1/P2B2PP/4RRK1 b - - 7 19",
+    "r3r1k1/2p2ppp/p1p1bn2/8/1q2P3/2NPQN2/PPP3PP/R4RK1 b - - 2 15",
+    "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+    "8/8/4k3/3p4/3P4/4K3/8/8 w - - 0 1",
+];
+
+/// Result of searching one of the [`BENCH_POSITIONS`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionBenchmark {
+    pub fen: String,
+    /// Best move in UCI notation
+    pub best_move: String,
+    /// Depth of the last completed iteration
+    pub depth: u32,
+    pub nodes: u64,
+    pub time_ms: u64,
+}
+
+/// Result of [`crate::EngineImpl::benchmark`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    /// Depth every position was searched to
+    pub depth: u32,
+    pub positions: Vec<PositionBenchmark>,
+    pub total_nodes: u64,
+    pub total_time_ms: u64,
+    /// Total nodes over total time
+    pub nps: u64,
+}
+
+impl BenchmarkReport {
+    /// Build a report, summing the per-position results.
+    pub fn new(depth: u32, positions: Vec<PositionBenchmark>) -> Self {
+        let total_nodes = positions.iter().map(|p| p.nodes).sum();
+        let total_time_ms = positions.iter().map(|p| p.time_ms).sum();
+        Self {
+            depth,
+            positions,
+            total_nodes,
+            total_time_ms,
+            nps: total_nodes * 1000 / total_time_ms.max(1),
+        }
+    }
+}
+
+impl std::fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.positions.len();
+        for (index, position) in self.positions.iter().enumerate() {
+            writeln!(
+                f,
+                "Position {:>2}/{}: depth {:>2}  nodes {:>10}  time {:>6} ms  best {}",
+                index + 1,
+                count,
+                position.depth,
+                position.nodes,
+                position.time_ms,
+                position.best_move
+            )?;
+        }
+        writeln!(f, "===========================")?;
+        writeln!(f, "Total time (ms) : {}", self.total_time_ms)?;
+        writeln!(f, "Nodes searched  : {}", self.total_nodes)?;
+        write!(f, "Nodes/second    : {}", self.nps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EngineImpl;
+
+    #[test]
+    fn benchmark_is_reproducible() {
+        let mut engine = EngineImpl::default();
+        let report = engine.benchmark(3);
+        assert_eq!(report.positions.len(), BENCH_POSITIONS.len());
+        assert_eq!(report.depth, 3);
+
+        for (position, fen) in report.positions.iter().zip(BENCH_POSITIONS) {
+            assert_eq!(position.fen, *fen);
+            assert_eq!(position.depth, 3);
+            assert!(position.nodes > 0);
+            // No book moves: every position is searched
+            assert!(engine.is_move_legal(fen, &position.best_move), "{}", fen);
+        }
+        let nodes: u64 = report.positions.iter().map(|p| p.nodes).sum();
+        assert_eq!(report.total_nodes, nodes);
+
+        let again = engine.benchmark(3);
+        let counts = |r: &BenchmarkReport| r.positions.iter().map(|p| p.nodes).collect::<Vec<_>>();
+        assert_eq!(counts(&again), counts(&report));
+    }
+
+    #[test]
+    fn report_display_and_json() {
+        let position = |nodes, time_ms| PositionBenchmark {
+            fen: BENCH_POSITIONS[0].to_string(),
+            best_move: "e2e4".to_string(),
+            depth: 5,
+            nodes,
+            time_ms,
+        };
+        let report = BenchmarkReport::new(5, vec![position(3000, 2), position(1000, 2)]);
+        assert_eq!((report.total_nodes, report.total_time_ms), (4000, 4));
+        assert_eq!(report.nps, 1_000_000);
+
+        let text = report.to_string();
+        assert!(text.contains("Position  2/2: depth  5"), "{}", text);
+        assert!(text.ends_with("Nodes/second    : 1000000"), "{}", text);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["totalNodes"], 4000);
+        assert_eq!(json["positions"][0]["bestMove"], "e2e4");
+    }
+}
@@ -24,6 +24,7 @@
 //! assert!(bb.contains(Square::E4));
 //! ```
 
+use crate::piece::Color;
 use crate::square::Square;
 
 /// A bitboard representing a set of squares on the chessboard
@@ -40,6 +41,38 @@ impl Bitboard {
     /// Full bitboard (all squares set)
     pub const FULL: Bitboard = Bitboard(u64::MAX);
 
+    // File masks
+    pub const FILE_A: Bitboard = Bitboard(0x0101_0101_0101_0101);
+    pub const FILE_B: Bitboard = Bitboard(0x0202_0202_0202_0202);
+    pub const FILE_C: Bitboard = Bitboard(0x0404_0404_0404_0404);
+    pub const FILE_D: Bitboard = Bitboard(0x0808_0808_0808_0808);
+    pub const FILE_E: Bitboard = Bitboard(0x1010_1010_1010_1010);
+    pub const FILE_F: Bitboard = Bitboard(0x2020_2020_2020_2020);
+    pub const FILE_G: Bitboard = Bitboard(0x4040_4040_4040_4040);
+    pub const FILE_H: Bitboard = Bitboard(0x8080_8080_8080_8080);
+
+    // Rank masks
+    pub const RANK_1: Bitboard = Bitboard(0x0000_0000_0000_00FF);
+    pub const RANK_2: Bitboard = Bitboard(0x0000_0000_0000_FF00);
+    pub const RANK_3: Bitboard = Bitboard(0x0000_0000_00FF_0000);
+    pub const RANK_4: Bitboard = Bitboard(0x0000_0000_FF00_0000);
+    pub const RANK_5: Bitboard = Bitboard(0x0000_00FF_0000_0000);
+    pub const RANK_6: Bitboard = Bitboard(0x0000_FF00_0000_0000);
+    pub const RANK_7: Bitboard = Bitboard(0x00FF_0000_0000_0000);
+    pub const RANK_8: Bitboard = Bitboard(0xFF00_0000_0000_0000);
+
+    /// Light squares (b1, d1, ..., a2, c2, ...)
+    pub const LIGHT_SQUARES: Bitboard = Bitboard(0x55AA_55AA_55AA_55AA);
+
+    /// Dark squares (a1, c1, ..., b2, d2, ...)
+    pub const DARK_SQUARES: Bitboard = Bitboard(!Self::LIGHT_SQUARES.0);
+
+    /// The four center squares d4, e4, d5 and e5
+    pub const CENTER: Bitboard = Bitboard(0x0000_0018_1800_0000);
+
+    /// The 16 squares c3-f6
+    pub const EXTENDED_CENTER: Bitboard = Bitboard(0x0000_3C3C_3C3C_0000);
+
     /// Create a bitboard from a u64
     #[inline]
     pub const fn new(value: u64) -> Self {
@@ -52,6 +85,79 @@ impl Bitboard {
         Bitboard(1u64 << square.index())
     }
 
+    /// Create a bitboard with a single square set
+    ///
+    /// Same as [`Bitboard::from_square`]; reads better next to [`Bitboard::full`].
+    #[inline]
+    pub const fn singleton(square: Square) -> Self {
+        Self::from_square(square)
+    }
+
+    /// Create a bitboard with all 64 squares set
+    #[inline]
+    pub const fn full() -> Self {
+        Self::FULL
+    }
+
+    /// Create a bitboard with each of the given squares set
+    ///
+    /// # Example
+    /// ```
+    /// use engine::bitboard::Bitboard;
+    /// use engine::square::Square;
+    ///
+    /// let corners = Bitboard::from_squares(&[Square::A1, Square::H1, Square::A8, Square::H8]);
+    /// assert_eq!(corners.count(), 4);
+    /// ```
+    pub const fn from_squares(squares: &[Square]) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < squares.len() {
+            bits |= 1u64 << squares[i].index();
+            i += 1;
+        }
+        Bitboard(bits)
+    }
+
+    /// All squares on a file (0 = a-file, 7 = h-file)
+    ///
+    /// # Example
+    /// ```
+    /// use engine::bitboard::Bitboard;
+    ///
+    /// const E_FILE: Bitboard = Bitboard::from_file(4);
+    /// assert_eq!(E_FILE, Bitboard::FILE_E);
+    /// ```
+    #[inline]
+    pub const fn from_file(file: u8) -> Self {
+        Bitboard(Self::FILE_A.0 << file)
+    }
+
+    /// All squares on a rank (0 = 1st rank, 7 = 8th rank)
+    #[inline]
+    pub const fn from_rank(rank: u8) -> Self {
+        Bitboard(Self::RANK_1.0 << (rank * 8))
+    }
+
+    /// Alias for [`Self::from_file`].
+    #[inline]
+    pub const fn file_mask(file: u8) -> Self {
+        Self::from_file(file)
+    }
+
+    /// Alias for [`Self::from_rank`].
+    #[inline]
+    pub const fn rank_mask(rank: u8) -> Self {
+        Self::from_rank(rank)
+    }
+
+    /// All squares on the files next to `file` (one file at the board edge)
+    #[inline]
+    pub const fn adjacent_files(file: u8) -> Self {
+        let file_bb = Self::from_file(file);
+        Bitboard(file_bb.shift_east().0 | file_bb.shift_west().0)
+    }
+
     /// Check if a square is set
     #[inline]
     pub const fn contains(self, square: Square) -> bool {
@@ -88,6 +194,14 @@ impl Bitboard {
         self.0 != 0
     }
 
+    /// Check if more than one square is set
+    ///
+    /// Cheaper than `count() > 1`: clearing the lowest bit leaves something.
+    #[inline]
+    pub const fn more_than_one(self) -> bool {
+        (self.0 & self.0.wrapping_sub(1)) != 0
+    }
+
     /// Count the number of set bits (population count)
     #[inline]
     pub const fn count(self) -> u32 {
@@ -129,6 +243,11 @@ impl Bitboard {
         BitboardIterator { bb: self }
     }
 
+    /// Collect the set squares into a vector, from a1 towards h8
+    pub fn to_squares_vec(&self) -> Vec<Square> {
+        self.iter().collect()
+    }
+
     /// Shift the bitboard north (towards rank 8)
     #[inline]
     pub const fn shift_north(self) -> Self {
@@ -144,37 +263,67 @@ impl Bitboard {
     /// Shift the bitboard east (towards h-file)
     #[inline]
     pub const fn shift_east(self) -> Self {
-        Bitboard((self.0 << 1) & !FILE_A)
+        Bitboard((self.0 << 1) & !Self::FILE_A.0)
     }
 
     /// Shift the bitboard west (towards a-file)
     #[inline]
     pub const fn shift_west(self) -> Self {
-        Bitboard((self.0 >> 1) & !FILE_H)
+        Bitboard((self.0 >> 1) & !Self::FILE_H.0)
     }
 
     /// Shift the bitboard north-east
     #[inline]
     pub const fn shift_north_east(self) -> Self {
-        Bitboard((self.0 << 9) & !FILE_A)
+        Bitboard((self.0 << 9) & !Self::FILE_A.0)
     }
 
     /// Shift the bitboard north-west
     #[inline]
     pub const fn shift_north_west(self) -> Self {
-        Bitboard((self.0 << 7) & !FILE_H)
+        Bitboard((self.0 << 7) & !Self::FILE_H.0)
     }
 
     /// Shift the bitboard south-east
     #[inline]
     pub const fn shift_south_east(self) -> Self {
-        Bitboard((self.0 >> 7) & !FILE_A)
+        Bitboard((self.0 >> 7) & !Self::FILE_A.0)
     }
 
     /// Shift the bitboard south-west
     #[inline]
     pub const fn shift_south_west(self) -> Self {
-        Bitboard((self.0 >> 9) & !FILE_H)
+        Bitboard((self.0 >> 9) & !Self::FILE_H.0)
+    }
+
+    /// Smear every set square towards rank 8 (squares included)
+    #[inline]
+    pub const fn north_fill(self) -> Self {
+        let mut bb = self.0;
+        bb |= bb << 8;
+        bb |= bb << 16;
+        bb |= bb << 32;
+        Bitboard(bb)
+    }
+
+    /// Smear every set square towards rank 1 (squares included)
+    #[inline]
+    pub const fn south_fill(self) -> Self {
+        let mut bb = self.0;
+        bb |= bb >> 8;
+        bb |= bb >> 16;
+        bb |= bb >> 32;
+        Bitboard(bb)
+    }
+
+    /// Squares strictly in front of the set squares on the same files,
+    /// from `color`'s point of view.
+    #[inline]
+    pub const fn front_span(self, color: Color) -> Self {
+        match color {
+            Color::White => self.shift_north().north_fill(),
+            Color::Black => self.shift_south().south_fill(),
+        }
     }
 }
 
@@ -261,14 +410,18 @@ impl IntoIterator for Bitboard {
     }
 }
 
-// File and rank constants
-const FILE_A: u64 = 0x0101_0101_0101_0101;
-const FILE_H: u64 = 0x8080_8080_8080_8080;
+impl From<Square> for Bitboard {
+    fn from(square: Square) -> Self {
+        Bitboard::singleton(square)
+    }
+}
 
-#[allow(dead_code)]
-const RANK_1: u64 = 0x0000_0000_0000_00FF;
-#[allow(dead_code)]
-const RANK_8: u64 = 0xFF00_0000_0000_0000;
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Bitboard::EMPTY, |bb, square| bb.set(square))
+    }
+}
 
 impl std::fmt::Display for Bitboard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -411,4 +564,178 @@ mod tests {
         let west = bb.shift_west();
         assert!(west.contains(Square::from_coords(3, 3)));
     }
+
+    #[test]
+    fn bitboard_shifts_mask_wrapping() {
+        // Nothing wraps from the h-file to the a-file or vice versa
+        assert_eq!(Bitboard::FILE_H.shift_east(), Bitboard::EMPTY);
+        assert_eq!(Bitboard::FILE_A.shift_west(), Bitboard::EMPTY);
+        assert_eq!(Bitboard::FILE_A.shift_east(), Bitboard::FILE_B);
+        assert_eq!(Bitboard::FILE_H.shift_west(), Bitboard::FILE_G);
+        assert_eq!(Bitboard::FILE_H.shift_north_east(), Bitboard::EMPTY);
+        assert_eq!(Bitboard::FILE_A.shift_south_west(), Bitboard::EMPTY);
+
+        // Shifting off the top or bottom edge drops the squares
+        assert_eq!(Bitboard::RANK_8.shift_north(), Bitboard::EMPTY);
+        assert_eq!(Bitboard::RANK_1.shift_south(), Bitboard::EMPTY);
+        assert_eq!(Bitboard::RANK_1.shift_north(), Bitboard::RANK_2);
+    }
+
+    #[test]
+    fn bitboard_file_and_rank_masks() {
+        let files = [
+            Bitboard::FILE_A,
+            Bitboard::FILE_B,
+            Bitboard::FILE_C,
+            Bitboard::FILE_D,
+            Bitboard::FILE_E,
+            Bitboard::FILE_F,
+            Bitboard::FILE_G,
+            Bitboard::FILE_H,
+        ];
+        let ranks = [
+            Bitboard::RANK_1,
+            Bitboard::RANK_2,
+            Bitboard::RANK_3,
+            Bitboard::RANK_4,
+            Bitboard::RANK_5,
+            Bitboard::RANK_6,
+            Bitboard::RANK_7,
+            Bitboard::RANK_8,
+        ];
+
+        for i in 0..8u8 {
+            assert_eq!(Bitboard::from_file(i), files[i as usize]);
+            assert_eq!(Bitboard::from_rank(i), ranks[i as usize]);
+            assert_eq!(Bitboard::file_mask(i), files[i as usize]);
+            assert_eq!(Bitboard::rank_mask(i), ranks[i as usize]);
+            for j in 0..8u8 {
+                assert!(files[i as usize].contains(Square::from_coords(i, j)));
+                assert!(ranks[i as usize].contains(Square::from_coords(j, i)));
+            }
+        }
+    }
+
+    #[test]
+    fn bitboard_square_color_and_center_masks() {
+        assert_eq!(Bitboard::LIGHT_SQUARES.count(), 32);
+        assert_eq!(
+            Bitboard::LIGHT_SQUARES | Bitboard::DARK_SQUARES,
+            Bitboard::FULL
+        );
+        assert!(Bitboard::DARK_SQUARES.contains(Square::A1));
+        assert!(Bitboard::LIGHT_SQUARES.contains(Square::H1));
+
+        let center = Bitboard::from_squares(&[Square::D4, Square::E4, Square::D5, Square::E5]);
+        assert_eq!(Bitboard::CENTER, center);
+
+        let c3 = Square::from_algebraic("c3").unwrap();
+        let f6 = Square::from_algebraic("f6").unwrap();
+        assert_eq!(Bitboard::EXTENDED_CENTER.count(), 16);
+        assert!(Bitboard::EXTENDED_CENTER.contains(c3));
+        assert!(Bitboard::EXTENDED_CENTER.contains(f6));
+        assert_eq!(
+            Bitboard::EXTENDED_CENTER & Bitboard::CENTER,
+            Bitboard::CENTER
+        );
+    }
+
+    #[test]
+    fn bitboard_adjacent_files() {
+        assert_eq!(
+            Bitboard::adjacent_files(4),
+            Bitboard::FILE_D | Bitboard::FILE_F
+        );
+        assert_eq!(Bitboard::adjacent_files(0), Bitboard::FILE_B);
+        assert_eq!(Bitboard::adjacent_files(7), Bitboard::FILE_G);
+    }
+
+    #[test]
+    fn bitboard_fills() {
+        let e4 = Bitboard::from_square(Square::E4);
+        assert_eq!(
+            e4.north_fill(),
+            Bitboard::FILE_E & !(Bitboard::RANK_1 | Bitboard::RANK_2 | Bitboard::RANK_3)
+        );
+        assert_eq!(
+            e4.south_fill(),
+            Bitboard::FILE_E
+                & !(Bitboard::RANK_5 | Bitboard::RANK_6 | Bitboard::RANK_7 | Bitboard::RANK_8)
+        );
+
+        // Edge squares
+        assert_eq!(
+            Bitboard::from_square(Square::A1).north_fill(),
+            Bitboard::FILE_A
+        );
+        assert_eq!(
+            Bitboard::from_square(Square::H8).south_fill(),
+            Bitboard::FILE_H
+        );
+        assert_eq!(Bitboard::RANK_1.north_fill(), Bitboard::FULL);
+    }
+
+    #[test]
+    fn bitboard_front_span() {
+        let e4 = Bitboard::from_square(Square::E4);
+        let white = e4.front_span(Color::White);
+        assert_eq!(white.count(), 4);
+        assert!(white.contains(Square::E5) && white.contains(Square::E8));
+        assert!(!white.contains(Square::E4));
+
+        let black = e4.front_span(Color::Black);
+        assert_eq!(black.count(), 3);
+        assert!(black.contains(Square::E1) && !black.contains(Square::E4));
+
+        // Nothing in front of a pawn on its last rank
+        assert_eq!(
+            Bitboard::from_square(Square::H8).front_span(Color::White),
+            Bitboard::EMPTY
+        );
+        assert_eq!(
+            Bitboard::from_square(Square::A1).front_span(Color::Black),
+            Bitboard::EMPTY
+        );
+    }
+
+    #[test]
+    fn bitboard_more_than_one() {
+        assert!(!Bitboard::EMPTY.more_than_one());
+        assert!(!Bitboard::from_square(Square::A1).more_than_one());
+        assert!(!Bitboard::from_square(Square::H8).more_than_one());
+        assert!(Bitboard::from_square(Square::A1)
+            .set(Square::H8)
+            .more_than_one());
+        assert!(Bitboard::FULL.more_than_one());
+    }
+
+    #[test]
+    fn bitboard_from_squares_round_trip() {
+        let bb = Bitboard::from_squares(&[Square::A1, Square::H8]);
+        assert_eq!(bb.to_squares_vec(), vec![Square::A1, Square::H8]);
+
+        // Duplicates collapse, output is in square order
+        let bb = Bitboard::from_squares(&[Square::H8, Square::E4, Square::E4]);
+        assert_eq!(bb.to_squares_vec(), vec![Square::E4, Square::H8]);
+
+        assert_eq!(Bitboard::from_squares(&[]), Bitboard::EMPTY);
+        assert!(Bitboard::EMPTY.to_squares_vec().is_empty());
+        assert_eq!(Bitboard::full().to_squares_vec().len(), 64);
+    }
+
+    #[test]
+    fn bitboard_singleton_and_conversions() {
+        const E4: Bitboard = Bitboard::singleton(Square::E4);
+        const ALL: Bitboard = Bitboard::full();
+        assert_eq!(E4, Bitboard::from_square(Square::E4));
+        assert_eq!(Bitboard::from(Square::E4), E4);
+        assert_eq!(ALL, Bitboard::FULL);
+
+        let collected: Bitboard = [Square::A1, Square::E4, Square::H8].into_iter().collect();
+        assert_eq!(
+            collected,
+            Bitboard::from_squares(&[Square::A1, Square::E4, Square::H8])
+        );
+        assert_eq!(Bitboard::FULL.iter().collect::<Bitboard>(), Bitboard::FULL);
+    }
 }
@@ -2,6 +2,8 @@ use crate::bitboard::Bitboard;
 use crate::piece::{Color, Piece, PieceType};
 use crate::r#move::Move;
 use crate::square::Square;
+use crate::zobrist::{hash_castling, hash_en_passant, hash_piece, hash_side_to_move};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Castling rights for both colors
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -126,6 +128,76 @@ impl Default for CastlingRights {
     }
 }
 
+/// FEN castling field: `KQkq` letters in that order, or `-` for none.
+impl std::fmt::Display for CastlingRights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.bits == 0 {
+            return write!(f, "-");
+        }
+        for (has_right, c) in [
+            (self.white_kingside(), 'K'),
+            (self.white_queenside(), 'Q'),
+            (self.black_kingside(), 'k'),
+            (self.black_queenside(), 'q'),
+        ] {
+            if has_right {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error for a string that isn't a FEN castling field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CastlingError(pub String);
+
+impl std::fmt::Display for CastlingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid castling rights: {}", self.0)
+    }
+}
+
+impl std::error::Error for CastlingError {}
+
+impl std::str::FromStr for CastlingRights {
+    type Err = CastlingError;
+
+    /// Parse a FEN castling field such as `KQkq`, `Kq` or `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(CastlingRights::none());
+        }
+        if s.is_empty() {
+            return Err(CastlingError(s.to_string()));
+        }
+
+        s.chars()
+            .try_fold(CastlingRights::none(), |rights, c| match c {
+                'K' => Ok(rights.set_white_kingside()),
+                'Q' => Ok(rights.set_white_queenside()),
+                'k' => Ok(rights.set_black_kingside()),
+                'q' => Ok(rights.set_black_queenside()),
+                _ => Err(CastlingError(s.to_string())),
+            })
+    }
+}
+
+/// Serialized as the FEN castling field (`"KQkq"`, `"-"`).
+impl Serialize for CastlingRights {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CastlingRights {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 /// Information needed to unmake a move.
 ///
 /// This stores all the state that changes when making a move so it can be
@@ -144,6 +216,59 @@ pub struct UndoInfo {
     pub hash: u64,
 }
 
+/// Information needed to unmake a null move.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NullMoveUndo {
+    /// En passant square before the null move
+    pub ep_square: Option<Square>,
+    /// Halfmove clock before the null move
+    pub halfmove_clock: u32,
+    /// Zobrist hash before the null move
+    pub hash: u64,
+}
+
+/// Why a board cannot be searched (see [`Board::check_searchable`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Unsearchable {
+    /// The color has no king
+    MissingKing(Color),
+    /// The color has more than one king
+    ExtraKings(Color),
+    /// The side that just moved is in check, so its king could be captured
+    OpponentInCheck,
+}
+
+impl std::fmt::Display for Unsearchable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unsearchable::MissingKing(color) => write!(f, "no {} king", color),
+            Unsearchable::ExtraKings(color) => write!(f, "more than one {} king", color),
+            Unsearchable::OpponentInCheck => write!(f, "side not to move is in check"),
+        }
+    }
+}
+
+impl std::error::Error for Unsearchable {}
+
+/// How a piece bears on a king (see [`Board::threats_to_king`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttackType {
+    /// The piece attacks the king now
+    Direct,
+    /// A slider that would attack the king if the single piece between
+    /// them were removed
+    Discovery,
+}
+
+/// A piece attacking a king, directly or from behind one interposing piece.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ThreatenedSquare {
+    pub attacker_sq: Square,
+    pub attacker_piece: Piece,
+    pub attack_type: AttackType,
+}
+
 /// Chess board represented with bitboards
 #[derive(Clone, PartialEq, Eq)]
 pub struct Board {
@@ -174,6 +299,20 @@ pub struct Board {
 
     /// Zobrist hash of the position
     hash: u64,
+
+    /// Cached king square for each color (None if the color has no king)
+    king_squares: [Option<Square>; 2],
+
+    /// Material value for each color, kept up to date by set/remove_piece
+    material: [i32; 2],
+
+    /// Number of pieces of each type, indexed [color][piece_type], kept up
+    /// to date the same way
+    piece_counts: [[u8; 6]; 2],
+
+    /// Default piece-square table sums for each color (middlegame, endgame)
+    pst_mg: [i32; 2],
+    pst_eg: [i32; 2],
 }
 
 impl Board {
@@ -189,6 +328,11 @@ impl Board {
             halfmove_clock: 0,
             fullmove_number: 1,
             hash: 0,
+            king_squares: [None; 2],
+            material: [0; 2],
+            piece_counts: [[0; 6]; 2],
+            pst_mg: [0; 2],
+            pst_eg: [0; 2],
         };
         board.hash = crate::zobrist::zobrist_hash(&board);
         board
@@ -256,25 +400,65 @@ impl Board {
         None
     }
 
-    /// Set a piece at a square (overwrites existing piece)
+    /// Set a piece at a square (overwrites existing piece).
+    ///
+    /// Updates the Zobrist hash, like all the setters below, so a board
+    /// edited square by square hashes the same as one parsed from its FEN.
     pub fn set_piece(&mut self, square: Square, piece: Piece) {
+        let old_ep = self.legal_ep_square();
+        if let Some(old) = self.piece_at(square) {
+            self.hash = hash_piece(self.hash, old, square);
+        }
+        self.hash = hash_piece(self.hash, piece, square);
+        self.put_piece(square, piece);
+        self.update_ep_hash(old_ep);
+    }
+
+    /// Remove a piece from a square, updating the hash
+    pub fn remove_piece(&mut self, square: Square) {
+        let old_ep = self.legal_ep_square();
+        if let Some(piece) = self.piece_at(square) {
+            self.hash = hash_piece(self.hash, piece, square);
+        }
+        self.take_piece(square);
+        self.update_ep_hash(old_ep);
+    }
+
+    /// Put a piece on a square, leaving the hash alone (`make_move` and
+    /// `unmake_move` maintain it themselves)
+    fn put_piece(&mut self, square: Square, piece: Piece) {
         // Remove any existing piece at this square
-        self.remove_piece(square);
+        self.take_piece(square);
 
         // Add the new piece
         self.pieces[piece.color.index()][piece.piece_type.index()] =
             self.pieces[piece.color.index()][piece.piece_type.index()].set(square);
 
+        if piece.piece_type == PieceType::King {
+            self.king_squares[piece.color.index()] = Some(square);
+        }
+
+        self.update_scores(piece, square, 1);
+
         // Update occupied bitboards
         self.update_occupied();
     }
 
-    /// Remove a piece from a square
-    pub fn remove_piece(&mut self, square: Square) {
+    /// Remove a piece from a square, leaving the hash alone
+    fn take_piece(&mut self, square: Square) {
         for color in [Color::White, Color::Black] {
             for piece_type in PieceType::all() {
-                self.pieces[color.index()][piece_type.index()] =
-                    self.pieces[color.index()][piece_type.index()].clear(square);
+                let bb = self.pieces[color.index()][piece_type.index()];
+                if bb.contains(square) {
+                    self.pieces[color.index()][piece_type.index()] = bb.clear(square);
+                    self.update_scores(Piece::new(piece_type, color), square, -1);
+                }
+            }
+
+            // Refresh the cached king square if we just removed that king
+            if self.king_squares[color.index()] == Some(square) {
+                self.king_squares[color.index()] =
+                    self.pieces[color.index()][PieceType::King.index()].lsb();
             }
         }
 
@@ -282,6 +466,19 @@ impl Board {
         self.update_occupied();
     }
 
+    /// Add (`sign` = 1) or subtract (`sign` = -1) a piece's material,
+    /// count and piece-square values.
+    #[inline]
+    fn update_scores(&mut self, piece: Piece, square: Square, sign: i32) {
+        let (mg, eg) = crate::eval::pst::default_piece_square_value(piece, square);
+        let color = piece.color.index();
+        self.material[color] += sign * crate::eval::material::piece_value(piece.piece_type);
+        let count = &mut self.piece_counts[color][piece.piece_type.index()];
+        *count = count.wrapping_add_signed(sign as i8);
+        self.pst_mg[color] += sign * mg;
+        self.pst_eg[color] += sign * eg;
+    }
+
     /// Update the occupied bitboards based on piece positions
     fn update_occupied(&mut self) {
         self.occupied_by_color[Color::White.index()] = Bitboard::EMPTY;
@@ -306,6 +503,102 @@ impl Board {
         self.pieces[color.index()][piece_type.index()]
     }
 
+    /// Iterate over every piece on the board with its square.
+    ///
+    /// Walks the piece bitboards, so pieces come grouped by color (white
+    /// first) and type, each group in square order.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    ///
+    /// let board = Board::startpos();
+    /// assert_eq!(board.pieces_iter().count(), 32);
+    /// assert!(board.pieces_iter().all(|(sq, piece)| board.piece_at(sq) == Some(piece)));
+    /// ```
+    pub fn pieces_iter(&self) -> impl Iterator<Item = (Square, Piece)> + '_ {
+        Color::all().into_iter().flat_map(move |color| {
+            PieceType::all().into_iter().flat_map(move |piece_type| {
+                let piece = Piece::new(piece_type, color);
+                self.piece_bb(piece_type, color)
+                    .into_iter()
+                    .map(move |sq| (sq, piece))
+            })
+        })
+    }
+
+    /// Number of pieces of a type and color on the board.
+    ///
+    /// Maintained incrementally, so this is a plain lookup.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::piece::{Color, PieceType};
+    ///
+    /// assert_eq!(Board::startpos().piece_count(PieceType::Pawn, Color::Black), 8);
+    /// ```
+    #[inline]
+    pub fn piece_count(&self, piece_type: PieceType, color: Color) -> u32 {
+        self.piece_counts[color.index()][piece_type.index()] as u32
+    }
+
+    /// Get the king square for a color, or None if that color has no king.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::piece::Color;
+    /// use engine::square::Square;
+    ///
+    /// let board = Board::startpos();
+    /// assert_eq!(board.king_square(Color::White), Some(Square::E1));
+    /// assert_eq!(Board::empty().king_square(Color::Black), None);
+    /// ```
+    #[inline]
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.king_squares[color.index()]
+    }
+
+    /// Get the king square for a color.
+    ///
+    /// # Panics
+    /// Panics if that color has no king on the board.
+    #[inline]
+    pub fn king_square_unchecked(&self, color: Color) -> Square {
+        self.king_squares[color.index()]
+            .expect("king_square_unchecked called with no king on board")
+    }
+
+    /// Material of a color in centipawns, king included (see
+    /// [`crate::eval::evaluate_material`]).
+    ///
+    /// Maintained incrementally, so this is a plain lookup.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::piece::Color;
+    ///
+    /// assert_eq!(Board::startpos().material(Color::White), 24_000);
+    /// ```
+    #[inline]
+    pub fn material(&self, color: Color) -> i32 {
+        self.material[color.index()]
+    }
+
+    /// Middlegame piece-square score of a color under the default tables.
+    #[inline]
+    pub fn pst_mg(&self, color: Color) -> i32 {
+        self.pst_mg[color.index()]
+    }
+
+    /// Endgame piece-square score of a color under the default tables.
+    #[inline]
+    pub fn pst_eg(&self, color: Color) -> i32 {
+        self.pst_eg[color.index()]
+    }
+
     /// Get bitboard for all pieces of a color
     #[inline]
     pub fn color_bb(&self, color: Color) -> Bitboard {
@@ -330,10 +623,15 @@ impl Board {
         self.side_to_move
     }
 
-    /// Set the side to move
+    /// Set the side to move, updating the hash
     #[inline]
     pub fn set_side_to_move(&mut self, color: Color) {
+        let old_ep = self.legal_ep_square();
+        if color != self.side_to_move {
+            self.hash = hash_side_to_move(self.hash);
+        }
         self.side_to_move = color;
+        self.update_ep_hash(old_ep);
     }
 
     /// Get castling rights
@@ -342,9 +640,10 @@ impl Board {
         self.castling
     }
 
-    /// Set castling rights
+    /// Set castling rights, updating the hash
     #[inline]
     pub fn set_castling(&mut self, castling: CastlingRights) {
+        self.hash = hash_castling(self.hash, self.castling, castling);
         self.castling = castling;
     }
 
@@ -354,10 +653,108 @@ impl Board {
         self.ep_square
     }
 
-    /// Set en passant square
+    /// Set en passant square, updating the hash
     #[inline]
     pub fn set_ep_square(&mut self, square: Option<Square>) {
+        let old_ep = self.legal_ep_square();
         self.ep_square = square;
+        self.update_ep_hash(old_ep);
+    }
+
+    /// The en passant square if the side to move has a legal en passant
+    /// capture onto it, otherwise `None`.
+    ///
+    /// FENs and double pawn pushes record the square whether or not any pawn
+    /// can use it, and [`Self::ep_square`] reports it as recorded. The
+    /// Zobrist hash and [`Self::position_eq`] use this instead, so positions
+    /// that only differ by an unusable en passant square count as the same
+    /// position for repetitions and the transposition table.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::square::Square;
+    ///
+    /// // After 1. e4 no black pawn can take on e3
+    /// let board = parse_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    /// assert_eq!(board.ep_square(), Some(Square::E3));
+    /// assert_eq!(board.legal_ep_square(), None);
+    /// ```
+    pub fn legal_ep_square(&self) -> Option<Square> {
+        use crate::attacks::pawn_attacks;
+
+        let ep = self.ep_square?;
+        let us = self.side_to_move;
+        let (ep_rank, captured) = match us {
+            Color::White => (5, ep.index().wrapping_sub(8)),
+            Color::Black => (2, ep.index() + 8),
+        };
+        if ep.rank() != ep_rank {
+            return None;
+        }
+        let captured = Square::new(captured);
+        let capturers = pawn_attacks(ep, us.opponent()) & self.piece_bb(PieceType::Pawn, us);
+        capturers
+            .into_iter()
+            .any(|from| self.is_ep_capture_safe(from, ep, captured))
+            .then_some(ep)
+    }
+
+    /// Whether taking en passant from `from` onto `ep` (removing the pawn on
+    /// `captured`) leaves our king out of check.
+    ///
+    /// Checked on bitboards rather than with [`Self::is_legal`], which makes
+    /// the move and would recurse back into the hash update.
+    fn is_ep_capture_safe(&self, from: Square, ep: Square, captured: Square) -> bool {
+        use crate::attacks::{bishop_attacks, knight_attacks, pawn_attacks, rook_attacks};
+
+        let us = self.side_to_move;
+        let them = us.opponent();
+        let Some(king) = self.king_square(us) else {
+            return true;
+        };
+
+        let occupied = self.occupied().clear(from).clear(captured).set(ep);
+        let queens = self.piece_bb(PieceType::Queen, them);
+        (bishop_attacks(king, occupied) & (self.piece_bb(PieceType::Bishop, them) | queens))
+            .is_empty()
+            && (rook_attacks(king, occupied) & (self.piece_bb(PieceType::Rook, them) | queens))
+                .is_empty()
+            && (knight_attacks(king) & self.piece_bb(PieceType::Knight, them)).is_empty()
+            && (pawn_attacks(king, us) & self.piece_bb(PieceType::Pawn, them).clear(captured))
+                .is_empty()
+    }
+
+    /// Swap the hashed en passant file from `old_ep` to the current
+    /// [`Self::legal_ep_square`].
+    #[inline]
+    fn update_ep_hash(&mut self, old_ep: Option<Square>) {
+        self.hash = hash_en_passant(self.hash, old_ep, self.legal_ep_square());
+    }
+
+    /// Whether `self` and `other` are the same position in the FIDE sense:
+    /// same pieces, side to move, castling rights and legal en passant
+    /// capture. Unlike `==`, the move clocks and an en passant square no
+    /// pawn can use are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::r#move::Move;
+    ///
+    /// let mut board = Board::startpos();
+    /// for uci in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+    ///     let m = Move::from_uci(&board, uci).unwrap();
+    ///     board.make_move(m);
+    /// }
+    /// assert!(board.position_eq(&Board::startpos()));
+    /// assert_ne!(board, Board::startpos());
+    /// ```
+    pub fn position_eq(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
+            && self.side_to_move == other.side_to_move
+            && self.castling == other.castling
+            && self.legal_ep_square() == other.legal_ep_square()
     }
 
     /// Get halfmove clock
@@ -390,6 +787,23 @@ impl Board {
         self.hash
     }
 
+    /// Whether the incrementally updated hash matches a Zobrist hash
+    /// recomputed from scratch.
+    ///
+    /// `make_move`, `unmake_move` and the FEN parser check this with
+    /// `debug_assert!`, so a drifting hash fails loudly in debug builds instead
+    /// of silently corrupting TT lookups.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    ///
+    /// assert!(Board::startpos().validate_hash_consistency());
+    /// ```
+    pub fn validate_hash_consistency(&self) -> bool {
+        self.hash == crate::zobrist::zobrist_hash(self)
+    }
+
     /// Set the Zobrist hash (used internally by FEN parser)
     #[inline]
     pub(crate) fn set_hash(&mut self, hash: u64) {
@@ -430,6 +844,9 @@ impl Board {
                 Square::new(to.index() + 8)
             };
             self.piece_at(captured_pawn_square)
+        } else if m.is_castling() {
+            // The king "takes" its own rook, which is not a capture
+            None
         } else {
             self.piece_at(to)
         };
@@ -447,6 +864,8 @@ impl Board {
             .piece_at(from)
             .expect("make_move called with no piece at from square");
 
+        let old_ep = self.legal_ep_square();
+
         // Clear en passant square (will be set again if this is a double pawn push)
         self.ep_square = None;
 
@@ -459,10 +878,10 @@ impl Board {
                 } else {
                     Square::new(to.index() + 8)
                 };
-                self.remove_piece(captured_pawn_square);
+                self.take_piece(captured_pawn_square);
             } else {
                 // Normal capture: remove piece at destination
-                self.remove_piece(to);
+                self.take_piece(to);
             }
             // Reset halfmove clock on capture
             self.halfmove_clock = 0;
@@ -475,16 +894,22 @@ impl Board {
         }
 
         // Move the piece
-        self.remove_piece(from);
+        self.take_piece(from);
 
-        // Handle promotions
-        if m.is_promotion() {
+        // Handle promotions and castling
+        if m.is_castling() {
+            // Both pieces leave before either lands, as their squares may overlap
+            let rook = self.piece_at(to).expect("Castling without rook");
+            self.take_piece(to);
+            self.put_piece(m.destination(), moving_piece);
+            self.put_piece(castling_rook_square(m), rook);
+        } else if m.is_promotion() {
             let promoted_piece = m
                 .promotion_piece()
                 .expect("Promotion move without promotion piece");
-            self.set_piece(to, Piece::new(promoted_piece, us));
+            self.put_piece(to, Piece::new(promoted_piece, us));
         } else {
-            self.set_piece(to, moving_piece);
+            self.put_piece(to, moving_piece);
         }
 
         // Handle double pawn push (set en passant square)
@@ -497,28 +922,6 @@ impl Board {
             self.ep_square = Some(ep_square);
         }
 
-        // Handle castling
-        if m.is_castling() {
-            // Move the rook
-            let (rook_from, rook_to) = if to.file() > from.file() {
-                // Kingside castling
-                (
-                    Square::from_coords(7, from.rank()),
-                    Square::from_coords(5, from.rank()),
-                )
-            } else {
-                // Queenside castling
-                (
-                    Square::from_coords(0, from.rank()),
-                    Square::from_coords(3, from.rank()),
-                )
-            };
-
-            let rook = self.piece_at(rook_from).expect("Castling without rook");
-            self.remove_piece(rook_from);
-            self.set_piece(rook_to, rook);
-        }
-
         // Update castling rights
         // Remove castling rights if king or rook moves
         if moving_piece.piece_type == PieceType::King {
@@ -568,8 +971,6 @@ impl Board {
         }
 
         // Incrementally update hash
-        use crate::zobrist::{hash_castling, hash_en_passant, hash_piece, hash_side_to_move};
-
         // Remove old piece from source square
         self.hash = hash_piece(self.hash, moving_piece, from);
 
@@ -593,35 +994,25 @@ impl Board {
         } else {
             moving_piece
         };
-        self.hash = hash_piece(self.hash, final_piece, to);
+        self.hash = hash_piece(self.hash, final_piece, m.destination());
 
         // Handle castling rook move
         if m.is_castling() {
-            let (rook_from, rook_to) = if to.file() > from.file() {
-                (
-                    Square::from_coords(7, from.rank()),
-                    Square::from_coords(5, from.rank()),
-                )
-            } else {
-                (
-                    Square::from_coords(0, from.rank()),
-                    Square::from_coords(3, from.rank()),
-                )
-            };
             let rook = Piece::new(PieceType::Rook, us);
-            self.hash = hash_piece(self.hash, rook, rook_from);
-            self.hash = hash_piece(self.hash, rook, rook_to);
+            self.hash = hash_piece(self.hash, rook, to);
+            self.hash = hash_piece(self.hash, rook, castling_rook_square(m));
         }
 
         // Update castling rights hash
         self.hash = hash_castling(self.hash, undo.castling_rights, self.castling);
 
         // Update en passant hash
-        self.hash = hash_en_passant(self.hash, undo.ep_square, self.ep_square);
+        self.update_ep_hash(old_ep);
 
         // Toggle side to move (always XOR since we switched sides)
         self.hash = hash_side_to_move(self.hash);
 
+        debug_assert!(self.validate_hash_consistency(), "hash drifted after {}", m);
         undo
     }
 
@@ -658,16 +1049,26 @@ impl Board {
         }
 
         // Get the piece at destination (might be promoted)
-        let piece_at_dest = self.piece_at(to).expect("No piece at destination");
+        let piece_at_dest = self
+            .piece_at(m.destination())
+            .expect("No piece at destination");
 
-        // Move the piece back
-        self.remove_piece(to);
-
-        // If it was a promotion, restore the pawn
-        if m.is_promotion() {
-            self.set_piece(from, Piece::new(PieceType::Pawn, us));
+        if m.is_castling() {
+            // Move the king and rook back, lifting both first as in make_move
+            let rook_square = castling_rook_square(m);
+            let rook = self.piece_at(rook_square).expect("No rook to unmove");
+            self.take_piece(m.destination());
+            self.take_piece(rook_square);
+            self.put_piece(from, piece_at_dest);
+            self.put_piece(to, rook);
+        } else if m.is_promotion() {
+            // If it was a promotion, restore the pawn
+            self.take_piece(to);
+            self.put_piece(from, Piece::new(PieceType::Pawn, us));
         } else {
-            self.set_piece(from, piece_at_dest);
+            // Move the piece back
+            self.take_piece(to);
+            self.put_piece(from, piece_at_dest);
         }
 
         // Restore captured piece
@@ -679,40 +1080,19 @@ impl Board {
                 } else {
                     Square::new(to.index() + 8)
                 };
-                self.set_piece(captured_pawn_square, captured);
+                self.put_piece(captured_pawn_square, captured);
             } else {
                 // Normal capture: restore piece at destination
-                self.set_piece(to, captured);
+                self.put_piece(to, captured);
             }
         }
 
-        // Unmake castling
-        if m.is_castling() {
-            // Move the rook back
-            let (rook_from, rook_to) = if to.file() > from.file() {
-                // Kingside castling
-                (
-                    Square::from_coords(7, from.rank()),
-                    Square::from_coords(5, from.rank()),
-                )
-            } else {
-                // Queenside castling
-                (
-                    Square::from_coords(0, from.rank()),
-                    Square::from_coords(3, from.rank()),
-                )
-            };
-
-            let rook = self.piece_at(rook_to).expect("No rook to unmove");
-            self.remove_piece(rook_to);
-            self.set_piece(rook_from, rook);
-        }
-
         // Restore state
         self.castling = undo.castling_rights;
         self.ep_square = undo.ep_square;
         self.halfmove_clock = undo.halfmove_clock;
         self.hash = undo.hash;
+        debug_assert!(self.validate_hash_consistency(), "hash drifted unmaking {}", m);
     }
 
     /// Check if a square is attacked by the given color.
@@ -802,109 +1182,733 @@ impl Board {
         let them = us.opponent();
 
         // Find our king
-        let our_king = self.piece_bb(PieceType::King, us);
-        if our_king.is_empty() {
+        match self.king_square(us) {
+            Some(king_square) => self.is_square_attacked(king_square, them),
             // No king (shouldn't happen in valid position)
-            return false;
+            None => false,
         }
-
-        let king_square = our_king.into_iter().next().unwrap();
-        self.is_square_attacked(king_square, them)
     }
 
-    /// Check if a move gives check to the opponent.
-    ///
-    /// This requires making the move temporarily to check if the opponent's
-    /// king is in check. This is relatively expensive, so use sparingly.
+    /// Get all pieces of `by_color` that attack a square.
     ///
     /// # Example
     /// ```
     /// use engine::board::Board;
-    /// use engine::movegen::generate_moves;
+    /// use engine::piece::Color;
+    /// use engine::square::Square;
     ///
     /// let board = Board::startpos();
-    /// let moves = generate_moves(&board);
-    /// // Check if any move gives check
-    /// let checking_moves: Vec<_> = moves.into_iter()
-    ///     .filter(|&m| board.gives_check(m))
-    ///     .collect();
+    /// // E2 pawn is defended by the king, queen, bishop and knight
+    /// assert_eq!(board.attackers_to(Square::E2, Color::White).count(), 4);
     /// ```
-    pub fn gives_check(&self, m: Move) -> bool {
-        let mut board_after = self.clone();
-        board_after.make_move(m);
-        board_after.is_in_check()
+    pub fn attackers_to(&self, square: Square, by_color: Color) -> Bitboard {
+        use crate::attacks::{
+            bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks,
+        };
+
+        let occupied = self.occupied();
+        let queens = self.piece_bb(PieceType::Queen, by_color);
+
+        // Attack patterns are symmetric: look outward from the target square
+        (pawn_attacks(square, by_color.opponent()) & self.piece_bb(PieceType::Pawn, by_color))
+            | (knight_attacks(square) & self.piece_bb(PieceType::Knight, by_color))
+            | (bishop_attacks(square, occupied)
+                & (self.piece_bb(PieceType::Bishop, by_color) | queens))
+            | (rook_attacks(square, occupied) & (self.piece_bb(PieceType::Rook, by_color) | queens))
+            | (king_attacks(square) & self.piece_bb(PieceType::King, by_color))
     }
 
-    /// Make a null move (pass the turn without moving).
+    /// All squares attacked by `color`, whether empty or occupied by either
+    /// side.
     ///
-    /// This is used in null move pruning during search. A null move:
-    /// - Toggles side to move
-    /// - Clears en passant square
-    /// - Increments halfmove clock
-    /// - Updates Zobrist hash
+    /// Computed once in O(pieces), so it is cheaper than repeated
+    /// [`Self::is_square_attacked`] calls when many squares are tested.
     ///
     /// # Example
     /// ```
     /// use engine::board::Board;
     /// use engine::piece::Color;
+    /// use engine::square::Square;
     ///
-    /// let mut board = Board::startpos();
-    /// assert_eq!(board.side_to_move(), Color::White);
-    /// board.make_null_move();
-    /// assert_eq!(board.side_to_move(), Color::Black);
+    /// let board = Board::startpos();
+    /// let controlled = board.controlled_squares(Color::White);
+    /// assert!(controlled.contains(Square::F3));
+    /// assert!(!controlled.contains(Square::E4));
     /// ```
-    pub fn make_null_move(&mut self) {
-        use crate::zobrist::{hash_en_passant, hash_side_to_move};
-
-        // Clear en passant square if present
-        let old_ep = self.ep_square;
-        if old_ep.is_some() {
-            self.hash = hash_en_passant(self.hash, old_ep, None);
-            self.ep_square = None;
-        }
+    pub fn controlled_squares(&self, color: Color) -> Bitboard {
+        self.controlled_squares_with(color, self.occupied())
+    }
 
-        // Toggle side to move
-        self.side_to_move = self.side_to_move.opponent();
-        self.hash = hash_side_to_move(self.hash);
+    /// Squares attacked by `color` with sliders blocked by `occupied` instead
+    /// of the board's real occupancy.
+    pub(crate) fn controlled_squares_with(&self, color: Color, occupied: Bitboard) -> Bitboard {
+        use crate::attacks::{
+            bishop_attacks, king_attacks, knight_attacks, pawn_attacks_bb, rook_attacks,
+        };
 
-        // Increment halfmove clock (null move doesn't reset it)
-        self.halfmove_clock += 1;
+        let queens = self.piece_bb(PieceType::Queen, color);
+        let mut attacked = pawn_attacks_bb(self.piece_bb(PieceType::Pawn, color), color);
+        for sq in self.piece_bb(PieceType::Knight, color) {
+            attacked |= knight_attacks(sq);
+        }
+        for sq in self.piece_bb(PieceType::Bishop, color) | queens {
+            attacked |= bishop_attacks(sq, occupied);
+        }
+        for sq in self.piece_bb(PieceType::Rook, color) | queens {
+            attacked |= rook_attacks(sq, occupied);
+        }
+        for sq in self.piece_bb(PieceType::King, color) {
+            attacked |= king_attacks(sq);
+        }
+        attacked
     }
 
-    /// Check if a move is legal (doesn't leave the king in check).
+    /// Find the least valuable piece of `by_color` attacking a square.
     ///
-    /// This assumes the move is pseudo-legal (follows piece movement rules).
+    /// Ties go to the attacker on the lowest square. Pins are ignored.
     ///
     /// # Example
     /// ```
-    /// use engine::board::Board;
-    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::io::parse_fen;
+    /// use engine::piece::{Color, PieceType};
     /// use engine::square::Square;
     ///
-    /// let board = Board::startpos();
-    /// let m = Move::new(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH);
-    /// assert!(board.is_legal(m));
+    /// // d5 is attacked by the e4 pawn and the c3 knight
+    /// let board = parse_fen("4k3/8/8/3p4/4P3/2N5/8/4K3 w - - 0 1").unwrap();
+    /// let (piece, from) = board.least_valuable_attacker(Square::D5, Color::White).unwrap();
+    /// assert_eq!(piece.piece_type, PieceType::Pawn);
+    /// assert_eq!(from, Square::E4);
     /// ```
-    pub fn is_legal(&self, m: Move) -> bool {
-        // Special handling for castling
-        if m.is_castling() {
-            return self.is_castling_legal(m);
-        }
-
-        // For non-castling moves, make the move and check if our king is still safe
-        let us = self.side_to_move;
-        let mut board = self.clone();
-        board.make_move(m);
-
+    pub fn least_valuable_attacker(
+        &self,
+        square: Square,
+        by_color: Color,
+    ) -> Option<(Piece, Square)> {
+        crate::search::see::least_valuable_attacker(self, square, by_color, self.occupied())
+            .map(|(from, piece_type)| (Piece::new(piece_type, by_color), from))
+    }
+
+    /// Sign of the static exchange evaluation of `m`: +1 if it wins
+    /// material, 0 if it trades evenly (or isn't a capture), -1 if it loses
+    /// material. See [`crate::search::see_sign`].
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::san::parse_san;
+    ///
+    /// // The d5 pawn is defended by the e6 pawn
+    /// let board = parse_fen("4k3/8/4p3/3p4/8/8/3Q4/3RK3 w - - 0 1").unwrap();
+    /// assert_eq!(board.see_sign(parse_san(&board, "Qxd5").unwrap()), -1);
+    /// ```
+    pub fn see_sign(&self, m: Move) -> i32 {
+        crate::search::see::see_sign(self, m)
+    }
+
+    /// Material the opponent wins by capturing the piece on `square`, per
+    /// static exchange evaluation (see [`crate::search::see_on_square`]).
+    /// Returns 0 for an empty square or a piece that is safe.
+    pub fn en_prise_value(&self, square: Square) -> i32 {
+        match self.piece_at(square) {
+            Some(piece) => crate::search::see::see_on_square(self, square, piece.color.opponent()),
+            None => 0,
+        }
+    }
+
+    /// Check if the piece on `square` can be won: it is attacked and its
+    /// defenders can't make the exchange pay, e.g. a knight attacked by a
+    /// pawn is hanging even if a rook defends it.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::square::Square;
+    ///
+    /// // The d4 knight is defended by the rook, but the e5 pawn still wins it
+    /// let board = parse_fen("4k3/8/8/4p3/3N4/8/8/3RK3 w - - 0 1").unwrap();
+    /// assert!(board.is_hanging(Square::D4));
+    /// assert!(!board.is_hanging(Square::D1));
+    /// ```
+    pub fn is_hanging(&self, square: Square) -> bool {
+        self.en_prise_value(square) > 0
+    }
+
+    /// Squares of the hanging pieces of `color` (see [`Self::is_hanging`]),
+    /// in square order. The king is never included.
+    pub fn hanging_pieces(&self, color: Color) -> Vec<Square> {
+        let pieces = self.color_bb(color) & !self.piece_bb(PieceType::King, color);
+        pieces
+            .into_iter()
+            .filter(|&sq| self.is_hanging(sq))
+            .collect()
+    }
+
+    /// Pawn captures of enemy pawns available to `color`, whether or not it
+    /// is its turn. The moves are pseudo-legal: a capture by a pinned pawn
+    /// is included, and en passant is not.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::piece::Color;
+    ///
+    /// // The d4 pawn can take on c5 or e5
+    /// let board = parse_fen("4k3/8/8/2p1p3/3P4/8/8/4K3 b - - 0 1").unwrap();
+    /// assert_eq!(board.pawn_levers(Color::White).len(), 2);
+    /// assert_eq!(board.pawn_levers(Color::Black).len(), 2);
+    /// ```
+    pub fn pawn_levers(&self, color: Color) -> crate::movelist::MoveList {
+        use crate::attacks::pawn_attacks;
+        use crate::r#move::MoveFlags;
+
+        let enemy_pawns = self.piece_bb(PieceType::Pawn, color.opponent());
+        let mut moves = crate::movelist::MoveList::new();
+        for from in self.piece_bb(PieceType::Pawn, color) {
+            for to in pawn_attacks(from, color) & enemy_pawns {
+                moves.push(Move::new(from, to, MoveFlags::CAPTURE));
+            }
+        }
+        moves
+    }
+
+    /// The [`Self::pawn_levers`] of `color` that break a chain on both
+    /// sides: the capturing pawn is protected by one of its own pawns, and
+    /// so is the pawn it takes.
+    ///
+    /// Meant for analysis and annotation rather than search.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::piece::Color;
+    ///
+    /// // French Defense, advance variation after ...f6: exf6 and fxe5 break
+    /// // chains, while dxc5 and cxd4 hit the unprotected c5 pawn
+    /// let fen = "rnbqkbnr/pp4pp/4pp2/2ppP3/3P4/2P5/PP3PPP/RNBQKBNR w KQkq - 0 5";
+    /// let board = parse_fen(fen).unwrap();
+    /// assert_eq!(board.pawn_levers(Color::White).len(), 2);
+    /// assert_eq!(board.pawn_breaks(Color::White).len(), 1);
+    /// ```
+    pub fn pawn_breaks(&self, color: Color) -> crate::movelist::MoveList {
+        use crate::eval::pawns::is_protected_by_pawn;
+
+        let them = color.opponent();
+        let ours = self.piece_bb(PieceType::Pawn, color);
+        let theirs = self.piece_bb(PieceType::Pawn, them);
+        let mut breaks = self.pawn_levers(color);
+        breaks.retain(|m| {
+            is_protected_by_pawn(m.from(), color, ours)
+                && is_protected_by_pawn(m.to(), them, theirs)
+        });
+        breaks
+    }
+
+    /// Get the enemy pieces giving check to the side to move.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::square::Square;
+    ///
+    /// let fen = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3";
+    /// let board = parse_fen(fen).unwrap();
+    /// let checkers = board.checkers();
+    /// assert_eq!(checkers.count(), 1);
+    /// assert!(checkers.contains(Square::from_algebraic("h4").unwrap()));
+    /// ```
+    pub fn checkers(&self) -> Bitboard {
+        let us = self.side_to_move;
+        match self.king_square(us) {
+            Some(king_square) => self.attackers_to(king_square, us.opponent()),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    /// Get the enemy pieces bearing on the king of `color`: those attacking
+    /// it directly, and sliders that would attack it if the single piece
+    /// (of either color) between them were removed.
+    ///
+    /// Threats are returned in square order. Empty if `color` has no king.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::AttackType;
+    /// use engine::io::parse_fen;
+    /// use engine::piece::Color;
+    /// use engine::square::Square;
+    ///
+    /// // The d6 knight checks; the e1 rook is behind the e2 pawn
+    /// let board = parse_fen("4k3/8/3N4/8/8/8/4P3/4R1K1 b - - 0 1").unwrap();
+    /// let threats = board.threats_to_king(Color::Black);
+    /// assert_eq!(threats.len(), 2);
+    /// assert_eq!(threats[0].attacker_sq, Square::E1);
+    /// assert_eq!(threats[0].attack_type, AttackType::Discovery);
+    /// assert_eq!(threats[1].attack_type, AttackType::Direct);
+    /// ```
+    pub fn threats_to_king(&self, color: Color) -> Vec<ThreatenedSquare> {
+        use crate::attacks::{xray_bishop_attacks, xray_rook_attacks};
+
+        let Some(king_square) = self.king_square(color) else {
+            return Vec::new();
+        };
+
+        let them = color.opponent();
+        let occupied = self.occupied();
+        let queens = self.piece_bb(PieceType::Queen, them);
+        let direct = self.attackers_to(king_square, them);
+        // X-rays from the king pass through the first piece on each line,
+        // so they only reach sliders that are not already attacking it
+        let discovery = (xray_rook_attacks(king_square, occupied, occupied)
+            & (self.piece_bb(PieceType::Rook, them) | queens))
+            | (xray_bishop_attacks(king_square, occupied, occupied)
+                & (self.piece_bb(PieceType::Bishop, them) | queens));
+
+        (direct | discovery)
+            .into_iter()
+            .filter_map(|sq| {
+                Some(ThreatenedSquare {
+                    attacker_sq: sq,
+                    attacker_piece: self.piece_at(sq)?,
+                    attack_type: if direct.contains(sq) {
+                        AttackType::Direct
+                    } else {
+                        AttackType::Discovery
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Get the pieces of the side to move that are absolutely pinned to
+    /// their own king.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::square::Square;
+    ///
+    /// // The e2 knight is pinned by the rook on e8
+    /// let board = parse_fen("4r2k/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+    /// let pinned = board.pinned();
+    /// assert_eq!(pinned.count(), 1);
+    /// assert!(pinned.contains(Square::E2));
+    /// ```
+    pub fn pinned(&self) -> Bitboard {
+        let us = self.side_to_move;
+        match self.king_square(us) {
+            Some(king_square) => self.slider_blockers(king_square, us, us.opponent()),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    /// Get the pieces of the side to move that would reveal a check on the
+    /// enemy king from one of our sliders standing behind them, if moved off
+    /// the line.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::square::Square;
+    ///
+    /// // Moving the e2 knight uncovers the e1 rook against the e8 king
+    /// let board = parse_fen("4k3/8/8/8/8/8/4N3/4R2K w - - 0 1").unwrap();
+    /// let candidates = board.discovered_check_candidates();
+    /// assert_eq!(candidates.count(), 1);
+    /// assert!(candidates.contains(Square::E2));
+    /// ```
+    pub fn discovered_check_candidates(&self) -> Bitboard {
+        let us = self.side_to_move;
+        match self.king_square(us.opponent()) {
+            Some(king_square) => self.slider_blockers(king_square, us, us),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    /// Pieces of `blocker_color` that are the only piece between `target`
+    /// and a slider of `slider_color` aiming at it.
+    fn slider_blockers(
+        &self,
+        target: Square,
+        blocker_color: Color,
+        slider_color: Color,
+    ) -> Bitboard {
+        use crate::attacks::{bishop_attacks, line_through, rook_attacks};
+
+        let occupied = self.occupied();
+        let queens = self.piece_bb(PieceType::Queen, slider_color);
+        let rooks = self.piece_bb(PieceType::Rook, slider_color) | queens;
+        let bishops = self.piece_bb(PieceType::Bishop, slider_color) | queens;
+        let rook_seen = rook_attacks(target, occupied);
+        let bishop_seen = bishop_attacks(target, occupied);
+
+        // Only the first piece on each line from the target can block, and
+        // it does when removing it uncovers a slider further along the line
+        let mut result = Bitboard::EMPTY;
+        for blocker in (rook_seen | bishop_seen) & self.color_bb(blocker_color) {
+            let without = occupied & !Bitboard::from_square(blocker);
+            let uncovered = if rook_seen.contains(blocker) {
+                rook_attacks(target, without) & !rook_seen & rooks
+            } else {
+                bishop_attacks(target, without) & !bishop_seen & bishops
+            };
+            if (uncovered & line_through(target, blocker)).is_not_empty() {
+                result |= Bitboard::from_square(blocker);
+            }
+        }
+        result
+    }
+
+    /// Check if a move gives check to the opponent.
+    ///
+    /// This requires making the move temporarily to check if the opponent's
+    /// king is in check. This is relatively expensive, so use sparingly.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::movegen::generate_moves;
+    ///
+    /// let board = Board::startpos();
+    /// let moves = generate_moves(&board);
+    /// // Check if any move gives check
+    /// let checking_moves: Vec<_> = moves.into_iter()
+    ///     .filter(|&m| board.gives_check(m))
+    ///     .collect();
+    /// ```
+    pub fn gives_check(&self, m: Move) -> bool {
+        let mut board_after = self.clone();
+        board_after.make_move(m);
+        board_after.is_in_check()
+    }
+
+    /// Check if a move leaves the opponent stalemated: no legal moves while
+    /// not in check.
+    ///
+    /// Like [`Self::gives_check`] this plays the move on a copy and also
+    /// generates the opponent's legal moves, so use sparingly.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::san::parse_san;
+    ///
+    /// // Qb6?? takes every square from the a8 king without checking it
+    /// let board = parse_fen("k7/8/2K5/8/8/8/8/1Q6 w - - 0 1").unwrap();
+    /// assert!(board.is_stalemate_after(parse_san(&board, "Qb6").unwrap()));
+    /// assert!(!board.is_stalemate_after(parse_san(&board, "Qb7#").unwrap()));
+    /// ```
+    pub fn is_stalemate_after(&self, m: Move) -> bool {
+        let mut board_after = self.clone();
+        board_after.make_move(m);
+        !board_after.is_in_check() && board_after.generate_legal_moves().is_empty()
+    }
+
+    /// Alias for [`Self::is_stalemate_after`].
+    #[inline]
+    pub fn would_cause_stalemate_for_opponent(&self, m: Move) -> bool {
+        self.is_stalemate_after(m)
+    }
+
+    /// Make a null move (pass the turn without moving).
+    ///
+    /// This is used in null move pruning during search. A null move:
+    /// - Toggles side to move
+    /// - Clears en passant square
+    /// - Increments halfmove clock
+    /// - Updates Zobrist hash
+    ///
+    /// Returns what [`Self::unmake_null_move`] needs to take it back. Passing
+    /// while in check is illegal; [`Self::try_make_null_move`] refuses to.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::piece::Color;
+    ///
+    /// let mut board = Board::startpos();
+    /// assert_eq!(board.side_to_move(), Color::White);
+    /// let undo = board.make_null_move();
+    /// assert_eq!(board.side_to_move(), Color::Black);
+    /// board.unmake_null_move(undo);
+    /// assert_eq!(board, Board::startpos());
+    /// ```
+    pub fn make_null_move(&mut self) -> NullMoveUndo {
+        let undo = NullMoveUndo {
+            ep_square: self.ep_square,
+            halfmove_clock: self.halfmove_clock,
+            hash: self.hash,
+        };
+
+        // Clear en passant square if present
+        let old_ep = self.legal_ep_square();
+        self.ep_square = None;
+        self.update_ep_hash(old_ep);
+
+        // Toggle side to move
+        self.side_to_move = self.side_to_move.opponent();
+        self.hash = hash_side_to_move(self.hash);
+
+        // Increment halfmove clock (null move doesn't reset it)
+        self.halfmove_clock += 1;
+
+        undo
+    }
+
+    /// Make a null move unless the side to move is in check, where passing
+    /// would leave its king capturable.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::io::parse_fen;
+    ///
+    /// let mut checked = parse_fen("4k3/8/8/8/8/8/8/4K2r w - - 0 1").unwrap();
+    /// assert!(checked.try_make_null_move().is_none());
+    ///
+    /// let mut board = Board::startpos();
+    /// let undo = board.try_make_null_move().unwrap();
+    /// board.unmake_null_move(undo);
+    /// assert_eq!(board, Board::startpos());
+    /// ```
+    pub fn try_make_null_move(&mut self) -> Option<NullMoveUndo> {
+        if self.is_in_check() {
+            None
+        } else {
+            Some(self.make_null_move())
+        }
+    }
+
+    /// Take back a null move made with [`Self::make_null_move`].
+    pub fn unmake_null_move(&mut self, undo: NullMoveUndo) {
+        self.side_to_move = self.side_to_move.opponent();
+        self.ep_square = undo.ep_square;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.hash = undo.hash;
+    }
+
+    /// Check if a move is legal: pseudo-legal (see [`Self::is_pseudo_legal`])
+    /// and not leaving the king in check.
+    ///
+    /// Safe to call with any move, including ones parsed from user input or
+    /// read from the transposition table.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::square::Square;
+    ///
+    /// let board = Board::startpos();
+    /// let m = Move::new(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH);
+    /// assert!(board.is_legal(m));
+    /// assert!(!board.is_legal(Move::new(Square::E2, Square::E5, MoveFlags::QUIET)));
+    /// ```
+    pub fn is_legal(&self, m: Move) -> bool {
+        self.is_pseudo_legal(m) && self.is_pseudo_legal_move_safe(m)
+    }
+
+    /// Make `m` if it is legal, returning the undo information, or leave the
+    /// board unchanged and return `None`.
+    ///
+    /// Cheaper than [`Self::is_legal`] followed by [`Self::make_move`]: the
+    /// king safety check runs on this board after the move instead of on a
+    /// clone, and an illegal move is unmade straight away.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::square::Square;
+    ///
+    /// // The knight on e2 is pinned by the rook on e8
+    /// let mut board = parse_fen("4r1k1/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+    /// let before = board.clone();
+    /// let c3 = Square::from_algebraic("c3").unwrap();
+    /// assert!(board.make_move_if_legal(Move::new(Square::E2, c3, MoveFlags::QUIET)).is_none());
+    /// assert_eq!(board, before);
+    ///
+    /// let undo = board.make_move_if_legal(Move::new(Square::E1, Square::D1, MoveFlags::QUIET));
+    /// assert!(undo.is_some());
+    /// ```
+    pub fn make_move_if_legal(&mut self, m: Move) -> Option<UndoInfo> {
+        if !self.is_pseudo_legal(m) {
+            return None;
+        }
+        self.make_pseudo_legal_move_if_safe(m)
+    }
+
+    /// [`Self::make_move_if_legal`] for a move already known to be
+    /// pseudo-legal.
+    fn make_pseudo_legal_move_if_safe(&mut self, m: Move) -> Option<UndoInfo> {
+        // Castling is checked before the move: the king's path matters too
+        if m.is_castling() {
+            return self.is_castling_legal(m).then(|| self.make_move(m));
+        }
+
+        let us = self.side_to_move;
+        let undo = self.make_move(m);
+        let safe = match self.king_square(us) {
+            Some(king_square) => !self.is_square_attacked(king_square, us.opponent()),
+            None => false,
+        };
+        if safe {
+            Some(undo)
+        } else {
+            self.unmake_move(m, undo);
+            None
+        }
+    }
+
+    /// Check that a pseudo-legal move doesn't leave our king in check.
+    fn is_pseudo_legal_move_safe(&self, m: Move) -> bool {
+        // Special handling for castling
+        if m.is_castling() {
+            return self.is_castling_legal(m);
+        }
+
+        // For non-castling moves, make the move and check if our king is still safe
+        let us = self.side_to_move;
+        let mut board = self.clone();
+        board.make_move(m);
+
         // After making the move, side_to_move has switched to opponent.
         // We need to check if our king (the side that just moved) is attacked.
-        let our_king = board.piece_bb(PieceType::King, us);
-        if our_king.is_empty() {
-            return false; // No king (shouldn't happen in valid position)
+        match board.king_square(us) {
+            Some(king_square) => !board.is_square_attacked(king_square, us.opponent()),
+            None => false, // No king (shouldn't happen in valid position)
         }
+    }
 
-        let king_square = our_king.into_iter().next().unwrap();
-        !board.is_square_attacked(king_square, us.opponent())
+    /// Check if a move follows the movement rules for the side to move,
+    /// ignoring whether it leaves the king in check.
+    ///
+    /// Cheap compared to generating moves, so use it to reject impossible
+    /// moves early: one of our pieces must stand on `from`, `to` must not
+    /// hold one of ours, the piece must be able to get there (pawns forward,
+    /// sliders without jumping), and the capture, double push, castling,
+    /// en passant and promotion flags must all match the position.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::square::Square;
+    ///
+    /// let board = Board::startpos();
+    /// assert!(board.is_pseudo_legal(Move::new(Square::G1, Square::F3, MoveFlags::QUIET)));
+    /// // The bishop on f1 is blocked by the e2 pawn
+    /// let b5 = Square::from_algebraic("b5").unwrap();
+    /// assert!(!board.is_pseudo_legal(Move::new(Square::F1, b5, MoveFlags::QUIET)));
+    /// ```
+    pub fn is_pseudo_legal(&self, m: Move) -> bool {
+        use crate::attacks::{
+            bishop_attacks, king_attacks, knight_attacks, queen_attacks, rook_attacks,
+        };
+
+        if m.is_null() || !m.flags().is_valid() {
+            return false;
+        }
+
+        let us = self.side_to_move;
+        let (from, to) = (m.from(), m.to());
+        let Some(piece) = self.piece_at(from) else {
+            return false;
+        };
+        if piece.color != us {
+            return false;
+        }
+
+        // Castling is the only move onto one of our own pieces
+        if m.is_castling() {
+            return piece.piece_type == PieceType::King && self.is_pseudo_legal_castle(m);
+        }
+        if self.color_bb(us).contains(to) {
+            return false;
+        }
+
+        if piece.piece_type == PieceType::Pawn {
+            return self.is_pseudo_legal_pawn_move(m);
+        }
+        if m.is_promotion() || m.is_double_pawn_push() || m.is_en_passant() {
+            return false;
+        }
+        if m.is_capture() != self.color_bb(us.opponent()).contains(to) {
+            return false;
+        }
+
+        let occupied = self.occupied();
+        let attacks = match piece.piece_type {
+            PieceType::Knight => knight_attacks(from),
+            PieceType::Bishop => bishop_attacks(from, occupied),
+            PieceType::Rook => rook_attacks(from, occupied),
+            PieceType::Queen => queen_attacks(from, occupied),
+            PieceType::King => king_attacks(from),
+            PieceType::Pawn => unreachable!("pawn moves are checked above"),
+        };
+        attacks.contains(to)
+    }
+
+    /// Alias for [`Self::is_pseudo_legal`].
+    #[inline]
+    pub fn move_is_pseudo_legal(&self, m: Move) -> bool {
+        self.is_pseudo_legal(m)
+    }
+
+    /// Pseudo-legality of a castling move by our king, encoded as the king
+    /// taking its own rook.
+    fn is_pseudo_legal_castle(&self, m: Move) -> bool {
+        let us = self.side_to_move;
+        if m.from() != back_rank_square(us, 4)
+            || self.piece_at(m.to()) != Some(Piece::new(PieceType::Rook, us))
+        {
+            return false;
+        }
+        if m.is_kingside_castle() {
+            m.to() == back_rank_square(us, 7) && self.can_pseudo_castle_kingside(us)
+        } else {
+            m.to() == back_rank_square(us, 0) && self.can_pseudo_castle_queenside(us)
+        }
+    }
+
+    /// Pseudo-legality of a move by one of our pawns.
+    fn is_pseudo_legal_pawn_move(&self, m: Move) -> bool {
+        use crate::attacks::pawn_attacks;
+
+        let us = self.side_to_move;
+        let (from, to) = (m.from(), m.to());
+        let last_rank = if us == Color::White { 7 } else { 0 };
+        if m.is_promotion() != (to.rank() == last_rank) {
+            return false;
+        }
+
+        if m.is_en_passant() {
+            return self.ep_square == Some(to) && pawn_attacks(from, us).contains(to);
+        }
+        if m.is_capture() {
+            return self.color_bb(us.opponent()).contains(to)
+                && pawn_attacks(from, us).contains(to);
+        }
+
+        // Pushes: straight ahead onto empty squares
+        let occupied = self.occupied();
+        let forward = |sq: Square| match us {
+            Color::White => sq.index().checked_add(8).filter(|&i| i < 64),
+            Color::Black => sq.index().checked_sub(8),
+        };
+        let Some(single) = forward(from).map(Square::new) else {
+            return false;
+        };
+        if occupied.contains(single) {
+            return false;
+        }
+        if !m.is_double_pawn_push() {
+            return to == single;
+        }
+
+        let start_rank = if us == Color::White { 1 } else { 6 };
+        from.rank() == start_rank
+            && forward(single).map(Square::new) == Some(to)
+            && !occupied.contains(to)
     }
 
     /// Check if a castling move is legal.
@@ -914,42 +1918,114 @@ impl Board {
     /// 2. The king passes through a square under attack
     /// 3. The king ends up in check
     fn is_castling_legal(&self, m: Move) -> bool {
-        let us = self.side_to_move;
-        let them = us.opponent();
-
-        // Can't castle out of check
-        if self.is_in_check() {
-            return false;
+        if m.is_kingside_castle() {
+            self.can_castle_kingside()
+        } else {
+            self.can_castle_queenside()
         }
+    }
 
-        let from = m.from();
-        let to = m.to();
+    /// Check if `color` may castle kingside as far as the castling rights and
+    /// the squares between king and rook are concerned.
+    ///
+    /// Attacks on the king's path are not considered; see
+    /// [`Self::can_castle_kingside`].
+    pub fn can_pseudo_castle_kingside(&self, color: Color) -> bool {
+        let has_right = match color {
+            Color::White => self.castling.white_kingside(),
+            Color::Black => self.castling.black_kingside(),
+        };
+        has_right && self.back_rank_empty(color, &[5, 6])
+    }
 
-        // Determine which squares the king passes through
-        let passing_square = if m.is_kingside_castle() {
-            // Kingside: king passes through f1/f8
-            Square::from_coords(5, from.rank())
-        } else {
-            // Queenside: king passes through d1/d8
-            Square::from_coords(3, from.rank())
+    /// Check if `color` may castle queenside as far as the castling rights
+    /// and the squares between king and rook are concerned.
+    ///
+    /// Attacks on the king's path are not considered; see
+    /// [`Self::can_castle_queenside`].
+    pub fn can_pseudo_castle_queenside(&self, color: Color) -> bool {
+        let has_right = match color {
+            Color::White => self.castling.white_queenside(),
+            Color::Black => self.castling.black_queenside(),
         };
+        has_right && self.back_rank_empty(color, &[1, 2, 3])
+    }
 
-        // Check if king passes through attacked square
-        if self.is_square_attacked(passing_square, them) {
-            return false;
-        }
+    /// Check if the side to move can legally castle kingside: it has the
+    /// right, the path is clear, and the king is not in check and neither
+    /// passes through nor lands on an attacked square.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    ///
+    /// let board = parse_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    /// assert!(board.can_castle_kingside());
+    ///
+    /// // The bishop on c4 covers f1
+    /// let board = parse_fen("r3k2r/8/8/8/2b5/8/8/R3K2R w KQkq - 0 1").unwrap();
+    /// assert!(!board.can_castle_kingside());
+    /// ```
+    pub fn can_castle_kingside(&self) -> bool {
+        let us = self.side_to_move;
+        self.can_pseudo_castle_kingside(us) && self.castling_path_safe(us, &[5, 6])
+    }
+
+    /// Check if the side to move can legally castle queenside: it has the
+    /// right, the path is clear, and the king is not in check and neither
+    /// passes through nor lands on an attacked square.
+    ///
+    /// The rook may pass an attacked b-file square.
+    pub fn can_castle_queenside(&self) -> bool {
+        let us = self.side_to_move;
+        self.can_pseudo_castle_queenside(us) && self.castling_path_safe(us, &[3, 2])
+    }
+
+    /// Check that the given files of `color`'s back rank are empty.
+    fn back_rank_empty(&self, color: Color, files: &[u8]) -> bool {
+        let occupied = self.occupied();
+        files
+            .iter()
+            .all(|&file| !occupied.contains(back_rank_square(color, file)))
+    }
 
-        // Check if king ends up in check (destination square)
-        if self.is_square_attacked(to, them) {
+    /// Check that `color`'s king is not in check and the given files of its
+    /// back rank are not attacked.
+    fn castling_path_safe(&self, color: Color, files: &[u8]) -> bool {
+        let them = color.opponent();
+        let Some(king_sq) = self.king_square(color) else {
             return false;
-        }
+        };
+        !self.is_square_attacked(king_sq, them)
+            && files
+                .iter()
+                .all(|&file| !self.is_square_attacked(back_rank_square(color, file), them))
+    }
 
-        true
+    /// Generate pseudo-legal check evasions for the side to move.
+    ///
+    /// See [`crate::movegen::generate_evasions`].
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::parse_fen;
+    ///
+    /// // Black queen on h4 checks the white king
+    /// let fen = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3";
+    /// let board = parse_fen(fen).unwrap();
+    /// let evasions = board.generate_evasions();
+    /// assert!(evasions.len() < engine::movegen::generate_moves(&board).len());
+    /// ```
+    pub fn generate_evasions(&self) -> crate::movelist::MoveList {
+        crate::movegen::generate_evasions(self)
     }
 
     /// Generate all legal moves for the current position.
     ///
     /// This generates pseudo-legal moves and filters out illegal ones.
+    /// When in check, only check evasions are generated and filtered.
+    /// Boards where a side doesn't have exactly one king have no legal moves
+    /// (see [`Self::check_searchable`]).
     ///
     /// # Example
     /// ```
@@ -960,36 +2036,283 @@ impl Board {
     /// assert_eq!(legal_moves.len(), 20); // 16 pawn moves + 4 knight moves
     /// ```
     pub fn generate_legal_moves(&self) -> crate::movelist::MoveList {
-        use crate::movegen::generate_moves;
+        use crate::movegen::{generate_evasions, generate_moves};
         use crate::movelist::MoveList;
 
-        let pseudo_legal = generate_moves(self);
-        let mut legal = MoveList::new();
+        if !self.has_one_king_each() {
+            return MoveList::new();
+        }
+
+        let mut legal = if self.is_in_check() {
+            generate_evasions(self)
+        } else {
+            generate_moves(self)
+        };
+        legal.retain(|&m| self.is_pseudo_legal_move_safe(m));
+        legal
+    }
+
+    /// Check that the engine can search this board.
+    ///
+    /// Boards reached by legal moves from a legal position always can.
+    /// Setup-mode boards (the UI's board editor, [`Self::empty`], or any FEN
+    /// [`parse_fen`](crate::io::parse_fen) accepts) may not: they are fine
+    /// to display, edit and serialize, but need exactly one king per side
+    /// and the side not to move out of check before they can be searched.
+    /// Unsearchable boards have no legal moves, the searcher returns a null
+    /// move for them and [`EngineImpl::analyze`](crate::EngineImpl::analyze)
+    /// reports the reason as an error.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::{Board, Unsearchable};
+    /// use engine::io::parse_fen;
+    /// use engine::piece::Color;
+    ///
+    /// assert_eq!(Board::startpos().check_searchable(), Ok(()));
+    ///
+    /// let board = parse_fen("4k3/8/8/8/8/8/8/R7 w - - 0 1").unwrap();
+    /// assert_eq!(board.check_searchable(), Err(Unsearchable::MissingKing(Color::White)));
+    /// ```
+    pub fn check_searchable(&self) -> Result<(), Unsearchable> {
+        for color in [Color::White, Color::Black] {
+            match self.piece_bb(PieceType::King, color).count() {
+                0 => return Err(Unsearchable::MissingKing(color)),
+                1 => {}
+                _ => return Err(Unsearchable::ExtraKings(color)),
+            }
+        }
+
+        let them = self.side_to_move.opponent();
+        if self.is_square_attacked(self.king_square_unchecked(them), self.side_to_move) {
+            return Err(Unsearchable::OpponentInCheck);
+        }
+        Ok(())
+    }
+
+    /// Whether [`Self::check_searchable`] succeeds.
+    pub fn is_searchable(&self) -> bool {
+        self.check_searchable().is_ok()
+    }
+
+    /// Exactly one king for each side (cheap part of the searchable check).
+    #[inline]
+    fn has_one_king_each(&self) -> bool {
+        self.pieces[Color::White.index()][PieceType::King.index()].count() == 1
+            && self.pieces[Color::Black.index()][PieceType::King.index()].count() == 1
+    }
 
-        for m in pseudo_legal {
-            if self.is_legal(m) {
-                legal.push(m);
+    /// Apply a sequence of SAN moves and return the resulting board.
+    ///
+    /// The first move that fails to parse is reported as
+    /// [`SanError::AtIndex`](crate::san::SanError::AtIndex) with its 0-based index.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    ///
+    /// let board = Board::startpos()
+    ///     .apply_moves_from_san(&["e4", "e5", "Nf3", "Nc6"])
+    ///     .unwrap();
+    /// assert_eq!(board.fullmove_number(), 3);
+    /// ```
+    pub fn apply_moves_from_san(&self, moves: &[&str]) -> Result<Board, crate::san::SanError> {
+        self.apply_moves_from_san_annotated(moves)
+            .map(|(board, _)| board)
+    }
+
+    /// Like [`Self::apply_moves_from_san`], but also returns every move with
+    /// its undo information so the game can be replayed backwards.
+    pub fn apply_moves_from_san_annotated(
+        &self,
+        moves: &[&str],
+    ) -> Result<(Board, Vec<(Move, UndoInfo)>), crate::san::SanError> {
+        let mut board = self.clone();
+        let mut history = Vec::with_capacity(moves.len());
+
+        for (index, san) in moves.iter().enumerate() {
+            let m = crate::san::parse_san(&board, san).map_err(|error| {
+                crate::san::SanError::AtIndex {
+                    index,
+                    error: Box::new(error),
+                }
+            })?;
+            let undo = board.make_move(m);
+            history.push((m, undo));
+        }
+
+        Ok((board, history))
+    }
+
+    /// Describe every way `self` differs from `other`, one line per
+    /// difference; empty if the boards are equal.
+    ///
+    /// Covers the pieces on each square, side to move, castling rights, en
+    /// passant square, both clocks and the Zobrist hash, plus the cached
+    /// king squares, material, piece counts and piece-square sums that
+    /// make/unmake keep up to date. Meant for test failures, see
+    /// [`crate::assert_boards_eq`].
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::io::parse_fen;
+    ///
+    /// let board = Board::startpos();
+    /// let other = parse_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    /// let diffs = board.debug_differences(&other);
+    /// assert!(diffs.contains(&"e2: P vs empty".to_string()));
+    /// assert!(diffs.contains(&"side to move: white vs black".to_string()));
+    /// assert!(board.debug_differences(&board).is_empty());
+    /// ```
+    pub fn debug_differences(&self, other: &Board) -> Vec<String> {
+        fn describe(piece: Option<Piece>) -> String {
+            piece.map_or("empty".to_string(), |p| p.to_string())
+        }
+        fn square_name(sq: Option<Square>) -> String {
+            sq.map_or("none".to_string(), |sq| sq.to_string())
+        }
+
+        let mut diffs = Vec::new();
+        let mut check = |name: &str, ours: String, theirs: String| {
+            if ours != theirs {
+                diffs.push(format!("{}: {} vs {}", name, ours, theirs));
             }
+        };
+
+        for sq in Square::all() {
+            check(
+                &sq.to_string(),
+                describe(self.piece_at(sq)),
+                describe(other.piece_at(sq)),
+            );
         }
+        check(
+            "side to move",
+            self.side_to_move.to_string(),
+            other.side_to_move.to_string(),
+        );
+        check(
+            "castling",
+            self.castling.to_string(),
+            other.castling.to_string(),
+        );
+        check(
+            "en passant",
+            square_name(self.ep_square),
+            square_name(other.ep_square),
+        );
+        check(
+            "halfmove clock",
+            self.halfmove_clock.to_string(),
+            other.halfmove_clock.to_string(),
+        );
+        check(
+            "fullmove number",
+            self.fullmove_number.to_string(),
+            other.fullmove_number.to_string(),
+        );
+        check(
+            "hash",
+            format!("{:#018x}", self.hash),
+            format!("{:#018x}", other.hash),
+        );
+        for color in Color::all() {
+            check(
+                &format!("{} king square", color),
+                square_name(self.king_squares[color.index()]),
+                square_name(other.king_squares[color.index()]),
+            );
+        }
+        check(
+            "material",
+            format!("{:?}", self.material),
+            format!("{:?}", other.material),
+        );
+        check(
+            "piece counts",
+            format!("{:?}", self.piece_counts),
+            format!("{:?}", other.piece_counts),
+        );
+        check(
+            "pst",
+            format!("{:?}", (self.pst_mg, self.pst_eg)),
+            format!("{:?}", (other.pst_mg, other.pst_eg)),
+        );
 
-        legal
+        // Bitboards that disagree with the squares above
+        if diffs.is_empty() && self != other {
+            diffs.push("bitboards differ with the same pieces on every square".to_string());
+        }
+        diffs
     }
 }
 
+/// Assert that two boards are equal, listing each difference (see
+/// [`Board::debug_differences`]) if they are not.
+///
+/// # Example
+/// ```
+/// use engine::assert_boards_eq;
+/// use engine::board::Board;
+/// use engine::san::parse_san;
+///
+/// let mut board = Board::startpos();
+/// let original = board.clone();
+/// let m = parse_san(&board, "Nf3").unwrap();
+/// let undo = board.make_move(m);
+/// board.unmake_move(m, undo);
+/// assert_boards_eq!(board, original, "after {}", m);
+/// ```
+#[macro_export]
+macro_rules! assert_boards_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_boards_eq!($left, $right, "boards differ")
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let (left, right): (&$crate::board::Board, &$crate::board::Board) = (&$left, &$right);
+        if left != right {
+            panic!(
+                "{}:\n  {}",
+                format_args!($($arg)+),
+                left.debug_differences(right).join("\n  ")
+            );
+        }
+    }};
+}
+
 impl Default for Board {
     fn default() -> Self {
         Self::startpos()
     }
 }
 
+/// Hashes the Zobrist key, so boards can go in a `HashSet` without
+/// rehashing every field.
+///
+/// This agrees with `==` (equal boards have equal keys) and, since the key
+/// ignores the move clocks, also with [`Board::position_eq`]. Different
+/// positions can still collide on the 64-bit key; hashed collections fall
+/// back to `==` for those, but code comparing keys alone (the transposition
+/// table, repetition detection) accepts that rare false match.
+impl std::hash::Hash for Board {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// Draws the board with FEN letters, or with Unicode chess symbols for
+/// `{:#?}` where the output is known to render them.
 impl std::fmt::Debug for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unicode = f.alternate();
         writeln!(f)?;
         for rank in (0..8).rev() {
             write!(f, "{} ", rank + 1)?;
             for file in 0..8 {
                 let sq = Square::from_coords(file, rank);
                 let c = match self.piece_at(sq) {
+                    Some(piece) if unicode => piece.to_unicode_char(),
                     Some(piece) => piece.to_char(),
                     None => '.',
                 };
@@ -999,7 +2322,7 @@ impl std::fmt::Debug for Board {
         }
         writeln!(f, "  a b c d e f g h")?;
         writeln!(f, "Side to move: {}", self.side_to_move)?;
-        writeln!(f, "Castling: {}", format_castling(self.castling))?;
+        writeln!(f, "Castling: {}", self.castling)?;
         writeln!(f, "EP square: {:?}", self.ep_square)?;
         writeln!(f, "Halfmove: {}", self.halfmove_clock)?;
         writeln!(f, "Fullmove: {}", self.fullmove_number)?;
@@ -1007,29 +2330,25 @@ impl std::fmt::Debug for Board {
     }
 }
 
-fn format_castling(castling: CastlingRights) -> String {
-    if castling.bits() == 0 {
-        return "-".to_string();
-    }
-    let mut s = String::new();
-    if castling.white_kingside() {
-        s.push('K');
-    }
-    if castling.white_queenside() {
-        s.push('Q');
-    }
-    if castling.black_kingside() {
-        s.push('k');
+/// Square on `file` of `color`'s back rank.
+fn back_rank_square(color: Color, file: u8) -> Square {
+    match color {
+        Color::White => Square::from_coords(file, 0),
+        Color::Black => Square::from_coords(file, 7),
     }
-    if castling.black_queenside() {
-        s.push('q');
-    }
-    s
+}
+
+/// The square the rook lands on when castling with `m`: the f-file
+/// kingside, the d-file queenside.
+fn castling_rook_square(m: Move) -> Square {
+    let file = if m.is_kingside_castle() { 5 } else { 3 };
+    Square::from_coords(file, m.from().rank())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_boards_eq;
     use crate::r#move::MoveFlags;
 
     #[test]
@@ -1050,6 +2369,27 @@ mod tests {
         assert!(!rights.black_queenside());
     }
 
+    #[test]
+    fn castling_rights_fen_round_trip() {
+        for bits in 0..16 {
+            let rights = CastlingRights::from_bits(bits);
+            let fen = rights.to_string();
+            assert_eq!(fen.parse::<CastlingRights>(), Ok(rights));
+
+            let json = serde_json::to_string(&rights).unwrap();
+            assert_eq!(json, format!("\"{}\"", fen));
+            assert_eq!(
+                serde_json::from_str::<CastlingRights>(&json).unwrap(),
+                rights
+            );
+        }
+        assert_eq!(CastlingRights::all().to_string(), "KQkq");
+        assert_eq!(CastlingRights::none().to_string(), "-");
+        assert_eq!("qK".parse(), Ok(CastlingRights::from_bits(0b1001)));
+        assert!("KQx".parse::<CastlingRights>().is_err());
+        assert!("".parse::<CastlingRights>().is_err());
+    }
+
     #[test]
     fn castling_rights_partial() {
         let rights = CastlingRights::none()
@@ -1101,6 +2441,16 @@ mod tests {
         assert_eq!(board.occupied().count(), 32);
     }
 
+    #[test]
+    fn debug_draws_unicode_when_alternate() {
+        let board = Board::startpos();
+        let ascii = format!("{:?}", board);
+        assert!(ascii.contains("8 r n b q k b n r"));
+        let unicode = format!("{:#?}", board);
+        assert!(unicode.contains("8 ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜"));
+        assert!(unicode.contains("1 ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖"));
+    }
+
     #[test]
     fn set_and_remove_piece() {
         let mut board = Board::empty();
@@ -1192,7 +2542,7 @@ mod tests {
         board.unmake_move(m, undo);
 
         // Board should be identical to original
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 
     #[test]
@@ -1222,7 +2572,7 @@ mod tests {
         board.unmake_move(m, undo);
 
         // Board should be restored
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 
     #[test]
@@ -1235,7 +2585,7 @@ mod tests {
 
         let original = board.clone();
 
-        let m = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let m = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         let undo = board.make_move(m);
 
         // King should be on G1
@@ -1262,7 +2612,7 @@ mod tests {
         board.unmake_move(m, undo);
 
         // Board should be restored
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 
     #[test]
@@ -1287,7 +2637,7 @@ mod tests {
         board.unmake_move(m, undo);
 
         // Board should be restored (pawn back on E7)
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 
     #[test]
@@ -1325,7 +2675,7 @@ mod tests {
         board.unmake_move(ep_capture, undo);
 
         // Board should be restored
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 
     #[test]
@@ -1434,13 +2784,98 @@ mod tests {
             ),
         ];
 
-        // Unmake in reverse order
-        for (m, undo) in moves_and_undos.into_iter().rev() {
-            board.unmake_move(m, undo);
+        // Unmake in reverse order
+        for (m, undo) in moves_and_undos.into_iter().rev() {
+            board.unmake_move(m, undo);
+        }
+
+        // Should be back to original
+        assert_boards_eq!(board, original);
+    }
+
+    #[test]
+    fn king_square_startpos() {
+        let board = Board::startpos();
+        assert_eq!(board.king_square(Color::White), Some(Square::E1));
+        assert_eq!(board.king_square(Color::Black), Some(Square::E8));
+        assert_eq!(board.king_square_unchecked(Color::Black), Square::E8);
+    }
+
+    #[test]
+    fn king_square_absent() {
+        let mut board = Board::empty();
+        assert_eq!(board.king_square(Color::White), None);
+        assert_eq!(board.king_square(Color::Black), None);
+
+        board.set_piece(Square::E1, Piece::new(PieceType::King, Color::White));
+        assert_eq!(board.king_square(Color::White), Some(Square::E1));
+        assert_eq!(board.king_square(Color::Black), None);
+
+        // Overwriting the king with another piece clears the cache
+        board.set_piece(Square::E1, Piece::new(PieceType::Rook, Color::White));
+        assert_eq!(board.king_square(Color::White), None);
+
+        board.set_piece(Square::E1, Piece::new(PieceType::King, Color::White));
+        board.remove_piece(Square::E1);
+        assert_eq!(board.king_square(Color::White), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn king_square_unchecked_panics_without_king() {
+        Board::empty().king_square_unchecked(Color::White);
+    }
+
+    #[test]
+    fn king_square_make_unmake() {
+        let mut board = Board::empty();
+        board.set_piece(Square::E1, Piece::new(PieceType::King, Color::White));
+        board.set_piece(Square::H1, Piece::new(PieceType::Rook, Color::White));
+        board.set_piece(Square::E8, Piece::new(PieceType::King, Color::Black));
+        board.set_castling(CastlingRights::all());
+        let original = board.clone();
+
+        // Quiet king move
+        let m = Move::new(Square::E1, Square::E2, MoveFlags::QUIET);
+        let undo = board.make_move(m);
+        assert_eq!(board.king_square(Color::White), Some(Square::E2));
+        board.unmake_move(m, undo);
+        assert_eq!(board.king_square(Color::White), Some(Square::E1));
+
+        // Castling moves the king as well
+        let castle = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
+        let undo = board.make_move(castle);
+        assert_eq!(board.king_square(Color::White), Some(Square::G1));
+        assert_eq!(board.king_square(Color::Black), Some(Square::E8));
+        board.unmake_move(castle, undo);
+        assert_boards_eq!(board, original);
+    }
+
+    #[test]
+    fn debug_differences_lists_each_difference() {
+        let board = Board::startpos();
+        assert!(board.debug_differences(&board.clone()).is_empty());
+
+        // Castle without moving the rook, and forget to flip the side
+        let mut broken = board.clone();
+        broken.remove_piece(Square::E1);
+        broken.set_piece(Square::G1, Piece::new(PieceType::King, Color::White));
+        broken.set_castling(CastlingRights::none());
+        let diffs = board.debug_differences(&broken);
+        for expected in ["e1: K vs empty", "g1: N vs K", "castling: KQkq vs -"] {
+            assert!(diffs.contains(&expected.to_string()), "{:?}", diffs);
         }
+        assert!(diffs.contains(&"white king square: e1 vs g1".to_string()));
+        assert!(!diffs.iter().any(|d| d.starts_with("side to move")));
+    }
 
-        // Should be back to original
-        assert_eq!(board, original);
+    #[test]
+    #[should_panic(expected = "after castling:\n  g1: N vs K")]
+    fn assert_boards_eq_reports_differences() {
+        let mut board = Board::startpos();
+        let original = board.clone();
+        board.set_piece(Square::G1, Piece::new(PieceType::King, Color::White));
+        assert_boards_eq!(original, board, "after castling");
     }
 
     #[test]
@@ -1504,6 +2939,290 @@ mod tests {
         assert!(board.is_legal(legal_move));
     }
 
+    #[test]
+    fn test_pinned() {
+        use crate::io::parse_fen;
+
+        // Knight pinned on the e-file; the d2 bishop is shielded by the black
+        // pawn on c3
+        let fen = "k3r3/8/8/b7/8/2p5/3BN3/4K3 w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        assert_eq!(board.pinned(), Bitboard::from_square(Square::E2));
+
+        // Knight on the file and bishop on the diagonal
+        let fen = "k3r3/8/8/b7/8/8/3BN3/4K3 w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let pinned = board.pinned();
+        assert_eq!(pinned.count(), 2);
+        assert!(pinned.contains(Square::E2));
+        assert!(pinned.contains(Square::from_coords(3, 1))); // D2
+
+        // Two pieces on the line: neither is pinned
+        let fen = "k3r3/8/8/8/4N3/8/4N3/4K3 w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        assert!(board.pinned().is_empty());
+
+        // Pinned for black too
+        let fen = "4k3/4n3/8/8/8/8/8/K3Q3 b - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        assert_eq!(
+            board.pinned(),
+            Bitboard::from_square(Square::from_coords(4, 6)) // E7
+        );
+
+        // The h2 rook pins f2; the a2 rook checks from the other side of
+        // the king and pins nothing, and the c4 rook cannot pin the d3
+        // knight along a diagonal
+        let fen = "k7/8/8/8/2r5/3N4/r3KR1r/8 w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let f2 = Square::from_coords(5, 1);
+        assert_eq!(board.pinned(), Bitboard::from_square(f2));
+    }
+
+    #[test]
+    fn test_pawn_levers_and_breaks() {
+        use crate::io::parse_fen;
+        use crate::movelist::MoveList;
+
+        let names = |moves: MoveList| moves.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+
+        assert!(Board::startpos().pawn_levers(Color::White).is_empty());
+        assert!(Board::startpos().pawn_breaks(Color::Black).is_empty());
+
+        // French Defense, advance variation after 4...f6: both sides can
+        // capture on c5/d4 and e5/f6, but c5 is not protected by a pawn
+        let board =
+            parse_fen("rnbqkbnr/pp4pp/4pp2/2ppP3/3P4/2P5/PP3PPP/RNBQKBNR w KQkq - 0 5").unwrap();
+        assert_eq!(names(board.pawn_levers(Color::White)), ["d4c5", "e5f6"]);
+        assert_eq!(names(board.pawn_breaks(Color::White)), ["e5f6"]);
+        assert_eq!(names(board.pawn_levers(Color::Black)), ["c5d4", "f6e5"]);
+        assert_eq!(names(board.pawn_breaks(Color::Black)), ["f6e5"]);
+
+        // With ...b6 the c5 pawn joins a chain and the queenside levers
+        // become breaks too
+        let board =
+            parse_fen("rnbqkbnr/p5pp/1p2pp2/2ppP3/3P4/2P5/PP3PPP/RNBQKBNR w KQkq - 0 6").unwrap();
+        assert_eq!(names(board.pawn_breaks(Color::White)), ["d4c5", "e5f6"]);
+        assert_eq!(names(board.pawn_breaks(Color::Black)), ["c5d4", "f6e5"]);
+
+        // Only one pawn of each capture is protected: levers, not breaks
+        let board = parse_fen("4k3/8/4p3/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(names(board.pawn_levers(Color::White)), ["e4d5"]);
+        assert!(board.pawn_breaks(Color::White).is_empty());
+        assert_eq!(names(board.pawn_levers(Color::Black)), ["d5e4"]);
+        assert!(board.pawn_breaks(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_threats_to_king() {
+        use crate::io::parse_fen;
+
+        assert!(Board::startpos().threats_to_king(Color::White).is_empty());
+        assert!(Board::empty().threats_to_king(Color::White).is_empty());
+
+        // Double check from the d6 knight and the e1 rook
+        let board = parse_fen("4k3/8/3N4/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+        let threats = board.threats_to_king(Color::Black);
+        assert_eq!(threats.len(), 2);
+        assert!(threats.iter().all(|t| t.attack_type == AttackType::Direct));
+        assert_eq!(threats[0].attacker_sq, Square::E1);
+        assert_eq!(
+            threats[0].attacker_piece,
+            Piece::new(PieceType::Rook, Color::White)
+        );
+
+        // The h4 queen checks and the a4 queen is behind the b4 knight; the
+        // b7 bishop is behind two pawns, so it does not count
+        let board = parse_fen("8/1B6/2p5/3p4/QN2k2Q/8/8/6K1 b - - 0 1").unwrap();
+        let threats = board.threats_to_king(Color::Black);
+        assert_eq!(
+            threats
+                .iter()
+                .map(|t| (t.attacker_sq.to_algebraic(), t.attack_type))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a4".to_string(), AttackType::Discovery),
+                ("h4".to_string(), AttackType::Direct),
+            ]
+        );
+
+        // A queen behind a checking rook on the same file is a discovery
+        let board = parse_fen("4k3/8/8/8/8/4R3/4Q3/6K1 b - - 0 1").unwrap();
+        let threats = board.threats_to_king(Color::Black);
+        assert_eq!(threats.len(), 2);
+        assert_eq!(threats[0].attacker_sq, Square::E2);
+        assert_eq!(threats[0].attack_type, AttackType::Discovery);
+        assert_eq!(threats[1].attack_type, AttackType::Direct);
+    }
+
+    #[test]
+    fn test_discovered_check_candidates() {
+        use crate::io::parse_fen;
+
+        // Knight in front of the rook, bishop on the queen's diagonal
+        let fen = "4k3/8/2B5/8/Q7/8/4N3/4R2K w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let candidates = board.discovered_check_candidates();
+        assert_eq!(candidates.count(), 2);
+        assert!(candidates.contains(Square::E2));
+        assert!(candidates.contains(Square::from_coords(2, 5))); // C6
+
+        // Enemy pieces in between are not candidates
+        let fen = "4k3/4n3/8/8/8/8/8/4R2K w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        assert!(board.discovered_check_candidates().is_empty());
+
+        // Moving a candidate really does give check
+        let fen = "4k3/8/8/8/8/8/4N3/4R2K w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let m = Move::new(Square::E2, Square::from_coords(2, 2), MoveFlags::QUIET); // Nc3
+        assert!(board.gives_check(m));
+    }
+
+    #[test]
+    fn test_check_searchable() {
+        use crate::io::parse_fen;
+
+        assert!(Board::startpos().is_searchable());
+        assert_eq!(
+            Board::empty().check_searchable(),
+            Err(Unsearchable::MissingKing(Color::White))
+        );
+
+        let board = parse_fen("4k3/8/8/8/8/8/8/4K1KR w - - 0 1").unwrap();
+        assert_eq!(
+            board.check_searchable(),
+            Err(Unsearchable::ExtraKings(Color::White))
+        );
+        assert!(board.generate_legal_moves().is_empty());
+
+        // White to move with the black king in check
+        let board = parse_fen("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(board.check_searchable(), Err(Unsearchable::OpponentInCheck));
+        assert_eq!(
+            Unsearchable::OpponentInCheck.to_string(),
+            "side not to move is in check"
+        );
+    }
+
+    #[test]
+    fn test_controlled_squares_startpos() {
+        let board = Board::startpos();
+        let white = board.controlled_squares(Color::White);
+        let black = board.controlled_squares(Color::Black);
+
+        // Ranks 2 and 3 plus b1-g1 (a1 and h1 are not defended)
+        assert_eq!(white.count(), 22);
+        assert_eq!(black.count(), 22);
+        assert!(white.contains(Square::from_coords(1, 0))); // B1
+        assert!(!white.contains(Square::A1) && !white.contains(Square::H1));
+        assert!((white & black).is_empty());
+
+        // Same answer as asking square by square
+        for color in [Color::White, Color::Black] {
+            let controlled = board.controlled_squares(color);
+            for index in 0..64 {
+                let sq = Square::new(index);
+                assert_eq!(controlled.contains(sq), board.is_square_attacked(sq, color));
+            }
+        }
+    }
+
+    #[test]
+    fn test_controlled_squares_sliders_and_king_moves() {
+        use crate::io::parse_fen;
+        use crate::movegen::generate_moves;
+
+        // The rook's file stops at the white king, which can neither step
+        // back along it nor next to the black king
+        let board = parse_fen("4r3/8/8/8/8/8/2k5/4K3 w - - 0 1").unwrap();
+        let controlled = board.controlled_squares(Color::Black);
+        assert!(controlled.contains(Square::E2));
+        assert!(controlled.contains(Square::E1));
+        assert!(!controlled.contains(Square::from_coords(5, 0))); // F1
+
+        let king_moves: Vec<_> = generate_moves(&board).iter().map(|m| m.to()).collect();
+        assert_eq!(
+            king_moves,
+            vec![Square::from_coords(5, 0), Square::from_coords(5, 1)] // F1, F2
+        );
+    }
+
+    #[test]
+    fn test_hanging_defended_but_losing() {
+        use crate::io::parse_fen;
+
+        // Knight attacked by a pawn and defended by a rook: still hanging
+        let board = parse_fen("4k3/8/8/4p3/3N4/8/8/3RK3 w - - 0 1").unwrap();
+        let d4 = Square::D4;
+        assert_eq!(board.en_prise_value(d4), 220);
+        assert!(board.is_hanging(d4));
+        assert_eq!(board.hanging_pieces(Color::White), vec![d4]);
+        assert!(board.hanging_pieces(Color::Black).is_empty());
+
+        let (piece, from) = board.least_valuable_attacker(d4, Color::Black).unwrap();
+        assert_eq!(piece, Piece::new(PieceType::Pawn, Color::Black));
+        assert_eq!(from, Square::from_coords(4, 4));
+
+        // Defended pawn attacked by a knight: safe
+        let board = parse_fen("4k3/8/3p4/4p3/8/5N2/8/4K3 w - - 0 1").unwrap();
+        assert!(!board.is_hanging(Square::from_coords(4, 4)));
+        assert!(board.hanging_pieces(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_hanging_xray() {
+        use crate::io::parse_fen;
+
+        let e4 = Square::E4;
+
+        // Doubled rooks: the e8 rook x-rays through e7, one defender isn't enough
+        let board = parse_fen("4r1k1/4r3/8/8/4N3/8/8/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(board.en_prise_value(e4), 320);
+        assert!(board.is_hanging(e4));
+
+        // A queen behind the defending rook restores the balance
+        let board = parse_fen("4r1k1/4r3/8/8/4N3/8/4R3/4Q1K1 w - - 0 1").unwrap();
+        assert_eq!(board.en_prise_value(e4), 0);
+        assert!(!board.is_hanging(e4));
+
+        // The king can't recapture on a square that's still attacked
+        let board = parse_fen("6k1/8/8/8/8/8/rr2P3/4K3 w - - 0 1").unwrap();
+        assert!(board.is_hanging(Square::E2));
+        let board = parse_fen("6k1/8/8/8/8/8/r3P3/4K3 w - - 0 1").unwrap();
+        assert!(!board.is_hanging(Square::E2));
+        assert!(board
+            .least_valuable_attacker(Square::A8, Color::White)
+            .is_none());
+    }
+
+    #[test]
+    fn test_is_stalemate_after() {
+        use crate::io::parse_fen;
+        use crate::san::parse_san;
+
+        let cases = [
+            // Queen takes the last squares from a cornered king
+            ("7k/8/8/8/8/8/8/5QK1 w - - 0 1", "Qf7", true),
+            ("7k/8/8/8/8/8/8/5QK1 w - - 0 1", "Qf6+", false),
+            ("k7/8/2K5/8/8/8/8/1Q6 w - - 0 1", "Qb6", true),
+            ("k7/8/2K5/8/8/8/8/1Q6 w - - 0 1", "Qb7#", false),
+            // Capturing the last mobile pawn
+            ("7k/5p2/8/8/8/8/8/5QK1 w - - 0 1", "Qxf7", true),
+            // King and pawn: pushing to the 7th with the king on f6 is fine,
+            // Kg6 stalemates once the pawn is on h7
+            ("7k/7P/5K2/8/8/8/8/8 w - - 0 1", "Kg6", true),
+            ("7k/8/5K1P/8/8/8/8/8 w - - 0 1", "h7", false),
+        ];
+
+        for (fen, san, expected) in cases {
+            let board = parse_fen(fen).unwrap();
+            let m = parse_san(&board, san).unwrap();
+            assert_eq!(board.is_stalemate_after(m), expected, "{} {}", fen, san);
+            assert_eq!(board.would_cause_stalemate_for_opponent(m), expected);
+        }
+    }
+
     #[test]
     fn test_is_legal_blocks_check() {
         use crate::io::parse_fen;
@@ -1523,6 +3242,56 @@ mod tests {
         assert!(board.is_legal(king_move));
     }
 
+    #[test]
+    fn test_make_move_if_legal_matches_is_legal() {
+        use crate::io::parse_fen;
+        use crate::movegen::generate_moves;
+
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            // Castling through the f1 bishop's diagonal, pinned knight
+            "r3k2r/8/8/8/8/8/4N3/R3K2R w KQkq - 0 1",
+            "4r1k1/8/8/8/2b5/8/4N3/R3K2R w KQ - 0 1",
+        ];
+
+        for fen in fens {
+            let board = parse_fen(fen).unwrap();
+            for m in generate_moves(&board) {
+                let mut copy = board.clone();
+                match copy.make_move_if_legal(m) {
+                    Some(undo) => {
+                        assert!(board.is_legal(m), "{} in {}", m, fen);
+                        let mut expected = board.clone();
+                        assert_eq!(expected.make_move(m), undo);
+                        assert_boards_eq!(copy, expected);
+                    }
+                    None => {
+                        assert!(!board.is_legal(m), "{} in {}", m, fen);
+                        assert_boards_eq!(copy, board);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_make_move_if_legal_rejects_impossible_moves() {
+        let mut board = Board::startpos();
+        let before = board.clone();
+
+        assert!(board.make_move_if_legal(Move::null()).is_none());
+        assert!(board
+            .make_move_if_legal(Move::new(Square::E2, Square::E5, MoveFlags::QUIET))
+            .is_none());
+        assert_boards_eq!(board, before);
+
+        let m = Move::new(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH);
+        let undo = board.make_move_if_legal(m).unwrap();
+        board.unmake_move(m, undo);
+        assert_boards_eq!(board, before);
+    }
+
     #[test]
     fn test_castling_legal() {
         let mut board = Board::empty();
@@ -1531,7 +3300,7 @@ mod tests {
         board.set_side_to_move(Color::White);
         board.set_castling(CastlingRights::all());
 
-        let castle_move = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let castle_move = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         assert!(board.is_legal(castle_move));
     }
 
@@ -1544,7 +3313,7 @@ mod tests {
         board.set_side_to_move(Color::White);
         board.set_castling(CastlingRights::all());
 
-        let castle_move = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let castle_move = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         assert!(!board.is_legal(castle_move)); // Can't castle through check
     }
 
@@ -1560,7 +3329,7 @@ mod tests {
         board.set_side_to_move(Color::White);
         board.set_castling(CastlingRights::all());
 
-        let castle_move = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let castle_move = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         assert!(!board.is_legal(castle_move)); // Can't castle into check
     }
 
@@ -1573,10 +3342,147 @@ mod tests {
         board.set_side_to_move(Color::White);
         board.set_castling(CastlingRights::all());
 
-        let castle_move = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let castle_move = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         assert!(!board.is_legal(castle_move)); // Can't castle out of check
     }
 
+    #[test]
+    fn can_castle_with_clear_safe_paths() {
+        for fen in [
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1",
+        ] {
+            let board = crate::io::parse_fen(fen).unwrap();
+            let us = board.side_to_move();
+            assert!(board.can_pseudo_castle_kingside(us), "{fen}");
+            assert!(board.can_pseudo_castle_queenside(us), "{fen}");
+            assert!(board.can_castle_kingside(), "{fen}");
+            assert!(board.can_castle_queenside(), "{fen}");
+        }
+    }
+
+    #[test]
+    fn castling_blocked_or_without_rights() {
+        // Knights on b1/g1 and b8/g8 block one square of each path
+        for fen in [
+            "rn2k1nr/8/8/8/8/8/8/RN2K1NR w KQkq - 0 1",
+            "rn2k1nr/8/8/8/8/8/8/RN2K1NR b KQkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R b - - 0 1",
+        ] {
+            let board = crate::io::parse_fen(fen).unwrap();
+            let us = board.side_to_move();
+            assert!(!board.can_pseudo_castle_kingside(us), "{fen}");
+            assert!(!board.can_pseudo_castle_queenside(us), "{fen}");
+            assert!(!board.can_castle_kingside(), "{fen}");
+            assert!(!board.can_castle_queenside(), "{fen}");
+        }
+
+        // The other side's rights and path don't matter
+        let board = crate::io::parse_fen("rn2k1nr/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        assert!(board.can_pseudo_castle_kingside(Color::White));
+        assert!(!board.can_pseudo_castle_kingside(Color::Black));
+    }
+
+    #[test]
+    fn castling_through_attacked_squares() {
+        // White: knight on h2 covers f1, rook on d3 covers d1
+        let board = crate::io::parse_fen("r3k2r/8/8/8/8/3r4/7n/R3K2R w KQkq - 0 1").unwrap();
+        assert!(board.can_pseudo_castle_kingside(Color::White));
+        assert!(board.can_pseudo_castle_queenside(Color::White));
+        assert!(!board.can_castle_kingside());
+        assert!(!board.can_castle_queenside());
+
+        // Black: knight on h6 covers g8, bishop on a5 covers d8
+        let board = crate::io::parse_fen("r3k2r/8/7N/B7/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+        assert!(board.can_pseudo_castle_kingside(Color::Black));
+        assert!(board.can_pseudo_castle_queenside(Color::Black));
+        assert!(!board.can_castle_kingside());
+        assert!(!board.can_castle_queenside());
+
+        // No castling out of check
+        let board = crate::io::parse_fen("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1").unwrap();
+        assert!(!board.can_castle_kingside());
+        assert!(!board.can_castle_queenside());
+    }
+
+    #[test]
+    fn queenside_castling_ignores_attacked_b_file() {
+        // Only the rook crosses b1/b8
+        for fen in [
+            "r3k2r/8/8/8/8/8/b7/R3K2R w KQkq - 0 1",
+            "r3k2r/B7/8/8/8/8/8/R3K2R b KQkq - 0 1",
+        ] {
+            let board = crate::io::parse_fen(fen).unwrap();
+            assert!(board.can_castle_queenside(), "{fen}");
+        }
+    }
+
+    fn uci_move(from: &str, to: &str, flags: MoveFlags) -> Move {
+        Move::new(
+            Square::from_algebraic(from).unwrap(),
+            Square::from_algebraic(to).unwrap(),
+            flags,
+        )
+    }
+
+    #[test]
+    fn pseudo_legal_rejects_impossible_moves() {
+        use MoveFlags as F;
+
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let open = "r3k2r/1P4p1/8/3pP3/8/2n5/6P1/R3K2R w KQkq d6 0 1";
+        let cases = [
+            (start, "e3", "e4", F::QUIET, "empty from-square"),
+            (start, "e7", "e5", F::DOUBLE_PAWN_PUSH, "opponent's piece"),
+            (start, "d1", "d2", F::QUIET, "own piece on to-square"),
+            (start, "e2", "e5", F::QUIET, "pawn pushed three squares"),
+            (start, "e2", "e4", F::QUIET, "double push without its flag"),
+            (start, "e2", "e3", F::DOUBLE_PAWN_PUSH, "not a double push"),
+            (start, "e2", "d3", F::CAPTURE, "pawn capturing nothing"),
+            (start, "f1", "b5", F::QUIET, "bishop jumping over a pawn"),
+            (start, "a1", "a3", F::QUIET, "rook jumping over a pawn"),
+            (start, "g1", "g3", F::QUIET, "knight moving straight"),
+            (start, "e1", "h1", F::KING_CASTLE, "castling through pieces"),
+            (start, "g1", "f3", F::CAPTURE, "knight capturing nothing"),
+            (open, "g2", "g1", F::QUIET, "pawn moving backwards"),
+            (open, "b7", "b8", F::QUIET, "unpromoted last-rank push"),
+            (open, "g2", "g3", F::QUEEN_PROMOTION, "early promotion"),
+            (open, "e1", "f2", F::QUEEN_PROMOTION, "promotion by a king"),
+            (open, "e5", "f6", F::EP_CAPTURE, "en passant, wrong square"),
+            (open, "e1", "e2", F::EP_CAPTURE, "en passant by a king"),
+            (open, "e1", "d1", F::QUEEN_CASTLE, "castling, wrong square"),
+            (open, "e1", "g1", F::KING_CASTLE, "castling onto g1"),
+            (open, "a1", "c3", F::QUIET, "quiet move onto an enemy piece"),
+        ];
+
+        for (fen, from, to, flags, reason) in cases {
+            let board = crate::io::parse_fen(fen).unwrap();
+            let m = uci_move(from, to, flags);
+            assert!(!board.is_pseudo_legal(m), "{reason}: {from}{to}");
+            assert!(!board.is_legal(m), "{reason}: {from}{to}");
+        }
+        assert!(!Board::startpos().is_pseudo_legal(Move::null()));
+    }
+
+    #[test]
+    fn pseudo_legal_accepts_generated_moves() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/1P4p1/8/3pP3/8/2n5/6P1/R3K2R w KQkq d6 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbqkb1r/pp1p1pPp/8/2p1pP2/1P1P4/3P3P/P1P1P3/RNBQKBNR w KQkq e6 0 1",
+        ] {
+            let board = crate::io::parse_fen(fen).unwrap();
+            let legal = board.generate_legal_moves();
+            for m in crate::movegen::generate_moves(&board) {
+                assert!(board.is_pseudo_legal(m), "{fen}: {}", m.to_uci());
+                let in_legal = legal.iter().any(|&l| l == m);
+                assert_eq!(board.is_legal(m), in_legal, "{fen}: {}", m.to_uci());
+            }
+        }
+    }
+
     #[test]
     fn test_generate_legal_moves_startpos() {
         let board = Board::startpos();
@@ -1626,4 +3532,283 @@ mod tests {
             assert!(board.is_legal(m));
         }
     }
+
+    #[test]
+    fn test_apply_moves_from_san_scholars_mate() {
+        use crate::io::ToFen;
+
+        let board = Board::startpos()
+            .apply_moves_from_san(&["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"])
+            .unwrap();
+
+        assert!(board.is_in_check());
+        assert!(board.generate_legal_moves().is_empty());
+        assert_eq!(
+            board.to_fen(),
+            "r1bqkb1r/pppp1Qpp/2n2n2/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 4"
+        );
+    }
+
+    #[test]
+    fn test_apply_moves_from_san_reports_index() {
+        let err = Board::startpos()
+            .apply_moves_from_san(&["e4", "e5", "Ke3", "Nc6"])
+            .unwrap_err();
+        assert_eq!(err.index(), Some(2));
+        assert!(matches!(
+            err,
+            crate::san::SanError::AtIndex { error, .. }
+                if *error == crate::san::SanError::IllegalMove("Ke3".into())
+        ));
+    }
+
+    #[test]
+    fn test_apply_moves_from_san_annotated_replays_back() {
+        let start = Board::startpos();
+        let (mut board, history) = start
+            .apply_moves_from_san_annotated(&["d4", "d5", "c4", "dxc4"])
+            .unwrap();
+        assert_eq!(history.len(), 4);
+        assert!(history[3].1.captured_piece.is_some());
+
+        for (m, undo) in history.into_iter().rev() {
+            board.unmake_move(m, undo);
+        }
+        assert_eq!(board.hash(), start.hash());
+    }
+
+    /// Material and piece-square sums recomputed from scratch.
+    fn recomputed_scores(board: &Board, color: Color) -> (i32, i32, i32) {
+        use crate::eval::{default_piece_square_value, evaluate_material};
+
+        let (mut mg, mut eg) = (0, 0);
+        for piece_type in PieceType::all() {
+            for sq in board.piece_bb(piece_type, color) {
+                let (m, e) = default_piece_square_value(Piece::new(piece_type, color), sq);
+                mg += m;
+                eg += e;
+            }
+        }
+        (evaluate_material(board, color), mg, eg)
+    }
+
+    fn assert_scores_match(board: &Board) {
+        for color in [Color::White, Color::Black] {
+            for piece_type in PieceType::all() {
+                assert_eq!(
+                    board.piece_count(piece_type, color),
+                    board.piece_bb(piece_type, color).count(),
+                    "{:?} {:?}",
+                    color,
+                    piece_type
+                );
+            }
+            assert_eq!(
+                (
+                    board.material(color),
+                    board.pst_mg(color),
+                    board.pst_eg(color)
+                ),
+                recomputed_scores(board, color),
+                "{:?}",
+                color
+            );
+        }
+    }
+
+    #[test]
+    fn test_incremental_scores_through_make_unmake() {
+        use crate::io::parse_fen;
+
+        // Castling, en passant and promotions (with and without capture)
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ];
+
+        for fen in fens {
+            let mut board = parse_fen(fen).unwrap();
+            assert_scores_match(&board);
+            let original = board.clone();
+
+            for &m in board.clone().generate_legal_moves().iter() {
+                let undo = board.make_move(m);
+                assert_scores_match(&board);
+                for &reply in board.clone().generate_legal_moves().iter() {
+                    let mut grandchild = board.clone();
+                    grandchild.make_move(reply);
+                    assert_scores_match(&grandchild);
+                }
+                board.unmake_move(m, undo);
+                assert_boards_eq!(board, original, "{}", m.to_uci());
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_scores_unchanged_by_null_move() {
+        let mut board = Board::startpos();
+        let before = (
+            board.material(Color::White),
+            board.pst_mg(Color::White),
+            board.pst_eg(Color::Black),
+        );
+        board.make_null_move();
+        assert_eq!(
+            (
+                board.material(Color::White),
+                board.pst_mg(Color::White),
+                board.pst_eg(Color::Black),
+            ),
+            before
+        );
+        assert_scores_match(&board);
+
+        assert_eq!(Board::empty().material(Color::White), 0);
+        assert_eq!(Board::startpos().material(Color::Black), 24_000);
+    }
+
+    #[test]
+    fn test_pieces_iter_matches_piece_at() {
+        let board = crate::io::parse_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+
+        let pieces: Vec<_> = board.pieces_iter().collect();
+        assert_eq!(pieces.len() as u32, board.occupied().count());
+        for (sq, piece) in &pieces {
+            assert_eq!(board.piece_at(*sq), Some(*piece));
+        }
+
+        for color in Color::all() {
+            for piece_type in PieceType::all() {
+                let expected = pieces
+                    .iter()
+                    .filter(|(_, p)| *p == Piece::new(piece_type, color))
+                    .count();
+                assert_eq!(board.piece_count(piece_type, color) as usize, expected);
+            }
+        }
+        assert_eq!(board.piece_count(PieceType::Pawn, Color::White), 8);
+        assert_eq!(board.piece_count(PieceType::Queen, Color::Black), 1);
+        assert_eq!(Board::empty().pieces_iter().count(), 0);
+    }
+
+    #[test]
+    fn hash_consistency_after_random_game() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x2B1D);
+        let mut board = Board::startpos();
+        let mut history = Vec::new();
+        for _ in 0..50 {
+            let moves = board.generate_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let m = moves[rng.gen_range(0..moves.len())];
+            history.push((m, board.make_move(m)));
+            assert!(board.validate_hash_consistency(), "after {}", m);
+        }
+        while let Some((m, undo)) = history.pop() {
+            board.unmake_move(m, undo);
+            assert!(board.validate_hash_consistency(), "unmaking {}", m);
+        }
+        assert_eq!(board, Board::startpos());
+    }
+
+    #[test]
+    fn test_try_make_null_move_refuses_in_check() {
+        // Black rook on e8 checks the white king
+        let mut board = crate::io::parse_fen("4r1k1/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let before = board.clone();
+        assert!(board.try_make_null_move().is_none());
+        assert_eq!(board, before);
+
+        // After e4 the null move clears the en passant square; unmake restores it
+        let mut board =
+            crate::io::parse_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .unwrap();
+        let before = board.clone();
+        let undo = board.try_make_null_move().expect("not in check");
+        assert_eq!(board.ep_square(), None);
+        assert!(board.validate_hash_consistency());
+        board.unmake_null_move(undo);
+        assert_eq!(board, before);
+    }
+
+    fn play(board: &mut Board, moves: &[&str]) {
+        for uci in moves {
+            let m = Move::from_uci(board, uci).unwrap();
+            board.make_move(m);
+        }
+    }
+
+    #[test]
+    fn test_unusable_ep_square_is_not_a_different_position() {
+        // 1. e4 records e3, which no black pawn can use, so 1... Nf6 2. Nf3
+        // Ng8 3. Ng1 repeats the position after 1. e4
+        let mut board = Board::startpos();
+        play(&mut board, &["e2e4"]);
+        let after_e4 = board.clone();
+        play(&mut board, &["g8f6", "g1f3", "f6g8", "f3g1"]);
+
+        assert_eq!(after_e4.ep_square(), Some(Square::E3));
+        assert_eq!(board.ep_square(), None);
+        assert!(board.position_eq(&after_e4));
+        assert_eq!(board.hash(), after_e4.hash());
+        assert_ne!(board, after_e4);
+        assert!(board.validate_hash_consistency());
+    }
+
+    #[test]
+    fn test_usable_ep_square_is_a_different_position() {
+        let with_ep = crate::io::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let without = crate::io::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(with_ep.legal_ep_square(), Some(Square::D6));
+        assert!(!with_ep.position_eq(&without));
+        assert_ne!(with_ep.hash(), without.hash());
+
+        // exd6 would expose the king on a5 to the rook on h5
+        let pinned = crate::io::parse_fen("7k/8/8/K2pP2r/8/8/8/8 w - d6 0 1").unwrap();
+        let unpinned = crate::io::parse_fen("7k/8/8/K2pP2r/8/8/8/8 w - - 0 1").unwrap();
+        assert_eq!(pinned.legal_ep_square(), None);
+        assert!(pinned.position_eq(&unpinned));
+        assert_eq!(pinned.hash(), unpinned.hash());
+    }
+
+    #[test]
+    fn test_setters_keep_ep_hash_consistent() {
+        // The e5 pawn arriving makes d6 usable; removing it makes it unusable
+        let mut board = crate::io::parse_fen("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(board.legal_ep_square(), None);
+        board.set_piece(Square::E5, Piece::new(PieceType::Pawn, Color::White));
+        assert_eq!(board.legal_ep_square(), Some(Square::D6));
+        assert!(board.validate_hash_consistency());
+        board.set_side_to_move(Color::Black);
+        assert!(board.validate_hash_consistency());
+        board.set_side_to_move(Color::White);
+        board.remove_piece(Square::E5);
+        assert_eq!(board.legal_ep_square(), None);
+        assert!(board.validate_hash_consistency());
+    }
+
+    #[test]
+    fn test_boards_in_hash_set() {
+        use std::collections::HashSet;
+
+        let mut board = Board::startpos();
+        let mut seen = HashSet::new();
+        seen.insert(board.clone());
+        play(&mut board, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+
+        // Same position, but the clocks differ, so it is a different board
+        assert!(!seen.contains(&board));
+        assert!(seen.contains(&Board::startpos()));
+        seen.insert(board);
+        assert_eq!(seen.len(), 2);
+    }
 }
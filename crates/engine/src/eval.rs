@@ -3,22 +3,32 @@
 //! Evaluates positions from the current side to move's perspective.
 //! Positive scores favor the side to move, negative scores favor the opponent.
 
+pub mod bishop;
+pub mod cache;
+pub mod imbalance;
 pub mod king;
 pub mod material;
 pub mod pawns;
 pub mod phase;
 pub mod pieces;
 pub mod positional;
+pub mod progress;
 pub mod pst;
+pub mod space;
 pub mod threats;
 
+pub use bishop::*;
+pub use cache::*;
+pub use imbalance::*;
 pub use king::*;
 pub use material::*;
 pub use pawns::*;
 pub use phase::*;
 pub use pieces::*;
 pub use positional::*;
+pub use progress::*;
 pub use pst::*;
+pub use space::*;
 pub use threats::*;
 
 use crate::board::Board;
@@ -27,19 +37,47 @@ use crate::piece::Color;
 /// Main evaluator structure containing evaluation components.
 #[derive(Debug)]
 pub struct Evaluator {
-    pst: PieceSquareTables,
+    pst: &'static PieceSquareTables,
     pawn_hash: PawnHashTable,
+    /// Global parameter generation the pawn hash was filled with
+    params_generation: u64,
 }
 
 impl Evaluator {
     /// Create a new evaluator with default evaluation parameters.
     pub fn new() -> Self {
+        Self::with_pawn_hash(PawnHashTable::default())
+    }
+
+    /// Create a new evaluator whose pawn hash table uses about
+    /// `size_bytes` of memory.
+    pub fn with_pawn_hash_bytes(size_bytes: usize) -> Self {
+        Self::with_pawn_hash(PawnHashTable::with_bytes(size_bytes))
+    }
+
+    fn with_pawn_hash(pawn_hash: PawnHashTable) -> Self {
         Self {
-            pst: PieceSquareTables::default(),
-            pawn_hash: PawnHashTable::default(),
+            pst: &DEFAULT_PIECE_SQUARE_TABLES,
+            pawn_hash,
+            params_generation: crate::tune::params_generation(),
         }
     }
 
+    /// Clear the cached pawn structure scores, keeping the table allocated.
+    pub fn clear_cache(&mut self) {
+        self.pawn_hash.clear();
+    }
+
+    /// Memory used by the pawn hash table, in bytes.
+    pub fn pawn_hash_bytes(&self) -> usize {
+        self.pawn_hash.memory_bytes()
+    }
+
+    /// Pawn hash table hits and misses since it was last cleared.
+    pub fn pawn_hash_stats(&self) -> PawnHashStats {
+        self.pawn_hash.stats()
+    }
+
     /// Evaluate a position from the current side to move's perspective.
     ///
     /// Returns a score in centipawns (1 pawn = 100 centipawns).
@@ -61,16 +99,15 @@ impl Evaluator {
     fn evaluate_minimal(&mut self, board: &Board) -> i32 {
         use crate::tune;
 
-        // 1. Material
-        let white_material = evaluate_material(board, Color::White);
-        let black_material = evaluate_material(board, Color::Black);
-        let material = white_material - black_material;
+        // Cached pawn scores are stale once runtime parameters change
+        let generation = tune::params_generation();
+        if generation != self.params_generation {
+            self.pawn_hash.clear();
+            self.params_generation = generation;
+        }
 
-        // 2. PST with tunable divisor (default: 4)
-        let pst_divisor = tune::get_param_or_default(|p| p.pst_scale, 4);
-        let white_pst = self.pst.evaluate_position(board, Color::White) / pst_divisor;
-        let black_pst = self.pst.evaluate_position(board, Color::Black) / pst_divisor;
-        let pst = white_pst - black_pst;
+        // 1-2. Material and PST, both incremental
+        let material_pst = self.material_pst(board);
 
         // 3. Calculate game phase for MG/EG blending
         let phase = phase::calculate_phase(board);
@@ -100,17 +137,97 @@ impl Evaluator {
         let black_king = (black_king_mg * (256 - phase) + black_king_eg * phase) / 256;
         let king_safety = (white_king - black_king) / king_safety_divisor;
 
-        // 7. Threat detection (disabled - still causes timeouts despite optimization)
-        // Even with 2.14x speedup (cached attack maps), NPS drop from 120k to 116k
-        // causes 14% timeout rate and -56 ELO regression.
-        // let threat_divisor = tune::get_param_or_default(|p| p.threat_divisor, 8);
-        // let (threats_mg, threats_eg) = evaluate_threats(board);
-        // let threats = (threats_mg * (256 - phase) + threats_eg * phase) / 256;
-        // let threats = threats / threat_divisor;
+        // 7. Bishops (pair, bad bishops, fianchetto), already White minus Black
+        let (bishops_mg, bishops_eg) = evaluate_bishops(board);
+        let bishops = (bishops_mg * (256 - phase) + bishops_eg * phase) / 256;
+
+        // 8. Threats: hanging pieces (by SEE) and pawn threats, off by
+        // default (divisor 0). At the old default of 8 it scores 15.5/24 in
+        // self-play at equal nodes, but costs about 10% NPS, solves 190
+        // instead of 193 of WAC-300 at 20,000 nodes, loses WAC.008 in the
+        // depth 7 regression set and makes TT re-searches several times
+        // bigger, so it stays off until tuned.
+        let threat_divisor = tune::get_param_or_default(|p| p.threat_divisor, 0);
+        let threats = if threat_divisor > 0 {
+            let (threats_mg, threats_eg) = evaluate_threats(board);
+            (threats_mg * (256 - phase) + threats_eg * phase) / 256 / threat_divisor
+        } else {
+            0
+        };
+
+        // 9. Mop-up when clearly winning: corner the bare king
+        let progress = evaluate_progress(board);
+
+        // 10. Space with tunable divisor (default: 4), middlegame only
+        let space_divisor = tune::get_param_or_default(|p| p.space_divisor, 4);
+        let (space_mg, space_eg) = evaluate_space(board);
+        let space = (space_mg * (256 - phase) + space_eg * phase) / 256 / space_divisor;
+
+        // 11. Material imbalance (rook pair, knight/rook values by pawn count)
+        let (imbalance_mg, imbalance_eg) = evaluate_imbalance(board);
+        let imbalance = (imbalance_mg * (256 - phase) + imbalance_eg * phase) / 256;
 
-        let score = material + pst + pawn_structure + mobility + king_safety;
+        let score = material_pst
+            + pawn_structure
+            + mobility
+            + king_safety
+            + bishops
+            + progress
+            + threats
+            + space
+            + imbalance;
 
         // Return from side to move's perspective
+        let score = if board.side_to_move() == Color::Black {
+            -score
+        } else {
+            score
+        };
+
+        // 12. Tempo for the side to move (default: off, see TuningParams)
+        let tempo_mg = tune::get_param_or_default(|p| p.tempo_mg, 0);
+        let tempo_eg = tune::get_param_or_default(|p| p.tempo_eg, 0);
+        score + (tempo_mg * (256 - phase) + tempo_eg * phase) / 256
+    }
+
+    /// Material plus piece-square score, White minus Black. Both are
+    /// maintained incrementally by the board, so this costs next to nothing.
+    fn material_pst(&self, board: &Board) -> i32 {
+        // 1. Material
+        let white_material = board.material(Color::White);
+        let black_material = board.material(Color::Black);
+        debug_assert_eq!(white_material, evaluate_material(board, Color::White));
+        debug_assert_eq!(black_material, evaluate_material(board, Color::Black));
+        let material = white_material - black_material;
+
+        // 2. PST with tunable divisor (default: 4)
+        let pst_divisor = crate::tune::get_param_or_default(|p| p.pst_scale, 4);
+        let is_eg = is_endgame(board);
+        let white_pst = incremental_pst(board, Color::White, is_eg);
+        let black_pst = incremental_pst(board, Color::Black, is_eg);
+        debug_assert_eq!(white_pst, self.pst.evaluate_position(board, Color::White));
+        debug_assert_eq!(black_pst, self.pst.evaluate_position(board, Color::Black));
+        let pst = white_pst / pst_divisor - black_pst / pst_divisor;
+
+        material + pst
+    }
+
+    /// The cheap part of [`evaluate`](Self::evaluate), material and
+    /// piece-square tables only, from the side to move's perspective.
+    ///
+    /// The remaining terms rarely add up to more than a few hundred
+    /// centipawns, so the search can use this instead of a full evaluation
+    /// when it is already far outside the window (lazy evaluation).
+    ///
+    /// ```
+    /// use engine::board::Board;
+    /// use engine::eval::Evaluator;
+    ///
+    /// let evaluator = Evaluator::new();
+    /// assert_eq!(evaluator.evaluate_lazy(&Board::startpos()), 0);
+    /// ```
+    pub fn evaluate_lazy(&self, board: &Board) -> i32 {
+        let score = self.material_pst(board);
         if board.side_to_move() == Color::Black {
             -score
         } else {
@@ -206,6 +323,17 @@ impl Evaluator {
     }
 }
 
+/// Piece-square score of a color from the board's running sums, using the
+/// endgame or middlegame tables like [`PieceSquareTables::evaluate_position`].
+#[inline]
+fn incremental_pst(board: &Board, color: Color, is_eg: bool) -> i32 {
+    if is_eg {
+        board.pst_eg(color)
+    } else {
+        board.pst_mg(color)
+    }
+}
+
 impl Default for Evaluator {
     fn default() -> Self {
         Self::new()
@@ -270,6 +398,65 @@ mod tests {
         assert_eq!(score1, score2, "Evaluation should be side-to-move relative");
     }
 
+    #[test]
+    fn test_threats_weight() {
+        use crate::tune::{clear_tuning_params, set_tuning_params, TuningParams};
+
+        // Black's e5 knight hangs to the f3 knight until d6 defends it
+        let hanging =
+            parse_fen("rnbqkb1r/pppp1ppp/8/4n3/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 0 1").unwrap();
+        let defended =
+            parse_fen("rnbqkb1r/ppp2ppp/3p4/4n3/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 0 1").unwrap();
+        let evaluate = |board: &Board| Evaluator::new().evaluate(board);
+        let off = (evaluate(&hanging), evaluate(&defended));
+
+        set_tuning_params(TuningParams {
+            threat_divisor: 1,
+            ..TuningParams::from_current_eval()
+        });
+        let on = (evaluate(&hanging), evaluate(&defended));
+        clear_tuning_params();
+
+        // White to move: the threat term shows up only while the knight hangs
+        let (threats_mg, threats_eg) = evaluate_threats(&hanging);
+        let phase = phase::calculate_phase(&hanging);
+        assert_eq!(on.0 - off.0, (threats_mg * (256 - phase) + threats_eg * phase) / 256);
+        assert!(on.0 - off.0 > 0);
+        assert_eq!(on.1, off.1);
+    }
+
+    #[test]
+    fn test_tempo_bonus_for_side_to_move() {
+        use crate::tune::{clear_tuning_params, set_tuning_params, TuningParams};
+
+        set_tuning_params(TuningParams {
+            tempo_mg: 15,
+            tempo_eg: 5,
+            ..TuningParams::from_current_eval()
+        });
+        let mut eval = Evaluator::new();
+
+        // Full material: middlegame tempo for whoever is to move
+        let white = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let black = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        assert_eq!(eval.evaluate(&white), 15);
+        assert_eq!(eval.evaluate(&black), 15);
+
+        // Color-flipped positions still score the same for the side to move
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let flipped = "rnbqk2r/pppp1ppp/5n2/4p3/1b2P3/2N2N2/PPPP1PPP/R1BQKB1R b KQkq - 4 4";
+        let score = eval.evaluate(&parse_fen(fen).unwrap());
+        assert_eq!(score, eval.evaluate(&parse_fen(flipped).unwrap()));
+
+        // The same placement with the other side to move differs by two tempi
+        let other = fen.replace(" w ", " b ");
+        let other_score = eval.evaluate(&parse_fen(&other).unwrap());
+        clear_tuning_params();
+        let base = Evaluator::new().evaluate(&parse_fen(fen).unwrap());
+        assert_eq!(score, base + 15);
+        assert_eq!(other_score, -base + 15);
+    }
+
     #[test]
     fn test_phase_interpolation() {
         // Test that evaluation uses phase interpolation
@@ -348,6 +535,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bishop_pair_against_bishop_and_knight() {
+        // Same pawns and kings, bishop pair against bishop and knight
+        let fen = "2b1kn2/pppppppp/8/8/8/8/PPPPPPPP/2B1KB2 w - - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let score = Evaluator::new().evaluate(&board);
+        assert!(score > 0, "Bishop pair side should be ahead, got {}", score);
+
+        let mirrored = "2b1kb2/pppppppp/8/8/8/8/PPPPPPPP/2B1KN2 b - - 0 1";
+        let score = Evaluator::new().evaluate(&parse_fen(mirrored).unwrap());
+        assert!(score > 0, "Bishop pair side should be ahead, got {}", score);
+    }
+
     #[test]
     fn test_m6_rook_open_file() {
         // White rook on open e-file (e2 pawn removed, e7 pawn removed)
@@ -473,4 +673,28 @@ mod tests {
             score
         );
     }
+
+    #[test]
+    fn test_cramped_side_scores_lower_with_space() {
+        // Hedgehog-like: Black's c6/d6/e6 pawns face c4/d4/e4 and every
+        // square in front of them is covered
+        let fen = "r1bqkb1r/pp1n1ppp/2pppn2/8/2PPP3/2N2N2/PP3PPP/R1BQKB1R b KQkq - 0 6";
+        let board = parse_fen(fen).unwrap();
+
+        let with_space = Evaluator::new().evaluate(&board);
+
+        crate::tune::set_tuning_params(crate::tune::TuningParams {
+            space_divisor: i32::MAX,
+            ..crate::tune::TuningParams::from_current_eval()
+        });
+        let without_space = Evaluator::new().evaluate(&board);
+        crate::tune::clear_tuning_params();
+
+        assert!(
+            with_space < without_space,
+            "Cramped Black should score lower with space: {} vs {}",
+            with_space,
+            without_space
+        );
+    }
 }
@@ -0,0 +1,162 @@
+//! Bishop evaluation.
+//!
+//! Evaluates:
+//! - Bishop pair
+//! - Bad bishops (own pawns fixed on the bishop's square color)
+//! - Fianchettoed bishops in front of a castled king
+
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::piece::{Color, PieceType};
+use crate::square::Square;
+
+/// Bishop evaluation parameters (in centipawns).
+mod values {
+    /// Bishop pair bonus [mg, eg]
+    pub const BISHOP_PAIR: [i32; 2] = [50, 40];
+
+    /// Bad bishop penalty [mg, eg]
+    pub const BAD_BISHOP: [i32; 2] = [-15, -20];
+
+    /// Fianchetto bonus (middlegame only) [mg, eg]
+    pub const FIANCHETTO: [i32; 2] = [15, 0];
+}
+
+/// Evaluate bishops for both sides.
+///
+/// Returns (mg_score, eg_score) from White's perspective.
+pub fn evaluate_bishops(board: &Board) -> (i32, i32) {
+    let (white_mg, white_eg) = evaluate_bishops_for(board, Color::White);
+    let (black_mg, black_eg) = evaluate_bishops_for(board, Color::Black);
+    (white_mg - black_mg, white_eg - black_eg)
+}
+
+/// Evaluate bishops for a single color.
+fn evaluate_bishops_for(board: &Board, color: Color) -> (i32, i32) {
+    let mut mg_score = 0;
+    let mut eg_score = 0;
+
+    let bishops = board.piece_bb(PieceType::Bishop, color);
+
+    // 1. Bishop pair
+    if bishops.count() >= 2 {
+        mg_score += values::BISHOP_PAIR[0];
+        eg_score += values::BISHOP_PAIR[1];
+    }
+
+    let our_pawns = board.piece_bb(PieceType::Pawn, color);
+    let king_sq = board.king_square(color);
+
+    for bishop_sq in bishops {
+        // 2. Bad bishop
+        if is_bad_bishop(bishop_sq, our_pawns) {
+            mg_score += values::BAD_BISHOP[0];
+            eg_score += values::BAD_BISHOP[1];
+        }
+
+        // 3. Fianchetto
+        if let Some(king_sq) = king_sq {
+            if is_fianchetto(bishop_sq, king_sq, color) {
+                mg_score += values::FIANCHETTO[0];
+                eg_score += values::FIANCHETTO[1];
+            }
+        }
+    }
+
+    (mg_score, eg_score)
+}
+
+/// A bishop is bad when more of its own pawns stand on its square color
+/// than on the other color, since those pawns block its diagonals.
+fn is_bad_bishop(bishop_sq: Square, our_pawns: Bitboard) -> bool {
+    let same_color = if Bitboard::LIGHT_SQUARES.contains(bishop_sq) {
+        Bitboard::LIGHT_SQUARES
+    } else {
+        Bitboard::DARK_SQUARES
+    };
+
+    let on_bishop_color = (our_pawns & same_color).count();
+    let on_other_color = our_pawns.count() - on_bishop_color;
+    on_bishop_color > on_other_color
+}
+
+/// A bishop is fianchettoed when it sits on g2/b2 (g7/b7 for Black) and the
+/// king is castled to the same wing.
+fn is_fianchetto(bishop_sq: Square, king_sq: Square, color: Color) -> bool {
+    let (home_rank, bishop_rank) = match color {
+        Color::White => (0, 1),
+        Color::Black => (7, 6),
+    };
+
+    if bishop_sq.rank() != bishop_rank || king_sq.rank() != home_rank {
+        return false;
+    }
+
+    match bishop_sq.file() {
+        // g-file bishop: king on g or h file
+        6 => king_sq.file() >= 6,
+        // b-file bishop: king on a, b or c file
+        1 => king_sq.file() <= 2,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::parse_fen;
+
+    #[test]
+    fn test_startpos_balanced() {
+        let board = Board::startpos();
+        assert_eq!(evaluate_bishops(&board), (0, 0));
+    }
+
+    #[test]
+    fn test_bishop_pair() {
+        // White has both bishops, Black only one
+        let board = parse_fen("2b1k3/8/8/8/8/8/8/2B1KB2 w - - 0 1").unwrap();
+        let (mg, eg) = evaluate_bishops(&board);
+        assert_eq!(mg, values::BISHOP_PAIR[0]);
+        assert_eq!(eg, values::BISHOP_PAIR[1]);
+    }
+
+    #[test]
+    fn test_fianchetto_beats_bishop_on_b3() {
+        let fianchetto = parse_fen("4k3/8/8/8/8/8/1B6/1K6 w - - 0 1").unwrap();
+        let on_b3 = parse_fen("4k3/8/8/8/8/1B6/8/1K6 w - - 0 1").unwrap();
+
+        let (fianchetto_mg, _) = evaluate_bishops(&fianchetto);
+        let (b3_mg, _) = evaluate_bishops(&on_b3);
+        assert!(
+            fianchetto_mg > b3_mg,
+            "Fianchettoed bishop should score higher: {} vs {}",
+            fianchetto_mg,
+            b3_mg
+        );
+    }
+
+    #[test]
+    fn test_fianchetto_requires_castled_king() {
+        // Bishop on g2 but king still in the center
+        let board = parse_fen("4k3/8/8/8/8/8/6B1/4K3 w - - 0 1").unwrap();
+        assert_eq!(evaluate_bishops(&board), (0, 0));
+
+        // Black fianchetto on g7 with king on g8
+        let board = parse_fen("6k1/6b1/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(evaluate_bishops(&board).0, -values::FIANCHETTO[0]);
+    }
+
+    #[test]
+    fn test_bishop_surrounded_by_own_pawns() {
+        // Light-squared bishop on d3 hemmed in by pawns on c2, e2, c4, e4
+        let blocked = parse_fen("4k3/8/8/8/2P1P3/3B4/2P1P3/4K3 w - - 0 1").unwrap();
+        // Same bishop with the pawns on dark squares
+        let free = parse_fen("4k3/8/8/8/3P4/2PB4/3P4/4K3 w - - 0 1").unwrap();
+
+        let (blocked_mg, blocked_eg) = evaluate_bishops(&blocked);
+        let (free_mg, free_eg) = evaluate_bishops(&free);
+        assert!(blocked_mg < free_mg);
+        assert!(blocked_eg < free_eg);
+    }
+}
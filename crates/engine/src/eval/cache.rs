@@ -0,0 +1,180 @@
+//! Evaluation cache.
+//!
+//! The same position is often evaluated several times during a search (by
+//! razoring, reverse futility pruning, futility pruning and quiescence stand
+//! pat). The cache keeps the most recent static evaluation per slot so those
+//! repeats cost a single lookup.
+
+/// Default cache size in bytes (2 MB).
+pub const DEFAULT_EVAL_CACHE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Entry in the evaluation cache.
+#[derive(Debug, Clone, Copy, Default)]
+struct EvalEntry {
+    /// Lower 32 bits of the Zobrist hash
+    key: u32,
+    /// Static evaluation from the side to move's perspective
+    value: i16,
+    /// Whether the slot holds an evaluation; an empty slot's zero key would
+    /// otherwise match every hash whose lower 32 bits are zero
+    filled: bool,
+}
+
+/// Fixed-size, always-replace cache of static evaluations.
+#[derive(Debug)]
+pub struct EvalCache {
+    entries: Vec<EvalEntry>,
+    mask: usize,
+    probes: u64,
+    hits: u64,
+}
+
+impl EvalCache {
+    /// Create a cache using about `size_bytes` of memory (rounded down to a
+    /// power of two number of entries).
+    pub fn new(size_bytes: usize) -> Self {
+        let count = (size_bytes / std::mem::size_of::<EvalEntry>()).max(1);
+        let count = if count.is_power_of_two() {
+            count
+        } else {
+            count.next_power_of_two() / 2
+        };
+        Self {
+            entries: vec![EvalEntry::default(); count],
+            mask: count - 1,
+            probes: 0,
+            hits: 0,
+        }
+    }
+
+    /// Slot and key for a hash: the slot comes from the upper bits so the
+    /// stored lower 32 bits still tell positions in the same slot apart.
+    #[inline]
+    fn slot(&self, hash: u64) -> (usize, u32) {
+        ((hash >> 32) as usize & self.mask, hash as u32)
+    }
+
+    /// Look up the evaluation of a position.
+    #[inline]
+    pub fn probe(&mut self, hash: u64) -> Option<i32> {
+        let (index, key) = self.slot(hash);
+        let entry = self.entries[index];
+        self.probes += 1;
+
+        if entry.filled && entry.key == key {
+            self.hits += 1;
+            Some(entry.value as i32)
+        } else {
+            None
+        }
+    }
+
+    /// Store an evaluation, replacing whatever was in the slot.
+    #[inline]
+    pub fn store(&mut self, hash: u64, value: i32) {
+        let (index, key) = self.slot(hash);
+        self.entries[index] = EvalEntry {
+            key,
+            value: value.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            filled: true,
+        };
+    }
+
+    /// Clear all entries and statistics.
+    pub fn clear(&mut self) {
+        self.entries.fill(EvalEntry::default());
+        self.probes = 0;
+        self.hits = 0;
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache has no entries (never true, it holds at least one).
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Probes since the last clear.
+    pub fn probes(&self) -> u64 {
+        self.probes
+    }
+
+    /// Hits since the last clear.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Fraction of probes that hit, in [0, 1].
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.probes as f64
+        }
+    }
+}
+
+impl Default for EvalCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVAL_CACHE_BYTES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_cache_size() {
+        assert_eq!(EvalCache::default().len(), DEFAULT_EVAL_CACHE_BYTES / 8);
+        assert_eq!(EvalCache::new(1000).len(), 64);
+        assert_eq!(EvalCache::new(0).len(), 1);
+    }
+
+    #[test]
+    fn test_eval_cache_probe_and_store() {
+        let mut cache = EvalCache::new(1024);
+        let hash = 0x1234_5678_9abc_def0;
+
+        assert_eq!(cache.probe(hash), None);
+        cache.store(hash, -42);
+        assert_eq!(cache.probe(hash), Some(-42));
+
+        // Same slot, different key: always replaced
+        let other = hash ^ 0xff;
+        assert_eq!(cache.probe(other), None);
+        cache.store(other, 7);
+        assert_eq!(cache.probe(other), Some(7));
+        assert_eq!(cache.probe(hash), None);
+
+        assert_eq!(cache.probes(), 5);
+        assert_eq!(cache.hits(), 2);
+
+        cache.clear();
+        assert_eq!(cache.probe(other), None);
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn test_eval_cache_clamps_to_i16() {
+        let mut cache = EvalCache::new(1024);
+        cache.store(1 << 40, 100_000);
+        assert_eq!(cache.probe(1 << 40), Some(i16::MAX as i32));
+    }
+
+    #[test]
+    fn test_eval_cache_empty_slot_misses_zero_key() {
+        let mut cache = EvalCache::new(1024);
+        assert_eq!(cache.probe(0), None);
+        assert_eq!(cache.probe(3 << 32), None);
+        assert_eq!(cache.hits(), 0);
+
+        cache.store(0, 0);
+        assert_eq!(cache.probe(0), Some(0));
+        cache.clear();
+        assert_eq!(cache.probe(0), None);
+    }
+}
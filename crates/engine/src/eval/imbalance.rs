@@ -0,0 +1,138 @@
+//! Material imbalance.
+//!
+//! Corrections to the flat piece values that depend on what else is on the
+//! board:
+//! - Rook pair redundancy: two rooks do some of the same work
+//! - Knights gain value with more of our pawns on the board (outposts and
+//!   forks in closed positions), rooks lose it (fewer open files). Only
+//!   knights and rooks the opponent has no counterpart for are adjusted, so
+//!   symmetrical material is left alone
+//!
+//! The bishop pair is scored in [`crate::eval::bishop`]. Everything here is
+//! a function of piece counts, which the board keeps incrementally
+//! ([`Board::piece_count`]).
+
+use crate::board::Board;
+use crate::piece::{Color, PieceType};
+use crate::tune;
+
+/// Imbalance parameters (in centipawns).
+mod values {
+    /// Rook pair redundancy [mg, eg]
+    pub const ROOK_PAIR: [i32; 2] = [-16, -8];
+
+    /// Knight adjustment per own pawn above (or below) five
+    pub const KNIGHT_PAWN_ADJUST: i32 = 6;
+
+    /// Rook adjustment per own pawn above (or below) five
+    pub const ROOK_PAWN_ADJUST: i32 = -12;
+
+    /// Pawn count at which knights and rooks have their nominal value
+    pub const NOMINAL_PAWNS: i32 = 5;
+}
+
+/// Evaluate material imbalance for both sides.
+///
+/// Returns (mg_score, eg_score) from White's perspective.
+///
+/// # Example
+/// ```
+/// use engine::eval::evaluate_imbalance;
+/// use engine::io::parse_fen;
+///
+/// // A knight with all eight pawns against a rook with all eight
+/// let board = parse_fen("4k2r/pppppppp/8/8/8/8/PPPPPPPP/4K1N1 w - - 0 1").unwrap();
+/// let (mg, eg) = evaluate_imbalance(&board);
+/// assert!(mg > 0 && eg > 0);
+/// ```
+pub fn evaluate_imbalance(board: &Board) -> (i32, i32) {
+    let (white_mg, white_eg) = evaluate_imbalance_for(board, Color::White);
+    let (black_mg, black_eg) = evaluate_imbalance_for(board, Color::Black);
+    (white_mg - black_mg, white_eg - black_eg)
+}
+
+/// Evaluate material imbalance for a single color.
+fn evaluate_imbalance_for(board: &Board, color: Color) -> (i32, i32) {
+    let count = |piece_type, color| board.piece_count(piece_type, color) as i32;
+    let pawns = count(PieceType::Pawn, color);
+    let knights = count(PieceType::Knight, color);
+    let rooks = count(PieceType::Rook, color);
+
+    // Pieces the opponent can match one for one trade at par, so the pawn
+    // count only changes the value of the ones left over
+    let unmatched_knights = (knights - count(PieceType::Knight, color.opponent())).max(0);
+    let unmatched_rooks = (rooks - count(PieceType::Rook, color.opponent())).max(0);
+
+    let mut mg_score = 0;
+    let mut eg_score = 0;
+
+    // 1. Rook pair
+    if rooks >= 2 {
+        mg_score += tune::get_param_or_default(|p| p.rook_pair_mg, values::ROOK_PAIR[0]);
+        eg_score += tune::get_param_or_default(|p| p.rook_pair_eg, values::ROOK_PAIR[1]);
+    }
+
+    // 2. Unmatched knight and rook values by pawn count
+    let extra_pawns = pawns - values::NOMINAL_PAWNS;
+    let knight_adjust =
+        tune::get_param_or_default(|p| p.knight_pawn_adjust, values::KNIGHT_PAWN_ADJUST);
+    let rook_adjust = tune::get_param_or_default(|p| p.rook_pawn_adjust, values::ROOK_PAWN_ADJUST);
+    let pawn_adjust =
+        extra_pawns * (unmatched_knights * knight_adjust + unmatched_rooks * rook_adjust);
+    mg_score += pawn_adjust;
+    eg_score += pawn_adjust;
+
+    (mg_score, eg_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::parse_fen;
+
+    #[test]
+    fn test_startpos_is_balanced() {
+        assert_eq!(evaluate_imbalance(&Board::startpos()), (0, 0));
+    }
+
+    #[test]
+    fn test_rook_pair_redundancy() {
+        // Two rooks against rook and five pawns each: only White pays
+        let board = parse_fen("4k2r/ppppp3/8/8/8/8/PPPPP3/R3K2R w - - 0 1").unwrap();
+        assert_eq!(
+            evaluate_imbalance(&board),
+            (values::ROOK_PAIR[0], values::ROOK_PAIR[1])
+        );
+    }
+
+    #[test]
+    fn test_knight_and_rook_by_pawn_count() {
+        // Knight with eight pawns gains 3 steps, with two pawns loses 3
+        let closed = parse_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K1N1 w - - 0 1").unwrap();
+        let open = parse_fen("4k3/pp6/8/8/8/8/PP6/4K1N1 w - - 0 1").unwrap();
+        let step = values::KNIGHT_PAWN_ADJUST;
+        assert_eq!(evaluate_imbalance(&closed), (3 * step, 3 * step));
+        assert_eq!(evaluate_imbalance(&open), (-3 * step, -3 * step));
+
+        // The rook does the opposite
+        let rook = parse_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/R3K3 w - - 0 1").unwrap();
+        let step = values::ROOK_PAWN_ADJUST;
+        assert_eq!(evaluate_imbalance(&rook), (3 * step, 3 * step));
+
+        // Knights facing knights are left alone, even with unequal pawns
+        let matched = parse_fen("4k1n1/pppp4/8/8/8/8/PPPPPPPP/4K1N1 w - - 0 1").unwrap();
+        assert_eq!(evaluate_imbalance(&matched), (0, 0));
+    }
+
+    #[test]
+    fn test_tuning_params_override_values() {
+        let board = parse_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K1N1 w - - 0 1").unwrap();
+        tune::set_tuning_params(tune::TuningParams {
+            knight_pawn_adjust: 10,
+            ..tune::TuningParams::from_current_eval()
+        });
+        let tuned = evaluate_imbalance(&board);
+        tune::clear_tuning_params();
+        assert_eq!(tuned, (30, 30));
+    }
+}
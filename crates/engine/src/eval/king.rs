@@ -6,7 +6,9 @@
 //! - Open files near king (-10 to -40 cp)
 //! - King tropism (enemy piece proximity in middlegame)
 
-use crate::attacks::{bishop_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks};
+use crate::attacks::{
+    bishop_attacks, knight_attacks, pawn_attacks_bb, queen_attacks, rook_attacks,
+};
 use crate::bitboard::Bitboard;
 use crate::board::Board;
 use crate::piece::{Color, PieceType};
@@ -52,12 +54,10 @@ pub fn evaluate_king_safety(board: &Board, color: Color, phase: i32) -> (i32, i3
     let mut eg_score = 0;
 
     // Find king position
-    let king_bb = board.piece_bb(PieceType::King, color);
-    if king_bb.is_empty() {
-        return (0, 0); // No king (should not happen in valid positions)
-    }
-
-    let king_sq = king_bb.lsb().unwrap();
+    let king_sq = match board.king_square(color) {
+        Some(sq) => sq,
+        None => return (0, 0), // No king (should not happen in valid positions)
+    };
 
     // 1. Evaluate pawn shield
     let (shield_mg, shield_eg) = evaluate_pawn_shield(board, king_sq, color);
@@ -182,7 +182,28 @@ fn is_castled_or_central(king_sq: Square, color: Color) -> bool {
 
 /// Evaluate attacking pressure on the king.
 fn evaluate_king_attackers(board: &Board, king_sq: Square, color: Color) -> i32 {
-    let king_zone = get_king_zone(king_sq);
+    // Convert attack weight to penalty
+    match zone_attack_weight(board, king_sq, color) {
+        0..=2 => 0,
+        3..=5 => -20,
+        6..=9 => -50,
+        10..=15 => -100,
+        _ => -200,
+    }
+}
+
+/// Summed attacker weights (queen 4, rook 3, minor 2, pawn 1) of the enemy
+/// pieces hitting the zone around `color`'s king, 0 without a king. King
+/// safety penalties start at 3 and peak from 16.
+pub fn king_attack_weight(board: &Board, color: Color) -> i32 {
+    board
+        .king_square(color)
+        .map_or(0, |king_sq| zone_attack_weight(board, king_sq, color))
+}
+
+/// Attacker weight on the zone around `king_sq`, see [`king_attack_weight`].
+fn zone_attack_weight(board: &Board, king_sq: Square, color: Color) -> i32 {
+    let king_zone = king_sq.adjacent();
     let enemy_color = color.opponent();
 
     let occupied = board.occupied();
@@ -226,46 +247,13 @@ fn evaluate_king_attackers(board: &Board, king_sq: Square, color: Color) -> i32
         }
     }
 
-    // Pawns
+    // Pawns: an enemy pawn hits the zone exactly when it stands on a square
+    // one of our pawns on the zone would attack
     let enemy_pawns = board.piece_bb(PieceType::Pawn, enemy_color);
-    for pawn_sq in enemy_pawns {
-        let attacks = pawn_attacks(pawn_sq, enemy_color);
-        if !(attacks & king_zone).is_empty() {
-            attack_weight += values::PAWN_ATTACK_WEIGHT;
-        }
-    }
-
-    // Convert attack weight to penalty
-    match attack_weight {
-        0..=2 => 0,
-        3..=5 => -20,
-        6..=9 => -50,
-        10..=15 => -100,
-        _ => -200,
-    }
-}
-
-/// Get the king zone (3x3 area around king).
-fn get_king_zone(king_sq: Square) -> Bitboard {
-    let mut zone = Bitboard::EMPTY;
+    let zone_attackers = enemy_pawns & pawn_attacks_bb(king_zone, enemy_color.opponent());
+    attack_weight += zone_attackers.count() as i32 * values::PAWN_ATTACK_WEIGHT;
 
-    let file = king_sq.file();
-    let rank = king_sq.rank();
-
-    // Add all squares within 1 square of the king
-    for df in -1..=1i8 {
-        for dr in -1..=1i8 {
-            let new_file = file as i8 + df;
-            let new_rank = rank as i8 + dr;
-
-            if (0..8).contains(&new_file) && (0..8).contains(&new_rank) {
-                let sq = Square::from_coords(new_file as u8, new_rank as u8);
-                zone = zone.set(sq);
-            }
-        }
-    }
-
-    zone
+    attack_weight
 }
 
 /// Evaluate open and semi-open files near the king.
@@ -285,7 +273,7 @@ fn evaluate_open_files_near_king(board: &Board, king_sq: Square, color: Color) -
         }
 
         let file = file as u8;
-        let file_bb = file_bitboard(file);
+        let file_bb = Bitboard::from_file(file);
 
         let has_our_pawns = !(our_pawns & file_bb).is_empty();
         let has_enemy_pawns = !(enemy_pawns & file_bb).is_empty();
@@ -337,7 +325,7 @@ fn evaluate_king_tropism(board: &Board, king_sq: Square, color: Color) -> i32 {
     for piece_type in attacking_pieces {
         let pieces = board.piece_bb(piece_type, enemy_color);
         for piece_sq in pieces {
-            let distance = chebyshev_distance(king_sq, piece_sq);
+            let distance = king_sq.chebyshev_distance(piece_sq);
             if distance < 8 {
                 bonus += values::TROPISM_BONUS[distance as usize];
             }
@@ -347,18 +335,6 @@ fn evaluate_king_tropism(board: &Board, king_sq: Square, color: Color) -> i32 {
     -bonus // Negative because closer enemy pieces are bad for us
 }
 
-/// Calculate Chebyshev distance (max of file/rank distance).
-fn chebyshev_distance(sq1: Square, sq2: Square) -> u8 {
-    let file_dist = (sq1.file() as i8 - sq2.file() as i8).abs();
-    let rank_dist = (sq1.rank() as i8 - sq2.rank() as i8).abs();
-    file_dist.max(rank_dist) as u8
-}
-
-/// Get a bitboard mask for a file.
-fn file_bitboard(file: u8) -> Bitboard {
-    Bitboard::new(0x0101010101010101u64 << file)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,35 +366,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_king_zone() {
-        let king_sq = Square::from_algebraic("e4").unwrap();
-        let zone = get_king_zone(king_sq);
-
-        // King zone should be 3x3 = 9 squares
-        assert_eq!(zone.count(), 9, "King zone should have 9 squares");
-
-        // Check that all adjacent squares are in zone
-        assert!(zone.contains(Square::from_algebraic("d3").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("e3").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("f3").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("d4").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("e4").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("f4").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("d5").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("e5").unwrap()));
-        assert!(zone.contains(Square::from_algebraic("f5").unwrap()));
-    }
-
-    #[test]
-    fn test_king_zone_corner() {
-        let king_sq = Square::from_algebraic("a1").unwrap();
-        let zone = get_king_zone(king_sq);
-
-        // Corner king zone should have 4 squares
-        assert_eq!(zone.count(), 4, "Corner king zone should have 4 squares");
-    }
-
     #[test]
     fn test_king_attackers_safe_position() {
         // King in starting position, no attackers
@@ -443,6 +390,16 @@ mod tests {
         assert!(penalty <= 0, "Attack penalty should be non-positive");
     }
 
+    #[test]
+    fn test_king_attack_weight() {
+        assert_eq!(king_attack_weight(&Board::startpos(), Color::White), 0);
+
+        // Queen on g6 and rook on the back rank aimed at the black king
+        let board = parse_fen("6k1/5ppp/6Q1/8/8/8/5PPP/4R1K1 w - - 0 1").unwrap();
+        assert!(king_attack_weight(&board, Color::Black) >= values::QUEEN_ATTACK_WEIGHT);
+        assert_eq!(king_attack_weight(&board, Color::White), 0);
+    }
+
     #[test]
     fn test_open_file_on_king() {
         // White king on e-file with no pawns on e-file (truly open)
@@ -470,21 +427,6 @@ mod tests {
         assert!(bonus < 0, "Enemy piece near king should give penalty");
     }
 
-    #[test]
-    fn test_chebyshev_distance() {
-        let e4 = Square::from_algebraic("e4").unwrap();
-        let e5 = Square::from_algebraic("e5").unwrap();
-        assert_eq!(chebyshev_distance(e4, e5), 1);
-
-        let a1 = Square::from_algebraic("a1").unwrap();
-        let h8 = Square::from_algebraic("h8").unwrap();
-        assert_eq!(chebyshev_distance(a1, h8), 7);
-
-        let e4_2 = Square::from_algebraic("e4").unwrap();
-        let g6 = Square::from_algebraic("g6").unwrap();
-        assert_eq!(chebyshev_distance(e4_2, g6), 2); // max(2, 2) = 2
-    }
-
     #[test]
     fn test_king_safety_startpos() {
         let board = Board::startpos();
@@ -515,7 +457,7 @@ mod tests {
 
     #[test]
     fn test_file_bitboard() {
-        let e_file = file_bitboard(4);
+        let e_file = Bitboard::from_file(4);
         assert_eq!(e_file.count(), 8, "File should have 8 squares");
 
         // Check all e-file squares
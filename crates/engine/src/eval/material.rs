@@ -39,7 +39,7 @@ pub fn evaluate_material(board: &Board, color: Color) -> i32 {
 
 /// Count total material on the board (for both sides).
 pub fn total_material(board: &Board) -> i32 {
-    evaluate_material(board, Color::White) + evaluate_material(board, Color::Black)
+    board.material(Color::White) + board.material(Color::Black)
 }
 
 /// Determine if we're in the endgame based on material.
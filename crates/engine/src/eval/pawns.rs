@@ -8,6 +8,7 @@
 //! - Pawn chains (bonus +5 cp per protected pawn)
 //! - Pawn islands (penalty -10 cp per island beyond 1)
 
+use crate::attacks::pawn_attacks_bb;
 use crate::bitboard::Bitboard;
 use crate::board::Board;
 use crate::piece::{Color, PieceType};
@@ -98,58 +99,132 @@ mod values {
 struct PawnEntry {
     /// Zobrist key for pawn positions only
     key: u64,
-    /// Middlegame score
-    mg_score: i16,
-    /// Endgame score
-    eg_score: i16,
+    /// White's (mg, eg) and Black's (mg, eg) pawn structure scores
+    scores: [i16; 4],
 }
 
 /// Pawn hash table for caching pawn structure evaluations.
+///
+/// Pawn structure scores depend on the pawns alone, which rarely change
+/// during a search, so nearly every evaluation can reuse them.
 #[derive(Debug)]
 pub struct PawnHashTable {
     entries: Vec<PawnEntry>,
     size: usize,
+    hits: u64,
+    misses: u64,
+}
+
+/// Pawn hash table hit and miss counts since the last clear.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PawnHashStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl PawnHashStats {
+    /// Fraction of probes that hit, in [0, 1].
+    pub fn hit_rate(&self) -> f64 {
+        let probes = self.hits + self.misses;
+        if probes == 0 {
+            0.0
+        } else {
+            self.hits as f64 / probes as f64
+        }
+    }
 }
 
 impl PawnHashTable {
     /// Create a new pawn hash table with given number of entries.
     ///
-    /// Default size is 16384 entries (~512KB memory).
+    /// Default size is 16384 entries (256KB memory).
     pub fn new(size: usize) -> Self {
-        let size = size.next_power_of_two();
+        let size = size.max(1).next_power_of_two();
         Self {
             entries: vec![PawnEntry::default(); size],
             size,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Create a pawn hash table using about `size_bytes` of memory (rounded
+    /// down to a power of two number of entries, at least one).
+    pub fn with_bytes(size_bytes: usize) -> Self {
+        let count = (size_bytes / std::mem::size_of::<PawnEntry>()).max(1);
+        if count.is_power_of_two() {
+            Self::new(count)
+        } else {
+            Self::new(count.next_power_of_two() / 2)
         }
     }
 
     /// Probe the hash table for a pawn structure evaluation.
     ///
-    /// Returns (mg_score, eg_score) if found, None otherwise.
-    pub fn probe(&self, key: u64) -> Option<(i32, i32)> {
+    /// Returns (white_mg, white_eg, black_mg, black_eg) if found, None
+    /// otherwise.
+    pub fn probe(&mut self, key: u64) -> Option<(i32, i32, i32, i32)> {
         let index = (key as usize) & (self.size - 1);
         let entry = self.entries[index];
 
         if entry.key == key {
-            Some((entry.mg_score as i32, entry.eg_score as i32))
+            self.hits += 1;
+            let [white_mg, white_eg, black_mg, black_eg] = entry.scores.map(i32::from);
+            Some((white_mg, white_eg, black_mg, black_eg))
         } else {
+            self.misses += 1;
             None
         }
     }
 
     /// Store a pawn structure evaluation in the hash table.
-    pub fn store(&mut self, key: u64, mg_score: i32, eg_score: i32) {
+    pub fn store(&mut self, key: u64, scores: (i32, i32, i32, i32)) {
         let index = (key as usize) & (self.size - 1);
+        let (white_mg, white_eg, black_mg, black_eg) = scores;
         self.entries[index] = PawnEntry {
             key,
-            mg_score: mg_score as i16,
-            eg_score: eg_score as i16,
+            scores: [white_mg, white_eg, black_mg, black_eg]
+                .map(|score| score.clamp(i16::MIN as i32, i16::MAX as i32) as i16),
         };
     }
 
-    /// Clear the hash table.
+    /// Return the cached scores for `key`, or compute, store and return
+    /// them on a miss.
+    ///
+    /// Scores are (white_mg, white_eg, black_mg, black_eg) as in
+    /// [`Self::probe`].
+    pub fn probe_or_compute(
+        &mut self,
+        key: u64,
+        compute: impl FnOnce() -> (i32, i32, i32, i32),
+    ) -> (i32, i32, i32, i32) {
+        if let Some(scores) = self.probe(key) {
+            return scores;
+        }
+
+        let scores = compute();
+        self.store(key, scores);
+        scores
+    }
+
+    /// Clear the hash table and its statistics.
     pub fn clear(&mut self) {
         self.entries.fill(PawnEntry::default());
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Hits and misses since the last clear.
+    pub fn stats(&self) -> PawnHashStats {
+        PawnHashStats {
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    /// Memory used by the entries, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.entries.len() * std::mem::size_of::<PawnEntry>()
     }
 }
 
@@ -161,14 +236,14 @@ impl Default for PawnHashTable {
 
 /// Calculate a Zobrist key for pawn positions only.
 fn pawn_hash_key(board: &Board) -> u64 {
-    use crate::zobrist::ZOBRIST;
+    use crate::zobrist::{piece_index, ZOBRIST};
 
     let mut key = 0u64;
 
     for color in [Color::White, Color::Black] {
         let pawns = board.piece_bb(PieceType::Pawn, color);
         for sq in pawns {
-            key ^= ZOBRIST.pieces[PieceType::Pawn.index()][color.index()][sq.index() as usize];
+            key ^= ZOBRIST.pieces[piece_index(PieceType::Pawn, color)][sq.index() as usize];
         }
     }
 
@@ -191,8 +266,8 @@ fn evaluate_pawn_structure(board: &Board, color: Color) -> (i32, i32) {
         let rank = sq.rank();
 
         // Get file masks
-        let file_mask = file_bitboard(file);
-        let adjacent_files_mask = adjacent_files_bitboard(file);
+        let file_mask = Bitboard::from_file(file);
+        let adjacent_files_mask = Bitboard::adjacent_files(file);
 
         // Count pawns on this file
         let pawns_on_file = (our_pawns & file_mask).count();
@@ -251,20 +326,8 @@ fn evaluate_pawn_structure(board: &Board, color: Color) -> (i32, i32) {
 
 /// Check if a pawn is passed (no enemy pawns in front on same or adjacent files).
 fn is_passed(sq: Square, color: Color, enemy_pawns: Bitboard) -> bool {
-    let file = sq.file();
-    let rank = sq.rank();
-
-    // Create mask for squares in front
-    let front_mask = if color == Color::White {
-        // Ranks above current rank
-        passed_pawn_mask_white(file, rank)
-    } else {
-        // Ranks below current rank
-        passed_pawn_mask_black(file, rank)
-    };
-
     // Check if any enemy pawns in the way
-    (enemy_pawns & front_mask).is_empty()
+    (enemy_pawns & passed_pawn_mask(sq, color)).is_empty()
 }
 
 /// Check if a pawn is backward.
@@ -291,10 +354,10 @@ fn is_backward(sq: Square, color: Color, our_pawns: Bitboard, enemy_pawns: Bitbo
     };
 
     // Check if the front square is controlled by enemy pawns
-    let enemy_control = pawn_attacks(enemy_pawns, color.opponent());
+    let enemy_control = pawn_attacks_bb(enemy_pawns, color.opponent());
     if enemy_control.contains(front_sq) {
         // Check if we have supporting pawns that could advance
-        let adjacent_files = adjacent_files_bitboard(file);
+        let adjacent_files = Bitboard::adjacent_files(file);
         let support_mask = if color == Color::White {
             // Pawns behind us on adjacent files
             let behind_mask = Bitboard::new(!0u64 << (rank * 8));
@@ -311,20 +374,8 @@ fn is_backward(sq: Square, color: Color, our_pawns: Bitboard, enemy_pawns: Bitbo
 }
 
 /// Check if a pawn is protected by another friendly pawn.
-fn is_protected_by_pawn(sq: Square, color: Color, our_pawns: Bitboard) -> bool {
-    let protectors = pawn_attacks(our_pawns, color.opponent());
-    protectors.contains(sq)
-}
-
-/// Calculate pawn attacks for all pawns of a given color.
-fn pawn_attacks(pawns: Bitboard, color: Color) -> Bitboard {
-    use crate::attacks::pawn_attacks as get_pawn_attacks;
-
-    let mut attacks = Bitboard::EMPTY;
-    for sq in pawns {
-        attacks |= get_pawn_attacks(sq, color);
-    }
-    attacks
+pub(crate) fn is_protected_by_pawn(sq: Square, color: Color, our_pawns: Bitboard) -> bool {
+    pawn_attacks_bb(our_pawns, color).contains(sq)
 }
 
 /// Count the number of pawn islands.
@@ -335,7 +386,7 @@ fn count_pawn_islands(pawns: Bitboard) -> u32 {
     let mut in_island = false;
 
     for file in 0..8 {
-        let file_mask = file_bitboard(file);
+        let file_mask = Bitboard::from_file(file);
         let has_pawn = !(pawns & file_mask).is_empty();
 
         if has_pawn && !in_island {
@@ -349,73 +400,29 @@ fn count_pawn_islands(pawns: Bitboard) -> u32 {
     islands
 }
 
-/// Get a bitboard mask for a file.
-fn file_bitboard(file: u8) -> Bitboard {
-    Bitboard::new(0x0101010101010101u64 << file)
-}
-
-/// Get a bitboard mask for adjacent files.
-fn adjacent_files_bitboard(file: u8) -> Bitboard {
-    let mut mask = Bitboard::EMPTY;
-    if file > 0 {
-        mask |= file_bitboard(file - 1);
-    }
-    if file < 7 {
-        mask |= file_bitboard(file + 1);
-    }
-    mask
-}
-
-/// Get passed pawn mask for white (squares in front on same and adjacent files).
-fn passed_pawn_mask_white(file: u8, rank: u8) -> Bitboard {
-    let file_mask = file_bitboard(file);
-    let adjacent_mask = adjacent_files_bitboard(file);
-    let all_files = file_mask | adjacent_mask;
-
-    // Mask ranks above current rank
-    let rank_mask = Bitboard::new(!0u64 << ((rank + 1) * 8));
-
-    all_files & rank_mask
-}
-
-/// Get passed pawn mask for black (squares in front on same and adjacent files).
-fn passed_pawn_mask_black(file: u8, rank: u8) -> Bitboard {
-    let file_mask = file_bitboard(file);
-    let adjacent_mask = adjacent_files_bitboard(file);
-    let all_files = file_mask | adjacent_mask;
-
-    // Mask ranks below current rank
-    let rank_mask = if rank > 0 {
-        Bitboard::new((1u64 << (rank * 8)) - 1)
-    } else {
-        Bitboard::EMPTY
-    };
-
-    all_files & rank_mask
+/// Get passed pawn mask (squares in front on same and adjacent files).
+fn passed_pawn_mask(sq: Square, color: Color) -> Bitboard {
+    let front = Bitboard::from_square(sq).front_span(color);
+    front | front.shift_east() | front.shift_west()
 }
 
 /// Evaluate pawn structure using the hash table.
+///
+/// Returns (white_mg, white_eg, black_mg, black_eg), exactly as
+/// [`evaluate_pawns`] would.
 pub fn evaluate_pawns_cached(
     board: &Board,
     pawn_table: &mut PawnHashTable,
 ) -> (i32, i32, i32, i32) {
-    let key = pawn_hash_key(board);
-
-    // Try to probe the hash table
-    if let Some((_white_mg, _white_eg)) = pawn_table.probe(key) {
-        // Note: We store the evaluation relative to white
-        // Need to evaluate both colors separately
-        // For now, we'll re-evaluate if we miss
-        // TODO: Implement proper caching that returns both colors
-    }
+    pawn_table.probe_or_compute(pawn_hash_key(board), || evaluate_pawns(board))
+}
 
-    // Evaluate both colors
+/// Evaluate pawn structure for both colors without the hash table.
+///
+/// Returns (white_mg, white_eg, black_mg, black_eg).
+pub fn evaluate_pawns(board: &Board) -> (i32, i32, i32, i32) {
     let (white_mg, white_eg) = evaluate_pawn_structure(board, Color::White);
     let (black_mg, black_eg) = evaluate_pawn_structure(board, Color::Black);
-
-    // Store in hash table (storing white's perspective)
-    pawn_table.store(key, white_mg - black_mg, white_eg - black_eg);
-
     (white_mg, white_eg, black_mg, black_eg)
 }
 
@@ -472,6 +479,18 @@ mod tests {
         assert!(mg > 0, "Protected pawns should have bonus");
     }
 
+    #[test]
+    fn test_is_protected_by_pawn() {
+        // The chain base protects the pawn in front of it, not the reverse
+        let board = parse_fen("4k3/8/3p4/4p3/3P4/4P3/8/4K3 w - - 0 1").unwrap();
+        let white = board.piece_bb(PieceType::Pawn, Color::White);
+        let black = board.piece_bb(PieceType::Pawn, Color::Black);
+        assert!(is_protected_by_pawn(Square::D4, Color::White, white));
+        assert!(!is_protected_by_pawn(Square::E3, Color::White, white));
+        assert!(is_protected_by_pawn(Square::E5, Color::Black, black));
+        assert!(!is_protected_by_pawn(Square::D6, Color::Black, black));
+    }
+
     #[test]
     fn test_pawn_islands() {
         // White has 3 pawn islands: a2, c4-d4, g5
@@ -488,15 +507,54 @@ mod tests {
         let key = pawn_hash_key(&board);
 
         // Store evaluation
-        table.store(key, 100, 150);
+        table.store(key, (100, 150, -20, 30));
 
         // Probe should return same values
         let result = table.probe(key);
-        assert_eq!(result, Some((100, 150)));
+        assert_eq!(result, Some((100, 150, -20, 30)));
 
         // Different key should miss
         let result = table.probe(key + 1);
         assert_eq!(result, None);
+
+        assert_eq!(table.stats(), PawnHashStats { hits: 1, misses: 1 });
+        assert_eq!(table.stats().hit_rate(), 0.5);
+        table.clear();
+        assert_eq!(table.stats(), PawnHashStats::default());
+    }
+
+    #[test]
+    fn test_probe_or_compute() {
+        let mut table = PawnHashTable::new(16);
+        let mut calls = 0;
+        let mut compute = || {
+            calls += 1;
+            (10, 20, -30, 40)
+        };
+
+        assert_eq!(table.probe_or_compute(7, &mut compute), (10, 20, -30, 40));
+        assert_eq!(table.probe_or_compute(7, &mut compute), (10, 20, -30, 40));
+        assert_eq!(calls, 1);
+        assert_eq!(table.stats(), PawnHashStats { hits: 1, misses: 1 });
+    }
+
+    #[test]
+    fn test_pawn_hash_sizing() {
+        assert_eq!(PawnHashTable::default().memory_bytes(), 16384 * 16);
+        assert_eq!(PawnHashTable::with_bytes(1000).memory_bytes(), 32 * 16);
+        assert_eq!(PawnHashTable::with_bytes(0).memory_bytes(), 16);
+    }
+
+    #[test]
+    fn test_cached_pawn_eval_matches_uncached() {
+        let mut table = PawnHashTable::new(16);
+        let board =
+            parse_fen("r1bqkb1r/pp3ppp/2n1pn2/2pp4/3P4/2PBPN2/PP3PPP/RNBQK2R w KQkq - 0 6").unwrap();
+        let miss = evaluate_pawns_cached(&board, &mut table);
+        let hit = evaluate_pawns_cached(&board, &mut table);
+        assert_eq!(miss, evaluate_pawns(&board));
+        assert_eq!(hit, miss);
+        assert_eq!(table.stats(), PawnHashStats { hits: 1, misses: 1 });
     }
 
     #[test]
@@ -510,7 +568,7 @@ mod tests {
 
     #[test]
     fn test_file_bitboard() {
-        let file_e = file_bitboard(4); // e-file
+        let file_e = Bitboard::from_file(4); // e-file
         assert_eq!(file_e.count(), 8, "File should have 8 squares");
 
         // Check that e1, e2, ..., e8 are set
@@ -526,7 +584,7 @@ mod tests {
 
     #[test]
     fn test_adjacent_files() {
-        let adjacent = adjacent_files_bitboard(4); // d and f files
+        let adjacent = Bitboard::adjacent_files(4); // d and f files
         assert_eq!(
             adjacent.count(),
             16,
@@ -552,6 +610,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_passed_pawn_mask_edges() {
+        // a2 pawn: a3-a8 and b3-b8
+        let mask = passed_pawn_mask(Square::A2, Color::White);
+        assert_eq!(mask.count(), 12);
+        assert!(mask.contains(Square::A8));
+        assert!(!mask.contains(Square::from_coords(1, 1))); // b2
+
+        // h7 pawn for Black: h6-h1 and g6-g1
+        let mask = passed_pawn_mask(Square::H7, Color::Black);
+        assert_eq!(mask.count(), 12);
+        assert!(mask.contains(Square::G1));
+        assert!(!mask.contains(Square::G7));
+    }
+
     #[test]
     fn test_passed_pawn_detection_white() {
         // White pawn on e5, no black pawns blocking
@@ -657,14 +730,9 @@ fn evaluate_candidate_passers(our_pawns: Bitboard, enemy_pawns: Bitboard, color:
         // Check if this is a candidate passer:
         // 1. No enemy pawns directly in front on same file
         // 2. Outnumber enemy pawns on adjacent files ahead
-        let file_mask = file_bitboard(file);
-        let adjacent_mask = adjacent_files_bitboard(file);
-
-        let front_mask = if color == Color::White {
-            passed_pawn_mask_white(file, rank)
-        } else {
-            passed_pawn_mask_black(file, rank)
-        };
+        let file_mask = Bitboard::from_file(file);
+        let adjacent_mask = Bitboard::adjacent_files(file);
+        let front_mask = passed_pawn_mask(sq, color);
 
         // No enemy pawns directly ahead on same file
         if !(enemy_pawns & file_mask & front_mask).is_empty() {
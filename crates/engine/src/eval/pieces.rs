@@ -2,7 +2,7 @@
 //!
 //! Evaluates:
 //! - Rook activity (open files, 7th rank)
-//! - Bishop activity (bishop pair, bad bishops, trapped bishops)
+//! - Trapped bishops (the bishop pair and bad bishops are in [`super::bishop`])
 //! - Knight activity (outposts, trapped knights)
 //! - Piece centralization
 
@@ -26,12 +26,6 @@ mod values {
     /// Two rooks on 7th rank [mg, eg]
     pub const TWO_ROOKS_ON_SEVENTH: [i32; 2] = [40, 50];
 
-    /// Bishop pair bonus [mg, eg]
-    pub const BISHOP_PAIR: [i32; 2] = [50, 60];
-
-    /// Bad bishop penalty [mg, eg]
-    pub const BAD_BISHOP: [i32; 2] = [-15, -10];
-
     /// Trapped bishop penalty [mg, eg]
     pub const TRAPPED_BISHOP: [i32; 2] = [-150, -100];
 
@@ -95,7 +89,7 @@ fn evaluate_rook_activity(board: &Board, color: Color) -> (i32, i32) {
         let rank = rook_sq.rank();
 
         // Check if on open or semi-open file
-        let file_bb = file_bitboard(file);
+        let file_bb = Bitboard::from_file(file);
         let has_our_pawns = !(our_pawns & file_bb).is_empty();
         let has_enemy_pawns = !(enemy_pawns & file_bb).is_empty();
 
@@ -114,16 +108,14 @@ fn evaluate_rook_activity(board: &Board, color: Color) -> (i32, i32) {
         if rank == seventh_rank {
             // Check if enemy king is on 8th or enemy pawns on 7th
             let king_rank = if color == Color::White { 7 } else { 0 };
-            let enemy_king_bb = board.piece_bb(PieceType::King, color.opponent());
-
-            if let Some(enemy_king_sq) = enemy_king_bb.lsb() {
+            if let Some(enemy_king_sq) = board.king_square(color.opponent()) {
                 if enemy_king_sq.rank() == king_rank {
                     rooks_on_seventh += 1;
                 }
             }
 
             // Or check for enemy pawns on 7th
-            let pawns_on_seventh = !(enemy_pawns & rank_bitboard(seventh_rank)).is_empty();
+            let pawns_on_seventh = !(enemy_pawns & Bitboard::from_rank(seventh_rank)).is_empty();
             if pawns_on_seventh {
                 rooks_on_seventh += 1;
             }
@@ -147,30 +139,11 @@ fn evaluate_bishop_activity(board: &Board, color: Color) -> (i32, i32) {
     let mut mg_score = 0;
     let mut eg_score = 0;
 
-    let bishops = board.piece_bb(PieceType::Bishop, color);
-    let bishop_count = bishops.count();
-
-    // 1. Bishop pair bonus
-    if bishop_count >= 2 {
-        mg_score += values::BISHOP_PAIR[0];
-        eg_score += values::BISHOP_PAIR[1];
-    }
-
-    // 2. Bad bishops and trapped bishops
-    let our_pawns = board.piece_bb(PieceType::Pawn, color);
-
-    for bishop_sq in bishops {
-        // Check if trapped
+    for bishop_sq in board.piece_bb(PieceType::Bishop, color) {
         if is_trapped_bishop(bishop_sq, color) {
             mg_score += values::TRAPPED_BISHOP[0];
             eg_score += values::TRAPPED_BISHOP[1];
         }
-
-        // Check if bad bishop
-        if is_bad_bishop(bishop_sq, our_pawns) {
-            mg_score += values::BAD_BISHOP[0];
-            eg_score += values::BAD_BISHOP[1];
-        }
     }
 
     (mg_score, eg_score)
@@ -259,36 +232,6 @@ fn is_trapped_bishop(bishop_sq: Square, color: Color) -> bool {
     }
 }
 
-/// Check if a bishop is bad (majority of pawns on bishop's color).
-fn is_bad_bishop(bishop_sq: Square, our_pawns: Bitboard) -> bool {
-    let bishop_on_light = (bishop_sq.file() + bishop_sq.rank()) % 2 == 0;
-
-    let mut light_pawns = 0;
-    let mut dark_pawns = 0;
-
-    for pawn_sq in our_pawns {
-        if (pawn_sq.file() + pawn_sq.rank()) % 2 == 0 {
-            light_pawns += 1;
-        } else {
-            dark_pawns += 1;
-        }
-    }
-
-    let total_pawns = light_pawns + dark_pawns;
-    if total_pawns == 0 {
-        return false;
-    }
-
-    let pawns_on_bishop_color = if bishop_on_light {
-        light_pawns
-    } else {
-        dark_pawns
-    };
-
-    // Bad if more than 50% of pawns on bishop's color
-    pawns_on_bishop_color > total_pawns / 2
-}
-
 /// Check if a knight is trapped (no legal moves).
 fn is_trapped_knight(knight_sq: Square, occupied: Bitboard) -> bool {
     let attacks = knight_attacks(knight_sq);
@@ -359,52 +302,15 @@ fn is_knight_outpost(
 
 /// Check if a square can be attacked by enemy pawns.
 fn can_be_attacked_by_enemy_pawn(sq: Square, color: Color, enemy_pawns: Bitboard) -> bool {
-    let file = sq.file();
-
-    // Get files that can attack this square
-    let attack_files: Vec<u8> = if file > 0 && file < 7 {
-        vec![file - 1, file + 1]
-    } else if file == 0 {
-        vec![1]
-    } else {
-        vec![6]
-    };
-
-    // Check if enemy pawns on attack files ahead of this square
-    for attack_file in attack_files {
-        let file_bb = file_bitboard(attack_file);
-        let pawns_on_file = enemy_pawns & file_bb;
-
-        for pawn_sq in pawns_on_file {
-            // Check if pawn is ahead of knight square (can advance to attack)
-            if color == Color::White {
-                if pawn_sq.rank() > sq.rank() {
-                    return true;
-                }
-            } else if pawn_sq.rank() < sq.rank() {
-                return true;
-            }
-        }
-    }
-
-    false
+    // Enemy pawns on adjacent files ahead of this square can advance to attack it
+    let ahead = Bitboard::from_square(sq).front_span(color);
+    let attack_span = ahead.shift_east() | ahead.shift_west();
+    !(enemy_pawns & attack_span).is_empty()
 }
 
 /// Check if a square is central (d4, d5, e4, e5).
 fn is_central_square(sq: Square) -> bool {
-    let file = sq.file();
-    let rank = sq.rank();
-    (3..5).contains(&file) && (3..5).contains(&rank)
-}
-
-/// Get a bitboard mask for a file.
-fn file_bitboard(file: u8) -> Bitboard {
-    Bitboard::new(0x0101010101010101u64 << file)
-}
-
-/// Get a bitboard mask for a rank.
-fn rank_bitboard(rank: u8) -> Bitboard {
-    Bitboard::new(0xFFu64 << (rank * 8))
+    Bitboard::CENTER.contains(sq)
 }
 
 #[cfg(test)]
@@ -441,32 +347,6 @@ mod tests {
         assert!(mg > 0, "Rook on 7th should have bonus, got mg={}", mg);
     }
 
-    #[test]
-    fn test_bishop_pair() {
-        // White has bishop pair
-        let board = Board::startpos();
-        let (mg, eg) = evaluate_bishop_activity(&board, Color::White);
-
-        assert!(mg > 0, "Bishop pair should have bonus in middlegame");
-        assert!(eg > 0, "Bishop pair should have bonus in endgame");
-    }
-
-    #[test]
-    fn test_bad_bishop() {
-        // White bishop on light squares with most pawns on light squares
-        let board = parse_fen("rnbqkbnr/8/8/8/8/P1P1P1P1/1P1P1P1P/RNBQKBNR w KQkq - 0 1").unwrap();
-        let bishops = board.piece_bb(PieceType::Bishop, Color::White);
-        let our_pawns = board.piece_bb(PieceType::Pawn, Color::White);
-
-        for bishop_sq in bishops {
-            let is_bad = is_bad_bishop(bishop_sq, our_pawns);
-            // One of the bishops should be bad
-            if is_bad {
-                return; // Test passes if we find a bad bishop
-            }
-        }
-    }
-
     #[test]
     fn test_trapped_bishop() {
         // White bishop trapped on a7
@@ -509,13 +389,13 @@ mod tests {
 
     #[test]
     fn test_file_bitboard() {
-        let e_file = file_bitboard(4);
+        let e_file = Bitboard::from_file(4);
         assert_eq!(e_file.count(), 8, "File should have 8 squares");
     }
 
     #[test]
     fn test_rank_bitboard() {
-        let rank_4 = rank_bitboard(3); // 4th rank (0-indexed as 3)
+        let rank_4 = Bitboard::from_rank(3); // 4th rank (0-indexed as 3)
         assert_eq!(rank_4.count(), 8, "Rank should have 8 squares");
     }
 
@@ -525,18 +405,16 @@ mod tests {
         let (white_mg, _white_eg) = evaluate_piece_activity(&board, Color::White, 0);
         let (black_mg, _black_eg) = evaluate_piece_activity(&board, Color::Black, 0);
 
-        // Both sides have bishop pair in startpos (+50)
-        // But also back rank penalty for 6 pieces (-60)
-        // Net should be slightly negative or around zero
-        // The important thing is symmetry
+        // Back rank penalty for 6 pieces (-60); the important thing is
+        // symmetry
         assert_eq!(
             white_mg, black_mg,
             "Activity should be symmetric in startpos"
         );
 
-        // Both should have bishop pair bonus
+        // The bishop pair is scored by the bishop module, not here
         let (bishop_mg, _) = evaluate_bishop_activity(&board, Color::White);
-        assert!(bishop_mg > 0, "Should have bishop pair bonus");
+        assert_eq!(bishop_mg, 0, "No trapped bishops in startpos");
     }
 
     #[test]
@@ -4,22 +4,118 @@
 //! Separate tables for middlegame and endgame.
 
 use crate::board::Board;
-use crate::piece::{Color, PieceType};
+use crate::piece::{Color, Piece, PieceType};
 use crate::square::Square;
+use once_cell::sync::Lazy;
+
+/// Number of piece types (pawn, knight, bishop, rook) whose tables are
+/// file-symmetric and stored as 32-entry halves.
+const SYMMETRIC_PIECES: usize = 4;
+
+/// Index into a 32-entry file-symmetric table: `min(file, 7 - file) * 8 + rank`.
+///
+/// Squares mirrored across the d/e boundary share an entry.
+#[inline]
+pub const fn mirror_file_index(sq: Square) -> usize {
+    (mirror_file(sq.file()) as usize) * 8 + sq.rank() as usize
+}
+
+/// Index into a 32-entry rank-symmetric table: `min(rank, 7 - rank) * 8 + file`.
+///
+/// Squares mirrored across the 4th/5th rank boundary share an entry.
+#[inline]
+pub const fn mirror_rank_index(sq: Square) -> usize {
+    (mirror_file(sq.rank()) as usize) * 8 + sq.file() as usize
+}
+
+/// Fold a file (or rank) onto the a-d half of the board.
+#[inline]
+const fn mirror_file(file: u8) -> u8 {
+    if file < 4 {
+        file
+    } else {
+        7 - file
+    }
+}
+
+/// Fold a file-symmetric 64-entry table into its 32-entry half.
+///
+/// Only the a-d files are read; the e-h files are assumed to mirror them.
+const fn fold_file_symmetric(table: &[i32; 64]) -> [i32; 32] {
+    let mut half = [0; 32];
+    let mut i = 0;
+    while i < 64 {
+        let sq = Square::new(i as u8);
+        if sq.file() < 4 {
+            half[mirror_file_index(sq)] = table[i];
+        }
+        i += 1;
+    }
+    half
+}
 
 /// Piece-square tables for all piece types.
 ///
 /// Tables are from White's perspective (rank 0 = rank 1, rank 7 = rank 8).
 /// For Black, we flip the rank (7 - rank).
+///
+/// Pawns, knights, bishops and rooks are file-symmetric and only store the
+/// a-d half of their tables; queens and kings keep all 64 entries.
 #[derive(Debug, Clone)]
 pub struct PieceSquareTables {
-    /// Middlegame tables [piece_type][square]
-    pub mg_tables: [[i32; 64]; 6],
-    /// Endgame tables [piece_type][square]
-    pub eg_tables: [[i32; 64]; 6],
+    /// Middlegame half tables [piece_type][mirror_file_index]
+    pub mg_symmetric: [[i32; 32]; SYMMETRIC_PIECES],
+    /// Endgame half tables [piece_type][mirror_file_index]
+    pub eg_symmetric: [[i32; 32]; SYMMETRIC_PIECES],
+    /// Middlegame queen and king tables [piece_type - 4][square]
+    pub mg_full: [[i32; 64]; 2],
+    /// Endgame queen and king tables [piece_type - 4][square]
+    pub eg_full: [[i32; 64]; 2],
 }
 
 impl PieceSquareTables {
+    /// Build the tables from full 64-entry tables [piece_type][square].
+    ///
+    /// Pawn, knight, bishop and rook tables must be file-symmetric.
+    pub fn from_full_tables(mg_tables: &[[i32; 64]; 6], eg_tables: &[[i32; 64]; 6]) -> Self {
+        let mut mg_symmetric = [[0; 32]; SYMMETRIC_PIECES];
+        let mut eg_symmetric = [[0; 32]; SYMMETRIC_PIECES];
+        for piece in 0..SYMMETRIC_PIECES {
+            mg_symmetric[piece] = fold_file_symmetric(&mg_tables[piece]);
+            eg_symmetric[piece] = fold_file_symmetric(&eg_tables[piece]);
+        }
+
+        Self {
+            mg_symmetric,
+            eg_symmetric,
+            mg_full: [
+                mg_tables[PieceType::Queen.index()],
+                mg_tables[PieceType::King.index()],
+            ],
+            eg_full: [
+                eg_tables[PieceType::Queen.index()],
+                eg_tables[PieceType::King.index()],
+            ],
+        }
+    }
+
+    /// Table value for a piece on a square, from White's perspective.
+    #[inline]
+    pub fn value(&self, piece_type: PieceType, sq: Square, is_eg: bool) -> i32 {
+        let piece_idx = piece_type.index();
+        if piece_idx < SYMMETRIC_PIECES {
+            let tables = if is_eg {
+                &self.eg_symmetric
+            } else {
+                &self.mg_symmetric
+            };
+            tables[piece_idx][mirror_file_index(sq)]
+        } else {
+            let tables = if is_eg { &self.eg_full } else { &self.mg_full };
+            tables[piece_idx - SYMMETRIC_PIECES][sq.index() as usize]
+        }
+    }
+
     /// Evaluate position using piece-square tables.
     pub fn evaluate_position(&self, board: &Board, color: Color) -> i32 {
         let mut score = 0;
@@ -29,18 +125,7 @@ impl PieceSquareTables {
             let pieces = board.piece_bb(piece_type, color);
 
             for sq in pieces {
-                let table_sq = if color == Color::White {
-                    sq.index() as usize
-                } else {
-                    // Flip rank for black
-                    Square::from_coords(sq.file(), 7 - sq.rank()).index() as usize
-                };
-
-                if is_eg {
-                    score += self.eg_tables[piece_type.index()][table_sq];
-                } else {
-                    score += self.mg_tables[piece_type.index()][table_sq];
-                }
+                score += self.value(piece_type, relative_square(sq, color), is_eg);
             }
         }
 
@@ -53,125 +138,164 @@ impl PieceSquareTables {
     /// Positive values mean the move improves piece placement.
     ///
     /// This is used in move ordering to give quiet moves a positional bonus.
-    pub fn move_bonus(&self, piece_type: PieceType, from: Square, to: Square, color: Color, is_eg: bool) -> i32 {
-        let from_sq = if color == Color::White {
-            from.index() as usize
-        } else {
-            Square::from_coords(from.file(), 7 - from.rank()).index() as usize
-        };
+    pub fn move_bonus(
+        &self,
+        piece_type: PieceType,
+        from: Square,
+        to: Square,
+        color: Color,
+        is_eg: bool,
+    ) -> i32 {
+        self.value(piece_type, relative_square(to, color), is_eg)
+            - self.value(piece_type, relative_square(from, color), is_eg)
+    }
+}
 
-        let to_sq = if color == Color::White {
-            to.index() as usize
-        } else {
-            Square::from_coords(to.file(), 7 - to.rank()).index() as usize
-        };
+/// Default tables, shared so evaluators and move orderers don't each build
+/// their own copy.
+pub static DEFAULT_PIECE_SQUARE_TABLES: Lazy<PieceSquareTables> =
+    Lazy::new(PieceSquareTables::default);
 
-        let tables = if is_eg { &self.eg_tables } else { &self.mg_tables };
-        let piece_idx = piece_type.index();
+/// (middlegame, endgame) values indexed by [color][piece_type][square].
+type PieceSquareValues = [[[(i32, i32); 64]; 6]; 2];
+
+/// Default table values per piece and square, already flipped for Black.
+static DEFAULT_PIECE_SQUARE_VALUES: Lazy<Box<PieceSquareValues>> = Lazy::new(|| {
+    let tables = &*DEFAULT_PIECE_SQUARE_TABLES;
+    let mut values = Box::new([[[(0, 0); 64]; 6]; 2]);
+
+    for color in [Color::White, Color::Black] {
+        for piece_type in PieceType::all() {
+            for sq_idx in 0..64 {
+                let sq = relative_square(Square::new(sq_idx as u8), color);
+                values[color.index()][piece_type.index()][sq_idx] = (
+                    tables.value(piece_type, sq, false),
+                    tables.value(piece_type, sq, true),
+                );
+            }
+        }
+    }
+
+    values
+});
+
+/// Middlegame and endgame values of a piece on a square in the default
+/// tables, from the piece owner's point of view.
+///
+/// [`Board`] keeps running sums of these so evaluation does not have to
+/// scan every piece.
+#[inline]
+pub fn default_piece_square_value(piece: Piece, sq: Square) -> (i32, i32) {
+    DEFAULT_PIECE_SQUARE_VALUES[piece.color.index()][piece.piece_type.index()][sq.index() as usize]
+}
 
-        tables[piece_idx][to_sq] - tables[piece_idx][from_sq]
+/// Flip the rank for Black so tables can be written from White's side.
+#[inline]
+fn relative_square(sq: Square, color: Color) -> Square {
+    match color {
+        Color::White => sq,
+        Color::Black => sq.mirror_vertical(),
     }
 }
 
 impl Default for PieceSquareTables {
     fn default() -> Self {
-        Self {
-            mg_tables: [
-                // Pawn middlegame
-                [
-                    0, 0, 0, 0, 0, 0, 0, 0, // Rank 1
-                    5, 10, 10, -20, -20, 10, 10, 5, // Rank 2
-                    5, -5, -10, 0, 0, -10, -5, 5, // Rank 3
-                    0, 0, 0, 20, 20, 0, 0, 0, // Rank 4
-                    5, 5, 10, 25, 25, 10, 5, 5, // Rank 5
-                    10, 10, 20, 30, 30, 20, 10, 10, // Rank 6
-                    50, 50, 50, 50, 50, 50, 50, 50, // Rank 7
-                    0, 0, 0, 0, 0, 0, 0, 0, // Rank 8
-                ],
-                // Knight middlegame
-                [
-                    -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 5, 5, 0, -20, -40, -30, 5,
-                    10, 15, 15, 10, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 15, 20, 20, 15,
-                    5, -30, -30, 0, 10, 15, 15, 10, 0, -30, -40, -20, 0, 0, 0, 0, -20, -40, -50,
-                    -40, -30, -30, -30, -30, -40, -50,
-                ],
-                // Bishop middlegame
-                [
-                    -20, -10, -10, -10, -10, -10, -10, -20, -10, 5, 0, 0, 0, 0, 5, -10, -10, 10,
-                    10, 10, 10, 10, 10, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 5, 5, 10, 10, 5,
-                    5, -10, -10, 0, 5, 10, 10, 5, 0, -10, -10, 0, 0, 0, 0, 0, 0, -10, -20, -10,
-                    -10, -10, -10, -10, -10, -20,
-                ],
-                // Rook middlegame
-                [
-                    0, 0, 0, 5, 5, 0, 0, 0, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5,
-                    0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, 5,
-                    10, 10, 10, 10, 10, 10, 5, 0, 0, 0, 0, 0, 0, 0, 0,
-                ],
-                // Queen middlegame
-                [
-                    -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 5, 0, 0, 0, 0, -10, -10, 5, 5, 5,
-                    5, 5, 0, -10, 0, 0, 5, 5, 5, 5, 0, -5, -5, 0, 5, 5, 5, 5, 0, -5, -10, 0, 5, 5,
-                    5, 5, 0, -10, -10, 0, 0, 0, 0, 0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
-                ],
-                // King middlegame (focus on safety)
-                [
-                    20, 30, 10, 0, 0, 10, 30, 20, 20, 20, 0, 0, 0, 0, 20, 20, -10, -20, -20, -20,
-                    -20, -20, -20, -10, -20, -30, -30, -40, -40, -30, -30, -20, -30, -40, -40, -50,
-                    -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40, -50,
-                    -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30,
-                ],
-            ],
-            eg_tables: [
-                // Pawn endgame (passed pawns more valuable)
-                [
-                    0, 0, 0, 0, 0, 0, 0, 0, // Rank 1
-                    10, 10, 10, 10, 10, 10, 10, 10, // Rank 2
-                    20, 20, 20, 20, 20, 20, 20, 20, // Rank 3
-                    30, 30, 30, 30, 30, 30, 30, 30, // Rank 4
-                    40, 40, 40, 40, 40, 40, 40, 40, // Rank 5
-                    50, 50, 50, 50, 50, 50, 50, 50, // Rank 6
-                    70, 70, 70, 70, 70, 70, 70, 70, // Rank 7
-                    0, 0, 0, 0, 0, 0, 0, 0, // Rank 8
-                ],
-                // Knight endgame (less valuable)
-                [
-                    -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 0, 0, 0, -20, -40, -30, 0,
-                    10, 15, 15, 10, 0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 15, 20, 20, 15,
-                    0, -30, -30, 5, 10, 15, 15, 10, 5, -30, -40, -20, 0, 5, 5, 0, -20, -40, -50,
-                    -40, -30, -30, -30, -30, -40, -50,
-                ],
-                // Bishop endgame
-                [
-                    -20, -10, -10, -10, -10, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5,
-                    10, 10, 5, 0, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 0, 10, 10, 10, 10, 0,
-                    -10, -10, 0, 5, 10, 10, 5, 0, -10, -10, 0, 0, 0, 0, 0, 0, -10, -20, -10, -10,
-                    -10, -10, -10, -10, -20,
-                ],
-                // Rook endgame
-                [
-                    0, 0, 0, 0, 0, 0, 0, 0, 5, 10, 10, 10, 10, 10, 10, 5, -5, 0, 0, 0, 0, 0, 0, -5,
-                    -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5,
-                    -5, 0, 0, 0, 0, 0, 0, -5, 0, 0, 0, 0, 0, 0, 0, 0,
-                ],
-                // Queen endgame
-                [
-                    -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 5,
-                    5, 5, 0, -10, -5, 0, 5, 5, 5, 5, 0, -5, 0, 0, 5, 5, 5, 5, 0, 0, -10, 5, 5, 5,
-                    5, 5, 0, -10, -10, 0, 5, 0, 0, 0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
-                ],
-                // King endgame (active king)
-                [
-                    -50, -30, -30, -30, -30, -30, -30, -50, -30, -30, 0, 0, 0, 0, -30, -30, -30,
-                    -10, 20, 30, 30, 20, -10, -30, -30, -10, 30, 40, 40, 30, -10, -30, -30, -10,
-                    30, 40, 40, 30, -10, -30, -30, -10, 20, 30, 30, 20, -10, -30, -30, -20, -10, 0,
-                    0, -10, -20, -30, -50, -40, -30, -20, -20, -30, -40, -50,
-                ],
-            ],
-        }
+        Self::from_full_tables(&MG_TABLES, &EG_TABLES)
     }
 }
 
+/// Default middlegame tables [piece_type][square]
+const MG_TABLES: [[i32; 64]; 6] = [
+    // Pawn middlegame
+    [
+        0, 0, 0, 0, 0, 0, 0, 0, // Rank 1
+        5, 10, 10, -20, -20, 10, 10, 5, // Rank 2
+        5, -5, -10, 0, 0, -10, -5, 5, // Rank 3
+        0, 0, 0, 20, 20, 0, 0, 0, // Rank 4
+        5, 5, 10, 25, 25, 10, 5, 5, // Rank 5
+        10, 10, 20, 30, 30, 20, 10, 10, // Rank 6
+        50, 50, 50, 50, 50, 50, 50, 50, // Rank 7
+        0, 0, 0, 0, 0, 0, 0, 0, // Rank 8
+    ],
+    // Knight middlegame
+    [
+        -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 5, 5, 0, -20, -40, -30, 5, 10, 15, 15,
+        10, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 10, 15,
+        15, 10, 0, -30, -40, -20, 0, 0, 0, 0, -20, -40, -50, -40, -30, -30, -30, -30, -40, -50,
+    ],
+    // Bishop middlegame
+    [
+        -20, -10, -10, -10, -10, -10, -10, -20, -10, 5, 0, 0, 0, 0, 5, -10, -10, 10, 10, 10, 10,
+        10, 10, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 5, 5, 10, 10, 5, 5, -10, -10, 0, 5, 10,
+        10, 5, 0, -10, -10, 0, 0, 0, 0, 0, 0, -10, -20, -10, -10, -10, -10, -10, -10, -20,
+    ],
+    // Rook middlegame
+    [
+        0, 0, 0, 5, 5, 0, 0, 0, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0,
+        0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, 5, 10, 10, 10, 10, 10, 10, 5,
+        0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    // Queen middlegame
+    [
+        -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 5, 0, 0, 0, 0, -10, -10, 5, 5, 5, 5, 5, 0,
+        -10, 0, 0, 5, 5, 5, 5, 0, -5, -5, 0, 5, 5, 5, 5, 0, -5, -10, 0, 5, 5, 5, 5, 0, -10, -10, 0,
+        0, 0, 0, 0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
+    ],
+    // King middlegame (focus on safety)
+    [
+        20, 30, 10, 0, 0, 10, 30, 20, 20, 20, 0, 0, 0, 0, 20, 20, -10, -20, -20, -20, -20, -20,
+        -20, -10, -20, -30, -30, -40, -40, -30, -30, -20, -30, -40, -40, -50, -50, -40, -40, -30,
+        -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -30, -40,
+        -40, -50, -50, -40, -40, -30,
+    ],
+];
+
+/// Default endgame tables [piece_type][square]
+const EG_TABLES: [[i32; 64]; 6] = [
+    // Pawn endgame (passed pawns more valuable)
+    [
+        0, 0, 0, 0, 0, 0, 0, 0, // Rank 1
+        10, 10, 10, 10, 10, 10, 10, 10, // Rank 2
+        20, 20, 20, 20, 20, 20, 20, 20, // Rank 3
+        30, 30, 30, 30, 30, 30, 30, 30, // Rank 4
+        40, 40, 40, 40, 40, 40, 40, 40, // Rank 5
+        50, 50, 50, 50, 50, 50, 50, 50, // Rank 6
+        70, 70, 70, 70, 70, 70, 70, 70, // Rank 7
+        0, 0, 0, 0, 0, 0, 0, 0, // Rank 8
+    ],
+    // Knight endgame (less valuable)
+    [
+        -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 0, 0, 0, -20, -40, -30, 0, 10, 15, 15,
+        10, 0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 10, 15,
+        15, 10, 5, -30, -40, -20, 0, 5, 5, 0, -20, -40, -50, -40, -30, -30, -30, -30, -40, -50,
+    ],
+    // Bishop endgame
+    [
+        -20, -10, -10, -10, -10, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 10, 10, 5,
+        0, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 0, 5, 10, 10,
+        5, 0, -10, -10, 0, 0, 0, 0, 0, 0, -10, -20, -10, -10, -10, -10, -10, -10, -20,
+    ],
+    // Rook endgame
+    [
+        0, 0, 0, 0, 0, 0, 0, 0, 5, 10, 10, 10, 10, 10, 10, 5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0,
+        0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0,
+        -5, 0, 0, 0, 0, 0, 0, 0, 0,
+    ],
+    // Queen endgame
+    [
+        -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 5, 5, 5, 0,
+        -10, -5, 0, 5, 5, 5, 5, 0, -5, 0, 0, 5, 5, 5, 5, 0, 0, -10, 5, 5, 5, 5, 5, 0, -10, -10, 0,
+        5, 0, 0, 0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
+    ],
+    // King endgame (active king)
+    [
+        -50, -30, -30, -30, -30, -30, -30, -50, -30, -30, 0, 0, 0, 0, -30, -30, -30, -10, 20, 30,
+        30, 20, -10, -30, -30, -10, 30, 40, 40, 30, -10, -30, -30, -10, 30, 40, 40, 30, -10, -30,
+        -30, -10, 20, 30, 30, 20, -10, -30, -30, -20, -10, 0, 0, -10, -20, -30, -50, -40, -30, -20,
+        -20, -30, -40, -50,
+    ],
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,12 +348,110 @@ mod tests {
         let idx_black = Square::from_coords(sq_black.file(), 7 - sq_black.rank()).index();
 
         // Should have similar values (mirror positions)
-        let white_value = pst.mg_tables[PieceType::Pawn.index()][idx_white as usize];
-        let black_value = pst.mg_tables[PieceType::Pawn.index()][idx_black as usize];
+        let white_value = pst.value(PieceType::Pawn, Square::new(idx_white), false);
+        let black_value = pst.value(PieceType::Pawn, Square::new(idx_black), false);
 
         assert_eq!(
             white_value, black_value,
             "Mirrored positions should have same PST value"
         );
     }
+
+    #[test]
+    fn test_mirror_indices() {
+        assert_eq!(mirror_file_index(Square::A1), 0);
+        assert_eq!(mirror_file_index(Square::H1), 0);
+        assert_eq!(mirror_file_index(Square::D5), 3 * 8 + 4);
+        assert_eq!(mirror_file_index(Square::E5), 3 * 8 + 4);
+        assert_eq!(mirror_rank_index(Square::A1), 0);
+        assert_eq!(mirror_rank_index(Square::A8), 0);
+        assert_eq!(mirror_rank_index(Square::E4), mirror_rank_index(Square::E5));
+
+        for sq in Square::all() {
+            assert!(mirror_file_index(sq) < 32);
+            assert!(mirror_rank_index(sq) < 32);
+            assert_eq!(
+                mirror_file_index(sq),
+                mirror_file_index(sq.mirror_horizontal())
+            );
+            assert_eq!(
+                mirror_rank_index(sq),
+                mirror_rank_index(sq.mirror_vertical())
+            );
+        }
+    }
+
+    #[test]
+    fn test_symmetric_tables_match_full_tables() {
+        let pst = PieceSquareTables::default();
+
+        for piece_type in PieceType::all() {
+            for sq in Square::all() {
+                let idx = piece_type.index();
+                assert_eq!(
+                    pst.value(piece_type, sq, false),
+                    MG_TABLES[idx][sq.index() as usize]
+                );
+                assert_eq!(
+                    pst.value(piece_type, sq, true),
+                    EG_TABLES[idx][sq.index() as usize]
+                );
+            }
+        }
+    }
+
+    /// Evaluate with the full 64-entry tables directly.
+    fn evaluate_full_tables(board: &Board, color: Color) -> i32 {
+        let tables = if crate::eval::material::is_endgame(board) {
+            &EG_TABLES
+        } else {
+            &MG_TABLES
+        };
+        let mut score = 0;
+        for piece_type in PieceType::all() {
+            for sq in board.piece_bb(piece_type, color) {
+                let table_sq = relative_square(sq, color);
+                score += tables[piece_type.index()][table_sq.index() as usize];
+            }
+        }
+        score
+    }
+
+    /// Mirror a FEN across the d/e file boundary (castling rights dropped).
+    fn mirror_fen_files(fen: &str) -> String {
+        let mut parts: Vec<String> = fen.split_whitespace().map(String::from).collect();
+        parts[0] = parts[0]
+            .split('/')
+            .map(|rank| rank.chars().rev().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("/");
+        parts[2] = "-".to_string();
+        parts[3] = "-".to_string();
+        parts.join(" ")
+    }
+
+    #[test]
+    fn test_symmetric_evaluation_on_mirrored_positions() {
+        let pst = PieceSquareTables::default();
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ];
+
+        for fen in fens {
+            for fen in [fen.to_string(), mirror_fen_files(fen)] {
+                let board = parse_fen(&fen).unwrap();
+                for color in [Color::White, Color::Black] {
+                    assert_eq!(
+                        pst.evaluate_position(&board, color),
+                        evaluate_full_tables(&board, color),
+                        "{} ({:?})",
+                        fen,
+                        color
+                    );
+                }
+            }
+        }
+    }
 }
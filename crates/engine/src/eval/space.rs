@@ -0,0 +1,143 @@
+//! Space evaluation.
+//!
+//! Evaluates (middlegame only):
+//! - Safe central squares our pawns control or shelter
+//! - A central pawn duo (d4+e4 or d5+e5)
+//!
+//! A side with more room behind and around its pawns can regroup its pieces,
+//! while a cramped side gets in its own way. Space matters less as pieces
+//! come off, so the square count is weighted by our remaining pieces. Only
+//! pawn bitboards are used, which keeps the term cheap.
+
+use crate::attacks::pawn_attacks_bb;
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::piece::{Color, PieceType};
+use crate::square::Square;
+
+/// Space evaluation parameters (in centipawns, before the space divisor).
+mod values {
+    /// Bonus per safe square with all 7 pieces on the board
+    pub const SQUARE: i32 = 16;
+
+    /// Central pawn duo bonus [mg, eg]
+    pub const CENTER_DUO: [i32; 2] = [40, 0];
+}
+
+/// The 16 center squares c3-f6, where space is counted for both sides.
+const SPACE_ZONE: Bitboard = Bitboard::EXTENDED_CENTER;
+
+/// Evaluate space for both sides.
+///
+/// Returns (mg_score, eg_score) from White's perspective.
+///
+/// # Example
+/// ```
+/// use engine::eval::evaluate_space;
+/// use engine::io::parse_fen;
+///
+/// // White's d4/e4 duo against Black's passive d6/e6
+/// let board = parse_fen("rnbqkbnr/ppp2ppp/3pp3/8/3PP3/8/PPP2PPP/RNBQKBNR w KQkq - 0 3").unwrap();
+/// let (mg, eg) = evaluate_space(&board);
+/// assert!(mg > 0);
+/// assert_eq!(eg, 0);
+/// ```
+pub fn evaluate_space(board: &Board) -> (i32, i32) {
+    let (white_mg, white_eg) = evaluate_space_for(board, Color::White);
+    let (black_mg, black_eg) = evaluate_space_for(board, Color::Black);
+    (white_mg - black_mg, white_eg - black_eg)
+}
+
+/// Evaluate space for a single color.
+fn evaluate_space_for(board: &Board, color: Color) -> (i32, i32) {
+    let our_pawns = board.piece_bb(PieceType::Pawn, color);
+    let their_pawns = board.piece_bb(PieceType::Pawn, color.opponent());
+
+    let mut mg_score = safe_squares(our_pawns, their_pawns, color).count() as i32
+        * values::SQUARE
+        * piece_count(board, color)
+        / 7;
+    let mut eg_score = 0;
+
+    if has_center_duo(our_pawns) {
+        mg_score += values::CENTER_DUO[0];
+        eg_score += values::CENTER_DUO[1];
+    }
+
+    (mg_score, eg_score)
+}
+
+/// Center squares our pawns attack or stand in front of, that are neither
+/// attacked by enemy pawns nor blocked by our own.
+fn safe_squares(our_pawns: Bitboard, their_pawns: Bitboard, color: Color) -> Bitboard {
+    let controlled = pawn_attacks_bb(our_pawns, color) | our_pawns.front_span(color.opponent());
+    let unsafe_squares = pawn_attacks_bb(their_pawns, color.opponent()) | our_pawns;
+    SPACE_ZONE & controlled & !unsafe_squares
+}
+
+/// Knights, bishops, rooks and queens of `color`, at most 7 counted.
+fn piece_count(board: &Board, color: Color) -> i32 {
+    let pieces = board.color_bb(color)
+        & !board.piece_bb(PieceType::Pawn, color)
+        & !board.piece_bb(PieceType::King, color);
+    (pieces.count() as i32).min(7)
+}
+
+/// Pawns side by side on d4+e4 or d5+e5, for either color.
+fn has_center_duo(our_pawns: Bitboard) -> bool {
+    [(Square::D4, Square::E4), (Square::D5, Square::E5)]
+        .iter()
+        .any(|&(d, e)| our_pawns.contains(d) && our_pawns.contains(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::parse_fen;
+
+    #[test]
+    fn test_startpos_is_balanced() {
+        let board = Board::startpos();
+        assert_eq!(evaluate_space(&board), (0, 0));
+    }
+
+    #[test]
+    fn test_space_scales_with_pieces() {
+        // Same pawns, but the queens and rooks are gone
+        let full =
+            parse_fen("rnbqkbnr/ppp2ppp/3pp3/8/3PP3/8/PPP2PPP/RNBQKBNR w KQkq - 0 3").unwrap();
+        let light = parse_fen("1nb1kbn1/ppp2ppp/3pp3/8/3PP3/8/PPP2PPP/1NB1KBN1 w - - 0 3").unwrap();
+        let (full_mg, _) = evaluate_space(&full);
+        let (light_mg, _) = evaluate_space(&light);
+        assert!(light_mg > 0);
+        assert!(full_mg > light_mg);
+    }
+
+    #[test]
+    fn test_center_duo() {
+        let white = parse_fen("4k3/8/8/8/3PP3/8/8/4K3 w - - 0 1").unwrap();
+        let black = parse_fen("4k3/8/8/3pp3/8/8/8/4K3 w - - 0 1").unwrap();
+        let split = parse_fen("4k3/8/8/3P4/4P3/8/8/4K3 w - - 0 1").unwrap();
+
+        // Without pieces only the duo counts
+        assert_eq!(evaluate_space(&white), (values::CENTER_DUO[0], 0));
+        assert_eq!(evaluate_space(&black), (-values::CENTER_DUO[0], 0));
+        assert_eq!(evaluate_space(&split), (0, 0));
+    }
+
+    #[test]
+    fn test_enemy_pawns_take_away_space() {
+        let white_pawns = Bitboard::from_square(Square::D4) | Bitboard::from_square(Square::E4);
+        let free = safe_squares(white_pawns, Bitboard::EMPTY, Color::White);
+        // c6 attacks d5 and b5, e6 attacks d5 and f5
+        let contested = safe_squares(
+            white_pawns,
+            Bitboard::from_square(Square::C6) | Bitboard::from_square(Square::from_coords(4, 5)),
+            Color::White,
+        );
+        assert!(free.contains(Square::D5));
+        assert!(!contested.contains(Square::D5));
+        assert!(!contested.contains(Square::from_coords(5, 4)));
+        assert!(contested.count() < free.count());
+    }
+}
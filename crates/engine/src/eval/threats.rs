@@ -1,184 +1,123 @@
 //! Threat detection and evaluation
 //!
 //! Evaluates tactical threats in a position:
-//! - Hanging pieces (undefended)
-//! - Pieces attacked by lower value pieces
-//! - Weak pieces under attack
+//! - Hanging pieces (an exchange on their square loses material, by SEE)
+//! - Minor and major pieces attacked by pawns
+//! - Pawn-attacked pieces without a pawn defending them
 
+use crate::attacks::pawn_attacks_bb;
+use crate::bitboard::Bitboard;
 use crate::board::Board;
 use crate::piece::{Color, PieceType};
-use crate::square::Square;
-use crate::bitboard::Bitboard;
-
-/// Piece values for threat evaluation (in centipawns)
-const PIECE_VALUES: [i32; 6] = [
-    100,  // Pawn
-    320,  // Knight
-    330,  // Bishop
-    500,  // Rook
-    900,  // Queen
-    0,    // King (not used for hanging)
-];
-
-/// Get the value of a piece type
-#[inline]
-fn piece_value(piece_type: PieceType) -> i32 {
-    match piece_type {
-        PieceType::Pawn => PIECE_VALUES[0],
-        PieceType::Knight => PIECE_VALUES[1],
-        PieceType::Bishop => PIECE_VALUES[2],
-        PieceType::Rook => PIECE_VALUES[3],
-        PieceType::Queen => PIECE_VALUES[4],
-        PieceType::King => PIECE_VALUES[5],
-    }
-}
-
-/// Compute all squares attacked by a color
-/// This is computed once and reused to avoid repeated is_square_attacked calls
-fn compute_attacks(board: &Board, color: Color) -> Bitboard {
-    use crate::attacks::{
-        bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks,
-    };
-
-    let mut attacks = Bitboard::EMPTY;
-    let occupied = board.occupied();
-
-    // Pawn attacks
-    let pawns = board.piece_bb(PieceType::Pawn, color);
-    for sq in pawns {
-        attacks |= pawn_attacks(sq, color);
-    }
-
-    // Knight attacks
-    let knights = board.piece_bb(PieceType::Knight, color);
-    for sq in knights {
-        attacks |= knight_attacks(sq);
-    }
-
-    // Bishop, rook, and queen attacks
-    let bishops = board.piece_bb(PieceType::Bishop, color);
-    let rooks = board.piece_bb(PieceType::Rook, color);
-    let queens = board.piece_bb(PieceType::Queen, color);
+use crate::search::see::see_on_square;
 
-    // Diagonal attacks (bishops + queens)
-    let diagonal_attackers = bishops | queens;
-    for sq in diagonal_attackers {
-        attacks |= bishop_attacks(sq, occupied);
-    }
+/// Threat evaluation constants
+mod values {
+    /// Material the enemy must win on a square (by SEE) for the piece on it
+    /// to count as hanging
+    pub const HANGING_THRESHOLD: i32 = 50;
 
-    // Orthogonal attacks (rooks + queens)
-    let orthogonal_attackers = rooks | queens;
-    for sq in orthogonal_attackers {
-        attacks |= rook_attacks(sq, occupied);
-    }
+    /// A hanging piece costs its SEE loss divided by this [mg, eg]
+    pub const HANGING_DIVISOR: [i32; 2] = [4, 4];
 
-    // King attacks
-    let king = board.piece_bb(PieceType::King, color);
-    for sq in king {
-        attacks |= king_attacks(sq);
-    }
+    /// Bonus per enemy minor or major piece attacked by one of our pawns [mg, eg]
+    pub const PAWN_THREAT: [i32; 2] = [40, 30];
 
-    attacks
+    /// Penalty for a pawn-attacked piece no pawn of ours defends [mg, eg]
+    pub const UNSUPPORTED_PAWN_TARGET: [i32; 2] = [-20, -10];
 }
 
 /// Evaluate threats in the position
 ///
 /// Returns (mg_score, eg_score) from white's perspective
 pub fn evaluate_threats(board: &Board) -> (i32, i32) {
-    // Compute attack maps once for both colors
-    let white_attacks = compute_attacks(board, Color::White);
-    let black_attacks = compute_attacks(board, Color::Black);
-
-    // Evaluate threats for both sides using cached attacks
-    let (white_mg, white_eg) = evaluate_threats_for_side(board, Color::White, &white_attacks, &black_attacks);
-    let (black_mg, black_eg) = evaluate_threats_for_side(board, Color::Black, &black_attacks, &white_attacks);
+    let (white_mg, white_eg) = evaluate_threats_for_side(board, Color::White);
+    let (black_mg, black_eg) = evaluate_threats_for_side(board, Color::Black);
 
-    let mg_score = white_mg - black_mg;
-    let eg_score = white_eg - black_eg;
-
-    (mg_score, eg_score)
+    (white_mg - black_mg, white_eg - black_eg)
 }
 
-/// Evaluate threats for one side using precomputed attack maps
-fn evaluate_threats_for_side(
-    board: &Board,
-    color: Color,
-    our_attacks: &Bitboard,
-    enemy_attacks: &Bitboard,
-) -> (i32, i32) {
+/// Threat score for `color`: bonuses for the threats it makes, penalties for
+/// its own pieces under threat.
+fn evaluate_threats_for_side(board: &Board, color: Color) -> (i32, i32) {
+    let enemy = color.opponent();
     let mut mg_score = 0;
     let mut eg_score = 0;
 
-    let enemy_color = color.opponent();
-
-    // Check all enemy pieces for threats
-    for piece_type in [
-        PieceType::Queen,
-        PieceType::Rook,
-        PieceType::Bishop,
-        PieceType::Knight,
-        PieceType::Pawn,
-    ] {
-        let pieces = board.piece_bb(piece_type, enemy_color);
-
-        for sq in pieces {
-            let sq_bb = Bitboard::from_square(sq);
-
-            // Check if piece is defended by enemy
-            let is_defended = !(*enemy_attacks & sq_bb).is_empty();
-
-            // Check if we attack this piece
-            let is_attacked_by_us = !(*our_attacks & sq_bb).is_empty();
-
-            // Hanging piece: attacked by us but not defended
-            if is_attacked_by_us && !is_defended {
-                let value = piece_value(piece_type);
-                mg_score += value / 2; // Half the piece value as bonus
-                eg_score += value / 2;
-            }
-
-            // Weak piece: attacked by us even if defended
-            // (gives bonus for pieces we can attack with lower value pieces)
-            if is_attacked_by_us {
-                // Simplified: assume we can attack with pawn (lowest value)
-                // More accurate would be to find actual lowest attacker
-                let attacker_value = piece_value(PieceType::Pawn);
-                let piece_val = piece_value(piece_type);
-
-                if attacker_value < piece_val {
-                    let bonus = (piece_val - attacker_value) / 8; // Smaller bonus (divided by 8 instead of 4)
-                    mg_score += bonus;
-                    eg_score += bonus;
-                }
-            }
+    // Hanging pieces: only squares the enemy attacks can lose material
+    let our_pieces = board.color_bb(color) & !board.piece_bb(PieceType::King, color);
+    for sq in our_pieces & board.controlled_squares(enemy) {
+        let loss = see_on_square(board, sq, enemy);
+        if loss >= values::HANGING_THRESHOLD {
+            mg_score -= loss / values::HANGING_DIVISOR[0];
+            eg_score -= loss / values::HANGING_DIVISOR[1];
         }
     }
 
+    // Pawn threats against minor and major pieces
+    let our_pawn_attacks = pawn_attacks_bb(board.piece_bb(PieceType::Pawn, color), color);
+    let threatened = our_pawn_attacks & non_pawn_material(board, enemy);
+    let threats = threatened.count() as i32;
+    mg_score += threats * values::PAWN_THREAT[0];
+    eg_score += threats * values::PAWN_THREAT[1];
+
+    // Our pieces attacked by pawns with no pawn of ours defending them
+    let enemy_pawn_attacks = pawn_attacks_bb(board.piece_bb(PieceType::Pawn, enemy), enemy);
+    let unsupported =
+        (enemy_pawn_attacks & non_pawn_material(board, color) & !our_pawn_attacks).count() as i32;
+    mg_score += unsupported * values::UNSUPPORTED_PAWN_TARGET[0];
+    eg_score += unsupported * values::UNSUPPORTED_PAWN_TARGET[1];
+
     (mg_score, eg_score)
 }
 
+/// Knights, bishops, rooks and queens of `color`.
+fn non_pawn_material(board: &Board, color: Color) -> Bitboard {
+    board.piece_bb(PieceType::Knight, color)
+        | board.piece_bb(PieceType::Bishop, color)
+        | board.piece_bb(PieceType::Rook, color)
+        | board.piece_bb(PieceType::Queen, color)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::io::parse_fen;
+    use crate::square::Square;
 
     #[test]
     fn test_hanging_piece() {
-        // Position with a hanging knight on e5
-        let board = parse_fen("rnbqkb1r/pppp1ppp/8/4n3/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
-
-        let (mg, eg) = evaluate_threats(&board);
+        // Black knight on e5 attacked by the f3 knight, then defended by d6
+        let hanging =
+            parse_fen("rnbqkb1r/pppp1ppp/8/4n3/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 0 1").unwrap();
+        let defended =
+            parse_fen("rnbqkb1r/ppp2ppp/3p4/4n3/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 0 1").unwrap();
+
+        let (hanging_mg, hanging_eg) = evaluate_threats(&hanging);
+        let (defended_mg, defended_eg) = evaluate_threats(&defended);
+
+        // Black (the side with the hanging piece) scores lower while it hangs
+        assert!(hanging_mg > 0, "Should detect hanging knight");
+        assert!(hanging_eg > 0, "Should detect hanging knight in endgame");
+        assert!(hanging_mg > defended_mg);
+        assert!(hanging_eg > defended_eg);
+        assert_eq!(defended_mg, 0);
+    }
 
-        // White should get bonus for threatening the hanging knight
-        assert!(mg > 0, "Should detect hanging knight");
-        assert!(eg > 0, "Should detect hanging knight in endgame");
+    #[test]
+    fn test_piece_attacked_by_lower_value_piece_hangs() {
+        // Rook on d5 defended by the c6 pawn but attacked by a knight
+        let board = parse_fen("4k3/8/2p5/3r4/8/4N3/8/4K3 w - - 0 1").unwrap();
+        let (mg, _) = evaluate_threats(&board);
+        let loss = see_on_square(&board, Square::D5, Color::White);
+        assert_eq!(loss, 500 - 320);
+        assert_eq!(mg, loss / values::HANGING_DIVISOR[0]);
     }
 
     #[test]
     fn test_defended_piece() {
         // Position with defended pieces
-        let board = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let board = Board::startpos();
 
         let (mg, eg) = evaluate_threats(&board);
 
@@ -189,13 +128,47 @@ mod tests {
 
     #[test]
     fn test_attacked_by_pawn() {
-        // Position where pawn attacks knight
-        let board = parse_fen("rnbqkb1r/pppp1ppp/8/4n3/3P4/8/PPP1PPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        // d4 pawn attacks the e5 knight, which no black pawn defends
+        let board =
+            parse_fen("rnbqkb1r/pppp1ppp/8/4n3/3P4/8/PPP1PPPP/RNBQKBNR w KQkq - 0 1").unwrap();
 
         let (mg, eg) = evaluate_threats(&board);
 
-        // White pawn threatens black knight (worth more)
-        assert!(mg > 0, "Pawn should threaten knight");
+        // Pawn threat plus the unsupported knight, and the knight hangs
+        let hanging = see_on_square(&board, Square::E5, Color::White);
+        assert_eq!(hanging, 320);
+        assert_eq!(
+            mg,
+            values::PAWN_THREAT[0] - values::UNSUPPORTED_PAWN_TARGET[0]
+                + hanging / values::HANGING_DIVISOR[0]
+        );
         assert!(eg > 0, "Pawn should threaten knight in endgame");
     }
+
+    #[test]
+    fn test_pawn_supported_target() {
+        // Same threat, but the d6 pawn defends the knight
+        let supported =
+            parse_fen("rnbqkb1r/ppp2ppp/3p4/4n3/3P4/8/PPP1PPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let (mg, _) = evaluate_threats(&supported);
+        // The knight loses 220 to the pawn capture even when defended
+        let loss = see_on_square(&supported, Square::E5, Color::White);
+        assert_eq!(loss, 320 - 100);
+        assert_eq!(
+            mg,
+            values::PAWN_THREAT[0] + loss / values::HANGING_DIVISOR[0]
+        );
+    }
+
+    #[test]
+    fn test_threats_are_symmetric() {
+        let board =
+            parse_fen("rnbqkb1r/pppp1ppp/8/4n3/3P4/8/PPP1PPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let mirrored =
+            parse_fen("rnbqkbnr/ppp1pppp/8/3p4/4N3/8/PPPP1PPP/RNBQKB1R b KQkq - 0 1").unwrap();
+        assert_eq!(evaluate_threats(&board), {
+            let (mg, eg) = evaluate_threats(&mirrored);
+            (-mg, -eg)
+        });
+    }
 }
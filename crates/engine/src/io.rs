@@ -12,8 +12,12 @@
 use crate::board::{Board, CastlingRights};
 use crate::movegen::generate_moves;
 use crate::piece::{Color, Piece, PieceType};
+use crate::r#move::Move;
 use crate::square::Square;
 
+pub mod pgn;
+pub mod pgn_variation;
+
 /// The starting position FEN string.
 pub const STARTPOS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
@@ -34,6 +38,8 @@ pub enum FenError {
     InvalidHalfmoveClock(String),
     /// Invalid fullmove number
     InvalidFullmoveNumber(String),
+    /// A move after the FEN is malformed or illegal in its position
+    InvalidMove(String),
 }
 
 impl std::fmt::Display for FenError {
@@ -46,6 +52,7 @@ impl std::fmt::Display for FenError {
             FenError::InvalidEnPassant(s) => write!(f, "Invalid en passant square: {}", s),
             FenError::InvalidHalfmoveClock(s) => write!(f, "Invalid halfmove clock: {}", s),
             FenError::InvalidFullmoveNumber(s) => write!(f, "Invalid fullmove number: {}", s),
+            FenError::InvalidMove(s) => write!(f, "Invalid move: {}", s),
         }
     }
 }
@@ -62,7 +69,77 @@ impl std::error::Error for FenError {}
 /// assert_eq!(board.to_fen(), STARTPOS_FEN);
 /// ```
 pub fn parse_fen(fen: &str) -> Result<Board, FenError> {
-    let parts: Vec<&str> = fen.split_whitespace().collect();
+    parse_fen_with(fen, &mut FenScratch::default())
+}
+
+/// Parse a FEN whose piece placement may use Unicode chess symbols
+/// (`♖♘♗♕♔` for White, `♜♞♝♛♚` for Black) as well as FEN letters.
+///
+/// # Example
+/// ```
+/// use engine::io::{parse_fen, parse_fen_unicode, STARTPOS_FEN};
+///
+/// let fen = "♜♞♝♛♚♝♞♜/♟♟♟♟♟♟♟♟/8/8/8/8/♙♙♙♙♙♙♙♙/♖♘♗♕♔♗♘♖ w KQkq - 0 1";
+/// assert_eq!(parse_fen_unicode(fen), parse_fen(STARTPOS_FEN));
+/// ```
+pub fn parse_fen_unicode(fen: &str) -> Result<Board, FenError> {
+    let fen = fen.trim_start();
+    let (placement, rest) = fen.split_once(' ').unwrap_or((fen, ""));
+    let placement: String = placement
+        .chars()
+        .map(|c| Piece::from_unicode_char(c).map_or(c, Piece::to_char))
+        .collect();
+    parse_fen(&format!("{} {}", placement, rest))
+}
+
+/// Parse many FEN strings, one result per input in the same order.
+///
+/// Equivalent to calling [`parse_fen`] on each string, but the buffers used
+/// to split each FEN are allocated once and reused for the whole batch.
+///
+/// # Example
+/// ```
+/// use engine::io::{parse_fen_batch, STARTPOS_FEN};
+///
+/// let results = parse_fen_batch(&[STARTPOS_FEN, "not a fen"]);
+/// assert!(results[0].is_ok());
+/// assert!(results[1].is_err());
+/// ```
+pub fn parse_fen_batch(fens: &[&str]) -> Vec<Result<Board, FenError>> {
+    let mut scratch = FenScratch::default();
+    fens.iter()
+        .map(|fen| parse_fen_with(fen, &mut scratch))
+        .collect()
+}
+
+/// Parse many FEN strings on the rayon thread pool.
+///
+/// Returns the same results as [`parse_fen_batch`], in input order; each
+/// worker thread reuses its own split buffers.
+#[cfg(feature = "parallel")]
+pub fn parse_fen_batch_rayon(fens: &[&str]) -> Vec<Result<Board, FenError>> {
+    use rayon::prelude::*;
+
+    fens.par_iter()
+        .map_init(FenScratch::default, |scratch, fen| {
+            parse_fen_with(fen, scratch)
+        })
+        .collect()
+}
+
+/// Buffers for splitting a FEN into its fields and ranks, kept between
+/// parses so a batch allocates them once.
+#[derive(Default)]
+struct FenScratch<'a> {
+    parts: Vec<&'a str>,
+    ranks: Vec<&'a str>,
+}
+
+/// Parse a FEN string using `scratch` for the intermediate splits.
+fn parse_fen_with<'a>(fen: &'a str, scratch: &mut FenScratch<'a>) -> Result<Board, FenError> {
+    let FenScratch { parts, ranks } = scratch;
+    parts.clear();
+    parts.extend(fen.split_whitespace());
 
     if parts.len() != 6 {
         return Err(FenError::InvalidFormat(format!(
@@ -74,7 +151,7 @@ pub fn parse_fen(fen: &str) -> Result<Board, FenError> {
     let mut board = Board::empty();
 
     // 1. Parse piece placement
-    parse_piece_placement(&mut board, parts[0])?;
+    parse_piece_placement(&mut board, parts[0], ranks)?;
 
     // 2. Parse side to move
     let side_to_move = parse_side_to_move(parts[1])?;
@@ -104,13 +181,19 @@ pub fn parse_fen(fen: &str) -> Result<Board, FenError> {
     use crate::zobrist::zobrist_hash;
     let hash = zobrist_hash(&board);
     board.set_hash(hash);
+    debug_assert!(board.validate_hash_consistency());
 
     Ok(board)
 }
 
 /// Parse the piece placement component of a FEN string.
-fn parse_piece_placement(board: &mut Board, placement: &str) -> Result<(), FenError> {
-    let ranks: Vec<&str> = placement.split('/').collect();
+fn parse_piece_placement<'a>(
+    board: &mut Board,
+    placement: &'a str,
+    ranks: &mut Vec<&'a str>,
+) -> Result<(), FenError> {
+    ranks.clear();
+    ranks.extend(placement.split('/'));
 
     if ranks.len() != 8 {
         return Err(FenError::InvalidPiecePlacement(format!(
@@ -193,28 +276,8 @@ fn parse_side_to_move(s: &str) -> Result<Color, FenError> {
 
 /// Parse the castling rights component.
 fn parse_castling_rights(s: &str) -> Result<CastlingRights, FenError> {
-    if s == "-" {
-        return Ok(CastlingRights::none());
-    }
-
-    let mut rights = CastlingRights::none();
-
-    for ch in s.chars() {
-        rights = match ch {
-            'K' => rights.set_white_kingside(),
-            'Q' => rights.set_white_queenside(),
-            'k' => rights.set_black_kingside(),
-            'q' => rights.set_black_queenside(),
-            _ => {
-                return Err(FenError::InvalidCastlingRights(format!(
-                    "Invalid character: {}",
-                    ch
-                )))
-            }
-        };
-    }
-
-    Ok(rights)
+    s.parse()
+        .map_err(|_| FenError::InvalidCastlingRights(s.to_string()))
 }
 
 /// Parse the en passant square component.
@@ -233,6 +296,49 @@ pub fn is_valid_fen(fen: &str) -> bool {
     parse_fen(fen).is_ok()
 }
 
+/// A square whose contents differ between two positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquareChange {
+    pub square: Square,
+    /// Piece on the square in the first position
+    pub before: Option<Piece>,
+    /// Piece on the square in the second position
+    pub after: Option<Piece>,
+}
+
+/// Compare the piece placement of two FENs, square by square.
+///
+/// Only squares occupied in either position are examined; the result is in
+/// square order (a1 first). Side to move, castling rights and the clocks are
+/// ignored.
+///
+/// # Example
+/// ```
+/// use engine::io::{fen_diff, STARTPOS_FEN};
+///
+/// // 1. e4: the pawn leaves e2 and lands on e4
+/// let after = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+/// let changes = fen_diff(STARTPOS_FEN, after).unwrap();
+/// assert_eq!(changes.len(), 2);
+/// ```
+pub fn fen_diff(fen_a: &str, fen_b: &str) -> Result<Vec<SquareChange>, FenError> {
+    let a = parse_fen(fen_a)?;
+    let b = parse_fen(fen_b)?;
+
+    Ok((a.occupied() | b.occupied())
+        .into_iter()
+        .filter_map(|square| {
+            let before = a.piece_at(square);
+            let after = b.piece_at(square);
+            (before != after).then_some(SquareChange {
+                square,
+                before,
+                after,
+            })
+        })
+        .collect())
+}
+
 /// Extension trait for Board to support FEN serialization.
 pub trait ToFen {
     /// Convert the board to a FEN string.
@@ -284,23 +390,7 @@ impl ToFen for Board {
 
         // 3. Castling rights
         fen.push(' ');
-        let castling = self.castling();
-        if castling.bits() == 0 {
-            fen.push('-');
-        } else {
-            if castling.white_kingside() {
-                fen.push('K');
-            }
-            if castling.white_queenside() {
-                fen.push('Q');
-            }
-            if castling.black_kingside() {
-                fen.push('k');
-            }
-            if castling.black_queenside() {
-                fen.push('q');
-            }
-        }
+        fen.push_str(&self.castling().to_string());
 
         // 4. En passant square
         fen.push(' ');
@@ -322,6 +412,181 @@ impl ToFen for Board {
     }
 }
 
+impl Board {
+    /// Serialize this position followed by moves played from it, as in UCI
+    /// `position fen <fen> moves <m1> <m2> ...`.
+    ///
+    /// Moves are written in UCI notation and are not checked for legality.
+    /// Without moves this is just the FEN.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::prelude::*;
+    ///
+    /// let board = Board::startpos();
+    /// let e4 = Move::new(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH);
+    /// assert_eq!(
+    ///     board.to_fen_with_moves(&[e4]),
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 moves e2e4"
+    /// );
+    /// ```
+    pub fn to_fen_with_moves(&self, moves: &[Move]) -> String {
+        let mut s = self.to_fen();
+        if !moves.is_empty() {
+            s.push_str(" moves");
+            for m in moves {
+                s.push(' ');
+                s.push_str(&m.to_uci());
+            }
+        }
+        s
+    }
+
+    /// Parse the output of [`Board::to_fen_with_moves`]: a FEN, optionally
+    /// followed by ` moves ` and UCI moves, which are played in order.
+    ///
+    /// Fails on an invalid FEN or the first move that is not legal.
+    pub fn from_fen_with_moves(s: &str) -> Result<Board, FenError> {
+        let (fen, moves) = s.split_once(" moves ").unwrap_or((s, ""));
+        let mut board = parse_fen(fen)?;
+        for uci in moves.split_whitespace() {
+            let m = board
+                .generate_legal_moves()
+                .iter()
+                .find(|m| m.to_uci() == uci)
+                .copied()
+                .ok_or_else(|| FenError::InvalidMove(uci.to_string()))?;
+            board.make_move(m);
+        }
+        Ok(board)
+    }
+}
+
+/// Error returned by [`setup_position`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionSetupError {
+    /// The position specification is neither `startpos` nor a valid FEN
+    InvalidFen(FenError),
+    /// The move at `index` (0-based) is malformed or illegal in its position
+    IllegalMove { index: usize, uci: String },
+}
+
+impl std::fmt::Display for PositionSetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionSetupError::InvalidFen(e) => write!(f, "{}", e),
+            PositionSetupError::IllegalMove { index, uci } => {
+                write!(f, "Illegal move at index {}: {}", index, uci)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionSetupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PositionSetupError::InvalidFen(e) => Some(e),
+            PositionSetupError::IllegalMove { .. } => None,
+        }
+    }
+}
+
+impl From<FenError> for PositionSetupError {
+    fn from(e: FenError) -> Self {
+        PositionSetupError::InvalidFen(e)
+    }
+}
+
+/// Parse a position specification: `startpos`, a FEN, or either prefixed
+/// with `fen` as in the UCI `position` command.
+///
+/// A FEN may omit the halfmove clock and fullmove number, which then
+/// default to `0 1`.
+pub fn parse_position(spec: &str) -> Result<Board, FenError> {
+    let spec = spec.trim();
+    let fen = spec.strip_prefix("fen ").unwrap_or(spec).trim();
+    if fen == "startpos" {
+        return Ok(Board::startpos());
+    }
+    if fen.split_whitespace().count() == 4 {
+        return parse_fen(&format!("{} 0 1", fen));
+    }
+    parse_fen(fen)
+}
+
+/// Play UCI moves on `board` in order, stopping at the first one that is
+/// malformed or illegal.
+///
+/// The hash of the position before each move is appended to `history`,
+/// which is what repetition detection needs. On error the board and
+/// history are left after the last legal move.
+pub fn play_uci_moves<S: AsRef<str>>(
+    board: &mut Board,
+    moves: &[S],
+    history: &mut Vec<u64>,
+) -> Result<(), PositionSetupError> {
+    for (index, uci) in moves.iter().enumerate() {
+        let uci = uci.as_ref();
+        let hash = board.hash();
+        Move::from_uci(board, uci)
+            .and_then(|m| board.make_move_if_legal(m))
+            .ok_or_else(|| PositionSetupError::IllegalMove {
+                index,
+                uci: uci.to_string(),
+            })?;
+        history.push(hash);
+    }
+    Ok(())
+}
+
+/// Set up a position from a specification (see [`parse_position`]) and a
+/// list of UCI moves played from it.
+///
+/// This is the one place protocol layers turn `position ... moves ...`
+/// into a board, so castling, en passant and promotion flags are resolved
+/// the same way everywhere.
+///
+/// # Example
+/// ```
+/// use engine::io::{setup_position, PositionSetupError};
+///
+/// let board = setup_position("startpos", &["e2e4", "e7e5"]).unwrap();
+/// assert_eq!(board.fullmove_number(), 2);
+///
+/// let err = setup_position("startpos", &["e2e4", "e2e4"]).unwrap_err();
+/// assert!(matches!(err, PositionSetupError::IllegalMove { index: 1, .. }));
+/// ```
+pub fn setup_position<S: AsRef<str>>(spec: &str, moves: &[S]) -> Result<Board, PositionSetupError> {
+    let mut board = parse_position(spec)?;
+    play_uci_moves(&mut board, moves, &mut Vec::new())?;
+    Ok(board)
+}
+
+/// Rewrite a line of UCI moves played from `board` with castling in the
+/// notation of `chess960` (see [`Move::to_uci_in`]), stopping at the first
+/// move that doesn't parse.
+///
+/// # Example
+/// ```
+/// use engine::io::{parse_fen, uci_line_in};
+///
+/// let board = parse_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+/// assert_eq!(uci_line_in(&board, &["e1g1", "e8c8"], true), ["e1h1", "e8a8"]);
+/// assert_eq!(uci_line_in(&board, &["e1h1", "e8a8"], false), ["e1g1", "e8c8"]);
+/// ```
+pub fn uci_line_in<S: AsRef<str>>(board: &Board, line: &[S], chess960: bool) -> Vec<String> {
+    let mut board = board.clone();
+    let mut converted = Vec::with_capacity(line.len());
+    for uci in line {
+        let Some(m) = Move::from_uci(&board, uci.as_ref()) else {
+            break;
+        };
+        converted.push(m.to_uci_in(chess960));
+        board.make_move(m);
+    }
+    converted
+}
+
 /// Convert a piece to its FEN character representation.
 fn piece_to_char(piece: Piece) -> char {
     let ch = match piece.piece_type {
@@ -340,6 +605,245 @@ fn piece_to_char(piece: Piece) -> char {
     }
 }
 
+/// ANSI background for light squares.
+const ANSI_LIGHT: &str = "\x1b[48;5;180m";
+/// ANSI background for dark squares.
+const ANSI_DARK: &str = "\x1b[48;5;94m";
+/// ANSI foreground for white pieces.
+const ANSI_WHITE_PIECE: &str = "\x1b[97m";
+/// ANSI foreground for black pieces.
+const ANSI_BLACK_PIECE: &str = "\x1b[30m";
+/// ANSI reset sequence.
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Preset board rendering for [`Board::display_with`].
+///
+/// ```
+/// use engine::board::Board;
+/// use engine::io::DisplayMode;
+///
+/// let text = Board::startpos().display_with(DisplayMode::Unicode).to_string();
+/// assert!(text.starts_with("8 ♜ ♞ ♝"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayMode {
+    /// FEN letters, uppercase for White
+    #[default]
+    Ascii,
+    /// Chess symbols from the Unicode block U+2654..U+265F
+    Unicode,
+    /// Unicode symbols on ANSI-shaded squares, with colored pieces
+    ColoredUnicode,
+}
+
+impl DisplayMode {
+    /// The display options this mode stands for, with coordinates and footer.
+    pub const fn options(self) -> BoardDisplayOptions {
+        match self {
+            DisplayMode::Ascii => BoardDisplayOptions::new().unicode(false),
+            DisplayMode::Unicode => BoardDisplayOptions::new(),
+            DisplayMode::ColoredUnicode => BoardDisplayOptions::new().ansi_colors(true),
+        }
+    }
+}
+
+/// Remove ANSI escape sequences (`ESC [ ... final byte`) from rendered
+/// text, e.g. to log a [`DisplayMode::ColoredUnicode`] board where the
+/// output isn't a terminal.
+///
+/// Plain character scanning, no terminal or std-only dependencies.
+///
+/// ```
+/// use engine::io::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1b[48;5;94m\x1b[97m♔ \x1b[0m"), "♔ ");
+/// ```
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            // Parameters and intermediates, up to the final byte @..~
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Options for [`ToAsciiBoard::to_ascii_with`].
+///
+/// ```
+/// use engine::board::Board;
+/// use engine::io::{BoardDisplayOptions, ToAsciiBoard};
+///
+/// let options = BoardDisplayOptions::new().unicode(false).footer(false);
+/// let text = Board::startpos().to_ascii_with(&options);
+/// assert_eq!(text.lines().count(), 9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardDisplayOptions {
+    unicode: bool,
+    ansi_colors: bool,
+    coordinates: bool,
+    footer: bool,
+}
+
+impl BoardDisplayOptions {
+    /// Unicode pieces with coordinates and footer, no colors.
+    pub const fn new() -> Self {
+        Self {
+            unicode: true,
+            ansi_colors: false,
+            coordinates: true,
+            footer: true,
+        }
+    }
+
+    /// Use Unicode chess symbols instead of FEN letters.
+    pub const fn unicode(mut self, on: bool) -> Self {
+        self.unicode = on;
+        self
+    }
+
+    /// Shade light and dark squares with ANSI background colors and draw
+    /// the pieces in white and black.
+    pub const fn ansi_colors(mut self, on: bool) -> Self {
+        self.ansi_colors = on;
+        self
+    }
+
+    /// Show rank numbers on the left and file letters along the bottom.
+    pub const fn coordinates(mut self, on: bool) -> Self {
+        self.coordinates = on;
+        self
+    }
+
+    /// Show the FEN metadata (side to move, castling, en passant, clocks).
+    pub const fn footer(mut self, on: bool) -> Self {
+        self.footer = on;
+        self
+    }
+}
+
+impl Default for BoardDisplayOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extension trait for Board to render a human-readable diagram.
+///
+/// Rank 8 is printed first. Lines are separated by `\n` without a trailing
+/// newline.
+pub trait ToAsciiBoard {
+    /// Render with Unicode pieces, coordinates and FEN footer.
+    fn to_ascii(&self) -> String {
+        self.to_ascii_with(&BoardDisplayOptions::new())
+    }
+
+    /// Render with plain ASCII letters (uppercase = White), e.g. for CI logs.
+    fn to_ascii_simple(&self) -> String {
+        self.to_ascii_with(&BoardDisplayOptions::new().unicode(false))
+    }
+
+    /// Render with custom options.
+    fn to_ascii_with(&self, options: &BoardDisplayOptions) -> String;
+}
+
+impl ToAsciiBoard for Board {
+    fn to_ascii_with(&self, options: &BoardDisplayOptions) -> String {
+        let mut lines = Vec::with_capacity(10);
+
+        for rank in (0..8).rev() {
+            let mut line = String::new();
+            if options.coordinates {
+                line.push_str(&format!("{} ", rank + 1));
+            }
+
+            for file in 0..8 {
+                let square = Square::from_coords(file, rank);
+                let symbol = match self.piece_at(square) {
+                    Some(piece) if options.unicode => piece.to_unicode_char(),
+                    Some(piece) => piece_to_char(piece),
+                    None => '.',
+                };
+
+                if options.ansi_colors {
+                    let light = (file + rank) % 2 == 1;
+                    line.push_str(if light { ANSI_LIGHT } else { ANSI_DARK });
+                    match self.piece_at(square).map(|piece| piece.color) {
+                        Some(Color::White) => line.push_str(ANSI_WHITE_PIECE),
+                        Some(Color::Black) => line.push_str(ANSI_BLACK_PIECE),
+                        None => {}
+                    }
+                    line.push(symbol);
+                    line.push(' ');
+                } else {
+                    if file > 0 {
+                        line.push(' ');
+                    }
+                    line.push(symbol);
+                }
+            }
+
+            if options.ansi_colors {
+                line.push_str(ANSI_RESET);
+            }
+            lines.push(line);
+        }
+
+        if options.coordinates {
+            lines.push("  a b c d e f g h".to_string());
+        }
+
+        if options.footer {
+            let fen = self.to_fen();
+            let metadata = fen.split_once(' ').map_or("", |(_, rest)| rest);
+            lines.push(metadata.to_string());
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_ascii_simple())
+    }
+}
+
+/// A board rendered with fixed options, see [`Board::display_with`].
+struct BoardDisplay<'a> {
+    board: &'a Board,
+    options: BoardDisplayOptions,
+}
+
+impl std::fmt::Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.board.to_ascii_with(&self.options))
+    }
+}
+
+impl Board {
+    /// Render the board in a [`DisplayMode`], for `println!` or `format!`.
+    ///
+    /// `board.to_string()` is the same as [`DisplayMode::Ascii`].
+    pub fn display_with(&self, mode: DisplayMode) -> impl std::fmt::Display + '_ {
+        BoardDisplay {
+            board: self,
+            options: mode.options(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -387,6 +891,73 @@ mod tests {
         assert_eq!(board.to_fen(), STARTPOS_FEN);
     }
 
+    #[test]
+    fn test_ascii_simple_startpos() {
+        let expected = "\
+8 r n b q k b n r
+7 p p p p p p p p
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . . . . .
+3 . . . . . . . .
+2 P P P P P P P P
+1 R N B Q K B N R
+  a b c d e f g h
+w KQkq - 0 1";
+
+        let text = Board::startpos().to_ascii_simple();
+        assert_eq!(text, expected);
+        assert_eq!(text.lines().count(), 10);
+        assert!(text.lines().take(9).all(|line| line.chars().count() == 17));
+        assert_eq!(Board::startpos().to_string(), expected);
+    }
+
+    #[test]
+    fn test_ascii_unicode_and_colors() {
+        let text = Board::startpos().to_ascii();
+        assert_eq!(text.lines().next(), Some("8 ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜"));
+        assert_eq!(text.lines().nth(7), Some("1 ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖"));
+
+        let options = BoardDisplayOptions::new()
+            .ansi_colors(true)
+            .coordinates(false)
+            .footer(false);
+        let colored = Board::startpos().to_ascii_with(&options);
+        assert_eq!(colored.lines().count(), 8);
+        assert!(colored.lines().all(|line| line.ends_with(ANSI_RESET)));
+        // a1 is a dark square
+        assert!(colored.lines().last().unwrap().starts_with(ANSI_DARK));
+    }
+
+    #[test]
+    fn test_display_modes() {
+        let board = Board::startpos();
+        let render = |mode| board.display_with(mode).to_string();
+
+        for mode in [
+            DisplayMode::Ascii,
+            DisplayMode::Unicode,
+            DisplayMode::ColoredUnicode,
+        ] {
+            assert_eq!(render(mode).lines().count(), 10, "{:?}", mode);
+        }
+        assert_eq!(render(DisplayMode::Ascii), board.to_string());
+        assert_eq!(render(DisplayMode::Unicode), board.to_ascii());
+
+        // White pieces on a dark a1 square
+        let colored = render(DisplayMode::ColoredUnicode);
+        let rank1 = colored.lines().nth(7).unwrap();
+        assert!(rank1.starts_with(&format!("1 {}{}♖", ANSI_DARK, ANSI_WHITE_PIECE)));
+        assert!(colored.lines().next().unwrap().contains(ANSI_BLACK_PIECE));
+
+        // Without the escape codes only the square padding differs
+        let stripped = strip_ansi(&colored);
+        assert!(!stripped.contains('\x1b'));
+        for (plain, unicode) in stripped.lines().zip(render(DisplayMode::Unicode).lines()) {
+            assert_eq!(plain.trim_end(), unicode);
+        }
+    }
+
     #[test]
     fn test_parse_fen_with_en_passant() {
         let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
@@ -475,6 +1046,28 @@ mod tests {
         assert_eq!(board.to_fen(), fen);
     }
 
+    #[test]
+    fn test_parse_fen_unicode() {
+        let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let unicode = "♜3♚2♜/♟1♟♟♛♟♝1/♝♞2♟♞♟1/3♙♘3/1♟2♙3/2♘2♕1♟/♙♙♙♗♗♙♙♙/♖3♔2♖ w KQkq - 0 1";
+        assert_eq!(parse_fen_unicode(unicode), parse_fen(kiwipete));
+
+        // FEN letters still work, mixed in or not
+        assert_eq!(parse_fen_unicode(kiwipete), parse_fen(kiwipete));
+        assert_eq!(
+            parse_fen_unicode("4♚3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            parse_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1")
+        );
+
+        // Other Unicode symbols are rejected
+        assert!(matches!(
+            parse_fen_unicode("4♠3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(FenError::InvalidPiecePlacement(_))
+        ));
+        // Only the placement is translated
+        assert!(parse_fen_unicode("4k3/8/8/8/8/8/8/4K3 ♔ - - 0 1").is_err());
+    }
+
     #[test]
     fn test_parse_invalid_piece_count() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"; // Missing rank
@@ -493,6 +1086,47 @@ mod tests {
         assert!(parse_fen(fen).is_err());
     }
 
+    /// Every position one move away from the perft test positions, plus a
+    /// few broken FENs.
+    fn batch_fens() -> Vec<String> {
+        let mut fens = vec!["".to_string(), "8/8/8/8 w - - 0 1".to_string()];
+        for fen in [
+            STARTPOS_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ] {
+            let board = parse_fen(fen).unwrap();
+            for m in board.generate_legal_moves().iter() {
+                let mut child = board.clone();
+                child.make_move(*m);
+                fens.push(child.to_fen());
+            }
+        }
+        fens.push("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1".to_string());
+        fens
+    }
+
+    #[test]
+    fn test_parse_fen_batch_matches_parse_fen() {
+        let owned = batch_fens();
+        let fens: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let expected: Vec<_> = fens.iter().map(|fen| parse_fen(fen)).collect();
+        assert_eq!(parse_fen_batch(&fens), expected);
+        assert!(parse_fen_batch(&[]).is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parse_fen_batch_rayon_matches_sequential() {
+        let owned = batch_fens();
+        let fens: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let sequential = parse_fen_batch(&fens);
+        // Board equality includes the Zobrist hash
+        for _ in 0..10 {
+            assert_eq!(parse_fen_batch_rayon(&fens), sequential);
+        }
+    }
+
     #[test]
     fn test_parse_invalid_en_passant() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq z9 0 1";
@@ -508,6 +1142,201 @@ mod tests {
         assert!(!is_valid_fen("invalid"));
         assert!(!is_valid_fen(""));
     }
+    /// Play UCI moves from `board`, returning the moves and the final board.
+    fn play(board: &Board, ucis: &[&str]) -> (Vec<Move>, Board) {
+        let mut board = board.clone();
+        let mut moves = Vec::new();
+        for uci in ucis {
+            let m = board
+                .generate_legal_moves()
+                .iter()
+                .find(|m| m.to_uci() == *uci)
+                .copied()
+                .unwrap();
+            board.make_move(m);
+            moves.push(m);
+        }
+        (moves, board)
+    }
+
+    fn assert_fen_with_moves_roundtrip(fen: &str, ucis: &[&str]) {
+        let start = parse_fen(fen).unwrap();
+        let (moves, end) = play(&start, ucis);
+        let s = start.to_fen_with_moves(&moves);
+        assert_eq!(Board::from_fen_with_moves(&s).unwrap(), end, "{}", s);
+    }
+
+    #[test]
+    fn test_fen_with_moves_zero_moves() {
+        let board = parse_fen(STARTPOS_FEN).unwrap();
+        assert_eq!(board.to_fen_with_moves(&[]), STARTPOS_FEN);
+        assert_fen_with_moves_roundtrip(STARTPOS_FEN, &[]);
+    }
+
+    #[test]
+    fn test_fen_with_moves_one_move() {
+        let board = parse_fen(STARTPOS_FEN).unwrap();
+        let (moves, _) = play(&board, &["e2e4"]);
+        assert_eq!(
+            board.to_fen_with_moves(&moves),
+            format!("{} moves e2e4", STARTPOS_FEN)
+        );
+        assert_fen_with_moves_roundtrip(STARTPOS_FEN, &["e2e4"]);
+    }
+
+    #[test]
+    fn test_fen_with_moves_many_moves() {
+        assert_fen_with_moves_roundtrip(
+            STARTPOS_FEN,
+            &["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "d2d3", "f8c5"],
+        );
+    }
+
+    #[test]
+    fn test_fen_with_moves_castling_and_promotion() {
+        let fen = "4k2r/1P6/8/8/8/8/8/R3K2R w KQk - 0 1";
+        let ucis = ["e1c1", "e8g8", "b7b8q", "f8b8"];
+        let (moves, _) = play(&parse_fen(fen).unwrap(), &ucis);
+        assert_eq!(
+            parse_fen(fen).unwrap().to_fen_with_moves(&moves),
+            format!("{} moves e1c1 e8g8 b7b8q f8b8", fen)
+        );
+        assert_fen_with_moves_roundtrip(fen, &ucis);
+    }
+
+    #[test]
+    fn test_from_fen_with_moves_errors() {
+        assert!(matches!(
+            Board::from_fen_with_moves(&format!("{} moves e2e5", STARTPOS_FEN)),
+            Err(FenError::InvalidMove(m)) if m == "e2e5"
+        ));
+        assert!(Board::from_fen_with_moves("invalid moves e2e4").is_err());
+    }
+
+    /// Hash of `moves` played from `spec`, checked against the FEN reached.
+    fn assert_setup_matches_fen(spec: &str, moves: &[&str], fen: &str) {
+        let board = setup_position(spec, moves).unwrap();
+        assert_eq!(board.hash(), parse_position(fen).unwrap().hash());
+        assert_eq!(
+            board.to_fen().rsplitn(3, ' ').nth(2),
+            fen.rsplitn(3, ' ').nth(2)
+        );
+    }
+
+    #[test]
+    fn test_setup_position_castling_via_moves() {
+        assert_setup_matches_fen(
+            "startpos",
+            &["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1g1"],
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 5 4",
+        );
+        assert_setup_matches_fen(
+            "fen r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1",
+            &["e8c8", "e1g1"],
+            "2kr3r/8/8/8/8/8/8/R4RK1 b - - 2 2",
+        );
+    }
+
+    #[test]
+    fn test_setup_position_en_passant_via_moves() {
+        // The double push leaves an en passant square, the capture clears it
+        assert_setup_matches_fen(
+            "startpos",
+            &["e2e4", "a7a6", "e4e5", "d7d5"],
+            "rnbqkbnr/1pp1pppp/p7/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        );
+        assert_setup_matches_fen(
+            "startpos",
+            &["e2e4", "a7a6", "e4e5", "d7d5", "e5d6"],
+            "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3",
+        );
+    }
+
+    #[test]
+    fn test_setup_position_four_field_fen() {
+        let board = setup_position::<&str>("4k3/8/8/8/8/8/8/4K3 w - -", &[]).unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+        assert_eq!(
+            setup_position(
+                "fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+                &["e2e4"]
+            ),
+            setup_position("startpos", &["e2e4"])
+        );
+    }
+
+    #[test]
+    fn test_setup_position_errors() {
+        assert!(matches!(
+            setup_position::<&str>("startpos w", &[]),
+            Err(PositionSetupError::InvalidFen(_))
+        ));
+        assert_eq!(
+            setup_position("startpos", &["e2e4", "e7e5", "e1g1"]),
+            Err(PositionSetupError::IllegalMove {
+                index: 2,
+                uci: "e1g1".to_string()
+            })
+        );
+        // Pseudo-legal but walks into check
+        assert!(matches!(
+            setup_position("4k3/8/8/8/8/8/5r2/4K3 w - - 0 1", &["e1f1"]),
+            Err(PositionSetupError::IllegalMove { index: 0, .. })
+        ));
+        assert!(matches!(
+            setup_position("startpos", &["e2e4", "e7e8x"]),
+            Err(PositionSetupError::IllegalMove { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fen_diff_castling() {
+        let before = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let after = "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1";
+        let changes = fen_diff(before, after).unwrap();
+
+        let king = Some(Piece::new(PieceType::King, Color::White));
+        let rook = Some(Piece::new(PieceType::Rook, Color::White));
+        let change = |square, before, after| SquareChange {
+            square,
+            before,
+            after,
+        };
+        assert_eq!(
+            changes,
+            vec![
+                change(Square::E1, king, None),
+                change(Square::F1, None, rook),
+                change(Square::G1, None, king),
+                change(Square::H1, rook, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fen_diff_en_passant() {
+        // exd6 e.p. removes the d5 pawn from a square the capturer never visits
+        let before = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        let after = "4k3/8/3P4/8/8/8/8/4K3 b - - 0 1";
+        let changes = fen_diff(before, after).unwrap();
+
+        let squares: Vec<_> = changes.iter().map(|c| c.square).collect();
+        assert_eq!(squares, vec![Square::D5, Square::E5, Square::D6]);
+        assert_eq!(
+            changes[0].before,
+            Some(Piece::new(PieceType::Pawn, Color::Black))
+        );
+        assert!(changes.iter().all(|c| c.before != c.after));
+    }
+
+    #[test]
+    fn test_fen_diff_identical_and_invalid() {
+        assert!(fen_diff(STARTPOS_FEN, STARTPOS_FEN).unwrap().is_empty());
+        assert!(matches!(
+            fen_diff(STARTPOS_FEN, "not a fen"),
+            Err(FenError::InvalidFormat(_))
+        ));
+    }
 }
 
 // =============================================================================
@@ -698,7 +1527,7 @@ pub fn validate_epd_moves(epd: &EpdTestPosition) -> Option<String> {
             }
 
             // Also check algebraic notation variations
-            let to_sq = legal_move.to().to_algebraic();
+            let to_sq = legal_move.destination().to_algebraic();
 
             // Handle variations like "Nf3" vs "nf3", case-insensitive for simple moves
             if best_move_str.to_lowercase().ends_with(&to_sq) {
@@ -0,0 +1,374 @@
+//! PGN (Portable Game Notation) parsing.
+//!
+//! Reads the tag pairs and the main line of a single game. The movetext is
+//! parsed by [`crate::io::pgn_variation`], which keeps the comments, NAGs
+//! and variations this drops; moves are SAN, resolved against the position
+//! they are played in. Movetext ends at the result token (`1-0`, `0-1`,
+//! `1/2-1/2` or `*`).
+
+use crate::board::Board;
+use crate::io::parse_fen;
+use crate::io::pgn_variation::parse_game;
+use crate::r#move::Move;
+
+/// Outcome of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameResult {
+    /// `1-0`
+    WhiteWins,
+    /// `0-1`
+    BlackWins,
+    /// `1/2-1/2`
+    Draw,
+    /// `*`: ongoing, abandoned or unknown
+    #[default]
+    Unknown,
+}
+
+impl GameResult {
+    /// Parse a PGN result token.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::pgn::GameResult;
+    ///
+    /// assert_eq!(GameResult::from_token("1/2-1/2"), Some(GameResult::Draw));
+    /// assert_eq!(GameResult::from_token("*"), Some(GameResult::Unknown));
+    /// assert_eq!(GameResult::from_token("e4"), None);
+    /// ```
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+
+    /// The PGN result token.
+    pub fn as_token(self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        }
+    }
+}
+
+impl std::fmt::Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_token())
+    }
+}
+
+/// Deduce the result from the position alone: checkmate, stalemate or the
+/// fifty-move rule. Anything else is [`GameResult::Unknown`].
+///
+/// # Example
+/// ```
+/// use engine::io::parse_fen;
+/// use engine::io::pgn::{parse_result_from_board, GameResult};
+///
+/// // Fool's mate: white is checkmated
+/// let fen = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3";
+/// let board = parse_fen(fen).unwrap();
+/// assert_eq!(parse_result_from_board(&board), GameResult::BlackWins);
+/// ```
+pub fn parse_result_from_board(board: &Board) -> GameResult {
+    if board.generate_legal_moves().is_empty() {
+        if !board.is_in_check() {
+            return GameResult::Draw;
+        }
+        return match board.side_to_move() {
+            crate::piece::Color::White => GameResult::BlackWins,
+            crate::piece::Color::Black => GameResult::WhiteWins,
+        };
+    }
+
+    if board.halfmove_clock() >= 100 {
+        return GameResult::Draw;
+    }
+
+    GameResult::Unknown
+}
+
+/// A game read from PGN.
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    /// Tag pairs in file order (e.g. `("White", "Fischer")`)
+    pub tags: Vec<(String, String)>,
+    /// Starting position (from the `FEN` tag, or the standard position)
+    pub start: Board,
+    /// Main line moves
+    pub moves: Vec<Move>,
+    /// Result token, else the `Result` tag, else deduced from the final
+    /// position (see [`parse_result_from_board`])
+    pub result: GameResult,
+}
+
+impl PgnGame {
+    /// Value of a tag, if present.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Position after the last move.
+    pub fn final_board(&self) -> Board {
+        let mut board = self.start.clone();
+        for &m in &self.moves {
+            board.make_move(m);
+        }
+        board
+    }
+}
+
+/// Error type for PGN parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    /// Malformed tag pair
+    InvalidTag(String),
+    /// Invalid `FEN` tag
+    InvalidFen(String),
+    /// Move that doesn't parse or isn't legal (1-based ply, SAN)
+    InvalidMove(usize, String),
+    /// Comment or variation that is never closed
+    Unterminated(char),
+    /// Variation that replaces no move, closes nothing or is nested too deep
+    InvalidVariation(String),
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidTag(s) => write!(f, "Invalid PGN tag: {}", s),
+            PgnError::InvalidFen(s) => write!(f, "Invalid FEN tag: {}", s),
+            PgnError::InvalidMove(ply, san) => write!(f, "Invalid move at ply {}: {}", ply, san),
+            PgnError::Unterminated(c) => write!(f, "Unterminated '{}' in movetext", c),
+            PgnError::InvalidVariation(s) => write!(f, "Invalid variation: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// Parse a single PGN game.
+///
+/// This is the main line of [`parse_pgn_variations`], so moves inside
+/// variations must be legal too.
+///
+/// [`parse_pgn_variations`]: crate::io::pgn_variation::parse_pgn_variations
+///
+/// # Example
+/// ```
+/// use engine::io::pgn::{parse_pgn, GameResult};
+///
+/// let pgn = r#"[White "Anderssen"]
+/// [Result "1-0"]
+///
+/// 1. e4 {best by test} e5 2. Nf3 (2. f4 exf4) Nc6 $1 1-0"#;
+/// let game = parse_pgn(pgn).unwrap();
+/// assert_eq!(game.tag("White"), Some("Anderssen"));
+/// assert_eq!(game.moves.len(), 4);
+/// assert_eq!(game.result, GameResult::WhiteWins);
+/// ```
+pub fn parse_pgn(pgn: &str) -> Result<PgnGame, PgnError> {
+    let (tags, tree, result) = parse_game(pgn)?;
+    let moves = tree.main_line().map(|var_move| var_move.mv).collect();
+    let result = result.or_else(|| {
+        tags.iter()
+            .find(|(key, _)| key == "Result")
+            .and_then(|(_, value)| GameResult::from_token(value))
+    });
+
+    let mut game = PgnGame {
+        tags,
+        start: tree.start,
+        moves,
+        result: GameResult::Unknown,
+    };
+    game.result = result.unwrap_or_else(|| parse_result_from_board(&game.final_board()));
+    Ok(game)
+}
+
+/// Split a game into its tag pairs and its movetext, dropping `%` escape
+/// lines.
+pub(super) fn split_pgn(pgn: &str) -> Result<(Vec<(String, String)>, String), PgnError> {
+    let mut tags = Vec::new();
+    let mut movetext = String::new();
+
+    for line in pgn.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && movetext.trim().is_empty() {
+            tags.push(parse_tag(line)?);
+        } else if !line.starts_with('%') {
+            movetext.push_str(line);
+            movetext.push('\n');
+        }
+    }
+
+    Ok((tags, movetext))
+}
+
+/// The position set by the `FEN` tag, or the standard starting position.
+pub(super) fn start_position(tags: &[(String, String)]) -> Result<Board, PgnError> {
+    match tags.iter().find(|(key, _)| key == "FEN") {
+        Some((_, fen)) => parse_fen(fen).map_err(|e| PgnError::InvalidFen(e.to_string())),
+        None => Ok(Board::startpos()),
+    }
+}
+
+/// Parse `[Name "Value"]`.
+fn parse_tag(line: &str) -> Result<(String, String), PgnError> {
+    let invalid = || PgnError::InvalidTag(line.to_string());
+    let inner = line
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    let (name, value) = inner.split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    Ok((
+        name.to_string(),
+        value.replace("\\\"", "\"").replace("\\\\", "\\"),
+    ))
+}
+
+/// Strip a leading move number ("12.", "12...") from a movetext token; it
+/// may be glued to the move ("12.e4"). Digits not followed by a dot are
+/// kept, so castling written with zeros ("0-0") survives.
+pub(super) fn strip_move_number(token: &str) -> &str {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::ToFen;
+
+    const TAGS: &str = "[Event \"Test\"]\n[Site \"?\"]\n\n";
+
+    #[test]
+    fn test_result_tokens() {
+        let cases = [
+            (
+                "1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0",
+                GameResult::WhiteWins,
+                7,
+            ),
+            ("1. f3 e5 2. g4 Qh4# 0-1", GameResult::BlackWins, 4),
+            ("1. d4 d5 2. c4 1/2-1/2", GameResult::Draw, 3),
+            ("1. e4 c5 2. Nf3 *", GameResult::Unknown, 3),
+        ];
+
+        for (movetext, expected, plies) in cases {
+            let game = parse_pgn(&format!("{}{}", TAGS, movetext)).unwrap();
+            assert_eq!(game.result, expected, "{}", movetext);
+            assert_eq!(game.moves.len(), plies, "{}", movetext);
+            assert_eq!(game.tag("Event"), Some("Test"));
+        }
+    }
+
+    #[test]
+    fn test_result_token_stops_movetext() {
+        // Anything after the result belongs to the next game
+        let game = parse_pgn("1. e4 e5 0-1 1. d4 d5 *").unwrap();
+        assert_eq!(game.moves.len(), 2);
+        assert_eq!(game.result, GameResult::BlackWins);
+    }
+
+    #[test]
+    fn test_ongoing_game_keeps_unknown_result() {
+        let pgn = "[Result \"*\"]\n\n1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 *";
+        let game = parse_pgn(pgn).unwrap();
+        assert_eq!(game.result, GameResult::Unknown);
+        assert_eq!(
+            game.final_board().to_fen(),
+            "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4"
+        );
+    }
+
+    #[test]
+    fn test_missing_result_falls_back_to_tag_then_board() {
+        let game = parse_pgn("[Result \"1/2-1/2\"]\n\n1. e4 e5").unwrap();
+        assert_eq!(game.result, GameResult::Draw);
+
+        let game = parse_pgn("1. f3 e5 2. g4 Qh4#").unwrap();
+        assert_eq!(game.result, GameResult::BlackWins);
+
+        let game = parse_pgn("1. e4 e5").unwrap();
+        assert_eq!(game.result, GameResult::Unknown);
+    }
+
+    #[test]
+    fn test_comments_variations_and_fen_tag() {
+        let pgn = "[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\n\
+                   1.e4 {push} (1. e3 $2 (1. Kd2)) ; rest of line\n\
+                   Kd7 $1 2. Kd2 *";
+        let game = parse_pgn(pgn).unwrap();
+        assert_eq!(game.moves.len(), 3);
+        assert_eq!(
+            game.final_board().to_fen(),
+            "8/3k4/8/8/4P3/8/3K4/8 b - - 2 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_pgn("1. e4 e5 2. Ke3").unwrap_err(),
+            PgnError::InvalidMove(3, "Ke3".to_string())
+        );
+        assert_eq!(
+            parse_pgn("1. e4 {never closed").unwrap_err(),
+            PgnError::Unterminated('{')
+        );
+        assert!(matches!(
+            parse_pgn("[Event]\n1. e4"),
+            Err(PgnError::InvalidTag(_))
+        ));
+        assert!(matches!(
+            parse_pgn("1. e4 e5) 2. Nf3"),
+            Err(PgnError::InvalidVariation(_))
+        ));
+        assert_eq!(
+            parse_pgn("1. e4 (1. Ke2) e5").unwrap_err(),
+            PgnError::InvalidMove(1, "Ke2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_castling_with_zeros() {
+        let pgn = "1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4.0-0 Nf6 5. d3 d6 6. c3 0-0 *";
+        let game = parse_pgn(pgn).unwrap();
+        assert_eq!(game.moves[6].to_string(), "e1g1");
+        assert_eq!(game.moves[11].to_string(), "e8g8");
+    }
+
+    #[test]
+    fn test_parse_result_from_board() {
+        let cases = [
+            ("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", GameResult::Draw), // stalemate
+            ("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", GameResult::WhiteWins),
+            ("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80", GameResult::Draw),
+            ("4k3/8/8/8/8/8/4P3/4K3 w - - 99 80", GameResult::Unknown),
+        ];
+
+        for (fen, expected) in cases {
+            let board = parse_fen(fen).unwrap();
+            assert_eq!(parse_result_from_board(&board), expected, "{}", fen);
+        }
+    }
+}
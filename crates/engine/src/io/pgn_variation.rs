@@ -0,0 +1,433 @@
+//! PGN parsing that keeps the variation tree.
+//!
+//! [`crate::io::pgn::parse_pgn`] keeps only the main line of this tree;
+//! here the comments, NAGs and nested variations of annotated games, e.g.
+//! `1. e4 (1. d4 d5) e5`. A variation in parentheses is an alternative to
+//! the move just before it and is attached to that move. Variations may be
+//! nested up to [`MAX_VARIATION_DEPTH`] levels.
+
+use crate::board::Board;
+use crate::io::pgn::{split_pgn, start_position, strip_move_number, GameResult, PgnError};
+use crate::r#move::Move;
+use crate::san::parse_san;
+
+/// Deepest nesting of variations accepted, the main line being level 0.
+pub const MAX_VARIATION_DEPTH: usize = 10;
+
+/// Numeric Annotation Glyph, e.g. `$1` for a good move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Nag(pub u8);
+
+impl Nag {
+    /// The NAG for a move suffix annotation (`!`, `?`, `!!`, `??`, `!?` or
+    /// `?!`).
+    ///
+    /// # Example
+    /// ```
+    /// use engine::io::pgn_variation::Nag;
+    ///
+    /// assert_eq!(Nag::from_suffix("!?"), Some(Nag(5)));
+    /// assert_eq!(Nag::from_suffix("+"), None);
+    /// ```
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "!" => Some(Nag(1)),
+            "?" => Some(Nag(2)),
+            "!!" => Some(Nag(3)),
+            "??" => Some(Nag(4)),
+            "!?" => Some(Nag(5)),
+            "?!" => Some(Nag(6)),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Nag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}", self.0)
+    }
+}
+
+/// A move of a [`PgnVariation`] with its annotations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnVarMove {
+    pub mv: Move,
+    /// NAGs in file order, suffix annotations (`!?`) included
+    pub annotations: Vec<Nag>,
+    /// Alternatives to this move, each starting from the position before it
+    pub sub_variations: Vec<PgnVariation>,
+    /// Comments following the move, joined by a space
+    pub comment: Option<String>,
+}
+
+/// A line of moves from a position, with the variations branching off it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnVariation {
+    /// Position before the first move
+    pub start: Board,
+    pub moves: Vec<PgnVarMove>,
+}
+
+impl PgnVariation {
+    /// The moves of this line, without its variations.
+    pub fn main_line(&self) -> impl Iterator<Item = &PgnVarMove> {
+        self.moves.iter()
+    }
+
+    /// Every position of the tree: the start, then the position after each
+    /// move, with a variation's positions following the move it branches
+    /// from.
+    pub fn flatten(&self) -> Vec<Board> {
+        let mut boards = vec![self.start.clone()];
+        self.push_positions(&mut boards);
+        boards
+    }
+
+    /// Push the position after each move of this line and its variations.
+    fn push_positions(&self, boards: &mut Vec<Board>) {
+        let mut board = self.start.clone();
+        for var_move in &self.moves {
+            board.make_move(var_move.mv);
+            boards.push(board.clone());
+            for variation in &var_move.sub_variations {
+                variation.push_positions(boards);
+            }
+        }
+    }
+}
+
+/// Parse a single PGN game into its tree of variations.
+///
+/// The `FEN` tag sets the starting position. Comments before the first move
+/// of a line are dropped.
+///
+/// # Example
+/// ```
+/// use engine::io::pgn_variation::{parse_pgn_variations, Nag};
+///
+/// let tree = parse_pgn_variations("1. e4 (1. d4 d5) e5 $1 {solid} *").unwrap();
+/// assert_eq!(tree.main_line().count(), 2);
+/// assert_eq!(tree.moves[0].sub_variations[0].moves.len(), 2);
+/// assert_eq!(tree.moves[1].annotations, [Nag(1)]);
+/// assert_eq!(tree.moves[1].comment.as_deref(), Some("solid"));
+/// assert_eq!(tree.flatten().len(), 5);
+/// ```
+pub fn parse_pgn_variations(pgn: &str) -> Result<PgnVariation, PgnError> {
+    parse_game(pgn).map(|(_, tree, _)| tree)
+}
+
+/// Parse a single PGN game into its tag pairs, its tree of variations and
+/// the result token ending the main line, if any.
+pub(super) fn parse_game(pgn: &str) -> Result<ParsedGame, PgnError> {
+    let (tags, movetext) = split_pgn(pgn)?;
+    let start = start_position(&tags)?;
+    let mut parser = Parser {
+        tokens: tokens(&movetext)?,
+        pos: 0,
+        result: None,
+    };
+    let tree = parser.parse_line(start, 1, 0)?;
+    Ok((tags, tree, parser.result))
+}
+
+/// Tag pairs, variation tree and result token of a game.
+pub(super) type ParsedGame = (Vec<(String, String)>, PgnVariation, Option<GameResult>);
+
+/// A movetext token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    /// SAN, without move number or suffix annotation
+    Move(&'a str),
+    Nag(Nag),
+    Comment(&'a str),
+    Open,
+    Close,
+    Result(GameResult),
+}
+
+/// Split movetext into tokens, keeping comments and variation brackets.
+fn tokens(movetext: &str) -> Result<Vec<Token<'_>>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut chars = movetext.char_indices().peekable();
+    let mut word_start = None;
+
+    while let Some((i, c)) = chars.next() {
+        let separator = c.is_whitespace() || matches!(c, '{' | ';' | '(' | ')');
+        if separator {
+            if let Some(start) = word_start.take() {
+                push_word(&mut tokens, &movetext[start..i]);
+            }
+        }
+
+        match c {
+            '{' => {
+                let (end, _) = chars
+                    .find(|&(_, c)| c == '}')
+                    .ok_or(PgnError::Unterminated('{'))?;
+                tokens.push(Token::Comment(movetext[i + 1..end].trim()));
+            }
+            ';' => {
+                let end = chars
+                    .find(|&(_, c)| c == '\n')
+                    .map_or(movetext.len(), |(end, _)| end);
+                tokens.push(Token::Comment(movetext[i + 1..end].trim()));
+            }
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            _ if separator => {}
+            _ => {
+                word_start.get_or_insert(i);
+            }
+        }
+    }
+
+    if let Some(start) = word_start {
+        push_word(&mut tokens, &movetext[start..]);
+    }
+
+    Ok(tokens)
+}
+
+/// Classify a whitespace-separated word of movetext.
+fn push_word<'a>(tokens: &mut Vec<Token<'a>>, word: &'a str) {
+    if let Some(result) = GameResult::from_token(word) {
+        tokens.push(Token::Result(result));
+        return;
+    }
+    if let Some(number) = word.strip_prefix('$') {
+        if let Ok(nag) = number.parse() {
+            tokens.push(Token::Nag(Nag(nag)));
+        }
+        return;
+    }
+
+    let word = strip_move_number(word);
+    let san = word.trim_end_matches(['!', '?']);
+    if !san.is_empty() {
+        tokens.push(Token::Move(san));
+    }
+    if let Some(nag) = Nag::from_suffix(&word[san.len()..]) {
+        tokens.push(Token::Nag(nag));
+    }
+}
+
+/// Recursive descent over the movetext tokens.
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    /// Result token that ended the main line
+    result: Option<GameResult>,
+}
+
+impl Parser<'_> {
+    /// Parse a line from `start` up to its closing bracket (or the result
+    /// or end of movetext for the main line). `first_ply` is the 1-based
+    /// game ply of its first move, for error messages.
+    fn parse_line(
+        &mut self,
+        start: Board,
+        first_ply: usize,
+        depth: usize,
+    ) -> Result<PgnVariation, PgnError> {
+        let mut board = start.clone();
+        // Position before the last move, where its variations start
+        let mut before_last = None;
+        let mut moves: Vec<PgnVarMove> = Vec::new();
+
+        while let Some(&token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match token {
+                Token::Move(san) => {
+                    let m = parse_san(&board, san).map_err(|_| {
+                        PgnError::InvalidMove(first_ply + moves.len(), san.to_string())
+                    })?;
+                    before_last = Some(board.clone());
+                    board.make_move(m);
+                    moves.push(PgnVarMove {
+                        mv: m,
+                        annotations: Vec::new(),
+                        sub_variations: Vec::new(),
+                        comment: None,
+                    });
+                }
+                Token::Nag(nag) => {
+                    if let Some(last) = moves.last_mut() {
+                        last.annotations.push(nag);
+                    }
+                }
+                Token::Comment(text) => {
+                    if let Some(last) = moves.last_mut() {
+                        match &mut last.comment {
+                            Some(comment) => {
+                                comment.push(' ');
+                                comment.push_str(text);
+                            }
+                            None => last.comment = Some(text.to_string()),
+                        }
+                    }
+                }
+                Token::Open => {
+                    if depth == MAX_VARIATION_DEPTH {
+                        return Err(PgnError::InvalidVariation(format!(
+                            "nested more than {} levels deep",
+                            MAX_VARIATION_DEPTH
+                        )));
+                    }
+                    let before = before_last.clone().ok_or_else(|| {
+                        PgnError::InvalidVariation("no move to replace".to_string())
+                    })?;
+                    let ply = first_ply + moves.len() - 1;
+                    let variation = self.parse_line(before, ply, depth + 1)?;
+                    if let Some(last) = moves.last_mut() {
+                        last.sub_variations.push(variation);
+                    }
+                }
+                Token::Close if depth == 0 => {
+                    return Err(PgnError::InvalidVariation("unmatched ')'".to_string()));
+                }
+                Token::Close => return Ok(PgnVariation { start, moves }),
+                Token::Result(result) if depth == 0 => {
+                    self.result = Some(result);
+                    break;
+                }
+                Token::Result(_) => {}
+            }
+        }
+
+        if depth > 0 {
+            return Err(PgnError::Unterminated('('));
+        }
+        Ok(PgnVariation { start, moves })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::pgn::parse_pgn;
+    use crate::io::ToFen;
+
+    #[test]
+    fn test_two_level_nested_variation() {
+        let pgn = "[Event \"Test\"]\n\n\
+                   1. e4 {king pawn} (1. d4 d5 (1... Nf6 2. c4 $5) 2. c4) \
+                   1... e5! 2. Nf3 Nc6 1-0";
+        let tree = parse_pgn_variations(pgn).unwrap();
+
+        let main: Vec<String> = tree.main_line().map(|m| m.mv.to_string()).collect();
+        assert_eq!(main, ["e2e4", "e7e5", "g1f3", "b8c6"]);
+        assert_eq!(tree.moves[0].comment.as_deref(), Some("king pawn"));
+        assert_eq!(tree.moves[1].annotations, [Nag(1)]);
+
+        // The variation replaces 1. e4 and holds its own replacement of 1... d5
+        let first = &tree.moves[0].sub_variations;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].start, Board::startpos());
+        let line: Vec<String> = first[0].main_line().map(|m| m.mv.to_string()).collect();
+        assert_eq!(line, ["d2d4", "d7d5", "c2c4"]);
+
+        let second = &first[0].moves[1].sub_variations;
+        assert_eq!(second.len(), 1);
+        let line: Vec<String> = second[0].main_line().map(|m| m.mv.to_string()).collect();
+        assert_eq!(line, ["g8f6", "c2c4"]);
+        assert_eq!(second[0].moves[1].annotations, [Nag(5)]);
+        assert!(second[0].moves[1].sub_variations.is_empty());
+
+        // The main line matches the plain parser
+        let game = parse_pgn(pgn).unwrap();
+        assert!(tree.main_line().map(|m| m.mv).eq(game.moves));
+    }
+
+    #[test]
+    fn test_flatten_visits_every_position() {
+        let tree = parse_pgn_variations("1. e4 (1. d4 d5 (1... Nf6 2. c4) 2. c4) e5 *").unwrap();
+        let fens: Vec<String> = tree.flatten().iter().map(|b| b.to_fen()).collect();
+        assert_eq!(fens.len(), 8);
+        assert_eq!(fens[0], Board::startpos().to_fen());
+
+        // After 1. e4 come the 1. d4 line and its 1... Nf6 branch, then 1... e5
+        let after = |san: &[&str]| {
+            let mut board = Board::startpos();
+            for s in san {
+                board.make_move(parse_san(&board, s).unwrap());
+            }
+            board.to_fen()
+        };
+        assert_eq!(fens[1], after(&["e4"]));
+        assert_eq!(fens[2], after(&["d4"]));
+        assert_eq!(fens[3], after(&["d4", "d5"]));
+        assert_eq!(fens[4], after(&["d4", "Nf6"]));
+        assert_eq!(fens[5], after(&["d4", "Nf6", "c4"]));
+        assert_eq!(fens[6], after(&["d4", "d5", "c4"]));
+        assert_eq!(fens[7], after(&["e4", "e5"]));
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        // Each variation replaces the first move of the line it is in
+        let firsts = [
+            "e4", "d4", "c4", "Nf3", "g3", "b3", "f4", "Nc3", "b4", "a3", "h3", "a4",
+        ];
+        let nested = |levels: usize| {
+            let mut pgn = format!("1. {} ", firsts[0]);
+            for first in &firsts[1..=levels] {
+                pgn.push_str(&format!("(1. {} ", first));
+            }
+            pgn.push_str(&")".repeat(levels));
+            pgn
+        };
+
+        let mut line = &parse_pgn_variations(&nested(MAX_VARIATION_DEPTH)).unwrap();
+        for _ in 0..MAX_VARIATION_DEPTH {
+            line = &line.moves[0].sub_variations[0];
+        }
+        assert_eq!(line.moves[0].mv.to_string(), "h2h3");
+
+        assert!(matches!(
+            parse_pgn_variations(&nested(MAX_VARIATION_DEPTH + 1)),
+            Err(PgnError::InvalidVariation(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_pgn_variations("1. e4 (1. d4 d5 2. Ke3) e5").unwrap_err(),
+            PgnError::InvalidMove(3, "Ke3".to_string())
+        );
+        assert_eq!(
+            parse_pgn_variations("1. e4 (1. d4 d5").unwrap_err(),
+            PgnError::Unterminated('(')
+        );
+        assert!(matches!(
+            parse_pgn_variations("(1. d4) 1. e4"),
+            Err(PgnError::InvalidVariation(_))
+        ));
+        assert!(matches!(
+            parse_pgn_variations("1. e4 e5) 2. Nf3"),
+            Err(PgnError::InvalidVariation(_))
+        ));
+    }
+
+    #[test]
+    fn test_castling_with_zeros() {
+        let tree = parse_pgn_variations("1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 (3... Nf6 4.0-0) 4.0-0 *")
+            .unwrap();
+        assert_eq!(tree.moves[6].mv.to_string(), "e1g1");
+        let variation = &tree.moves[5].sub_variations[0];
+        assert_eq!(variation.moves[1].mv.to_string(), "e1g1");
+    }
+
+    #[test]
+    fn test_fen_tag_sets_the_start() {
+        let pgn = "[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\n\
+                   1. e4 (1. Kd2 Kd7) Kd7 ; line comment\n2. Kd2 *";
+        let tree = parse_pgn_variations(pgn).unwrap();
+        assert_eq!(tree.moves.len(), 3);
+        assert_eq!(tree.moves[1].comment.as_deref(), Some("line comment"));
+        assert_eq!(
+            tree.moves[0].sub_variations[0].start.to_fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"
+        );
+    }
+}
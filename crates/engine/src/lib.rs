@@ -11,8 +11,7 @@
 //! ## Quick Start
 //!
 //! ```
-//! use engine::board::Board;
-//! use engine::io::parse_fen;
+//! use engine::prelude::*;
 //!
 //! // Create a board from starting position
 //! let board = Board::startpos();
@@ -37,8 +36,22 @@
 //! - [`zobrist`] - Zobrist hashing for positions
 //! - [`io`] - FEN parsing and serialization
 //! - [`perft`] - Performance testing and validation
+//! - [`prelude`] - Re-exports of the commonly used types
+//! - [`version`] - Engine name, version and build information
+//!
+//! ## API Stability
+//!
+//! Import from [`prelude`] (or the crate root, for [`Move`] and
+//! [`EngineImpl`]) rather than from module paths. The prelude is kept
+//! stable across minor releases; module paths may move, and items that are
+//! only used inside the engine (such as the incremental zobrist helpers)
+//! are `pub(crate)` and not part of the API. Removing or renaming a prelude
+//! item is a breaking change and needs a major version bump.
 
+pub mod adjudication;
+pub mod analysis;
 pub mod attacks;
+pub mod bench;
 pub mod bitboard;
 pub mod board;
 pub mod eval;
@@ -52,6 +65,8 @@ pub mod opening_book;
 pub mod perft;
 pub mod piece;
 pub mod polyglot;
+pub mod prelude;
+pub mod san;
 pub mod search;
 pub mod search_params;
 pub mod square;
@@ -60,22 +75,49 @@ pub mod tt;
 pub mod tune;
 pub mod types;
 pub mod uci;
+pub mod version;
 pub mod zobrist;
 
+pub use r#move::Move;
+
 use board::Board;
 use io::{parse_fen, ToFen};
-use r#move::Move;
-use search::Searcher;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use search::{Searcher, StopHandle};
+use std::collections::BTreeMap;
 use time::TimeControl;
 use types::*;
 
+/// Convert a protocol SearchLimit to the searcher's (max_depth, TimeControl).
+pub(crate) fn search_bounds(limit: SearchLimit) -> (u32, TimeControl) {
+    match limit {
+        SearchLimit::Depth { depth } => (depth, TimeControl::Depth { depth }),
+        SearchLimit::Nodes { nodes } => (search::MAX_DEPTH, TimeControl::Nodes { nodes }),
+        SearchLimit::Time { move_time_ms } => {
+            (search::MAX_DEPTH, TimeControl::MoveTime { millis: move_time_ms })
+        }
+        SearchLimit::Infinite => (search::MAX_DEPTH, TimeControl::Infinite),
+        SearchLimit::Mate { moves } => (search::MAX_DEPTH, TimeControl::Mate { moves }),
+    }
+}
+
+/// Restate a score relative to the side to move from the `pov` reported.
+fn score_from_pov(score: Score, white_to_move: bool, pov: ScorePov) -> Score {
+    match pov {
+        ScorePov::White if !white_to_move => score.negated(),
+        _ => score,
+    }
+}
+
 pub struct EngineImpl {
     pub opts: EngineOptions,
     pub current_fen: String,
+    /// UCI moves played from `current_fen`
+    pub current_moves: Vec<String>,
     pub current_board: Option<Board>,
-    stopped: Arc<AtomicBool>,
+    score_pov: ScorePov,
+    show_wdl: bool,
+    stop_handle: StopHandle,
+    next_stop_handle: Option<StopHandle>,
     searcher: Searcher,
 }
 
@@ -88,15 +130,19 @@ impl Default for EngineImpl {
             skill_level: None,
             multi_pv: Some(1),
             use_tablebases: None,
+            chess960: None,
         };
         let tt_size = opts.hash_size_mb as usize;
-        let stopped = Arc::new(AtomicBool::new(false));
         Self {
             opts,
             current_fen: "startpos".to_string(),
+            current_moves: Vec::new(),
             current_board: None,
-            stopped: Arc::clone(&stopped),
-            searcher: Searcher::with_tt_size_and_stop_flag(tt_size, stopped),
+            score_pov: ScorePov::SideToMove,
+            show_wdl: false,
+            stop_handle: StopHandle::new(),
+            next_stop_handle: None,
+            searcher: Searcher::with_tt_size(tt_size),
         }
     }
 }
@@ -104,110 +150,548 @@ impl Default for EngineImpl {
 impl EngineImpl {
     pub fn new_with(opts: EngineOptions) -> Self {
         let tt_size = opts.hash_size_mb as usize;
-        let stopped = Arc::new(AtomicBool::new(false));
         Self {
             opts,
             current_fen: "startpos".to_string(),
+            current_moves: Vec::new(),
             current_board: None,
-            stopped: Arc::clone(&stopped),
-            searcher: Searcher::with_tt_size_and_stop_flag(tt_size, stopped),
+            score_pov: ScorePov::SideToMove,
+            show_wdl: false,
+            stop_handle: StopHandle::new(),
+            next_stop_handle: None,
+            searcher: Searcher::with_tt_size(tt_size),
         }
     }
 
+    /// Start a new game: forget the position and everything the searcher
+    /// learned, keeping its tables allocated so engines can be reused.
     pub fn new_game(&mut self) {
         self.current_fen = "startpos".to_string();
-        self.stopped.store(false, Ordering::Relaxed);
+        self.current_moves.clear();
+        self.current_board = None;
+        self.searcher.reset_for_new_game();
     }
 
-    pub fn position(&mut self, fen: &str, _moves: &[String]) {
+    /// Set the position to analyze: `startpos` or a FEN (see
+    /// [`io::parse_position`]) followed by UCI moves.
+    ///
+    /// An invalid FEN or illegal move is reported by the next analysis.
+    pub fn position(&mut self, fen: &str, moves: &[String]) {
         self.current_fen = fen.to_string();
-        // Handle "startpos" keyword or parse FEN
-        self.current_board = if fen == "startpos" {
-            Some(Board::startpos())
-        } else {
-            parse_fen(fen).ok()
-        };
-        // TODO: apply moves if provided
+        self.current_moves = moves.to_vec();
+        self.current_board = io::setup_position(fen, moves).ok();
     }
 
-    pub fn set_option(&mut self, _key: &str, _value: &str) {
-        // TODO: parse key/value into opts
+    /// Set a named engine option (names are case-insensitive).
+    ///
+    /// Supported: `MoveVariety` (0-100, random near-best moves in the
+    /// opening), `MoveVarietySeed` (fixes those random choices), `ScorePov`
+    /// (`SideToMove` or `White`, see [`ScorePov`]), `UCI_ShowWDL`
+    /// (`true` adds a win/draw/loss estimate to each [`SearchInfo`]) and
+    /// `UCI_Chess960` (sets [`EngineOptions::chess960`]).
+    /// Unknown names and unparsable values are ignored.
+    pub fn set_option(&mut self, key: &str, value: &str) {
+        // TODO: parse the remaining keys into opts
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "movevariety" => {
+                if let Ok(amount) = value.parse::<u32>() {
+                    self.searcher.set_move_variety(amount);
+                }
+            }
+            "movevarietyseed" => {
+                if let Ok(seed) = value.parse::<u64>() {
+                    self.searcher.set_move_variety_seed(seed);
+                }
+            }
+            "hash" => {
+                if let Ok(size_mb) = value.parse::<u32>() {
+                    self.set_hash_size(size_mb.clamp(1, tt::MAX_SIZE_MB as u32));
+                }
+            }
+            "scorepov" => match value.to_ascii_lowercase().as_str() {
+                "sidetomove" => self.score_pov = ScorePov::SideToMove,
+                "white" => self.score_pov = ScorePov::White,
+                _ => {}
+            },
+            "uci_showwdl" => {
+                if let Ok(show) = value.to_ascii_lowercase().parse::<bool>() {
+                    self.show_wdl = show;
+                }
+            }
+            "uci_chess960" => {
+                if let Ok(chess960) = value.to_ascii_lowercase().parse::<bool>() {
+                    self.opts.chess960 = Some(chess960);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn analyze<F>(&mut self, limit: SearchLimit, info_sink: F) -> BestMove
+    where
+        F: FnMut(SearchInfo),
+    {
+        self.analyze_with_search_moves(limit, None, info_sink)
+    }
+
+    /// Analyze with the root search restricted to `search_moves` (UCI
+    /// strings, as in "go searchmoves e2e4 d2d4"). Unknown or illegal moves
+    /// are ignored; `None` or no legal entries searches every move.
+    pub fn analyze_with_search_moves<F>(
+        &mut self,
+        limit: SearchLimit,
+        search_moves: Option<&[String]>,
+        info_sink: F,
+    ) -> BestMove
+    where
+        F: FnMut(SearchInfo),
+    {
+        let filter = RootMoveFilter {
+            search_moves: search_moves.map(<[String]>::to_vec),
+            ..Default::default()
+        };
+        self.analyze_with_filter(limit, &filter, info_sink)
     }
 
-    pub fn analyze<F>(&mut self, limit: SearchLimit, mut info_sink: F) -> BestMove
+    /// Analyze with the root moves restricted by `filter`: only its search
+    /// moves (as [`Self::analyze_with_search_moves`]), minus its excluded
+    /// moves. Returns an error [`BestMove`] if the exclusions leave no legal
+    /// move to play.
+    pub fn analyze_with_filter<F>(
+        &mut self,
+        limit: SearchLimit,
+        filter: &RootMoveFilter,
+        mut info_sink: F,
+    ) -> BestMove
     where
         F: FnMut(SearchInfo),
     {
-        self.stopped.store(false, Ordering::Relaxed);
+        self.install_stop_handle();
 
         // Parse board from FEN
         let board = match &self.current_board {
             Some(b) => b.clone(),
-            None => {
-                // Handle "startpos" keyword or parse FEN
-                if self.current_fen == "startpos" {
-                    Board::startpos()
-                } else {
-                    match parse_fen(&self.current_fen) {
-                        Ok(b) => b,
-                        Err(_e) => {
-                            return BestMove {
-                                id: String::new(),
-                                best: "0000".to_string(), // Invalid move to signal error
-                                ponder: None,
-                            };
-                        }
-                    }
-                }
-            }
+            None => match io::setup_position(&self.current_fen, &self.current_moves) {
+                Ok(b) => b,
+                Err(e) => return Self::error_best_move(e.to_string()),
+            },
         };
+        if let Err(reason) = board.check_searchable() {
+            return Self::error_best_move(format!("Position cannot be searched: {}", reason));
+        }
 
-        // Convert SearchLimit to (max_depth, TimeControl)
-        let (max_depth, time_control) = match limit {
-            SearchLimit::Depth { depth } => (depth, TimeControl::Depth { depth }),
-            SearchLimit::Nodes { nodes } => (search::MAX_DEPTH, TimeControl::Nodes { nodes }),
-            SearchLimit::Time { move_time_ms } => {
-                (search::MAX_DEPTH, TimeControl::MoveTime { millis: move_time_ms })
-            }
-            SearchLimit::Infinite => (search::MAX_DEPTH, TimeControl::Infinite),
+        let (max_depth, time_control) = search_bounds(limit);
+
+        let search_moves = match Self::filter_root_moves(&board, filter) {
+            Ok(moves) => moves,
+            Err(e) => return Self::error_best_move(e),
         };
+        self.searcher.set_search_moves(search_moves);
+        self.searcher
+            .set_skill_level(self.opts.skill_level.unwrap_or(search::MAX_SKILL_LEVEL));
 
-        // Call the real search engine with callback
-        let result = self.searcher.search_with_limit_callback(
+        // Call the real search engine with callback, keeping the last
+        // completed iteration for the BestMove statistics
+        let mut last_info: Option<SearchInfo> = None;
+        let num_pv = self.opts.multi_pv.unwrap_or(1).max(1) as usize;
+        let (score_pov, show_wdl) = (self.score_pov, self.show_wdl);
+        let chess960 = self.opts.chess960.unwrap_or(false);
+        let white_to_move = board.side_to_move() == piece::Color::White;
+        let result = self.searcher.search_multi_pv_with_limit_callback(
             &board,
             max_depth,
+            num_pv,
             time_control,
             |mut info| {
                 // ID will be set by caller if needed, leave empty here
                 info.id = String::new();
+                info.score = score_from_pov(info.score, white_to_move, score_pov);
+                info.score_pov = score_pov;
+                info.wdl = show_wdl.then(|| info.score.wdl());
+                if chess960 {
+                    info.pv = io::uci_line_in(&board, &info.pv, true);
+                }
+                // In multi-PV mode only the first line describes the best move
+                if info.multipv.unwrap_or(1) == 1 {
+                    last_info = Some(info.clone());
+                }
                 info_sink(info);
             },
         );
+        self.searcher.set_search_moves(None);
 
         // Convert result to BestMove
-        let best_move_str = Self::move_to_string(&result.best_move);
-        let ponder_move_str = result.pv.get(1).map(|m| Self::move_to_string(m));
+        let to_uci = |m: &Move| m.to_uci_in(chess960);
+        let best_move_str = to_uci(&result.best_move);
+        let ponder_move_str = result.pv.get(1).map(to_uci);
 
-        BestMove {
+        let mut best = BestMove {
             id: String::new(), // ID is added by the caller (WASM bridge, server, etc.)
             best: best_move_str,
             ponder: ponder_move_str,
+            ..Default::default()
+        };
+
+        match last_info {
+            Some(info) => {
+                best.depth = Some(info.depth);
+                best.seldepth = info.seldepth;
+                best.nodes = Some(info.nodes);
+                best.time_ms = Some(info.time_ms);
+                best.nps = Some(info.nps);
+                best.score = Some(info.score);
+                best.pv = info.pv;
+                best.hashfull = info.hashfull;
+                best.tb_hits = info.tb_hits;
+            }
+            // No iteration completed (e.g. book move): report what we have
+            None => best.pv = result.pv.iter().map(to_uci).collect(),
+        }
+
+        // A reduced skill level may play a different move than the first line
+        if best.pv.first() != Some(&best.best) {
+            best.pv = result.pv.iter().map(to_uci).collect();
+            if best.score.is_some() {
+                let score = self.searcher.score_to_protocol(result.score);
+                best.score = Some(score_from_pov(score, white_to_move, score_pov));
+            }
+        }
+
+        best
+    }
+
+    /// Create an independent engine for a worker thread.
+    ///
+    /// Copies the options and current position, but gets a fresh
+    /// transposition table and stop flag so workers never contend.
+    pub fn clone_for_thread(&self) -> Self {
+        let mut engine = Self::new_with(self.opts.clone());
+        engine.current_fen = self.current_fen.clone();
+        engine.current_moves = self.current_moves.clone();
+        engine.current_board = self.current_board.clone();
+        engine.score_pov = self.score_pov;
+        engine.show_wdl = self.show_wdl;
+        engine
+    }
+
+    /// Analyze many positions in parallel to a fixed depth.
+    ///
+    /// Uses up to `opts.threads` workers (capped by the available
+    /// parallelism), each with its own engine from [`Self::clone_for_thread`].
+    /// Positions are distributed round-robin and the results are returned in
+    /// input order. Invalid or unsearchable FENs yield a `BestMove` with
+    /// `best == "0000"` and the reason in `error`.
+    pub fn split_analysis(&mut self, fens: &[&str], depth: u32) -> Vec<BestMove> {
+        if fens.is_empty() {
+            return Vec::new();
+        }
+
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let num_threads = (self.opts.threads.max(1) as usize)
+            .min(available)
+            .min(fens.len());
+
+        let mut results = vec![BestMove::default(); fens.len()];
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..num_threads)
+                .map(|thread_idx| {
+                    let mut engine = self.clone_for_thread();
+                    scope.spawn(move || {
+                        fens.iter()
+                            .enumerate()
+                            .skip(thread_idx)
+                            .step_by(num_threads)
+                            .map(|(index, fen)| {
+                                engine.position(fen, &[]);
+                                (index, engine.analyze(SearchLimit::Depth { depth }, |_| {}))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            for worker in workers {
+                for (index, best) in worker.join().expect("analysis thread panicked") {
+                    results[index] = best;
+                }
+            }
+        });
+
+        results
+    }
+
+    /// Analyze many positions one after another on this engine, e.g. to
+    /// score puzzle candidates or training data.
+    ///
+    /// Unlike [`Self::split_analysis`] the positions share one searcher, so
+    /// the transposition table is kept from one position to the next. An
+    /// invalid or unsearchable FEN gets an entry with its `error` set and
+    /// does not stop the batch; a stop request does, and only the positions
+    /// finished so far are returned. `progress(done, total)` is called after
+    /// each position. The current position is left as it was.
+    pub fn analyze_batch<F>(
+        &mut self,
+        fens: &[&str],
+        limit: SearchLimit,
+        mut progress: F,
+    ) -> Vec<BatchEvaluation>
+    where
+        F: FnMut(usize, usize),
+    {
+        let saved = (
+            std::mem::take(&mut self.current_fen),
+            std::mem::take(&mut self.current_moves),
+            self.current_board.take(),
+        );
+        let stop = self.next_stop_handle.take().unwrap_or_default();
+
+        let mut results = Vec::with_capacity(fens.len());
+        for (index, fen) in fens.iter().enumerate() {
+            if stop.is_stopped() {
+                break;
+            }
+            self.position(fen, &[]);
+            self.searcher.reset_move_ordering();
+            self.use_stop_handle(stop.clone());
+            let best = self.analyze(limit.clone(), |_| {});
+            results.push(BatchEvaluation {
+                fen: fen.to_string(),
+                best_move: best.best,
+                score: best.score,
+                depth: best.depth,
+                error: best.error,
+            });
+            progress(index + 1, fens.len());
+        }
+
+        (self.current_fen, self.current_moves, self.current_board) = saved;
+        results
+    }
+
+    /// Search each of the [`bench::BENCH_POSITIONS`] to `depth` and report
+    /// the nodes, time and best move for each.
+    ///
+    /// Every position gets a fresh engine with this engine's hash size and
+    /// no opening book, so the node counts only depend on the build, depth
+    /// and hash size. A stop request ends the benchmark after the current
+    /// position.
+    pub fn benchmark(&mut self, depth: u32) -> bench::BenchmarkReport {
+        let opts = EngineOptions {
+            threads: 1,
+            skill_level: None,
+            multi_pv: Some(1),
+            ..self.opts.clone()
+        };
+        let stop = self.next_stop_handle.take().unwrap_or_default();
+
+        let mut positions = Vec::with_capacity(bench::BENCH_POSITIONS.len());
+        for fen in bench::BENCH_POSITIONS {
+            if stop.is_stopped() {
+                break;
+            }
+            let mut engine = Self::new_with(opts.clone());
+            engine.searcher.set_use_book(false);
+            engine.position(fen, &[]);
+            engine.use_stop_handle(stop.clone());
+
+            let best = engine.analyze(SearchLimit::Depth { depth }, |_| {});
+            positions.push(bench::PositionBenchmark {
+                fen: fen.to_string(),
+                best_move: best.best,
+                depth: best.depth.unwrap_or(0),
+                nodes: best.nodes.unwrap_or(0),
+                time_ms: best.time_ms.unwrap_or(0),
+            });
         }
+
+        bench::BenchmarkReport::new(depth, positions)
+    }
+
+    /// Save the transposition table to a file so analysis can be resumed.
+    pub fn save_hash(&self, path: &str) -> Result<(), String> {
+        let file = std::fs::File::create(path)
+            .map_err(|e| format!("Cannot create {}: {}", path, e))?;
+        self.searcher
+            .save_tt(std::io::BufWriter::new(file))
+            .map_err(|e| format!("Cannot save hash to {}: {}", path, e))
+    }
+
+    /// Load a transposition table saved with [`Self::save_hash`].
+    ///
+    /// The loaded table keeps the size it was saved with. On error the
+    /// current table is left untouched.
+    pub fn load_hash(&mut self, path: &str) -> Result<(), String> {
+        let file =
+            std::fs::File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+        self.searcher
+            .load_tt(std::io::BufReader::new(file))
+            .map_err(|e| format!("Cannot load hash from {}: {}", path, e))
     }
 
-    /// Convert Move to UCI string (e.g., "e2e4", "e7e8q")
-    fn move_to_string(mv: &Move) -> String {
-        format!("{}", mv)
+    /// Resize the transposition table, dropping its entries.
+    pub fn set_hash_size(&mut self, size_mb: u32) {
+        self.opts.hash_size_mb = size_mb;
+        self.searcher.resize_tt(size_mb as usize);
     }
 
+    /// Size of the transposition table in MB, whether or not it has been
+    /// allocated yet.
+    pub fn tt_size_mb(&self) -> usize {
+        self.searcher.tt_size_mb()
+    }
+
+    /// Memory allocated for the transposition table, in bytes. The table is
+    /// allocated by the first search, so this is 0 before that.
+    pub fn tt_bytes(&self) -> usize {
+        self.searcher.tt_bytes()
+    }
+
+    /// Name, version and build information, including the bench signature
+    /// that identifies this build's search behavior.
+    pub fn about(&self) -> version::EngineAbout {
+        version::EngineAbout::current()
+    }
+
+    /// Memory used by the pawn hash table, in bytes.
+    pub fn pawn_hash_bytes(&self) -> usize {
+        self.searcher.pawn_hash_bytes()
+    }
+
+    /// Replace the evaluation parameters with a JSON parameter set (see
+    /// [`tune::load_params_from_json`]), or restore the defaults when `json`
+    /// is empty.
+    ///
+    /// The parameters are process-wide: every engine in the process uses them.
+    pub fn set_eval_params_json(&mut self, json: &str) -> Result<(), String> {
+        if json.trim().is_empty() {
+            tune::clear_global_params();
+            return Ok(());
+        }
+        let params = tune::load_params_from_json(json).map_err(|e| e.to_string())?;
+        tune::set_global_params(params);
+        Ok(())
+    }
+
+    /// Classify every move of a game (see [`analysis`]).
+    ///
+    /// Reuses this engine's searcher, so its transposition table is shared
+    /// across the game. `progress(done, total)` is called after each move.
+    pub fn analyze_game<F>(
+        &mut self,
+        fen: &str,
+        moves: &[String],
+        limit: SearchLimit,
+        progress: F,
+    ) -> Result<Vec<analysis::MoveAssessment>, String>
+    where
+        F: FnMut(usize, usize),
+    {
+        let game = analysis::Game::from_uci(fen, moves)?;
+        self.install_stop_handle();
+        Ok(analysis::analyze_game_with_progress(
+            &mut self.searcher,
+            &game,
+            limit,
+            progress,
+        ))
+    }
+
+    /// Classify a single move: how much worse (or better) `uci_move` is than
+    /// the engine's best move in `fen`, both searched to `depth`.
+    ///
+    /// Shares [`Self::analyze_game`]'s rules and thresholds, so a hint shown
+    /// while playing matches the post-game report.
+    pub fn move_classification(
+        &mut self,
+        fen: &str,
+        uci_move: &str,
+        depth: u32,
+    ) -> Result<analysis::MoveClassification, String> {
+        let moves = [uci_move.to_string()];
+        let assessments =
+            self.analyze_game(fen, &moves, SearchLimit::Depth { depth }, |_, _| {})?;
+        Ok(assessments[0].classification)
+    }
+
+    /// The root moves `filter` allows in `board`, or `None` if it leaves
+    /// the search unrestricted. Unknown or illegal UCI strings are ignored,
+    /// and search moves that match nothing restrict nothing.
+    pub fn filter_root_moves(
+        board: &Board,
+        filter: &RootMoveFilter,
+    ) -> Result<Option<Vec<Move>>, String> {
+        if filter.search_moves.is_none() && filter.exclude_moves.is_empty() {
+            return Ok(None);
+        }
+        let listed = |list: &[String], m: &Move| list.iter().any(|u| *u == m.to_uci());
+
+        let legal_moves = board.generate_legal_moves();
+        let mut moves: Vec<Move> = legal_moves.iter().copied().collect();
+        if let Some(search_moves) = &filter.search_moves {
+            let restricted: Vec<Move> = moves
+                .iter()
+                .filter(|m| listed(search_moves, m))
+                .copied()
+                .collect();
+            if !restricted.is_empty() {
+                moves = restricted;
+            }
+        }
+        if moves.is_empty() {
+            // Mate or stalemate: the search reports it
+            return Ok(None);
+        }
+
+        moves.retain(|m| !listed(&filter.exclude_moves, m));
+        if moves.is_empty() {
+            return Err("Excluded moves leave no legal move to search".to_string());
+        }
+        Ok(Some(moves))
+    }
+
+    /// A `BestMove` reporting that nothing was searched.
+    fn error_best_move(error: String) -> BestMove {
+        BestMove {
+            id: String::new(),
+            best: "0000".to_string(), // Invalid move to signal error
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+
+    /// Stop the running search, and the next one if its handle was
+    /// already reserved with [`EngineImpl::begin_analyze`].
     pub fn stop(&self) {
-        self.stopped.store(true, Ordering::Relaxed);
-        self.searcher.stop();
+        self.stop_handle.stop();
+        if let Some(next) = &self.next_stop_handle {
+            next.stop();
+        }
     }
 
-    /// Get a clone of the stop flag for external control.
-    pub fn stop_flag(&self) -> Arc<AtomicBool> {
-        Arc::clone(&self.stopped)
+    /// Reserve the stop handle for the next `analyze*` call.
+    ///
+    /// Stopping the returned handle cancels that search only, even if the
+    /// stop arrives before the search has started; later searches get
+    /// fresh handles and are never affected.
+    pub fn begin_analyze(&mut self) -> StopHandle {
+        let handle = StopHandle::new();
+        self.use_stop_handle(handle.clone());
+        handle
+    }
+
+    /// Like [`EngineImpl::begin_analyze`], with a handle created by the
+    /// caller. Useful when the engine is locked by another thread and the
+    /// handle has to be handed out before the search can be queued.
+    pub fn use_stop_handle(&mut self, handle: StopHandle) {
+        self.next_stop_handle = Some(handle);
+    }
+
+    /// Give the search about to start its own stop handle.
+    fn install_stop_handle(&mut self) {
+        let handle = self.next_stop_handle.take().unwrap_or_default();
+        self.stop_handle = handle.clone();
+        self.searcher.set_stop_handle(handle);
     }
 
     /// Validate if a UCI move is legal in the given position
@@ -248,6 +732,213 @@ impl EngineImpl {
         }
     }
 
+    /// Get all legal moves for a position with capture/castle/promotion/
+    /// check metadata and SAN. Returns an empty list for an invalid FEN.
+    pub fn legal_moves_detailed(&self, fen: &str) -> Vec<MoveInfo> {
+        match parse_fen(fen) {
+            Ok(board) => board
+                .generate_legal_moves()
+                .iter()
+                .map(|&m| Self::move_info(&board, m))
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Alias for [`Self::legal_moves_detailed`].
+    pub fn legal_moves_as_objects(&self, fen: &str) -> Vec<MoveInfo> {
+        self.legal_moves_detailed(fen)
+    }
+
+    /// Legal moves grouped by origin square ("what can the piece on e2 do").
+    pub fn legal_moves_by_square(&self, fen: &str) -> BTreeMap<String, Vec<MoveInfo>> {
+        let mut by_square: BTreeMap<String, Vec<MoveInfo>> = BTreeMap::new();
+        for info in self.legal_moves_detailed(fen) {
+            by_square.entry(info.from.clone()).or_default().push(info);
+        }
+        by_square
+    }
+
+    fn move_info(board: &Board, m: Move) -> MoveInfo {
+        let castle_side = if m.is_kingside_castle() {
+            Some(CastleSide::Kingside)
+        } else if m.is_queenside_castle() {
+            Some(CastleSide::Queenside)
+        } else {
+            None
+        };
+
+        let mut after = board.clone();
+        after.make_move(m);
+        let gives_check = after.is_in_check();
+
+        MoveInfo {
+            from: m.from().to_algebraic(),
+            to: m.destination().to_algebraic(),
+            uci: m.to_uci(),
+            san: san::move_to_san(board, m),
+            is_capture: m.is_capture(),
+            is_castle: m.is_castling(),
+            castle_side,
+            is_en_passant: m.is_en_passant(),
+            promotion: m.promotion_piece().map(|pt| pt.to_char()),
+            gives_check,
+            is_checkmate: gives_check && after.generate_legal_moves().is_empty(),
+        }
+    }
+
+    /// Hanging pieces of both sides, for beginner hints like "your knight
+    /// on d4 is hanging". Uses static exchange evaluation, no search.
+    pub fn position_hints(&self, fen: &str) -> Result<PositionHints, String> {
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        let hanging = |color| {
+            board
+                .hanging_pieces(color)
+                .into_iter()
+                .map(|sq| HangingPiece {
+                    square: sq.to_algebraic(),
+                    piece: board.piece_at(sq).map_or('?', |p| p.to_char()),
+                    value: board.en_prise_value(sq),
+                })
+                .collect()
+        };
+
+        Ok(PositionHints {
+            white_hanging: hanging(piece::Color::White),
+            black_hanging: hanging(piece::Color::Black),
+        })
+    }
+
+    /// Squares attacked by `color` ("w"/"b" or "white"/"black"), empty or
+    /// not, for attack heatmaps.
+    pub fn attacked_squares(&self, fen: &str, color: &str) -> Result<Vec<String>, String> {
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        let color: piece::Color = color.parse().map_err(|e| format!("{}", e))?;
+        Ok(Self::squares(board.controlled_squares(color)))
+    }
+
+    /// Whether the side to move is in check, from where, and its king square.
+    pub fn check_info(&self, fen: &str) -> Result<CheckInfo, String> {
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        let checkers = board.checkers();
+        Ok(CheckInfo {
+            in_check: !checkers.is_empty(),
+            king: board
+                .king_square(board.side_to_move())
+                .map(|sq| sq.to_algebraic()),
+            checkers: Self::squares(checkers),
+        })
+    }
+
+    /// Enemy pieces checking the side to move's king and sliders one piece
+    /// away from doing so, in square order.
+    pub fn explain_check(&self, fen: &str) -> Result<Vec<KingThreat>, String> {
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        Ok(board
+            .threats_to_king(board.side_to_move())
+            .into_iter()
+            .map(|t| KingThreat {
+                square: t.attacker_sq.to_algebraic(),
+                piece: t.attacker_piece.to_char(),
+                attack_type: t.attack_type,
+            })
+            .collect())
+    }
+
+    /// Pieces of the side to move pinned to their king, with the pinning
+    /// slider and the ray they are confined to.
+    pub fn pinned_pieces(&self, fen: &str) -> Result<Vec<PinInfo>, String> {
+        use attacks::{line_through, ray_between};
+        use piece::PieceType;
+
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        let us = board.side_to_move();
+        let Some(king) = board.king_square(us) else {
+            return Ok(vec![]);
+        };
+
+        let them = us.opponent();
+        let queens = board.piece_bb(PieceType::Queen, them);
+        let diagonal = board.piece_bb(PieceType::Bishop, them) | queens;
+        let orthogonal = board.piece_bb(PieceType::Rook, them) | queens;
+
+        let mut pins = Vec::new();
+        for pinned in board.pinned() {
+            let sliders = if pinned.file() == king.file() || pinned.rank() == king.rank() {
+                orthogonal
+            } else {
+                diagonal
+            };
+            // The pinner is the first slider behind the pinned piece
+            let behind = sliders & line_through(king, pinned);
+            let pinner = behind.into_iter().find(|&s| {
+                let between = ray_between(king, s);
+                between.contains(pinned) && (between & board.occupied()).count() == 1
+            });
+            if let Some(pinner) = pinner {
+                pins.push(PinInfo {
+                    square: pinned.to_algebraic(),
+                    pinner: pinner.to_algebraic(),
+                    ray: Self::squares(ray_between(king, pinner).set(pinner)),
+                });
+            }
+        }
+        Ok(pins)
+    }
+
+    /// Every piece on the board with its square, white pieces first.
+    pub fn pieces_list(&self, fen: &str) -> Result<Vec<PieceOnSquare>, String> {
+        let board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        Ok(board
+            .pieces_iter()
+            .map(|(sq, piece)| PieceOnSquare {
+                square: sq.to_algebraic(),
+                piece: piece.to_char(),
+            })
+            .collect())
+    }
+
+    /// Squares whose contents differ between two FENs, in square order.
+    pub fn fen_diff(&self, fen_a: &str, fen_b: &str) -> Result<Vec<SquareDiff>, String> {
+        let changes = io::fen_diff(fen_a, fen_b).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        Ok(changes
+            .into_iter()
+            .map(|c| SquareDiff {
+                square: c.square.to_algebraic(),
+                before: c.before.map(piece::Piece::to_char),
+                after: c.after.map(piece::Piece::to_char),
+            })
+            .collect())
+    }
+
+    /// Replay `pv` from `fen`, returning SAN and the FEN after each move so
+    /// a UI can jump to any position along the line.
+    pub fn expand_pv(&self, fen: &str, pv: &[String]) -> Result<Vec<PvNode>, String> {
+        let mut board = parse_fen(fen).map_err(|e| format!("Invalid FEN: {:?}", e))?;
+        let mut nodes = Vec::with_capacity(pv.len());
+        for uci in pv {
+            let mv = board
+                .generate_legal_moves()
+                .iter()
+                .copied()
+                .find(|m| m.to_uci() == *uci)
+                .ok_or_else(|| format!("Illegal move: {}", uci))?;
+            let san = san::move_to_san(&board, mv);
+            board.make_move(mv);
+            nodes.push(PvNode {
+                uci: uci.clone(),
+                san,
+                fen_after: board.to_fen(),
+                score_at_node: self.searcher.tt_score(&board),
+            });
+        }
+        Ok(nodes)
+    }
+
+    fn squares(bb: bitboard::Bitboard) -> Vec<String> {
+        bb.into_iter().map(|sq| sq.to_algebraic()).collect()
+    }
+
     /// Check if position is game over (checkmate, stalemate)
     pub fn is_game_over(&self, fen: &str) -> (bool, Option<String>) {
         match parse_fen(fen) {
@@ -1,4 +1,5 @@
-use crate::piece::PieceType;
+use crate::board::Board;
+use crate::piece::{Color, PieceType};
 use crate::square::Square;
 
 /// A chess move packed into 16 bits.
@@ -31,9 +32,40 @@ pub struct Move(u16);
 /// This encoding allows efficient checking:
 /// - `flags & 0x4` checks if capture
 /// - `flags & 0x8` checks if promotion
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// Values 6 and 7 (capture bit with special bits, but not en passant) mean
+/// nothing; [`MoveFlags::is_valid`] rejects them. Flags print as the name
+/// of their constant:
+///
+/// ```
+/// use engine::r#move::MoveFlags;
+///
+/// assert_eq!(MoveFlags::EP_CAPTURE.to_string(), "EP_CAPTURE");
+/// assert_eq!(format!("{:?}", MoveFlags::QUIET), "MoveFlags(QUIET)");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct MoveFlags(u8);
 
+/// Names of the flag constants by value; `None` for the unused values.
+const FLAG_NAMES: [Option<&str>; 16] = [
+    Some("QUIET"),
+    Some("DOUBLE_PAWN_PUSH"),
+    Some("KING_CASTLE"),
+    Some("QUEEN_CASTLE"),
+    Some("CAPTURE"),
+    Some("EP_CAPTURE"),
+    None,
+    None,
+    Some("KNIGHT_PROMOTION"),
+    Some("BISHOP_PROMOTION"),
+    Some("ROOK_PROMOTION"),
+    Some("QUEEN_PROMOTION"),
+    Some("KNIGHT_PROMOTION_CAPTURE"),
+    Some("BISHOP_PROMOTION_CAPTURE"),
+    Some("ROOK_PROMOTION_CAPTURE"),
+    Some("QUEEN_PROMOTION_CAPTURE"),
+];
+
 impl MoveFlags {
     // Quiet moves (0b00xx)
     pub const QUIET: Self = Self(0);
@@ -118,8 +150,68 @@ impl MoveFlags {
     pub fn value(self) -> u8 {
         self.0
     }
+
+    /// Returns true if this is one of the named flag constants, i.e. not
+    /// one of the unused values 6 and 7.
+    #[inline(always)]
+    pub fn is_valid(self) -> bool {
+        !matches!(self.0, 6 | 7)
+    }
+}
+
+impl std::fmt::Display for MoveFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match FLAG_NAMES[self.0 as usize] {
+            Some(name) => f.write_str(name),
+            None => write!(f, "INVALID({})", self.0),
+        }
+    }
+}
+
+impl std::fmt::Debug for MoveFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MoveFlags({})", self)
+    }
+}
+
+/// Why a move's flags don't fit the position, from
+/// [`Move::validate_against`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The flags are one of the unused values 6 and 7
+    InvalidFlags(MoveFlags),
+    /// No piece of the side to move stands on the from square
+    NoPiece,
+    /// The capture flag disagrees with what stands on the to square
+    Capture,
+    /// Castling flag on anything but a king stepping two files from its
+    /// home square, or a king doing so without it
+    Castling,
+    /// Double push flag on anything but a pawn stepping two ranks from its
+    /// start rank, or a pawn doing so without it
+    DoublePawnPush,
+    /// En passant flag on a pawn not moving to the en passant square
+    EnPassant,
+    /// Promotion flag without a pawn reaching the last rank, or the reverse
+    Promotion,
 }
 
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::InvalidFlags(flags) => write!(f, "invalid move flags {}", flags.value()),
+            MoveError::NoPiece => write!(f, "no piece of the side to move on the from square"),
+            MoveError::Capture => write!(f, "capture flag does not match the target square"),
+            MoveError::Castling => write!(f, "castling flag does not match the king move"),
+            MoveError::DoublePawnPush => write!(f, "double push flag does not match the pawn move"),
+            MoveError::EnPassant => write!(f, "en passant flag does not match the position"),
+            MoveError::Promotion => write!(f, "promotion flag does not match the pawn move"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
 impl Move {
     /// Creates a new move from source square, destination square, and flags.
     #[inline(always)]
@@ -130,15 +222,35 @@ impl Move {
         Self(from_bits | to_bits | flag_bits)
     }
 
-    /// Creates a null move (a1a1 with no flags). Used as a sentinel value.
+    /// The "no move" sentinel, see [`Move::null`].
+    pub const NULL: Move = Move::null();
+
+    /// Creates a null move: all 16 bits zero, i.e. a1a1 with no flags.
+    ///
+    /// Used as a sentinel for "no move" (an empty move list slot, no best
+    /// move, a missing TT move). A real move never has the same from and to
+    /// square, so no legal or pseudo-legal move is ever null. Prints as
+    /// `0000`, the UCI spelling of a null move.
     #[inline(always)]
-    pub fn null() -> Self {
+    pub const fn null() -> Self {
         Self(0)
     }
 
-    /// Returns true if this is a null move.
+    /// Returns the raw 16-bit encoding (from | to << 6 | flags << 12).
     #[inline(always)]
-    pub fn is_null(self) -> bool {
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a move from its raw 16-bit encoding (see [`Move::to_bits`]).
+    #[inline(always)]
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// Returns true if this is the null move.
+    #[inline(always)]
+    pub const fn is_null(self) -> bool {
         self.0 == 0
     }
 
@@ -148,12 +260,35 @@ impl Move {
         Square::new((self.0 & 0x3F) as u8)
     }
 
-    /// Returns the destination square.
+    /// Returns the target square.
+    ///
+    /// Castling is encoded as the king taking its own rook, so for castling
+    /// moves this is the rook's square; see [`Self::destination`].
     #[inline(always)]
     pub fn to(self) -> Square {
         Square::new(((self.0 >> 6) & 0x3F) as u8)
     }
 
+    /// Returns the square the moving piece lands on: [`Self::to`], except
+    /// for castling, where the king ends on the g- or c-file.
+    ///
+    /// ```
+    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::square::Square;
+    ///
+    /// let castle = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
+    /// assert_eq!(castle.to(), Square::H1);
+    /// assert_eq!(castle.destination(), Square::G1);
+    /// ```
+    #[inline(always)]
+    pub fn destination(self) -> Square {
+        match self.flags() {
+            MoveFlags::KING_CASTLE => Square::from_coords(6, self.from().rank()),
+            MoveFlags::QUEEN_CASTLE => Square::from_coords(2, self.from().rank()),
+            _ => self.to(),
+        }
+    }
+
     /// Returns the move flags.
     #[inline(always)]
     pub fn flags(self) -> MoveFlags {
@@ -214,10 +349,35 @@ impl Move {
         self.flags().promotion_piece()
     }
 
-    /// Returns a UCI-style move string (e.g., "e2e4", "e7e8q").
+    /// Returns a UCI-style move string (e.g., "e2e4", "e7e8q", or "0000"
+    /// for the null move). Castling is written as the king's step, "e1g1".
     pub fn to_uci(self) -> String {
+        self.to_uci_in(false)
+    }
+
+    /// Like [`Self::to_uci`], but with `chess960` set castling is written
+    /// as the king taking its own rook ("e1h1"), as UCI requires when
+    /// `UCI_Chess960` is on.
+    ///
+    /// ```
+    /// use engine::r#move::{Move, MoveFlags};
+    /// use engine::square::Square;
+    ///
+    /// let castle = Move::new(Square::E1, Square::A1, MoveFlags::QUEEN_CASTLE);
+    /// assert_eq!(castle.to_uci_in(false), "e1c1");
+    /// assert_eq!(castle.to_uci_in(true), "e1a1");
+    /// ```
+    pub fn to_uci_in(self, chess960: bool) -> String {
+        if self.is_null() {
+            return "0000".to_string();
+        }
         let from = self.from().to_algebraic();
-        let to = self.to().to_algebraic();
+        let to = if chess960 {
+            self.to()
+        } else {
+            self.destination()
+        };
+        let to = to.to_algebraic();
 
         if let Some(piece) = self.promotion_piece() {
             let promo_char = match piece {
@@ -232,6 +392,153 @@ impl Move {
             format!("{}{}", from, to)
         }
     }
+
+    /// Parse a UCI move string (e.g., "e2e4", "e7e8q") in `board`'s position.
+    ///
+    /// Castling is accepted both as the king's step ("e1g1") and as the king
+    /// taking its own rook ("e1h1"). The move's flags are read off the board, so impossible input is
+    /// rejected by [`Board::is_pseudo_legal`] without generating any moves.
+    /// A pawn reaching the last rank without a promotion letter promotes to
+    /// a queen. The result may still leave the king in check; see
+    /// [`Board::make_move_if_legal`].
+    ///
+    /// # Example
+    /// ```
+    /// use engine::prelude::*;
+    ///
+    /// let board = Board::startpos();
+    /// let m = Move::from_uci(&board, "e2e4").unwrap();
+    /// assert!(m.is_double_pawn_push());
+    /// assert!(Move::from_uci(&board, "e2e5").is_none());
+    /// ```
+    pub fn from_uci(board: &Board, uci: &str) -> Option<Move> {
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return None;
+        }
+
+        let from = Square::from_algebraic(&uci[0..2])?;
+        let to = Square::from_algebraic(&uci[2..4])?;
+        let promotion = match uci.as_bytes().get(4) {
+            None => None,
+            Some(b'q') => Some(PieceType::Queen),
+            Some(b'r') => Some(PieceType::Rook),
+            Some(b'b') => Some(PieceType::Bishop),
+            Some(b'n') => Some(PieceType::Knight),
+            Some(_) => return None,
+        };
+
+        let piece = board.piece_at(from)?;
+        let to = if piece.piece_type == PieceType::King && from.file().abs_diff(to.file()) == 2 {
+            // The king's two-square step names the rook on that side
+            Square::from_coords(if to.file() > from.file() { 7 } else { 0 }, to.rank())
+        } else {
+            to
+        };
+        let target = board.piece_at(to);
+        let capture = target.is_some_and(|p| p.color != piece.color);
+        let flags = match piece.piece_type {
+            PieceType::King if target.is_some_and(|p| p.color == piece.color) => {
+                if to.file() > from.file() {
+                    MoveFlags::KING_CASTLE
+                } else {
+                    MoveFlags::QUEEN_CASTLE
+                }
+            }
+            PieceType::Pawn if !capture && from.file() != to.file() => MoveFlags::EP_CAPTURE,
+            PieceType::Pawn if from.rank().abs_diff(to.rank()) == 2 => MoveFlags::DOUBLE_PAWN_PUSH,
+            PieceType::Pawn if to.rank() == 0 || to.rank() == 7 => {
+                match (promotion.unwrap_or(PieceType::Queen), capture) {
+                    (PieceType::Knight, false) => MoveFlags::KNIGHT_PROMOTION,
+                    (PieceType::Bishop, false) => MoveFlags::BISHOP_PROMOTION,
+                    (PieceType::Rook, false) => MoveFlags::ROOK_PROMOTION,
+                    (_, false) => MoveFlags::QUEEN_PROMOTION,
+                    (PieceType::Knight, true) => MoveFlags::KNIGHT_PROMOTION_CAPTURE,
+                    (PieceType::Bishop, true) => MoveFlags::BISHOP_PROMOTION_CAPTURE,
+                    (PieceType::Rook, true) => MoveFlags::ROOK_PROMOTION_CAPTURE,
+                    (_, true) => MoveFlags::QUEEN_PROMOTION_CAPTURE,
+                }
+            }
+            _ if capture => MoveFlags::CAPTURE,
+            _ => MoveFlags::QUIET,
+        };
+
+        let m = Move::new(from, to, flags);
+        board.is_pseudo_legal(m).then_some(m)
+    }
+
+    /// Check that this move's flags describe what it does in `board`: the
+    /// capture flag matches the target square, castling is flagged exactly
+    /// when the king on its home square takes a corner of its back rank,
+    /// double pushes exactly when a pawn makes that step, and so on. Catches hand-built nonsense such as a `KING_CASTLE` from e2.
+    ///
+    /// Whether the piece can actually get there is not checked; see
+    /// [`Board::is_pseudo_legal`], which also rejects every move this does.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::prelude::*;
+    /// use engine::r#move::MoveError;
+    ///
+    /// let board = Board::startpos();
+    /// let e4 = Move::new(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH);
+    /// assert_eq!(e4.validate_against(&board), Ok(()));
+    /// let castle = Move::new(Square::E2, Square::E3, MoveFlags::KING_CASTLE);
+    /// assert_eq!(castle.validate_against(&board), Err(MoveError::Castling));
+    /// ```
+    pub fn validate_against(self, board: &Board) -> Result<(), MoveError> {
+        let flags = self.flags();
+        if !flags.is_valid() {
+            return Err(MoveError::InvalidFlags(flags));
+        }
+
+        let us = board.side_to_move();
+        let (from, to) = (self.from(), self.to());
+        let piece = board
+            .piece_at(from)
+            .filter(|p| p.color == us)
+            .ok_or(MoveError::NoPiece)?;
+        let target = board.piece_at(to);
+        let is_king = piece.piece_type == PieceType::King;
+        let is_pawn = piece.piece_type == PieceType::Pawn;
+
+        let home_rank = if us == Color::White { 0 } else { 7 };
+        let castles = is_king
+            && from.file() == 4
+            && from.rank() == home_rank
+            && to.rank() == home_rank
+            && (to.file() == 0 || to.file() == 7);
+        if self.is_castling() != castles
+            || (castles && self.is_kingside_castle() != (to.file() > from.file()))
+        {
+            return Err(MoveError::Castling);
+        }
+
+        let start_rank = if us == Color::White { 1 } else { 6 };
+        let double_push = is_pawn
+            && from.rank() == start_rank
+            && from.file() == to.file()
+            && from.rank().abs_diff(to.rank()) == 2;
+        if self.is_double_pawn_push() != double_push {
+            return Err(MoveError::DoublePawnPush);
+        }
+
+        if self.is_en_passant() {
+            return if is_pawn && board.ep_square() == Some(to) && target.is_none() {
+                Ok(())
+            } else {
+                Err(MoveError::EnPassant)
+            };
+        }
+        if self.is_capture() != target.is_some_and(|p| p.color != us) {
+            return Err(MoveError::Capture);
+        }
+
+        let last_rank = 7 - home_rank;
+        if self.is_promotion() != (is_pawn && to.rank() == last_rank) {
+            return Err(MoveError::Promotion);
+        }
+        Ok(())
+    }
 }
 
 impl std::fmt::Display for Move {
@@ -274,11 +581,11 @@ mod tests {
 
     #[test]
     fn move_packing_castling() {
-        let kingside = Move::new(Square::E1, Square::G1, MoveFlags::KING_CASTLE);
+        let kingside = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
         assert!(kingside.is_castling());
         assert!(!kingside.is_capture());
 
-        let queenside = Move::new(Square::E1, Square::C1, MoveFlags::QUEEN_CASTLE);
+        let queenside = Move::new(Square::E1, Square::A1, MoveFlags::QUEEN_CASTLE);
         assert!(queenside.is_castling());
     }
 
@@ -324,8 +631,36 @@ mod tests {
     fn move_null() {
         let m = Move::null();
         assert!(m.is_null());
+        assert!(Move::NULL.is_null());
+        assert_eq!(m, Move::NULL);
         assert_eq!(m.from(), Square::A1);
         assert_eq!(m.to(), Square::A1);
+        assert_eq!(m.to_uci(), "0000");
+        assert!(!Move::new(Square::A1, Square::A2, MoveFlags::QUIET).is_null());
+    }
+
+    #[test]
+    fn no_legal_move_is_null() {
+        fn walk(board: &crate::board::Board, depth: u32) {
+            for m in board.generate_legal_moves().iter() {
+                assert!(!m.is_null(), "{}", m);
+                assert_ne!(m.from(), m.to());
+                if depth > 1 {
+                    let mut child = board.clone();
+                    child.make_move(*m);
+                    walk(&child, depth - 1);
+                }
+            }
+        }
+
+        for fen in [
+            crate::io::STARTPOS_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            walk(&crate::io::parse_fen(fen).unwrap(), 3);
+        }
     }
 
     #[test]
@@ -342,6 +677,157 @@ mod tests {
             Move::new(Square::G7, Square::H8, MoveFlags::KNIGHT_PROMOTION_CAPTURE).to_uci(),
             "g7h8n"
         );
+
+        let castle = Move::new(Square::E8, Square::H8, MoveFlags::KING_CASTLE);
+        assert_eq!(castle.to_uci(), "e8g8");
+        assert_eq!(castle.to_uci_in(true), "e8h8");
+    }
+
+    #[test]
+    fn from_uci_accepts_both_castling_notations() {
+        let board = crate::io::parse_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let kingside = Move::new(Square::E1, Square::H1, MoveFlags::KING_CASTLE);
+        let queenside = Move::new(Square::E1, Square::A1, MoveFlags::QUEEN_CASTLE);
+        assert_eq!(Move::from_uci(&board, "e1g1"), Some(kingside));
+        assert_eq!(Move::from_uci(&board, "e1h1"), Some(kingside));
+        assert_eq!(Move::from_uci(&board, "e1c1"), Some(queenside));
+        assert_eq!(Move::from_uci(&board, "e1a1"), Some(queenside));
+
+        let board = crate::io::parse_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1").unwrap();
+        assert_eq!(Move::from_uci(&board, "e1g1"), None);
+        assert_eq!(Move::from_uci(&board, "e1h1"), None);
+    }
+
+    #[test]
+    fn every_flag_value_decodes_consistently() {
+        use PieceType::*;
+        // (valid, capture, promotion, castling, en passant, double push, promotes to, name)
+        #[rustfmt::skip]
+        let table: [(bool, bool, bool, bool, bool, bool, Option<PieceType>, &str); 16] = [
+            (true,  false, false, false, false, false, None,         "QUIET"),
+            (true,  false, false, false, false, true,  None,         "DOUBLE_PAWN_PUSH"),
+            (true,  false, false, true,  false, false, None,         "KING_CASTLE"),
+            (true,  false, false, true,  false, false, None,         "QUEEN_CASTLE"),
+            (true,  true,  false, false, false, false, None,         "CAPTURE"),
+            (true,  true,  false, false, true,  false, None,         "EP_CAPTURE"),
+            (false, true,  false, false, false, false, None,         "INVALID(6)"),
+            (false, true,  false, false, false, false, None,         "INVALID(7)"),
+            (true,  false, true,  false, false, false, Some(Knight), "KNIGHT_PROMOTION"),
+            (true,  false, true,  false, false, false, Some(Bishop), "BISHOP_PROMOTION"),
+            (true,  false, true,  false, false, false, Some(Rook),   "ROOK_PROMOTION"),
+            (true,  false, true,  false, false, false, Some(Queen),  "QUEEN_PROMOTION"),
+            (true,  true,  true,  false, false, false, Some(Knight), "KNIGHT_PROMOTION_CAPTURE"),
+            (true,  true,  true,  false, false, false, Some(Bishop), "BISHOP_PROMOTION_CAPTURE"),
+            (true,  true,  true,  false, false, false, Some(Rook),   "ROOK_PROMOTION_CAPTURE"),
+            (true,  true,  true,  false, false, false, Some(Queen),  "QUEEN_PROMOTION_CAPTURE"),
+        ];
+
+        let squares = (Square::D2.index() as u16) | ((Square::G7.index() as u16) << 6);
+        for (value, &(valid, capture, promotion, castling, ep, double, promo, name)) in
+            table.iter().enumerate()
+        {
+            let m = Move::from_bits(((value as u16) << 12) | squares);
+            let flags = m.flags();
+            assert_eq!(flags.value(), value as u8);
+            assert_eq!((m.from(), m.to()), (Square::D2, Square::G7), "{name}");
+            assert_eq!(flags.is_valid(), valid, "{name}");
+            assert_eq!(m.is_capture(), capture, "{name}");
+            assert_eq!(m.is_promotion(), promotion, "{name}");
+            assert_eq!(m.is_castling(), castling, "{name}");
+            assert_eq!(m.is_en_passant(), ep, "{name}");
+            assert_eq!(m.is_double_pawn_push(), double, "{name}");
+            assert_eq!(m.promotion_piece(), promo, "{name}");
+            assert_eq!(flags.to_string(), name);
+            assert_eq!(format!("{flags:?}"), format!("MoveFlags({name})"));
+        }
+    }
+
+    #[test]
+    fn validate_against_rejects_incoherent_flags() {
+        let board = Board::startpos();
+        let check = |from, to, flags| Move::new(from, to, flags).validate_against(&board);
+
+        assert_eq!(check(Square::G1, Square::F3, MoveFlags::QUIET), Ok(()));
+        assert_eq!(
+            check(Square::E2, Square::E4, MoveFlags::DOUBLE_PAWN_PUSH),
+            Ok(())
+        );
+        assert_eq!(
+            check(Square::E2, Square::E4, MoveFlags::QUIET),
+            Err(MoveError::DoublePawnPush)
+        );
+        assert_eq!(
+            check(Square::E2, Square::E3, MoveFlags::KING_CASTLE),
+            Err(MoveError::Castling)
+        );
+        assert_eq!(
+            check(Square::E2, Square::E3, MoveFlags::CAPTURE),
+            Err(MoveError::Capture)
+        );
+        assert_eq!(
+            check(Square::E2, Square::E3, MoveFlags::EP_CAPTURE),
+            Err(MoveError::EnPassant)
+        );
+        assert_eq!(
+            check(Square::E2, Square::E3, MoveFlags::QUEEN_PROMOTION),
+            Err(MoveError::Promotion)
+        );
+        assert_eq!(
+            check(Square::E7, Square::E5, MoveFlags::QUIET),
+            Err(MoveError::NoPiece)
+        );
+
+        // Unused flag values are rejected here and by is_pseudo_legal.
+        let bogus = Move::from_bits(
+            (6 << 12) | (Square::E2.index() as u16) | ((Square::E3.index() as u16) << 6),
+        );
+        assert!(matches!(
+            bogus.validate_against(&board),
+            Err(MoveError::InvalidFlags(_))
+        ));
+        assert!(!board.is_pseudo_legal(bogus));
+
+        let board = crate::io::parse_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let castle = |to, flags| Move::new(Square::E1, to, flags).validate_against(&board);
+        assert_eq!(castle(Square::H1, MoveFlags::KING_CASTLE), Ok(()));
+        assert_eq!(castle(Square::A1, MoveFlags::QUEEN_CASTLE), Ok(()));
+        assert_eq!(
+            castle(Square::A1, MoveFlags::KING_CASTLE),
+            Err(MoveError::Castling)
+        );
+        assert_eq!(
+            castle(Square::H1, MoveFlags::QUIET),
+            Err(MoveError::Castling)
+        );
+    }
+
+    #[test]
+    fn generated_moves_pass_validate_against() {
+        fn walk(board: &Board, depth: u32) {
+            for m in board.generate_legal_moves().iter() {
+                assert_eq!(
+                    m.validate_against(board),
+                    Ok(()),
+                    "{} in {}",
+                    m,
+                    crate::io::ToFen::to_fen(board)
+                );
+                if depth > 1 {
+                    let mut child = board.clone();
+                    child.make_move(*m);
+                    walk(&child, depth - 1);
+                }
+            }
+        }
+
+        for fen in [
+            crate::io::STARTPOS_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            walk(&crate::io::parse_fen(fen).unwrap(), 3);
+        }
     }
 
     #[test]
@@ -4,20 +4,100 @@
 //! The goal is to search the best moves first to maximize cutoffs.
 
 use crate::board::Board;
-use crate::eval::pst::PieceSquareTables;
+use crate::eval::pst::{PieceSquareTables, DEFAULT_PIECE_SQUARE_TABLES};
 use crate::eval::PIECE_VALUES;
-use crate::movelist::MoveList;
+use crate::movelist::{MoveList, MAX_MOVES};
 use crate::piece::PieceType;
 use crate::r#move::Move;
 use crate::search::history::{CaptureHistory, ContinuationHistory, CountermoveTable};
-use crate::search::see::see_value;
-
-/// Maximum search depth (for killer move storage)
-const MAX_PLY: usize = 64;
+use crate::search::see::{see_beats_threshold, see_value};
+use crate::search::MAX_PLY;
 
 /// Number of killer moves per ply (increased from 2 to 3 for M7)
 const MAX_KILLERS: usize = 3;
 
+// Move scores fall into non-overlapping bands of `BAND_WIDTH`, band `n`
+// covering `[n * BAND_WIDTH, (n + 1) * BAND_WIDTH)`. Scores within a band
+// are clamped to it, so no history or SEE value can push a move across.
+//
+//   6   TT move
+//   5   good captures (SEE >= 0)
+//   4   killers, then the countermove (and bad captures with
+//       `bad_captures_before_quiets`)
+//   3   quiet moves by history
+//   2   bad captures (SEE < 0)
+//   1   underpromotions
+
+/// Width of one move ordering band
+const BAND_WIDTH: i32 = 1_000_000;
+
+/// Band 6: the TT move
+const TT_MOVE_SCORE: i32 = 6 * BAND_WIDTH;
+
+/// Band 5: good captures
+const GOOD_CAPTURE_BAND: i32 = 5 * BAND_WIDTH;
+
+/// Band 4: killers sit in the upper half
+const KILLER_SCORE: i32 = 4 * BAND_WIDTH + BAND_WIDTH / 2;
+
+/// Band 4: the countermove, just below the killers
+const COUNTERMOVE_SCORE: i32 = 4 * BAND_WIDTH + BAND_WIDTH / 4;
+
+/// Band 4: bad captures searched ahead of quiets take the bottom quarter
+const EARLY_BAD_CAPTURE_BAND: i32 = 4 * BAND_WIDTH;
+
+/// Band 3: quiet moves, centred so negative history still fits
+const QUIET_BAND: i32 = 3 * BAND_WIDTH;
+
+/// Band 2: bad captures
+const BAD_CAPTURE_BAND: i32 = 2 * BAND_WIDTH;
+
+/// Band 1: underpromotions
+const UNDERPROMOTION_BAND: i32 = BAND_WIDTH;
+
+/// Score `offset` within the band starting at `base` of width `width`.
+#[inline]
+fn in_band(base: i32, width: i32, offset: i32) -> i32 {
+    base + offset.clamp(0, width - 1)
+}
+
+/// Ordering penalty for moves that stalemate a losing opponent (sorts them
+/// below every other move, bad captures included)
+const STALEMATE_PENALTY: i32 = 20_000_000;
+
+/// Lookups shared by every move scored at one node.
+struct ScoringContext {
+    tt_move: Option<Move>,
+    is_endgame: bool,
+    killers: [Option<Move>; MAX_KILLERS],
+    countermove: Option<Move>,
+    /// The previous move and the piece that made it, for continuation history
+    prev: Option<(Move, PieceType)>,
+}
+
+impl ScoringContext {
+    fn new(
+        order: &MoveOrder,
+        board: &Board,
+        ply: usize,
+        tt_move: Option<Move>,
+        prev_move: Option<Move>,
+    ) -> Self {
+        Self {
+            tt_move,
+            is_endgame: crate::eval::material::is_endgame(board),
+            killers: order
+                .killers
+                .get(ply)
+                .copied()
+                .unwrap_or([None; MAX_KILLERS]),
+            countermove: prev_move.and_then(|prev| order.countermoves.get(prev)),
+            prev: prev_move
+                .and_then(|prev| Some((prev, board.piece_at(prev.destination())?.piece_type))),
+        }
+    }
+}
+
 /// Move ordering manager.
 ///
 /// Scores and orders moves to maximize alpha-beta pruning efficiency.
@@ -37,11 +117,18 @@ pub struct MoveOrder {
     /// Continuation history: move pairs that work well
     continuation_history: ContinuationHistory,
 
+    /// Whether continuation history is used for ordering
+    use_continuation_history: bool,
+
     /// Capture history: separate history for captures
     capture_history: CaptureHistory,
 
+    /// Whether bad captures go ahead of quiet moves (after the countermove)
+    /// instead of after them
+    bad_captures_before_quiets: bool,
+
     /// Piece-square tables for positional move bonuses
-    pst: PieceSquareTables,
+    pst: &'static PieceSquareTables,
 }
 
 impl MoveOrder {
@@ -52,8 +139,10 @@ impl MoveOrder {
             history: [[0; 64]; 64],
             countermoves: CountermoveTable::new(),
             continuation_history: ContinuationHistory::new(),
+            use_continuation_history: true,
             capture_history: CaptureHistory::new(),
-            pst: PieceSquareTables::default(),
+            bad_captures_before_quiets: false,
+            pst: &DEFAULT_PIECE_SQUARE_TABLES,
         }
     }
 
@@ -113,7 +202,7 @@ impl MoveOrder {
         }
 
         let from = m.from().index() as usize;
-        let to = m.to().index() as usize;
+        let to = m.destination().index() as usize;
 
         // Bonus proportional to depth squared
         // Deeper searches are more valuable
@@ -143,16 +232,73 @@ impl MoveOrder {
     /// Update continuation history for a move pair
     ///
     /// # Arguments
+    /// * `board` - Current board position (after `prev_move`, before `current_move`)
     /// * `prev_move` - The previous move
     /// * `current_move` - The move that caused a cutoff
     /// * `depth` - Depth of the cutoff
-    pub fn update_continuation_history(&mut self, prev_move: Move, current_move: Move, depth: i32) {
-        if !current_move.is_capture() {
+    pub fn update_continuation_history(
+        &mut self,
+        board: &Board,
+        prev_move: Move,
+        current_move: Move,
+        depth: i32,
+    ) {
+        if current_move.is_capture() || !self.use_continuation_history {
+            return;
+        }
+
+        if let Some((prev_piece, cur_piece)) = Self::pair_pieces(board, prev_move, current_move) {
             self.continuation_history
-                .update(prev_move, current_move, depth);
+                .update(prev_piece, prev_move, cur_piece, current_move, depth);
         }
     }
 
+    /// Continuation history score of `current_move` following `prev_move`.
+    fn continuation_history_score(
+        &self,
+        board: &Board,
+        prev_move: Move,
+        current_move: Move,
+    ) -> i32 {
+        if !self.use_continuation_history {
+            return 0;
+        }
+
+        // Weighted at half the butterfly history: the table is much sparser
+        // and its raw scores overwhelm the quiet-move ordering otherwise.
+        match Self::pair_pieces(board, prev_move, current_move) {
+            Some((prev_piece, cur_piece)) => {
+                self.continuation_history
+                    .get(prev_piece, prev_move, cur_piece, current_move)
+                    / 2
+            }
+            None => 0,
+        }
+    }
+
+    /// Piece types of a move pair: the previous move's piece now stands on
+    /// its target square, the current move's piece on its origin square.
+    fn pair_pieces(
+        board: &Board,
+        prev_move: Move,
+        current_move: Move,
+    ) -> Option<(PieceType, PieceType)> {
+        let prev_piece = board.piece_at(prev_move.destination())?.piece_type;
+        let cur_piece = board.piece_at(current_move.from())?.piece_type;
+        Some((prev_piece, cur_piece))
+    }
+
+    /// Enable or disable continuation history (enabled by default).
+    pub fn set_continuation_history(&mut self, enabled: bool) {
+        self.use_continuation_history = enabled;
+    }
+
+    /// Order bad captures ahead of quiet moves instead of after them
+    /// (off by default, see `SearchParams::bad_captures_before_quiets`).
+    pub fn set_bad_captures_before_quiets(&mut self, enabled: bool) {
+        self.bad_captures_before_quiets = enabled;
+    }
+
     /// Update capture history for a capture that caused a cutoff
     ///
     /// # Arguments
@@ -194,10 +340,44 @@ impl MoveOrder {
         }
 
         let from = m.from().index() as usize;
-        let to = m.to().index() as usize;
+        let to = m.destination().index() as usize;
         self.history[from][to]
     }
 
+    /// Change to the late move reduction of `m`, in plies, from what move
+    /// ordering knows about it.
+    ///
+    /// Killers and the countermove are reduced one ply less, and history
+    /// (butterfly plus continuation) removes one ply per `history_per_ply`
+    /// points, up to two. History is never negative, so a quiet move that
+    /// has not caused a single cutoff stands in for "bad history" and is
+    /// reduced one ply more. Captures and promotions are left alone.
+    pub fn lmr_adjustment(
+        &self,
+        board: &Board,
+        m: Move,
+        ply: usize,
+        prev_move: Option<Move>,
+        history_per_ply: i32,
+    ) -> i32 {
+        if m.is_capture() || m.is_promotion() {
+            return 0;
+        }
+        if self.is_killer(m, ply)
+            || prev_move.is_some_and(|prev| self.countermoves.get(prev) == Some(m))
+        {
+            return -1;
+        }
+
+        let history = self.history_score(m)
+            + prev_move.map_or(0, |prev| self.continuation_history_score(board, prev, m));
+        if history == 0 {
+            1
+        } else {
+            -(history / history_per_ply.max(1)).min(2)
+        }
+    }
+
     /// MVV-LVA (Most Valuable Victim - Least Valuable Attacker) score for a capture.
     ///
     /// NOTE: This is kept for testing purposes. M7 uses SEE instead of MVV-LVA.
@@ -216,7 +396,6 @@ impl MoveOrder {
     ///
     /// # Returns
     /// MVV-LVA score, or 0 if not a capture
-    #[allow(dead_code)]
     fn mvv_lva_score(board: &Board, m: Move) -> i32 {
         if !m.is_capture() {
             return 0;
@@ -247,17 +426,62 @@ impl MoveOrder {
         victim_value * 10 - attacker_value
     }
 
-    /// Score a move for ordering purposes (M7 Enhanced).
+    /// Score a capture: good captures (SEE >= 0) by MVV-LVA plus capture
+    /// history in band 5, bad captures by their (negative) SEE value in
+    /// band 2, or at the bottom of band 4 with `bad_captures_before_quiets`.
+    fn score_capture(&self, board: &Board, m: Move) -> i32 {
+        // Bad captures: SEE < 0, the least negative first
+        if !see_beats_threshold(board, m, 0) {
+            let see = see_value(board, m);
+            return if self.bad_captures_before_quiets {
+                let width = COUNTERMOVE_SCORE - EARLY_BAD_CAPTURE_BAND;
+                in_band(EARLY_BAD_CAPTURE_BAND, width, width + see)
+            } else {
+                in_band(BAD_CAPTURE_BAND, BAND_WIDTH, BAND_WIDTH + see)
+            };
+        }
+
+        // Get captured piece for capture history
+        let cap_hist_score = if let Some(victim) = board.piece_at(m.to()) {
+            self.capture_history.get(m, victim.piece_type)
+        } else if let Some(attacker) = board.piece_at(m.from()) {
+            // En passant
+            if attacker.piece_type == PieceType::Pawn && m.from().file() != m.to().file() {
+                self.capture_history.get(m, PieceType::Pawn)
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+
+        in_band(
+            GOOD_CAPTURE_BAND,
+            BAND_WIDTH,
+            BAND_WIDTH / 2 + Self::mvv_lva_score(board, m) + cap_hist_score,
+        )
+    }
+
+    /// Promotion piece of an underpromotion (knight, bishop or rook).
+    fn underpromotion(m: Move) -> Option<PieceType> {
+        m.promotion_piece()
+            .filter(|&piece| piece != PieceType::Queen)
+    }
+
+    /// Score a move for ordering purposes.
     ///
-    /// Higher scores are searched first.
+    /// Higher scores are searched first. Each kind of move has its own
+    /// band of scores (see `BAND_WIDTH`), so the kinds never interleave.
     ///
-    /// # Ordering Priority (M7)
-    /// 1. TT move (from transposition table) - 10M
-    /// 2. Good captures (SEE >= 0) - 2M + SEE value + capture history
-    /// 3. Killer moves (3 per ply) - 900k
-    /// 4. Countermove - 800k
-    /// 5. Quiet moves - history + continuation history
-    /// 6. Bad captures (SEE < 0) - SEE value (negative)
+    /// # Ordering Priority
+    /// 1. TT move - band 6
+    /// 2. Good captures (SEE >= 0) - band 5, by MVV-LVA + capture history
+    /// 3. Killer moves (3 per ply) - band 4
+    /// 4. Countermove - band 4, below the killers
+    /// 5. Quiet moves - band 3, by history + continuation history + PST
+    /// 6. Bad captures (SEE < 0) - band 2 by SEE value, or band 4 below the
+    ///    countermove with `bad_captures_before_quiets`
+    /// 7. Underpromotions - band 1
     ///
     /// # Arguments
     /// * `board` - Current board position
@@ -273,73 +497,108 @@ impl MoveOrder {
         tt_move: Option<Move>,
         prev_move: Option<Move>,
     ) -> i32 {
+        let context = ScoringContext::new(self, board, ply, tt_move, prev_move);
+        self.score_with(board, &context, m)
+    }
+
+    /// Score every move of `moves` as [`Self::score_move`] would, returning
+    /// the scores in the same order.
+    ///
+    /// What only depends on the position (game phase, killers, countermove,
+    /// the piece that made the previous move) is looked up once for the
+    /// whole list instead of once per move.
+    pub fn score_moves_bulk(
+        &self,
+        board: &Board,
+        moves: &MoveList,
+        ply: usize,
+        tt_move: Option<Move>,
+        prev_move: Option<Move>,
+    ) -> Vec<i32> {
+        let context = ScoringContext::new(self, board, ply, tt_move, prev_move);
+        let mut scores = vec![0; moves.len()];
+        self.score_into(board, &context, moves, &mut scores);
+        scores
+    }
+
+    /// Score `moves` into the start of `scores` with the position-level
+    /// lookups already done.
+    fn score_into(
+        &self,
+        board: &Board,
+        context: &ScoringContext,
+        moves: &MoveList,
+        scores: &mut [i32],
+    ) {
+        for (score, &m) in scores.iter_mut().zip(moves.iter()) {
+            *score = self.score_with(board, context, m);
+        }
+    }
+
+    /// Score `m` with the position-level lookups already done.
+    fn score_with(&self, board: &Board, context: &ScoringContext, m: Move) -> i32 {
         // 1. TT move gets highest priority
-        if Some(m) == tt_move {
-            return 10_000_000;
+        if Some(m) == context.tt_move {
+            return TT_MOVE_SCORE;
         }
 
-        // 2. Captures - separate good and bad captures using SEE
+        // 2. Underpromotions last; captures split into good and bad by SEE
+        if let Some(piece) = Self::underpromotion(m) {
+            return in_band(UNDERPROMOTION_BAND, BAND_WIDTH, PIECE_VALUES[piece.index()]);
+        }
         if m.is_capture() {
-            let see_score = see_value(board, m);
-
-            // Good captures: SEE >= 0
-            if see_score >= 0 {
-                // Get captured piece for capture history
-                let cap_hist_score = if let Some(victim) = board.piece_at(m.to()) {
-                    self.capture_history.get(m, victim.piece_type)
-                } else if let Some(attacker) = board.piece_at(m.from()) {
-                    // En passant
-                    if attacker.piece_type == PieceType::Pawn && m.from().file() != m.to().file() {
-                        self.capture_history.get(m, PieceType::Pawn)
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                };
-
-                return 2_000_000 + see_score + cap_hist_score;
-            }
-            // Bad captures: SEE < 0 - defer until after quiet moves
-            else {
-                return see_score; // Negative value, will be ordered last
-            }
+            return self.score_capture(board, m);
         }
 
         // 3. Killer moves (quiet moves that caused beta cutoffs)
-        if self.is_killer(m, ply) {
-            return 900_000;
+        if context.killers.contains(&Some(m)) {
+            return KILLER_SCORE;
         }
 
         // 4. Countermove (refutation of previous move)
-        if let Some(prev) = prev_move {
-            if Some(m) == self.countermoves.get(prev) {
-                return 800_000;
-            }
+        if context.countermove == Some(m) {
+            return COUNTERMOVE_SCORE;
         }
 
         // 5. Quiet moves: history + continuation history + PST bonus
         let hist_score = self.history_score(m);
-        let cont_hist_score = if let Some(prev) = prev_move {
-            self.continuation_history.get(prev, m)
-        } else {
-            0
+        let Some(piece) = board.piece_at(m.from()) else {
+            return in_band(QUIET_BAND, BAND_WIDTH, BAND_WIDTH / 2 + hist_score);
+        };
+
+        let cont_hist_score = match context.prev {
+            Some((prev, prev_piece)) if self.use_continuation_history => {
+                // Weighted as in `continuation_history_score`
+                self.continuation_history
+                    .get(prev_piece, prev, piece.piece_type, m)
+                    / 2
+            }
+            _ => 0,
         };
 
         // Add piece-square table bonus for positional improvement
         // This rewards moves that improve piece placement (centralization, advancement, etc.)
-        let pst_bonus = if let Some(piece) = board.piece_at(m.from()) {
-            let is_eg = crate::eval::material::is_endgame(board);
-            self.pst.move_bonus(piece.piece_type, m.from(), m.to(), board.side_to_move(), is_eg)
-        } else {
-            0
-        };
+        let pst_bonus = self.pst.move_bonus(
+            piece.piece_type,
+            m.from(),
+            m.destination(),
+            board.side_to_move(),
+            context.is_endgame,
+        );
 
-        hist_score + cont_hist_score + pst_bonus
+        in_band(
+            QUIET_BAND,
+            BAND_WIDTH,
+            BAND_WIDTH / 2 + hist_score + cont_hist_score + pst_bonus,
+        )
     }
 
     /// Sort moves in-place by score (highest first).
     ///
+    /// Every move is scored once (see [`Self::score_moves_bulk`]) into a
+    /// stack array and the moves are then insertion sorted by score, keeping
+    /// the generation order of equal scores. Nothing is heap allocated.
+    ///
     /// # Arguments
     /// * `board` - Current board position
     /// * `moves` - Moves to sort (modified in-place)
@@ -354,18 +613,90 @@ impl MoveOrder {
         tt_move: Option<Move>,
         prev_move: Option<Move>,
     ) {
+        let context = ScoringContext::new(self, board, ply, tt_move, prev_move);
+        let mut scores = [0; MAX_MOVES];
+        let scores = &mut scores[..moves.len()];
+        self.score_into(board, &context, moves, scores);
+
+        // Against a bare king (or king and pawns) stalemating moves go last,
+        // keeping the opponent able to move
+        if crate::search::pruning::stalemate_risk(board) {
+            for (score, &m) in scores.iter_mut().zip(moves.iter()) {
+                if board.is_stalemate_after(m) {
+                    *score -= STALEMATE_PENALTY;
+                }
+            }
+        }
+
         // Sort by score (descending - highest scores first)
-        moves.sort_by_key(|&m| -self.score_move(board, m, ply, tt_move, prev_move));
+        let moves = moves.as_mut_slice();
+        for i in 1..moves.len() {
+            let (m, score) = (moves[i], scores[i]);
+            let mut j = i;
+            while j > 0 && scores[j - 1] < score {
+                moves[j] = moves[j - 1];
+                scores[j] = scores[j - 1];
+                j -= 1;
+            }
+            moves[j] = m;
+            scores[j] = score;
+        }
+    }
+
+    /// Sort captures for quiescence search (highest first).
+    ///
+    /// Lightweight version of [`Self::order_moves`]: only the TT move and
+    /// capture scores are used, without killer, history or countermove lookups.
+    pub fn order_captures(&self, board: &Board, captures: &mut MoveList, tt_move: Option<Move>) {
+        captures.sort_by_key(|&m| {
+            if Some(m) == tt_move {
+                -TT_MOVE_SCORE
+            } else if let Some(piece) = Self::underpromotion(m) {
+                -in_band(UNDERPROMOTION_BAND, BAND_WIDTH, PIECE_VALUES[piece.index()])
+            } else {
+                -self.score_capture(board, m)
+            }
+        });
     }
 
     /// Clear history and killers for new search.
+    ///
+    /// Continuation history is only aged (halved), so move pair knowledge
+    /// survives from one search to the next.
     pub fn clear(&mut self) {
-        self.killers = [[None; MAX_KILLERS]; MAX_PLY];
+        self.clear_killers();
         self.history = [[0; 64]; 64];
         self.countermoves.clear();
-        self.continuation_history.clear();
+        // Continuation history carries over between searches, aged
+        self.continuation_history.age();
         self.capture_history.clear();
     }
+
+    /// Age the history tables for the next search of the same game.
+    ///
+    /// Butterfly, continuation and capture history are halved rather than
+    /// zeroed: their relative order survives, so the next search starts
+    /// with good ordering, while fresh cutoffs quickly outweigh the old
+    /// scores. Countermoves are kept; killers are left to [`Self::clear_killers`].
+    pub fn decay(&mut self) {
+        self.history
+            .iter_mut()
+            .flatten()
+            .for_each(|score| *score >>= 1);
+        self.continuation_history.age();
+        self.capture_history.age();
+    }
+
+    /// Forget all killer moves (they are ply-relative to the previous root).
+    pub fn clear_killers(&mut self) {
+        self.killers = [[None; MAX_KILLERS]; MAX_PLY];
+    }
+
+    /// Forget everything, including continuation history (for a new game).
+    pub fn reset(&mut self) {
+        self.clear();
+        self.continuation_history.clear();
+    }
 }
 
 impl Default for MoveOrder {
@@ -403,7 +734,7 @@ mod tests {
 
         // TT move should have higher score
         assert!(tt_score > other_score);
-        assert_eq!(tt_score, 10_000_000);
+        assert_eq!(tt_score, TT_MOVE_SCORE);
     }
 
     #[test]
@@ -467,7 +798,6 @@ mod tests {
 
         if let (Some(cap_idx), Some(quiet_idx)) = (first_capture_idx, first_quiet_idx) {
             // Good captures should come before quiet moves
-            // Note: Bad captures (SEE < 0) would come AFTER quiet moves in M7
             assert!(
                 cap_idx < quiet_idx,
                 "Good captures should be ordered before quiet moves"
@@ -475,6 +805,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_score_bands_are_ordered() {
+        let board = parse_fen("4k3/1P6/4p3/3p3n/6P1/8/8/3QK3 w - - 0 1").unwrap();
+        let moves = board.generate_legal_moves();
+        let find = |uci: &str| *moves.iter().find(|m| m.to_uci() == uci).unwrap();
+
+        let tt = find("e1f2");
+        let good_capture = find("g4h5");
+        let killer = find("e1e2");
+        let countermove = find("e1d2");
+        let quiet = find("d1c2");
+        let bad_capture = find("d1d5");
+        let underpromotion = find("b7b8n");
+
+        let prev = Move::new(
+            Square::E7,
+            Square::from_algebraic("e6").unwrap(),
+            crate::r#move::MoveFlags::QUIET,
+        );
+        let mut move_order = MoveOrder::new();
+        move_order.store_killer(killer, 0);
+        move_order.store_countermove(prev, countermove);
+        // History far beyond its normal range must not lift a quiet move
+        // out of its band
+        let (from, to) = (quiet.from().index() as usize, quiet.to().index() as usize);
+        move_order.history[from][to] = 50_000_000;
+
+        let score =
+            |move_order: &MoveOrder, m| move_order.score_move(&board, m, 0, Some(tt), Some(prev));
+
+        let expected = [
+            tt,
+            good_capture,
+            killer,
+            countermove,
+            quiet,
+            bad_capture,
+            underpromotion,
+        ];
+        let scores: Vec<i32> = expected.iter().map(|&m| score(&move_order, m)).collect();
+        assert!(scores.windows(2).all(|w| w[0] > w[1]), "{:?}", scores);
+        assert_eq!(scores[0], TT_MOVE_SCORE);
+        assert_eq!(scores[4] / BAND_WIDTH, 3);
+
+        // Bad captures can move ahead of the quiets, still after the countermove
+        move_order.set_bad_captures_before_quiets(true);
+        let early = [countermove, bad_capture, quiet, underpromotion];
+        let scores: Vec<i32> = early.iter().map(|&m| score(&move_order, m)).collect();
+        assert!(scores.windows(2).all(|w| w[0] > w[1]), "{:?}", scores);
+    }
+
     #[test]
     fn test_clear() {
         let mut move_order = MoveOrder::new();
@@ -495,6 +876,41 @@ mod tests {
         assert_eq!(move_order.history[0][0], 0);
     }
 
+    #[test]
+    fn test_decay_halves_history() {
+        let mut move_order = MoveOrder::new();
+        move_order.history[0][0] = 100;
+        move_order.history[1][2] = 41;
+
+        move_order.decay();
+
+        assert_eq!(move_order.history[0][0], 50);
+        assert_eq!(move_order.history[1][2], 20);
+    }
+
+    #[test]
+    fn test_lmr_adjustment() {
+        use crate::r#move::MoveFlags;
+
+        let board = Board::startpos();
+        let mut move_order = MoveOrder::new();
+        let nf3 = Move::new(Square::G1, Square::F3, MoveFlags::QUIET);
+        let e3 = Move::new(Square::E2, Square::E3, MoveFlags::QUIET);
+        let d4 = Move::new(Square::D2, Square::D4, MoveFlags::DOUBLE_PAWN_PUSH);
+
+        // No cutoff yet: reduce more
+        assert_eq!(move_order.lmr_adjustment(&board, nf3, 0, None, 100), 1);
+
+        move_order.store_killer(nf3, 0);
+        assert_eq!(move_order.lmr_adjustment(&board, nf3, 0, None, 100), -1);
+
+        // One ply less per 100 history points, at most two
+        move_order.update_history(e3, 10);
+        assert_eq!(move_order.lmr_adjustment(&board, e3, 0, None, 100), -1);
+        move_order.update_history(d4, 20);
+        assert_eq!(move_order.lmr_adjustment(&board, d4, 0, None, 100), -2);
+    }
+
     #[test]
     fn test_mvv_lva_basic() {
         use crate::r#move::MoveFlags;
@@ -671,8 +1087,8 @@ mod tests {
         let non_killer_score = move_order.score_move(&board, non_killer, 0, None, None);
 
         // Killer should score higher than non-killer
-        assert_eq!(killer_score, 900_000);
-        assert_eq!(non_killer_score, 0);
+        assert_eq!(killer_score, KILLER_SCORE);
+        assert_eq!(non_killer_score / BAND_WIDTH, 3);
         assert!(killer_score > non_killer_score);
     }
 
@@ -806,8 +1222,8 @@ mod tests {
 
         // mv1 should score higher due to better history
         assert!(score1 > score2);
-        assert_eq!(score1, 64);
-        assert_eq!(score2, 4);
+        assert_eq!(move_order.history_score(mv1), 64);
+        assert_eq!(move_order.history_score(mv2), 4);
     }
 
     #[test]
@@ -830,8 +1246,8 @@ mod tests {
         let history_score = move_order.score_move(&board, history_move, 0, None, None);
 
         // Killer should still score higher than history
-        assert_eq!(killer_score, 900_000);
-        assert_eq!(history_score, 90_000);
+        assert_eq!(killer_score, KILLER_SCORE);
+        assert_eq!(move_order.history_score(history_move), 90_000);
         assert!(killer_score > history_score);
     }
 
@@ -874,11 +1290,52 @@ mod tests {
         assert!(history_score > quiet_score, "History should beat quiet");
 
         // Verify specific values
-        assert_eq!(tt_score, 10_000_000);
-        assert!(capture_score >= 2_000_000, "Good captures start at 2M");
-        assert_eq!(killer_score, 900_000);
-        assert_eq!(history_score, 25);
-        assert_eq!(quiet_score, 0);
+        assert_eq!(tt_score, TT_MOVE_SCORE);
+        assert!(
+            capture_score >= GOOD_CAPTURE_BAND,
+            "Good captures start at band 5"
+        );
+        assert_eq!(killer_score, KILLER_SCORE);
+        assert_eq!(move_order.history_score(history_move), 25);
+        assert_eq!(quiet_score / BAND_WIDTH, 3);
+    }
+
+    #[test]
+    fn test_bulk_scores_match_single_scores() {
+        use crate::r#move::MoveFlags;
+
+        // Kiwipete after 1. Qf3-g3: captures, quiets and a previous move
+        let board =
+            parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N3Qp/PPPBBPPP/R3K2R b KQkq - 1 1")
+                .unwrap();
+        let g3 = Square::from_algebraic("g3").unwrap();
+        let prev = Move::new(Square::F3, g3, MoveFlags::QUIET);
+        let moves = board.generate_legal_moves();
+        let quiets: Vec<Move> = moves.iter().copied().filter(|m| !m.is_capture()).collect();
+
+        let mut move_order = MoveOrder::new();
+        move_order.store_killer(quiets[0], 2);
+        move_order.store_countermove(prev, quiets[1]);
+        move_order.update_history(quiets[2], 6);
+        move_order.update_continuation_history(&board, prev, quiets[3], 6);
+        let tt_move = Some(quiets[4]);
+
+        let scores = move_order.score_moves_bulk(&board, &moves, 2, tt_move, Some(prev));
+        let single: Vec<i32> = moves
+            .iter()
+            .map(|&m| move_order.score_move(&board, m, 2, tt_move, Some(prev)))
+            .collect();
+        assert_eq!(scores, single);
+        assert!(scores.contains(&KILLER_SCORE) && scores.contains(&COUNTERMOVE_SCORE));
+
+        // Sorted by score, ties kept in generation order
+        let mut expected: Vec<(Move, i32)> = moves.iter().copied().zip(scores).collect();
+        expected.sort_by_key(|&(_, score)| -score);
+        let mut ordered = moves.clone();
+        move_order.order_moves(&board, &mut ordered, 2, tt_move, Some(prev));
+        let expected: Vec<Move> = expected.into_iter().map(|(m, _)| m).collect();
+        assert_eq!(ordered.as_slice(), expected.as_slice());
+        assert_eq!(ordered[0], quiets[4]);
     }
 
     #[test]
@@ -920,4 +1377,41 @@ mod tests {
             qxr_score
         );
     }
+
+    #[test]
+    fn test_order_captures_tt_move_then_mvv_lva() {
+        use crate::r#move::MoveFlags;
+
+        let fen = "rnbqkbnr/1ppppppp/8/r7/P2Q4/8/1PPPPPPP/RNB1KBNR w KQkq - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let move_order = MoveOrder::new();
+
+        let a5 = Square::from_coords(0, 4);
+        let pxr = Move::new(Square::from_coords(0, 3), a5, MoveFlags::CAPTURE);
+        let qxr = Move::new(Square::D4, a5, MoveFlags::CAPTURE);
+
+        let mut captures = MoveList::new();
+        captures.push(qxr);
+        captures.push(pxr);
+        move_order.order_captures(&board, &mut captures, None);
+        assert_eq!(captures[0], pxr);
+
+        move_order.order_captures(&board, &mut captures, Some(qxr));
+        assert_eq!(captures[0], qxr);
+    }
+
+    #[test]
+    fn test_stalemating_moves_ordered_last() {
+        let board = parse_fen("7k/8/8/8/8/8/8/5QK1 w - - 0 1").unwrap();
+        let mut move_order = MoveOrder::new();
+        let mut moves = board.generate_legal_moves();
+        move_order.order_moves(&board, &mut moves, 0, None, None);
+
+        let stalemating: Vec<usize> = (0..moves.len())
+            .filter(|&i| board.is_stalemate_after(moves[i]))
+            .collect();
+        assert!(!stalemating.is_empty());
+        let first = moves.len() - stalemating.len();
+        assert_eq!(stalemating, (first..moves.len()).collect::<Vec<_>>());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::attacks::{
-    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, ray_between,
+    rook_attacks,
 };
 use crate::bitboard::Bitboard;
 use crate::board::Board;
@@ -44,6 +45,251 @@ pub fn generate_moves(board: &Board) -> MoveList {
     moves
 }
 
+/// Generate pseudo-legal check evasions for the side to move.
+///
+/// In double check only king moves can help. In single check the king may
+/// move, or another piece may capture the checker or block the check ray.
+/// Castling is never generated. The moves may still be illegal (king stepping
+/// onto an attacked square, pinned blockers), so legality checking is still
+/// needed, but far fewer candidates are produced than by [`generate_moves`].
+///
+/// Falls back to [`generate_moves`] when the side to move is not in check.
+pub(crate) fn generate_evasions(board: &Board) -> MoveList {
+    let us = board.side_to_move();
+    let checkers = board.checkers();
+
+    let king_sq = match board.king_square(us) {
+        Some(sq) if checkers.is_not_empty() => sq,
+        _ => return generate_moves(board),
+    };
+
+    let mut moves = MoveList::new();
+    let our_pieces = board.color_bb(us);
+    let their_pieces = board.color_bb(us.opponent());
+    let occupied = board.occupied();
+
+    // 1. King moves (always possible, castling out of check is not)
+    for to_sq in king_attacks(king_sq) & !our_pieces {
+        let flags = if their_pieces.contains(to_sq) {
+            MoveFlags::CAPTURE
+        } else {
+            MoveFlags::QUIET
+        };
+        moves.push(Move::new(king_sq, to_sq, flags));
+    }
+
+    // 2. Double check: only the king can move
+    if checkers.count() > 1 {
+        return moves;
+    }
+
+    // 3. Single check: capture the checker or block the ray
+    let checker_sq = checkers.lsb().unwrap();
+    let targets = checkers | ray_between(king_sq, checker_sq);
+
+    let mut pawn_moves = MoveList::new();
+    generate_pawn_moves(
+        board,
+        &mut pawn_moves,
+        us,
+        our_pieces,
+        their_pieces,
+        board.empty_squares(),
+    );
+    pawn_moves.retain(|m| {
+        // En passant removes a pawn that is not on the target square
+        let captured_sq = if m.is_en_passant() {
+            Square::from_coords(m.to().file(), m.from().rank())
+        } else {
+            m.to()
+        };
+        targets.contains(m.to()) || captured_sq == checker_sq
+    });
+    moves.extend_from(&pawn_moves);
+
+    for piece_type in [
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+    ] {
+        for from_sq in board.piece_bb(piece_type, us) {
+            let attacks = match piece_type {
+                PieceType::Knight => knight_attacks(from_sq),
+                PieceType::Bishop => bishop_attacks(from_sq, occupied),
+                PieceType::Rook => rook_attacks(from_sq, occupied),
+                _ => queen_attacks(from_sq, occupied),
+            };
+
+            for to_sq in attacks & targets & !our_pieces {
+                let flags = if to_sq == checker_sq {
+                    MoveFlags::CAPTURE
+                } else {
+                    MoveFlags::QUIET
+                };
+                moves.push(Move::new(from_sq, to_sq, flags));
+            }
+        }
+    }
+
+    moves
+}
+
+// =============================================================================
+// QUEEN PROMOTIONS
+// =============================================================================
+
+/// Generate pseudo-legal queen promotions, quiet and capturing, and no other
+/// moves.
+///
+/// All pawns one step from promoting are shifted at once: forward onto empty
+/// squares for pushes, diagonally onto enemy pieces for captures.
+/// Underpromotions are left out. Quiescence search uses this so that a pawn
+/// about to queen is not missed just because its push captures nothing.
+pub(crate) fn generate_queen_promotions(board: &Board) -> MoveList {
+    let mut moves = MoveList::new();
+
+    let us = board.side_to_move();
+    let their_pieces = board.color_bb(us.opponent());
+    let empty = board.empty_squares();
+
+    let (seventh_rank, forward) = match us {
+        Color::White => (Bitboard::RANK_7, 8),
+        Color::Black => (Bitboard::RANK_2, -8),
+    };
+    let pawns = board.piece_bb(PieceType::Pawn, us) & seventh_rank;
+    if pawns.is_empty() {
+        return moves;
+    }
+
+    let (pushes, east, west) = match us {
+        Color::White => (
+            pawns.shift_north(),
+            pawns.shift_north_east(),
+            pawns.shift_north_west(),
+        ),
+        Color::Black => (
+            pawns.shift_south(),
+            pawns.shift_south_east(),
+            pawns.shift_south_west(),
+        ),
+    };
+    let from = |to: Square, step: i8| Square::new((to.index() as i8 - step) as u8);
+
+    for to_sq in pushes & empty {
+        moves.push(Move::new(
+            from(to_sq, forward),
+            to_sq,
+            MoveFlags::QUEEN_PROMOTION,
+        ));
+    }
+    for (targets, step) in [(east, forward + 1), (west, forward - 1)] {
+        for to_sq in targets & their_pieces {
+            moves.push(Move::new(
+                from(to_sq, step),
+                to_sq,
+                MoveFlags::QUEEN_PROMOTION_CAPTURE,
+            ));
+        }
+    }
+
+    moves
+}
+
+// =============================================================================
+// CHECKING MOVES
+// =============================================================================
+
+/// Generate legal moves that give check.
+///
+/// Direct checks (including promotions) and discovered checks by a slider
+/// behind the moving piece are found from attack sets, without playing the
+/// moves. Castling checks and checks discovered by the pawn removed en
+/// passant are not generated, so the list may miss a few checks, but every
+/// move in it gives check.
+pub(crate) fn generate_checking_moves(board: &Board) -> MoveList {
+    let mut checks = MoveList::new();
+    let Some(king_sq) = board.king_square(board.side_to_move().opponent()) else {
+        return checks;
+    };
+    let discoverers = board.discovered_check_candidates();
+
+    let candidates = if board.is_in_check() {
+        generate_evasions(board)
+    } else {
+        generate_moves(board)
+    };
+    for m in candidates {
+        if gives_check_with(board, m, king_sq, discoverers) && board.is_legal(m) {
+            checks.push(m);
+        }
+    }
+
+    checks
+}
+
+/// Whether a move gives check, judged from attack sets without playing it.
+///
+/// Never reports a check that the move does not give. Castling and checks
+/// discovered by the pawn captured en passant are reported as no check;
+/// use [`Board::gives_check`] when those matter.
+///
+/// # Example
+/// ```
+/// use engine::io::parse_fen;
+/// use engine::movegen::gives_check_fast;
+/// use engine::r#move::{Move, MoveFlags};
+/// use engine::square::Square;
+///
+/// // The bishop on d4 moves away and uncovers the rook on d1
+/// let board = parse_fen("3k4/8/8/8/3B4/8/8/3R2K1 w - - 0 1").unwrap();
+/// let discovered = Move::new(Square::D4, Square::from_coords(4, 4), MoveFlags::QUIET);
+/// assert!(gives_check_fast(&board, discovered));
+/// ```
+pub fn gives_check_fast(board: &Board, m: Move) -> bool {
+    match board.king_square(board.side_to_move().opponent()) {
+        Some(king_sq) => gives_check_with(board, m, king_sq, board.discovered_check_candidates()),
+        None => false,
+    }
+}
+
+fn gives_check_with(board: &Board, m: Move, king_sq: Square, discoverers: Bitboard) -> bool {
+    if m.is_castling() {
+        return false;
+    }
+    let Some(piece) = board.piece_at(m.from()) else {
+        return false;
+    };
+
+    let us = board.side_to_move();
+    let from = Bitboard::from_square(m.from());
+    let occupied = (board.occupied() & !from) | Bitboard::from_square(m.to());
+
+    // Direct check from the destination square
+    let piece_type = m.promotion_piece().unwrap_or(piece.piece_type);
+    let attacks = match piece_type {
+        PieceType::Pawn => pawn_attacks(m.to(), us),
+        PieceType::Knight => knight_attacks(m.to()),
+        PieceType::Bishop => bishop_attacks(m.to(), occupied),
+        PieceType::Rook => rook_attacks(m.to(), occupied),
+        PieceType::Queen => queen_attacks(m.to(), occupied),
+        PieceType::King => Bitboard::EMPTY,
+    };
+    if attacks.contains(king_sq) {
+        return true;
+    }
+
+    // Discovered check by a slider behind the moving piece
+    if !discoverers.contains(m.from()) {
+        return false;
+    }
+    let queens = board.piece_bb(PieceType::Queen, us);
+    let rooks = (board.piece_bb(PieceType::Rook, us) | queens) & !from;
+    let bishops = (board.piece_bb(PieceType::Bishop, us) | queens) & !from;
+    (rook_attacks(king_sq, occupied) & rooks).is_not_empty()
+        || (bishop_attacks(king_sq, occupied) & bishops).is_not_empty()
+}
+
 // =============================================================================
 // PAWN MOVES
 // =============================================================================
@@ -275,6 +521,9 @@ fn generate_queen_moves(
 // =============================================================================
 
 /// Generate king moves (including castling).
+///
+/// Destinations controlled by the opponent are skipped up front, so the
+/// only king moves left for the legality check are castling moves.
 fn generate_king_moves(
     board: &Board,
     moves: &mut MoveList,
@@ -283,9 +532,16 @@ fn generate_king_moves(
     occupied: Bitboard,
 ) {
     let kings = board.piece_bb(PieceType::King, us);
+    if kings.is_empty() {
+        return;
+    }
+
+    // Squares the king can never step onto. Sliders see through our king so
+    // stepping back along a checking ray is excluded as well.
+    let danger = board.controlled_squares_with(us.opponent(), occupied & !kings);
 
     for from_sq in kings {
-        let attacks = king_attacks(from_sq);
+        let attacks = king_attacks(from_sq) & !danger;
 
         // Quiet moves
         let quiet_targets = attacks & !occupied;
@@ -300,75 +556,35 @@ fn generate_king_moves(
         }
 
         // Castling
-        generate_castling_moves(board, moves, us, from_sq, occupied);
+        generate_castling_moves(board, moves, us, from_sq);
     }
 }
 
-/// Generate castling moves for the given king position.
+/// Generate castling moves for the given king position, encoded as the king
+/// taking its own rook.
 ///
-/// This checks if castling is pseudo-legal (has rights, squares are empty).
-/// Legality checking (not in check, not moving through check) is done in Session 13.
-fn generate_castling_moves(
-    board: &Board,
-    moves: &mut MoveList,
-    us: Color,
-    king_sq: Square,
-    occupied: Bitboard,
-) {
-    let castling = board.castling();
-
+/// This checks if castling is pseudo-legal (has rights, squares are empty),
+/// see [`Board::can_pseudo_castle_kingside`]. Legality checking (not in check,
+/// not moving through check) is left to [`Board::is_legal`].
+fn generate_castling_moves(board: &Board, moves: &mut MoveList, us: Color, king_sq: Square) {
     // Kingside castling
-    if (us == Color::White && castling.white_kingside())
-        || (us == Color::Black && castling.black_kingside())
-    {
-        // Check if squares between king and rook are empty
-        let squares_to_check = if us == Color::White {
-            // f1, g1
-            vec![Square::F1, Square::G1]
+    if board.can_pseudo_castle_kingside(us) {
+        let to_sq = if us == Color::White {
+            Square::H1
         } else {
-            // f8, g8
-            vec![Square::from_coords(5, 7), Square::from_coords(6, 7)]
+            Square::H8
         };
-
-        let all_empty = squares_to_check.iter().all(|&sq| !occupied.contains(sq));
-
-        if all_empty {
-            let to_sq = if us == Color::White {
-                Square::G1
-            } else {
-                Square::from_coords(6, 7)
-            };
-            moves.push(Move::new(king_sq, to_sq, MoveFlags::KING_CASTLE));
-        }
+        moves.push(Move::new(king_sq, to_sq, MoveFlags::KING_CASTLE));
     }
 
     // Queenside castling
-    if (us == Color::White && castling.white_queenside())
-        || (us == Color::Black && castling.black_queenside())
-    {
-        // Check if squares between king and rook are empty
-        let squares_to_check = if us == Color::White {
-            // b1, c1, d1
-            vec![Square::B1, Square::C1, Square::D1]
+    if board.can_pseudo_castle_queenside(us) {
+        let to_sq = if us == Color::White {
+            Square::A1
         } else {
-            // b8, c8, d8
-            vec![
-                Square::from_coords(1, 7),
-                Square::from_coords(2, 7),
-                Square::from_coords(3, 7),
-            ]
+            Square::A8
         };
-
-        let all_empty = squares_to_check.iter().all(|&sq| !occupied.contains(sq));
-
-        if all_empty {
-            let to_sq = if us == Color::White {
-                Square::C1
-            } else {
-                Square::from_coords(2, 7)
-            };
-            moves.push(Move::new(king_sq, to_sq, MoveFlags::QUEEN_CASTLE));
-        }
+        moves.push(Move::new(king_sq, to_sq, MoveFlags::QUEEN_CASTLE));
     }
 }
 
@@ -379,6 +595,7 @@ fn generate_castling_moves(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::parse_fen;
     use crate::piece::Piece;
 
     #[test]
@@ -701,7 +918,8 @@ mod tests {
 
         let castle_move = moves.iter().find(|m| m.is_castling()).unwrap();
         assert_eq!(castle_move.from(), Square::E1);
-        assert_eq!(castle_move.to(), Square::G1);
+        assert_eq!(castle_move.to(), Square::H1);
+        assert_eq!(castle_move.destination(), Square::G1);
         assert!(castle_move.is_kingside_castle());
     }
 
@@ -723,7 +941,8 @@ mod tests {
 
         let castle_move = moves.iter().find(|m| m.is_castling()).unwrap();
         assert_eq!(castle_move.from(), Square::E1);
-        assert_eq!(castle_move.to(), Square::C1);
+        assert_eq!(castle_move.to(), Square::A1);
+        assert_eq!(castle_move.destination(), Square::C1);
         assert!(castle_move.is_queenside_castle());
     }
 
@@ -759,4 +978,179 @@ mod tests {
         let castling_moves = moves.iter().filter(|m| m.is_castling()).count();
         assert_eq!(castling_moves, 0);
     }
+
+    /// Legal moves computed the old way: all pseudo-legal moves, filtered.
+    fn legal_by_filtering(board: &Board) -> Vec<Move> {
+        let mut moves: Vec<Move> = generate_moves(board)
+            .into_iter()
+            .filter(|&m| board.is_legal(m))
+            .collect();
+        moves.sort_by_key(|m| m.to_uci());
+        moves
+    }
+
+    fn legal_evasions(board: &Board) -> Vec<Move> {
+        let mut moves: Vec<Move> = generate_evasions(board)
+            .into_iter()
+            .filter(|&m| board.is_legal(m))
+            .collect();
+        moves.sort_by_key(|m| m.to_uci());
+        moves
+    }
+
+    #[test]
+    fn test_evasions_single_check() {
+        let board = parse_fen("4r1k1/8/8/1B6/8/8/8/4K3 w - - 0 1").unwrap();
+        let evasions = generate_evasions(&board);
+
+        assert_eq!(evasions.len(), 7);
+        assert_eq!(legal_evasions(&board), legal_by_filtering(&board));
+    }
+
+    #[test]
+    fn test_evasions_double_check_king_only() {
+        // Rook on e8 and knight on f3 both check the king on e1
+        let board = parse_fen("4r1k1/8/8/1B6/8/5n2/3R4/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.checkers().count(), 2);
+
+        let evasions = generate_evasions(&board);
+        assert!(evasions.iter().all(|m| m.from() == Square::E1));
+        assert_eq!(legal_evasions(&board), legal_by_filtering(&board));
+    }
+
+    #[test]
+    fn test_evasions_en_passant_captures_checker() {
+        // Black just played e7-e5+, dxe6 e.p. removes the checking pawn
+        let board = parse_fen("8/8/8/3Pp3/5K2/8/8/7k w - e6 0 2").unwrap();
+        let evasions = generate_evasions(&board);
+
+        assert!(evasions.iter().any(|m| m.is_en_passant()));
+        assert_eq!(legal_evasions(&board), legal_by_filtering(&board));
+    }
+
+    #[test]
+    fn test_evasions_not_in_check_falls_back() {
+        let board = Board::startpos();
+        assert_eq!(generate_evasions(&board).len(), generate_moves(&board).len());
+    }
+
+    #[test]
+    fn test_evasions_match_filtering_in_tree() {
+        // Walk the tree of check-heavy positions and compare legal move sets
+        // wherever the side to move is in check
+        fn walk(board: &Board, depth: u32, checked: &mut u32) {
+            if board.is_in_check() {
+                assert_eq!(
+                    legal_evasions(board),
+                    legal_by_filtering(board),
+                    "Evasion mismatch in {:?}",
+                    board
+                );
+                *checked += 1;
+            }
+            if depth == 0 {
+                return;
+            }
+            for m in board.generate_legal_moves() {
+                let mut child = board.clone();
+                child.make_move(m);
+                walk(&child, depth - 1, checked);
+            }
+        }
+
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ];
+
+        let mut checked = 0;
+        for fen in fens {
+            walk(&parse_fen(fen).unwrap(), 3, &mut checked);
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_checking_moves_direct_discovered_and_promotion() {
+        fn checks(fen: &str) -> Vec<String> {
+            let board = parse_fen(fen).unwrap();
+            let mut checks: Vec<String> = generate_checking_moves(&board)
+                .iter()
+                .map(|m| m.to_uci())
+                .collect();
+            checks.sort();
+            checks
+        }
+
+        // Knight checks from f7 and g6; any bishop move uncovers the rook
+        assert_eq!(
+            checks("RB5k/8/8/4N3/8/8/1P6/4K3 w - - 0 1"),
+            vec!["b8a7", "b8c7", "b8d6", "e5f7", "e5g6"]
+        );
+
+        // Promotions check as the promoted piece
+        assert_eq!(
+            checks("7k/3P4/8/8/8/8/8/4K3 w - - 0 1"),
+            vec!["d7d8q", "d7d8r"]
+        );
+
+        // The pinned knight's checks (Nf6, Nh6) are illegal
+        assert_eq!(checks("6k1/4r3/8/8/4N3/8/8/4K3 w - - 0 1"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_checking_moves_match_gives_check() {
+        fn walk(board: &Board, depth: u32) {
+            let checks = generate_checking_moves(board);
+            for m in board.generate_legal_moves() {
+                let listed = checks.iter().any(|c| *c == m);
+                let gives_check = board.gives_check(m);
+                if listed {
+                    assert!(gives_check, "{} does not check in {:?}", m, board);
+                } else if !m.is_castling() && !m.is_en_passant() {
+                    assert!(!gives_check, "{} checks in {:?}", m, board);
+                }
+            }
+            if depth == 0 {
+                return;
+            }
+            for m in board.generate_legal_moves() {
+                let mut child = board.clone();
+                child.make_move(m);
+                walk(&child, depth - 1);
+            }
+        }
+
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ];
+        for fen in fens {
+            walk(&parse_fen(fen).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn test_generate_queen_promotions() {
+        // Black: g2 pushes, takes on f1 or h1; a2 is blocked but takes on b1
+        let board = parse_fen("4k3/8/8/8/8/8/p5p1/KN3R1R b - - 0 1").unwrap();
+        let mut promotions: Vec<String> = generate_queen_promotions(&board)
+            .iter()
+            .map(|m| m.to_uci())
+            .collect();
+        promotions.sort();
+        assert_eq!(promotions, vec!["a2b1q", "g2f1q", "g2g1q", "g2h1q"]);
+
+        for m in generate_queen_promotions(&board) {
+            assert_eq!(m.promotion_piece(), Some(PieceType::Queen));
+            assert_eq!(m.is_capture(), board.piece_at(m.to()).is_some());
+            assert!(generate_moves(&board).iter().any(|&g| g == m));
+        }
+
+        // No pawn on the seventh rank, no promotions
+        assert!(generate_queen_promotions(&Board::startpos()).is_empty());
+    }
 }
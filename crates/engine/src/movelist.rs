@@ -2,7 +2,7 @@ use crate::r#move::Move;
 
 /// Maximum number of moves in any chess position.
 /// The theoretical maximum is 218, but we use 256 for safety and alignment.
-const MAX_MOVES: usize = 256;
+pub const MAX_MOVES: usize = 256;
 
 /// A stack-allocated list of moves.
 ///
@@ -103,6 +103,72 @@ impl MoveList {
         self.moves.swap(a, b);
     }
 
+    /// Removes and returns the move at `index`, replacing it with the last
+    /// move. O(1), but does not preserve the order.
+    ///
+    /// # Panics
+    /// Panics if index >= len.
+    #[inline(always)]
+    pub fn swap_remove(&mut self, index: usize) -> Move {
+        assert!(index < self.len, "MoveList swap_remove out of bounds");
+        let m = self.moves[index];
+        self.len -= 1;
+        self.moves[index] = self.moves[self.len];
+        m
+    }
+
+    /// Keeps only the moves for which `keep` returns true, in their
+    /// original order. Works in place, without allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use engine::board::Board;
+    ///
+    /// let mut moves = Board::startpos().generate_legal_moves();
+    /// moves.retain(|m| m.is_double_pawn_push());
+    /// assert_eq!(moves.len(), 8);
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Move) -> bool,
+    {
+        let mut kept = 0;
+        for i in 0..self.len {
+            let m = self.moves[i];
+            if keep(&m) {
+                self.moves[kept] = m;
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+
+    /// Shortens the list to its first `len` moves. Does nothing if the list
+    /// is already that short.
+    #[inline(always)]
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Returns true if the list contains `m`.
+    #[inline(always)]
+    pub fn contains(&self, m: &Move) -> bool {
+        self.as_slice().contains(m)
+    }
+
+    /// Appends all moves of `other`.
+    ///
+    /// # Panics
+    /// Panics if the moves don't fit (256 moves).
+    #[inline(always)]
+    pub fn extend_from(&mut self, other: &MoveList) {
+        let end = self.len + other.len;
+        debug_assert!(end <= MAX_MOVES, "MoveList overflow");
+        self.moves[self.len..end].copy_from_slice(other.as_slice());
+        self.len = end;
+    }
+
     /// Returns a slice of all moves.
     #[inline(always)]
     pub fn as_slice(&self) -> &[Move] {
@@ -169,6 +235,26 @@ impl IntoIterator for MoveList {
     }
 }
 
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut MoveList {
+    type Item = &'a mut Move;
+    type IntoIter = std::slice::IterMut<'a, Move>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
 /// Iterator that consumes a MoveList.
 pub struct MoveListIntoIter {
     list: MoveList,
@@ -287,6 +373,138 @@ mod tests {
         assert_eq!(list[1], m1);
     }
 
+    /// A list of `n` distinct (non-null) moves.
+    fn filled(n: usize) -> MoveList {
+        let mut list = MoveList::new();
+        for i in 0..n {
+            list.push(Move::new(
+                Square::new((i % 64) as u8),
+                Square::new((8 + i / 64) as u8),
+                MoveFlags::QUIET,
+            ));
+        }
+        list
+    }
+
+    #[test]
+    fn movelist_swap_remove() {
+        let mut list = filled(4);
+        let moves: Vec<_> = list.iter().copied().collect();
+
+        assert_eq!(list.swap_remove(1), moves[1]);
+        assert_eq!(list.as_slice(), [moves[0], moves[3], moves[2]]);
+        // The last move just shrinks the list
+        assert_eq!(list.swap_remove(2), moves[2]);
+        assert_eq!(list.as_slice(), [moves[0], moves[3]]);
+
+        let mut full = filled(MAX_MOVES);
+        let last = full[MAX_MOVES - 1];
+        full.swap_remove(0);
+        assert_eq!(full.len(), MAX_MOVES - 1);
+        assert_eq!(full[0], last);
+
+        let mut one = filled(1);
+        one.swap_remove(0);
+        assert!(one.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn movelist_swap_remove_empty_panics() {
+        MoveList::new().swap_remove(0);
+    }
+
+    #[test]
+    fn movelist_retain() {
+        let mut list = filled(10);
+        let moves: Vec<_> = list.iter().copied().collect();
+        list.retain(|m| m.from().index() % 3 == 0);
+        assert_eq!(list.as_slice(), [moves[0], moves[3], moves[6], moves[9]]);
+
+        list.retain(|_| true);
+        assert_eq!(list.len(), 4);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+
+        let mut empty = MoveList::new();
+        empty.retain(|_| panic!("called on an empty list"));
+        assert!(empty.is_empty());
+
+        let mut full = filled(MAX_MOVES);
+        full.retain(|m| m.to().index() == 11);
+        assert_eq!(full.len(), 64);
+        assert!(full.iter().all(|m| m.to().index() == 11));
+        full.retain(|_| true);
+        assert_eq!(full.len(), 64);
+    }
+
+    #[test]
+    fn movelist_truncate() {
+        let mut list = filled(5);
+        list.truncate(8);
+        assert_eq!(list.len(), 5);
+        list.truncate(2);
+        assert_eq!(list.as_slice(), &filled(2).as_slice()[..]);
+        list.truncate(0);
+        assert!(list.is_empty());
+
+        let mut full = filled(MAX_MOVES);
+        full.truncate(MAX_MOVES);
+        assert_eq!(full.len(), MAX_MOVES);
+    }
+
+    #[test]
+    fn movelist_contains() {
+        let list = filled(3);
+        assert!(list.contains(&list[2]));
+        assert!(!list.contains(&filled(4)[3]));
+        assert!(!MoveList::new().contains(&Move::null()));
+
+        // Moves past the end don't count, even if they were there once
+        let mut list = filled(3);
+        let last = list[2];
+        list.truncate(2);
+        assert!(!list.contains(&last));
+
+        let full = filled(MAX_MOVES);
+        assert!(full.contains(&full[MAX_MOVES - 1]));
+    }
+
+    #[test]
+    fn movelist_extend_from() {
+        let full = filled(MAX_MOVES);
+        let mut list = filled(0);
+        list.extend_from(&MoveList::new());
+        assert!(list.is_empty());
+
+        let mut head = filled(100);
+        let mut tail = MoveList::new();
+        for &m in &full.as_slice()[100..] {
+            tail.push(m);
+        }
+        head.extend_from(&tail);
+        assert_eq!(head.as_slice(), full.as_slice());
+
+        list.extend_from(&full);
+        assert_eq!(list.len(), MAX_MOVES);
+    }
+
+    #[test]
+    fn movelist_ref_into_iter() {
+        let mut list = filled(3);
+        let mut count = 0;
+        for m in &list {
+            assert!(!m.is_null());
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        for m in &mut list {
+            *m = Move::new(m.from(), Square::H8, MoveFlags::QUIET);
+        }
+        assert!(list.iter().all(|m| m.to() == Square::H8));
+    }
+
     #[test]
     fn movelist_sort() {
         let mut list = MoveList::new();
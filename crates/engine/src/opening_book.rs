@@ -37,7 +37,7 @@ impl OpeningBook {
             // Find the move in legal moves
             for m in legal_moves.iter() {
                 let from_str = m.from().to_string();
-                let to_str = m.to().to_string();
+                let to_str = m.destination().to_string();
                 let move_uci = format!("{}{}", from_str, to_str);
 
                 if move_uci == *move_str {
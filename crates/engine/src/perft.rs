@@ -3,6 +3,174 @@
 /// Perft recursively counts all leaf nodes at a given depth. It's the gold standard
 /// for validating move generation correctness by comparing against canonical values.
 use crate::board::Board;
+use crate::io::{parse_fen, STARTPOS_FEN};
+use crate::r#move::Move;
+use std::fmt;
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Chessprogramming Wiki perft position 2, "Kiwipete".
+pub const KIWIPETE_FEN: &str =
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+/// Chessprogramming Wiki perft position 3, a rook endgame.
+pub const POSITION3_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+/// Chessprogramming Wiki perft position 4, with promotions and castling.
+pub const POSITION4_FEN: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+/// Chessprogramming Wiki perft position 5.
+pub const POSITION5_FEN: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+/// Chessprogramming Wiki perft position 6, a symmetrical middlegame.
+pub const POSITION6_FEN: &str =
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+/// Known node counts (FEN, depth, nodes) for the positions on the
+/// Chessprogramming Wiki "Perft Results" page.
+pub const STANDARD_POSITIONS: &[(&str, u32, u64)] = &[
+    (STARTPOS_FEN, 1, 20),
+    (STARTPOS_FEN, 2, 400),
+    (STARTPOS_FEN, 3, 8_902),
+    (STARTPOS_FEN, 4, 197_281),
+    (STARTPOS_FEN, 5, 4_865_609),
+    (STARTPOS_FEN, 6, 119_060_324),
+    (KIWIPETE_FEN, 1, 48),
+    (KIWIPETE_FEN, 2, 2_039),
+    (KIWIPETE_FEN, 3, 97_862),
+    (KIWIPETE_FEN, 4, 4_085_603),
+    (KIWIPETE_FEN, 5, 193_690_690),
+    (POSITION3_FEN, 1, 14),
+    (POSITION3_FEN, 2, 191),
+    (POSITION3_FEN, 3, 2_812),
+    (POSITION3_FEN, 4, 43_238),
+    (POSITION3_FEN, 5, 674_624),
+    (POSITION3_FEN, 6, 11_030_083),
+    (POSITION4_FEN, 1, 6),
+    (POSITION4_FEN, 2, 264),
+    (POSITION4_FEN, 3, 9_467),
+    (POSITION4_FEN, 4, 422_333),
+    (POSITION4_FEN, 5, 15_833_292),
+    (POSITION5_FEN, 1, 44),
+    (POSITION5_FEN, 2, 1_486),
+    (POSITION5_FEN, 3, 62_379),
+    (POSITION5_FEN, 4, 2_103_487),
+    (POSITION5_FEN, 5, 89_941_194),
+    (POSITION6_FEN, 1, 46),
+    (POSITION6_FEN, 2, 2_079),
+    (POSITION6_FEN, 3, 89_890),
+    (POSITION6_FEN, 4, 3_894_594),
+    (POSITION6_FEN, 5, 164_075_551),
+];
+
+/// Leaf counts by move type at one depth, as (FEN, depth, result), from the
+/// Chessprogramming Wiki "Perft Results" page. Positions 5 and 6 only have
+/// node counts published, see [`STANDARD_POSITIONS`].
+#[rustfmt::skip]
+pub const DETAILED_POSITIONS: &[(&str, u32, PerftResult)] = &[
+    (STARTPOS_FEN, 1, PerftResult::counts(20, 0, 0, 0, 0, 0, 0)),
+    (STARTPOS_FEN, 2, PerftResult::counts(400, 0, 0, 0, 0, 0, 0)),
+    (STARTPOS_FEN, 3, PerftResult::counts(8_902, 34, 0, 0, 0, 12, 0)),
+    (STARTPOS_FEN, 4, PerftResult::counts(197_281, 1_576, 0, 0, 0, 469, 8)),
+    (STARTPOS_FEN, 5, PerftResult::counts(4_865_609, 82_719, 258, 0, 0, 27_351, 347)),
+    (KIWIPETE_FEN, 1, PerftResult::counts(48, 8, 0, 2, 0, 0, 0)),
+    (KIWIPETE_FEN, 2, PerftResult::counts(2_039, 351, 1, 91, 0, 3, 0)),
+    (KIWIPETE_FEN, 3, PerftResult::counts(97_862, 17_102, 45, 3_162, 0, 993, 1)),
+    (KIWIPETE_FEN, 4, PerftResult::counts(4_085_603, 757_163, 1_929, 128_013, 15_172, 25_523, 43)),
+    (POSITION3_FEN, 1, PerftResult::counts(14, 1, 0, 0, 0, 2, 0)),
+    (POSITION3_FEN, 2, PerftResult::counts(191, 14, 0, 0, 0, 10, 0)),
+    (POSITION3_FEN, 3, PerftResult::counts(2_812, 209, 2, 0, 0, 267, 0)),
+    (POSITION3_FEN, 4, PerftResult::counts(43_238, 3_348, 123, 0, 0, 1_680, 17)),
+    (POSITION3_FEN, 5, PerftResult::counts(674_624, 52_051, 1_165, 0, 0, 52_950, 0)),
+    (POSITION4_FEN, 1, PerftResult::counts(6, 0, 0, 0, 0, 0, 0)),
+    (POSITION4_FEN, 2, PerftResult::counts(264, 87, 0, 6, 48, 10, 0)),
+    (POSITION4_FEN, 3, PerftResult::counts(9_467, 1_021, 4, 0, 120, 38, 22)),
+    (POSITION4_FEN, 4, PerftResult::counts(422_333, 131_393, 0, 7_795, 60_032, 15_492, 5)),
+];
+
+/// Leaf node counts from [`perft_detailed`], broken down by the kind of the
+/// last move played.
+///
+/// Captures include en passant and capturing promotions; checks include
+/// checkmates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerftResult {
+    pub nodes: u64,
+    pub captures: u64,
+    pub en_passants: u64,
+    pub castles: u64,
+    pub promotions: u64,
+    pub checks: u64,
+    pub checkmates: u64,
+}
+
+impl PerftResult {
+    /// Build a result from its counts, in field order.
+    pub const fn counts(
+        nodes: u64,
+        captures: u64,
+        en_passants: u64,
+        castles: u64,
+        promotions: u64,
+        checks: u64,
+        checkmates: u64,
+    ) -> Self {
+        Self {
+            nodes,
+            captures,
+            en_passants,
+            castles,
+            promotions,
+            checks,
+            checkmates,
+        }
+    }
+
+    /// Counts for the single leaf reached by playing `m` from `board`.
+    fn leaf(board: &Board, m: Move) -> Self {
+        let mut after = board.clone();
+        after.make_move(m);
+        let check = after.is_in_check();
+        let mate = check && after.generate_legal_moves().is_empty();
+
+        Self {
+            nodes: 1,
+            captures: m.is_capture() as u64,
+            en_passants: m.is_en_passant() as u64,
+            castles: m.is_castling() as u64,
+            promotions: m.is_promotion() as u64,
+            checks: check as u64,
+            checkmates: mate as u64,
+        }
+    }
+}
+
+impl AddAssign for PerftResult {
+    fn add_assign(&mut self, other: Self) {
+        self.nodes += other.nodes;
+        self.captures += other.captures;
+        self.en_passants += other.en_passants;
+        self.castles += other.castles;
+        self.promotions += other.promotions;
+        self.checks += other.checks;
+        self.checkmates += other.checkmates;
+    }
+}
+
+/// A perft count that differs from the known value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftMismatch {
+    pub fen: &'static str,
+    pub depth: u32,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl fmt::Display for PerftMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "perft({}) = {}, expected {} for {}",
+            self.depth, self.actual, self.expected, self.fen
+        )
+    }
+}
 
 /// Count all leaf nodes at the given depth.
 ///
@@ -46,6 +214,97 @@ pub fn perft(board: &Board, depth: u32) -> u64 {
     nodes
 }
 
+/// [`perft`] split at the root across `threads` worker threads.
+///
+/// Each legal root move is a task, handed out to whichever worker is free
+/// next, and the subtree counts are summed, so the result is exactly that of
+/// [`perft`]. Runs serially for a single thread or at depth 1 or less.
+///
+/// # Example
+/// ```
+/// use engine::board::Board;
+/// use engine::perft::{perft, perft_parallel};
+///
+/// let board = Board::startpos();
+/// assert_eq!(perft_parallel(&board, 4, 4), perft(&board, 4));
+/// ```
+pub fn perft_parallel(board: &Board, depth: u32, threads: usize) -> u64 {
+    if depth <= 1 || threads <= 1 {
+        return perft(board, depth);
+    }
+
+    let moves = board.generate_legal_moves();
+    let moves = moves.as_slice();
+    let next = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(moves.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut nodes = 0u64;
+                    while let Some(&m) = moves.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let mut new_board = board.clone();
+                        new_board.make_move(m);
+                        nodes += perft(&new_board, depth - 1);
+                    }
+                    nodes
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("perft thread panicked"))
+            .sum()
+    })
+}
+
+/// Perft that also counts leaf nodes by move type: captures, en passant,
+/// castling, promotions, checks and checkmates.
+///
+/// Much slower than [`perft`], since every leaf move is played to look for
+/// check and mate. Depth 0 counts this position as a single node with no
+/// move statistics.
+///
+/// # Example
+/// ```
+/// use engine::board::Board;
+/// use engine::perft::perft_detailed;
+///
+/// let result = perft_detailed(&Board::startpos(), 3);
+/// assert_eq!(result.nodes, 8_902);
+/// assert_eq!(result.captures, 34);
+/// assert_eq!(result.checks, 12);
+/// ```
+pub fn perft_detailed(board: &Board, depth: u32) -> PerftResult {
+    if depth == 0 {
+        return PerftResult {
+            nodes: 1,
+            ..PerftResult::default()
+        };
+    }
+
+    let mut result = PerftResult::default();
+    for m in board.generate_legal_moves().iter() {
+        if depth == 1 {
+            result += PerftResult::leaf(board, *m);
+        } else {
+            let mut new_board = board.clone();
+            new_board.make_move(*m);
+            result += perft_detailed(&new_board, depth - 1);
+        }
+    }
+
+    result
+}
+
+impl Board {
+    /// [`perft_detailed`] from this position.
+    pub fn perft_detailed(&self, depth: u32) -> PerftResult {
+        perft_detailed(self, depth)
+    }
+}
+
 /// Perft with per-move breakdown at the root.
 ///
 /// This is useful for debugging - it shows which moves lead to which counts,
@@ -80,6 +339,39 @@ pub fn perft_divide(board: &Board, depth: u32) -> Vec<(String, u64)> {
     results
 }
 
+/// Run perft and check the result against a known node count.
+///
+/// # Example
+/// ```
+/// use engine::board::Board;
+/// use engine::perft::perft_verify;
+///
+/// assert!(perft_verify(&Board::startpos(), 3, 8_902));
+/// assert!(!perft_verify(&Board::startpos(), 3, 8_903));
+/// ```
+pub fn perft_verify(board: &Board, depth: u32, expected_nodes: u64) -> bool {
+    perft(board, depth) == expected_nodes
+}
+
+/// Check every entry of [`STANDARD_POSITIONS`] up to `max_depth` and return
+/// the ones whose count is wrong. Any mismatch is a move generation bug.
+pub fn verify_all_standard_positions(max_depth: u32) -> Vec<PerftMismatch> {
+    STANDARD_POSITIONS
+        .iter()
+        .filter(|(_, depth, _)| *depth <= max_depth)
+        .filter_map(|&(fen, depth, expected)| {
+            let board = parse_fen(fen).expect("standard perft FEN is valid");
+            let actual = perft(&board, depth);
+            (actual != expected).then_some(PerftMismatch {
+                fen,
+                depth,
+                expected,
+                actual,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +579,106 @@ mod tests {
         assert_eq!(perft(&board, 4), 3_894_594);
     }
 
+    #[test]
+    fn test_standard_positions_are_valid() {
+        for (fen, depth, _) in STANDARD_POSITIONS {
+            assert!(parse_fen(fen).is_ok(), "{}", fen);
+            assert!(*depth >= 1);
+        }
+        assert_eq!(
+            parse_fen(STARTPOS_FEN).unwrap().hash(),
+            Board::startpos().hash()
+        );
+    }
+
+    #[test]
+    fn test_perft_parallel_matches_serial() {
+        for fen in [
+            STARTPOS_FEN,
+            KIWIPETE_FEN,
+            POSITION3_FEN,
+            POSITION4_FEN,
+            POSITION5_FEN,
+            POSITION6_FEN,
+        ] {
+            let board = parse_fen(fen).unwrap();
+            for depth in 0..=3 {
+                let serial = perft(&board, depth);
+                for threads in [1, 3, 64] {
+                    assert_eq!(perft_parallel(&board, depth, threads), serial, "{}", fen);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[ignore] // Very slow test - run with --release --ignored
+    fn test_perft_parallel_startpos_depth_7() {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        assert_eq!(
+            perft_parallel(&Board::startpos(), 7, threads),
+            3_195_901_860
+        );
+    }
+
+    #[test]
+    fn test_verify_all_standard_positions_shallow() {
+        assert_eq!(verify_all_standard_positions(2), Vec::new());
+    }
+
+    #[test]
+    fn test_perft_mismatch_display() {
+        let mismatch = PerftMismatch {
+            fen: STARTPOS_FEN,
+            depth: 2,
+            expected: 400,
+            actual: 401,
+        };
+        assert_eq!(
+            mismatch.to_string(),
+            format!("perft(2) = 401, expected 400 for {}", STARTPOS_FEN)
+        );
+    }
+
+    #[test]
+    fn test_perft_detailed_matches_published_counts() {
+        for &(fen, depth, expected) in DETAILED_POSITIONS.iter().filter(|(_, d, _)| *d <= 3) {
+            let board = parse_fen(fen).unwrap();
+            assert_eq!(
+                board.perft_detailed(depth),
+                expected,
+                "{} depth {}",
+                fen,
+                depth
+            );
+        }
+    }
+
+    #[test]
+    fn test_perft_detailed_nodes_match_perft() {
+        for fen in [POSITION5_FEN, POSITION6_FEN] {
+            let board = parse_fen(fen).unwrap();
+            for depth in 0..=3 {
+                assert_eq!(perft_detailed(&board, depth).nodes, perft(&board, depth));
+            }
+        }
+    }
+
+    #[test]
+    #[ignore] // Slow test
+    fn test_perft_detailed_deep() {
+        for &(fen, depth, expected) in DETAILED_POSITIONS {
+            let board = parse_fen(fen).unwrap();
+            assert_eq!(
+                perft_detailed(&board, depth),
+                expected,
+                "{} depth {}",
+                fen,
+                depth
+            );
+        }
+    }
+
     #[test]
     fn test_perft_divide_startpos() {
         let board = Board::startpos();
@@ -1,11 +1,18 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// Color of a chess piece
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Color {
     White = 0,
     Black = 1,
 }
 
 impl Color {
+    /// Both colors, White first
+    pub const fn all() -> [Color; 2] {
+        [Color::White, Color::Black]
+    }
+
     /// Get the opposite color
     #[inline]
     pub const fn opponent(self) -> Color {
@@ -41,8 +48,21 @@ impl std::fmt::Display for Color {
     }
 }
 
+impl std::str::FromStr for Color {
+    type Err = PieceError;
+
+    /// Parse `"w"`/`"b"` (as in FEN) or `"white"`/`"black"`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "w" | "white" => Ok(Color::White),
+            "b" | "black" => Ok(Color::Black),
+            _ => Err(PieceError(s.to_string())),
+        }
+    }
+}
+
 /// Type of chess piece
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PieceType {
     Pawn = 0,
     Knight = 1,
@@ -117,6 +137,26 @@ impl std::fmt::Display for PieceType {
     }
 }
 
+impl std::str::FromStr for PieceType {
+    type Err = PieceError;
+
+    /// Parse a single FEN character, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        single_char(s)
+            .and_then(PieceType::from_char)
+            .ok_or_else(|| PieceError(s.to_string()))
+    }
+}
+
+impl TryFrom<u8> for PieceType {
+    type Error = PieceError;
+
+    /// Convert an index (0 = pawn ... 5 = king).
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        PieceType::from_index(index as usize).ok_or_else(|| PieceError(index.to_string()))
+    }
+}
+
 /// A colored chess piece
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Piece {
@@ -150,6 +190,45 @@ impl Piece {
         };
         Some(Piece::new(piece_type, color))
     }
+
+    /// Get the Unicode chess symbol for this piece (`'♕'` is a white queen,
+    /// `'♛'` a black one)
+    pub fn to_unicode_char(self) -> char {
+        match (self.color, self.piece_type) {
+            (Color::White, PieceType::King) => '♔',
+            (Color::White, PieceType::Queen) => '♕',
+            (Color::White, PieceType::Rook) => '♖',
+            (Color::White, PieceType::Bishop) => '♗',
+            (Color::White, PieceType::Knight) => '♘',
+            (Color::White, PieceType::Pawn) => '♙',
+            (Color::Black, PieceType::King) => '♚',
+            (Color::Black, PieceType::Queen) => '♛',
+            (Color::Black, PieceType::Rook) => '♜',
+            (Color::Black, PieceType::Bishop) => '♝',
+            (Color::Black, PieceType::Knight) => '♞',
+            (Color::Black, PieceType::Pawn) => '♟',
+        }
+    }
+
+    /// Parse a Unicode chess symbol (U+2654..U+265F)
+    pub fn from_unicode_char(c: char) -> Option<Self> {
+        let piece = match c {
+            '♔' => Piece::new(PieceType::King, Color::White),
+            '♕' => Piece::new(PieceType::Queen, Color::White),
+            '♖' => Piece::new(PieceType::Rook, Color::White),
+            '♗' => Piece::new(PieceType::Bishop, Color::White),
+            '♘' => Piece::new(PieceType::Knight, Color::White),
+            '♙' => Piece::new(PieceType::Pawn, Color::White),
+            '♚' => Piece::new(PieceType::King, Color::Black),
+            '♛' => Piece::new(PieceType::Queen, Color::Black),
+            '♜' => Piece::new(PieceType::Rook, Color::Black),
+            '♝' => Piece::new(PieceType::Bishop, Color::Black),
+            '♞' => Piece::new(PieceType::Knight, Color::Black),
+            '♟' => Piece::new(PieceType::Pawn, Color::Black),
+            _ => return None,
+        };
+        Some(piece)
+    }
 }
 
 impl std::fmt::Display for Piece {
@@ -158,6 +237,52 @@ impl std::fmt::Display for Piece {
     }
 }
 
+impl std::str::FromStr for Piece {
+    type Err = PieceError;
+
+    /// Parse a single FEN character (`'Q'` is a white queen, `'q'` a black
+    /// one).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        single_char(s)
+            .and_then(Piece::from_char)
+            .ok_or_else(|| PieceError(s.to_string()))
+    }
+}
+
+/// Serialized as its FEN character (`"Q"`, `"q"`).
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error for a string or index that isn't a color, piece type or piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceError(pub String);
+
+impl std::fmt::Display for PieceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid piece: {}", self.0)
+    }
+}
+
+impl std::error::Error for PieceError {}
+
+/// The only character of `s`, if it has exactly one.
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +335,25 @@ mod tests {
         assert_eq!(Piece::from_char('x'), None);
     }
 
+    #[test]
+    fn piece_unicode_char_round_trip() {
+        let mut symbols = String::new();
+        for color in Color::all() {
+            for piece_type in PieceType::all() {
+                let piece = Piece::new(piece_type, color);
+                let c = piece.to_unicode_char();
+                assert_eq!(Piece::from_unicode_char(c), Some(piece));
+                symbols.push(c);
+            }
+        }
+        assert_eq!(symbols, "♙♘♗♖♕♔♟♞♝♜♛♚");
+
+        // Other symbols, even from neighbouring Unicode blocks, are not pieces
+        for c in ['K', 'k', '♠', '♡', '⚀', '\u{2653}', '\u{2660}', '🨀'] {
+            assert_eq!(Piece::from_unicode_char(c), None, "{:?}", c);
+        }
+    }
+
     #[test]
     fn piece_all_types() {
         let all = PieceType::all();
@@ -217,4 +361,48 @@ mod tests {
         assert_eq!(all[0], PieceType::Pawn);
         assert_eq!(all[5], PieceType::King);
     }
+
+    #[test]
+    fn from_str_and_try_from_round_trip() {
+        for color in Color::all() {
+            assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+            for piece_type in PieceType::all() {
+                let piece = Piece::new(piece_type, color);
+                assert_eq!(piece.to_string().parse::<Piece>(), Ok(piece));
+                assert_eq!(piece_type.to_string().parse::<PieceType>(), Ok(piece_type));
+                assert_eq!(
+                    PieceType::try_from(piece_type.index() as u8),
+                    Ok(piece_type)
+                );
+            }
+        }
+        assert_eq!("w".parse::<Color>(), Ok(Color::White));
+        assert_eq!("B".parse::<Color>(), Ok(Color::Black));
+        assert!("red".parse::<Color>().is_err());
+        assert!("Qq".parse::<Piece>().is_err());
+        assert!("".parse::<PieceType>().is_err());
+        assert_eq!(PieceType::try_from(6), Err(PieceError("6".to_string())));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for color in Color::all() {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+            for piece_type in PieceType::all() {
+                let json = serde_json::to_string(&piece_type).unwrap();
+                assert_eq!(
+                    serde_json::from_str::<PieceType>(&json).unwrap(),
+                    piece_type
+                );
+
+                let piece = Piece::new(piece_type, color);
+                let json = serde_json::to_string(&piece).unwrap();
+                assert_eq!(json, format!("\"{}\"", piece));
+                assert_eq!(serde_json::from_str::<Piece>(&json).unwrap(), piece);
+            }
+        }
+        assert_eq!(serde_json::to_string(&Color::White).unwrap(), "\"White\"");
+        assert!(serde_json::from_str::<Piece>("\"x\"").is_err());
+    }
 }
@@ -0,0 +1,26 @@
+//! Commonly used types, re-exported in one place.
+//!
+//! ```
+//! use engine::prelude::*;
+//!
+//! let board = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+//! let moves: MoveList = board.generate_legal_moves();
+//! assert_eq!(moves.len(), 20);
+//! assert_eq!(board.to_fen(), Board::startpos().to_fen());
+//! ```
+//!
+//! Everything here is part of the stable API: items are only added to the
+//! prelude, never removed or renamed, outside a breaking release.
+
+pub use crate::board::Board;
+pub use crate::io::{parse_fen, ToFen};
+pub use crate::movelist::MoveList;
+pub use crate::piece::{Color, Piece, PieceType};
+pub use crate::r#move::{Move, MoveFlags};
+pub use crate::search::{SearchResult, Searcher, StopHandle};
+/// Search tuning parameters, exposed as UCI options by tuning front ends
+pub use crate::search_params::{self, SearchParams};
+pub use crate::square::Square;
+pub use crate::time::TimeControl;
+pub use crate::types::{BestMove, EngineOptions, SearchInfo, SearchLimit};
+pub use crate::EngineImpl;
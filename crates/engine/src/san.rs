@@ -0,0 +1,314 @@
+//! Standard Algebraic Notation (SAN) parsing and formatting.
+//!
+//! Accepts the usual forms: `e4`, `exd5`, `Nf3`, `Nbd7`, `R1e2`, `Qh4xe1`,
+//! `e8=Q` (or `e8Q`), `O-O` / `O-O-O` (or with zeros). Check and annotation
+//! suffixes (`+`, `#`, `!`, `?`) are ignored.
+//!
+//! # Example
+//! ```
+//! use engine::board::Board;
+//! use engine::san::{move_to_san, parse_san};
+//!
+//! let board = Board::startpos();
+//! let m = parse_san(&board, "Nf3").unwrap();
+//! assert_eq!(m.to_uci(), "g1f3");
+//! assert_eq!(move_to_san(&board, m), "Nf3");
+//! ```
+
+use crate::board::Board;
+use crate::piece::PieceType;
+use crate::r#move::Move;
+use crate::square::Square;
+
+/// Error type for SAN parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SanError {
+    /// Empty move string
+    Empty,
+    /// String is not valid SAN
+    InvalidSyntax(String),
+    /// No legal move matches
+    IllegalMove(String),
+    /// More than one legal move matches
+    AmbiguousMove(String),
+    /// Error in a sequence of moves, with the 0-based index of the move
+    AtIndex { index: usize, error: Box<SanError> },
+}
+
+impl SanError {
+    /// Index of the failing move when parsing a sequence.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            SanError::AtIndex { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanError::Empty => write!(f, "Empty SAN move"),
+            SanError::InvalidSyntax(s) => write!(f, "Invalid SAN: {}", s),
+            SanError::IllegalMove(s) => write!(f, "Illegal move: {}", s),
+            SanError::AmbiguousMove(s) => write!(f, "Ambiguous move: {}", s),
+            SanError::AtIndex { index, error } => write!(f, "Move {}: {}", index, error),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+/// Parse a SAN move in the given position.
+///
+/// Returns the matching legal move.
+pub fn parse_san(board: &Board, san: &str) -> Result<Move, SanError> {
+    let text = san
+        .trim()
+        .trim_end_matches(['+', '#', '!', '?'])
+        .trim_end_matches("e.p.")
+        .trim_end();
+    if text.is_empty() {
+        return Err(SanError::Empty);
+    }
+
+    let legal_moves = board.generate_legal_moves();
+
+    // Castling
+    let castle = match text {
+        "O-O" | "0-0" => Some(true),
+        "O-O-O" | "0-0-0" => Some(false),
+        _ => None,
+    };
+    if let Some(kingside) = castle {
+        return legal_moves
+            .iter()
+            .find(|m| {
+                if kingside {
+                    m.is_kingside_castle()
+                } else {
+                    m.is_queenside_castle()
+                }
+            })
+            .copied()
+            .ok_or_else(|| SanError::IllegalMove(san.to_string()));
+    }
+
+    let invalid = || SanError::InvalidSyntax(san.to_string());
+
+    // Piece letter (pawn moves have none)
+    let (piece_type, rest) = match text.chars().next() {
+        Some(c @ ('N' | 'B' | 'R' | 'Q' | 'K')) => {
+            (PieceType::from_char(c).ok_or_else(invalid)?, &text[1..])
+        }
+        _ => (PieceType::Pawn, text),
+    };
+
+    // Promotion suffix: "=Q" or a bare trailing piece letter
+    let (rest, promotion) = match rest.char_indices().last() {
+        Some((i, c @ ('N' | 'B' | 'R' | 'Q'))) if piece_type == PieceType::Pawn => {
+            let body = rest[..i].trim_end_matches('=');
+            (body, Some(PieceType::from_char(c).ok_or_else(invalid)?))
+        }
+        _ => (rest, None),
+    };
+
+    // Destination square is always the last two characters
+    let rest: String = rest.chars().filter(|&c| c != 'x' && c != '-').collect();
+    if rest.len() < 2 || !rest.is_ascii() {
+        return Err(invalid());
+    }
+    let (disambiguation, destination) = rest.split_at(rest.len() - 2);
+    let to = Square::from_algebraic(destination).ok_or_else(invalid)?;
+
+    // Optional origin file and/or rank
+    let mut from_file = None;
+    let mut from_rank = None;
+    for c in disambiguation.chars() {
+        match c {
+            'a'..='h' if from_file.is_none() => from_file = Some(c as u8 - b'a'),
+            '1'..='8' if from_rank.is_none() => from_rank = Some(c as u8 - b'1'),
+            _ => return Err(invalid()),
+        }
+    }
+
+    let mut matches = legal_moves.iter().filter(|m| {
+        m.to() == to
+            && !m.is_castling()
+            && board.piece_at(m.from()).map(|p| p.piece_type) == Some(piece_type)
+            && m.promotion_piece() == promotion
+            && from_file.is_none_or(|file| m.from().file() == file)
+            && from_rank.is_none_or(|rank| m.from().rank() == rank)
+    });
+
+    match (matches.next(), matches.next()) {
+        (Some(&m), None) => Ok(m),
+        (Some(_), Some(_)) => Err(SanError::AmbiguousMove(san.to_string())),
+        (None, _) => Err(SanError::IllegalMove(san.to_string())),
+    }
+}
+
+/// Format a legal move in SAN, including the check (`+`) or mate (`#`) suffix.
+pub fn move_to_san(board: &Board, m: Move) -> String {
+    let mut san = String::new();
+
+    if m.is_kingside_castle() {
+        san.push_str("O-O");
+    } else if m.is_queenside_castle() {
+        san.push_str("O-O-O");
+    } else {
+        let piece_type = board
+            .piece_at(m.from())
+            .map_or(PieceType::Pawn, |p| p.piece_type);
+
+        if piece_type == PieceType::Pawn {
+            if m.is_capture() {
+                san.push((b'a' + m.from().file()) as char);
+            }
+        } else {
+            san.push(piece_type.to_char().to_ascii_uppercase());
+            san.push_str(&disambiguation(board, m, piece_type));
+        }
+
+        if m.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&m.to().to_algebraic());
+
+        if let Some(promotion) = m.promotion_piece() {
+            san.push('=');
+            san.push(promotion.to_char().to_ascii_uppercase());
+        }
+    }
+
+    let mut after = board.clone();
+    after.make_move(m);
+    if after.is_in_check() {
+        san.push(if after.generate_legal_moves().is_empty() {
+            '#'
+        } else {
+            '+'
+        });
+    }
+
+    san
+}
+
+/// Origin file and/or rank needed to tell `m` apart from other moves of the
+/// same piece type to the same square.
+fn disambiguation(board: &Board, m: Move, piece_type: PieceType) -> String {
+    let legal_moves = board.generate_legal_moves();
+    let rivals: Vec<Square> = legal_moves
+        .iter()
+        .filter(|other| {
+            other.to() == m.to()
+                && other.from() != m.from()
+                && board.piece_at(other.from()).map(|p| p.piece_type) == Some(piece_type)
+        })
+        .map(|other| other.from())
+        .collect();
+
+    let from = m.from();
+    let file = (b'a' + from.file()) as char;
+    let rank = (b'1' + from.rank()) as char;
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|sq| sq.file() != from.file()) {
+        file.to_string()
+    } else if rivals.iter().all(|sq| sq.rank() != from.rank()) {
+        rank.to_string()
+    } else {
+        format!("{}{}", file, rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::parse_fen;
+
+    #[test]
+    fn test_parse_simple_moves() {
+        let board = Board::startpos();
+        assert_eq!(parse_san(&board, "e4").unwrap().to_uci(), "e2e4");
+        assert_eq!(parse_san(&board, "Nc3").unwrap().to_uci(), "b1c3");
+        assert_eq!(parse_san(&board, "Nf3+").unwrap().to_uci(), "g1f3");
+        assert_eq!(
+            parse_san(&board, "e5"),
+            Err(SanError::IllegalMove("e5".into()))
+        );
+        assert_eq!(parse_san(&board, " "), Err(SanError::Empty));
+        assert!(matches!(
+            parse_san(&board, "Zz9"),
+            Err(SanError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_disambiguation_and_captures() {
+        // Knights on b1 and f3 can both reach d2; rooks on a1 and a5 both reach a3
+        let board = parse_fen("4k3/8/8/R3p3/3P4/5N2/8/RN2K3 w - - 0 1").unwrap();
+        assert!(matches!(
+            parse_san(&board, "Nd2"),
+            Err(SanError::AmbiguousMove(_))
+        ));
+        assert_eq!(parse_san(&board, "Nbd2").unwrap().to_uci(), "b1d2");
+        assert_eq!(parse_san(&board, "Nfd2").unwrap().to_uci(), "f3d2");
+        assert_eq!(parse_san(&board, "R1a3").unwrap().to_uci(), "a1a3");
+        assert_eq!(parse_san(&board, "R5a3").unwrap().to_uci(), "a5a3");
+        assert_eq!(parse_san(&board, "dxe5").unwrap().to_uci(), "d4e5");
+        assert_eq!(parse_san(&board, "Nxe5").unwrap().to_uci(), "f3e5");
+    }
+
+    #[test]
+    fn test_parse_castling_and_promotion() {
+        let board = parse_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        assert_eq!(parse_san(&board, "O-O").unwrap().to_uci(), "e1g1");
+        assert_eq!(parse_san(&board, "0-0-0").unwrap().to_uci(), "e1c1");
+        assert_eq!(parse_san(&board, "b8=Q").unwrap().to_uci(), "b7b8q");
+        assert_eq!(parse_san(&board, "b8N").unwrap().to_uci(), "b7b8n");
+        assert_eq!(parse_san(&board, "bxa8=R+").unwrap().to_uci(), "b7a8r");
+        // A promotion needs its piece
+        assert!(matches!(
+            parse_san(&board, "b8"),
+            Err(SanError::IllegalMove(_))
+        ));
+    }
+
+    #[test]
+    fn test_move_to_san() {
+        let board = parse_fen("4k3/8/8/R3p3/3P4/5N2/8/RN2K3 w - - 0 1").unwrap();
+        let san = |uci: &str| {
+            let m = board
+                .generate_legal_moves()
+                .iter()
+                .find(|m| m.to_uci() == uci)
+                .copied()
+                .unwrap();
+            move_to_san(&board, m)
+        };
+
+        assert_eq!(san("b1d2"), "Nbd2");
+        assert_eq!(san("a1a3"), "R1a3");
+        assert_eq!(san("d4e5"), "dxe5");
+        assert_eq!(san("f3e5"), "Nxe5");
+        assert_eq!(san("a5a8"), "Ra8+");
+        assert_eq!(san("e1e2"), "Ke2");
+    }
+
+    #[test]
+    fn test_move_to_san_roundtrip() {
+        let board = parse_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        for &m in board.generate_legal_moves().iter() {
+            let san = move_to_san(&board, m);
+            assert_eq!(parse_san(&board, &san), Ok(m), "{}", san);
+        }
+
+        let mate = Board::startpos()
+            .apply_moves_from_san(&["f3", "e5", "g4"])
+            .unwrap();
+        let qh4 = parse_san(&mate, "Qh4").unwrap();
+        assert_eq!(move_to_san(&mate, qh4), "Qh4#");
+    }
+}
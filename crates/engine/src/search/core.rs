@@ -1,26 +1,67 @@
 //! Chess search implementation using negamax with alpha-beta pruning.
 
 use crate::board::Board;
-use crate::eval::Evaluator;
+use crate::eval::{king_attack_weight, EvalCache, Evaluator};
 use crate::move_order::MoveOrder;
+use crate::movegen::{generate_checking_moves, generate_queen_promotions};
+use crate::movelist::MoveList;
 use crate::opening_book::OpeningBook;
+use crate::piece::{Color, PieceType};
 use crate::r#move::Move;
+use crate::search::root::RootMoves;
+use crate::search::skill::Skill;
+use crate::search::stop::StopHandle;
+use crate::search::variety::{MoveVariety, VARIETY_SEARCH_LINES};
 use crate::search_params;
-use crate::time::{TimeControl, TimeManager};
+use crate::time::{Clock, SystemClock, TimeControl, TimeManager};
 use crate::tt::{Bound, TranspositionTable};
-use crate::types::{Score, SearchInfo};
-use std::sync::atomic::{AtomicBool, Ordering};
+use crate::types::{Bound as ScoreBound, PvEnd, Score, ScorePov, SearchInfo};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 /// Maximum search depth.
 pub const MAX_DEPTH: u32 = 64;
 
+/// Maximum distance from the root, in plies, that the search reaches,
+/// extensions and quiescence included. Sizes every per-ply table (killers,
+/// static evals); nodes this deep return their static eval.
+pub const MAX_PLY: usize = MAX_DEPTH as usize;
+
 /// Checkmate score (very large value).
 pub const MATE_SCORE: i32 = 30_000;
 
 /// Infinity (larger than any possible score).
 pub const INFINITY: i32 = 32_000;
 
+/// Placeholder in the static eval stack for nodes without an eval.
+const NO_EVAL: i32 = i32::MIN;
+
+/// Convert a mate score from "plies from the root" to "plies from this
+/// node" before storing it in the TT, so it stays valid wherever the
+/// position is reached again. Other scores are unchanged.
+#[inline]
+fn score_to_tt(score: i32, ply: u32) -> i32 {
+    if score >= MATE_SCORE - MAX_DEPTH as i32 {
+        score + ply as i32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as i32 {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// Inverse of [`score_to_tt`] for a TT score probed at `ply`.
+#[inline]
+fn score_from_tt(score: i32, ply: u32) -> i32 {
+    if score >= MATE_SCORE - MAX_DEPTH as i32 {
+        score - ply as i32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as i32 {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
 /// Principal variation line (for multi-PV search).
 #[derive(Debug, Clone)]
 pub struct PVLine {
@@ -40,16 +81,53 @@ pub struct SearchResult {
     pub multi_pv: Vec<PVLine>,
 }
 
+impl SearchResult {
+    /// Result without a move, for positions with nothing to search.
+    fn no_move(score: i32) -> Self {
+        Self {
+            best_move: Move::null(),
+            score,
+            depth: 0,
+            nodes: 0,
+            pv: Vec::new(),
+            multi_pv: Vec::new(),
+        }
+    }
+}
+
 /// Main search engine.
 pub struct Searcher {
     evaluator: Evaluator,
+    eval_cache: EvalCache,
     tt: TranspositionTable,
     move_order: MoveOrder,
     opening_book: OpeningBook,
+    use_book: bool,
     nodes: u64,
     time_manager: Option<TimeManager>,
-    stopped: Arc<AtomicBool>,
+    /// Time source for the time manager of each search
+    clock: Arc<dyn Clock>,
+    stop: StopHandle,
+    reset_stop: bool,
+    aborted: bool,
     contempt: i32, // Centipawns to penalize draws (default: 20)
+    search_moves: Option<Vec<Move>>,
+    skill: Skill,
+    variety: MoveVariety,
+    /// Positions played before the root, oldest first
+    game_history: Vec<u64>,
+    /// Game history, the root and the current line's nodes, for repetitions
+    path: Vec<u64>,
+    /// Static eval of the current line's nodes by ply, `NO_EVAL` when in check
+    static_evals: [i32; MAX_PLY],
+    /// Quiescence plies allowed below the main search's leaves
+    max_qsearch_ply: u32,
+    /// Lazy stand pat margin, 0 when lazy evaluation is off
+    lazy_eval_margin: i32,
+    /// King attack weight from which the stand pat is always a full eval
+    lazy_eval_king_attack: i32,
+    /// Deepest ply reached in the current search
+    seldepth: u32,
 }
 
 impl Searcher {
@@ -65,15 +143,31 @@ impl Searcher {
 
     /// Create a new searcher with custom TT size and shared stop flag.
     pub fn with_tt_size_and_stop_flag(size_mb: usize, stopped: Arc<AtomicBool>) -> Self {
+        let params = search_params::SearchParams::default();
         Self {
             evaluator: Evaluator::new(),
+            eval_cache: EvalCache::default(),
             tt: TranspositionTable::new(size_mb),
             move_order: MoveOrder::new(),
             opening_book: OpeningBook::new(),
+            use_book: true,
             nodes: 0,
             time_manager: None,
-            stopped,
+            clock: Arc::new(SystemClock::new()),
+            stop: StopHandle::from_flag(stopped),
+            reset_stop: true,
+            aborted: false,
             contempt: 20, // Default: 20cp contempt (avoid draws slightly)
+            search_moves: None,
+            skill: Skill::default(),
+            variety: MoveVariety::default(),
+            game_history: Vec::new(),
+            path: Vec::new(),
+            static_evals: [NO_EVAL; MAX_PLY],
+            max_qsearch_ply: params.max_qsearch_ply as u32,
+            lazy_eval_margin: params.lazy_eval_margin,
+            lazy_eval_king_attack: params.lazy_eval_king_attack,
+            seldepth: 0,
         }
     }
 
@@ -84,6 +178,243 @@ impl Searcher {
         self.contempt = contempt;
     }
 
+    /// Deepest ply, quiescence included, reached by the last search.
+    pub fn seldepth(&self) -> u32 {
+        self.seldepth
+    }
+
+    /// Current contempt value (in centipawns).
+    pub fn contempt(&self) -> i32 {
+        self.contempt
+    }
+
+    /// Enable or disable opening book probing at the root.
+    ///
+    /// Book moves are returned without a score, so analysis that needs real
+    /// evaluations should disable the book.
+    pub fn set_use_book(&mut self, use_book: bool) {
+        self.use_book = use_book;
+    }
+
+    /// Whether the opening book is probed at the root.
+    pub fn use_book(&self) -> bool {
+        self.use_book
+    }
+
+    /// Read the time for time-limited searches from `clock` instead of the
+    /// wall clock, e.g. a [`crate::time::ManualClock`] in tests.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Restrict the root search to the given moves ("go searchmoves").
+    ///
+    /// Moves that are not legal in the searched position are ignored, and if
+    /// none of them are legal all moves are searched. Pass `None` to search
+    /// every root move again.
+    pub fn set_search_moves(&mut self, moves: Option<Vec<Move>>) {
+        self.search_moves = moves;
+    }
+
+    /// Set the hashes of the positions played before the one that will be
+    /// searched, oldest first.
+    ///
+    /// A position in the search that repeats one of them, or an earlier
+    /// position of the same line, is scored as a draw. Positions before the
+    /// last capture or pawn move can be left out.
+    pub fn set_game_history(&mut self, hashes: Vec<u64>) {
+        self.game_history = hashes;
+    }
+
+    /// Set the skill level (0-20, 20 = full strength).
+    ///
+    /// Below 20 the searcher sometimes plays one of the top root moves
+    /// instead of the best one; see [`Skill`].
+    pub fn set_skill_level(&mut self, level: u32) {
+        self.skill = Skill::new(level);
+    }
+
+    /// Set the opening move variety (0-100, 0 = always the best move).
+    ///
+    /// In the first moves of a game the searcher then plays a random root
+    /// move scoring close to the best one; see [`MoveVariety`].
+    pub fn set_move_variety(&mut self, amount: u32) {
+        self.variety.set_amount(amount);
+    }
+
+    /// Seed the random choices of [`Self::set_move_variety`], making them
+    /// reproducible.
+    pub fn set_move_variety_seed(&mut self, seed: u64) {
+        self.variety.set_seed(seed);
+    }
+
+    /// Forget everything learned from previous searches, as if the searcher
+    /// was just created, without reallocating the transposition table or
+    /// other caches. Options (contempt, skill, book) are kept.
+    pub fn reset_for_new_game(&mut self) {
+        self.tt.clear();
+        self.eval_cache.clear();
+        self.evaluator.clear_cache();
+        self.move_order.reset();
+        self.nodes = 0;
+        self.time_manager = None;
+        self.search_moves = None;
+    }
+
+    /// Forget the move ordering statistics (history, killers, counter
+    /// moves) but keep the transposition table, before searching an
+    /// unrelated position.
+    pub fn reset_move_ordering(&mut self) {
+        self.move_order.reset();
+    }
+
+    /// Replace the transposition table with an empty one of `size_mb`.
+    /// Existing entries are dropped.
+    ///
+    /// # Panics
+    /// If `size_mb` is larger than [`MAX_SIZE_MB`](crate::tt::MAX_SIZE_MB).
+    pub fn resize_tt(&mut self, size_mb: usize) {
+        assert!(
+            size_mb <= crate::tt::MAX_SIZE_MB,
+            "transposition table of {} MB is too large",
+            size_mb
+        );
+        self.tt = TranspositionTable::new(size_mb);
+    }
+
+    /// Size of the transposition table in MB, see
+    /// [`TranspositionTable::size_mb`].
+    pub fn tt_size_mb(&self) -> usize {
+        self.tt.size_mb()
+    }
+
+    /// Memory allocated for the transposition table, in bytes.
+    pub fn tt_bytes(&self) -> usize {
+        self.tt.memory_bytes()
+    }
+
+    /// Exact score stored for `board` in the transposition table, from the
+    /// side to move's point of view, or `None` if the table holds no exact
+    /// entry for it. Bound-only entries are not reported.
+    pub fn tt_score(&self, board: &Board) -> Option<i32> {
+        self.tt
+            .probe(board.hash())
+            .filter(|e| e.bound == Bound::Exact)
+            .map(|e| score_from_tt(e.score, 0))
+    }
+
+    /// Memory used by the pawn hash table, in bytes.
+    pub fn pawn_hash_bytes(&self) -> usize {
+        self.evaluator.pawn_hash_bytes()
+    }
+
+    /// Pawn hash table hits and misses, e.g. to read its hit rate after a
+    /// search.
+    pub fn pawn_hash_stats(&self) -> crate::eval::PawnHashStats {
+        self.evaluator.pawn_hash_stats()
+    }
+
+    /// Clear the evaluation cache and its statistics.
+    pub fn clear_eval_cache(&mut self) {
+        self.eval_cache.clear();
+    }
+
+    /// Evaluation cache, e.g. to read its hit rate after a search.
+    pub fn eval_cache(&self) -> &EvalCache {
+        &self.eval_cache
+    }
+
+    /// Static evaluation of a position, through the evaluation cache.
+    fn evaluate(&mut self, board: &Board) -> i32 {
+        let hash = board.hash();
+        if let Some(eval) = self.eval_cache.probe(hash) {
+            return eval;
+        }
+        let eval = self.evaluator.evaluate(board);
+        self.eval_cache.store(hash, eval);
+        eval
+    }
+
+    /// Static evaluation for the quiescence stand pat, lazily: when material
+    /// and piece-squares alone are more than the lazy eval margin outside
+    /// `alpha..beta`, that is returned without the expensive terms.
+    ///
+    /// Lazy values are never cached. Windows near mate scores and positions
+    /// with either king under heavy attack, where king safety can swing the
+    /// score by more than the margin, always get the full evaluation.
+    fn evaluate_lazy(&mut self, board: &Board, alpha: i32, beta: i32) -> i32 {
+        let margin = self.lazy_eval_margin;
+        let mate_bound = MATE_SCORE - MAX_DEPTH as i32;
+        if margin == 0 || alpha.abs() >= mate_bound || beta.abs() >= mate_bound {
+            return self.evaluate(board);
+        }
+        if let Some(eval) = self.eval_cache.probe(board.hash()) {
+            return eval;
+        }
+
+        let lazy = self.evaluator.evaluate_lazy(board);
+        if lazy - margin >= beta || lazy + margin <= alpha {
+            // Only lazy candidates pay for the king attack scans, one per
+            // king, and the second is skipped once the first is too high
+            let threshold = self.lazy_eval_king_attack;
+            if [Color::White, Color::Black]
+                .into_iter()
+                .all(|color| king_attack_weight(board, color) < threshold)
+            {
+                return lazy;
+            }
+        }
+
+        let eval = self.evaluator.evaluate(board);
+        self.eval_cache.store(board.hash(), eval);
+        eval
+    }
+
+    /// Record the static eval at `ply` and report whether it improved on the
+    /// one two plies earlier, i.e. after our previous move.
+    ///
+    /// Nodes in check have no usable static eval and never count as
+    /// improving.
+    fn update_improving(&mut self, board: &Board, ply: u32, in_check: bool) -> bool {
+        let ply = ply as usize;
+        if ply >= self.static_evals.len() {
+            return false;
+        }
+        if in_check {
+            self.static_evals[ply] = NO_EVAL;
+            return false;
+        }
+        let eval = self.evaluate(board);
+        self.static_evals[ply] = eval;
+        ply >= 2 && self.static_evals[ply - 2] != NO_EVAL && eval > self.static_evals[ply - 2]
+    }
+
+    /// Legal root moves, restricted to the search moves if any are set.
+    fn root_legal_moves(&self, board: &Board) -> MoveList {
+        let legal_moves = board.generate_legal_moves();
+        let Some(allowed) = &self.search_moves else {
+            return legal_moves;
+        };
+
+        let mut restricted = legal_moves.clone();
+        restricted.retain(|m| allowed.contains(m));
+
+        if restricted.is_empty() {
+            legal_moves
+        } else {
+            restricted
+        }
+    }
+
+    /// Check if a "go mate N" search has proven a short enough mate.
+    fn mate_limit_reached(&self, score: i32) -> bool {
+        score >= MATE_SCORE - MAX_DEPTH as i32
+            && self
+                .time_manager
+                .as_ref()
+                .is_some_and(|tm| tm.mate_limit_reached((MATE_SCORE - score) as u32))
+    }
+
     /// Apply contempt to an evaluation score.
     /// Adjusts scores near zero (draw territory) by contempt value.
     /// The effect tapers off for larger scores (clear advantages).
@@ -99,13 +430,72 @@ impl Searcher {
         }
     }
 
+    /// Save the transposition table (see [`TranspositionTable::save`]).
+    pub fn save_tt(&self, writer: impl std::io::Write) -> std::io::Result<()> {
+        self.tt.save(writer)
+    }
+
+    /// Replace the transposition table with one previously saved by
+    /// [`Searcher::save_tt`]. The current table is kept if loading fails.
+    pub fn load_tt(&mut self, reader: impl std::io::Read) -> std::io::Result<()> {
+        self.tt = TranspositionTable::load(reader)?;
+        Ok(())
+    }
+
     /// Stop the search.
     pub fn stop(&self) {
-        self.stopped.store(true, Ordering::Relaxed);
+        self.stop.stop();
+    }
+
+    /// Cancel searches through `handle` from now on.
+    ///
+    /// Unlike the shared flag of [`Searcher::with_tt_size_and_stop_flag`],
+    /// an installed handle is never cleared when a search starts: a stop
+    /// that arrives before the search begins still cancels it, and
+    /// installing a fresh handle per search means a late stop aimed at an
+    /// earlier search cannot cancel a later one.
+    pub fn set_stop_handle(&mut self, handle: StopHandle) {
+        self.stop = handle;
+        self.reset_stop = false;
+    }
+
+    /// The handle that currently cancels this searcher's searches.
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop.clone()
+    }
+
+    /// Whether the current search has been stopped, either through the
+    /// stop handle or by hitting its time or node limit.
+    #[inline]
+    fn is_stopped(&self) -> bool {
+        self.aborted || self.stop.is_stopped()
+    }
+
+    /// Reset per-search state at the start of a search.
+    fn begin_search(&mut self, board: &Board) {
+        self.path.clone_from(&self.game_history);
+        self.path.push(board.hash());
+        self.static_evals = [NO_EVAL; MAX_PLY];
+        let params = search_params::get_search_params();
+        self.max_qsearch_ply = params.max_qsearch_ply.max(0) as u32;
+        self.lazy_eval_margin = params.lazy_eval_margin.max(0);
+        self.lazy_eval_king_attack = params.lazy_eval_king_attack;
+        self.seldepth = 0;
+        self.nodes = 0;
+        self.tt.new_search();
+        self.clear_eval_cache();
+        self.move_order.clear_killers();
+        self.move_order.decay();
+        self.move_order
+            .set_bad_captures_before_quiets(params.bad_captures_before_quiets != 0);
+        self.aborted = false;
+        if self.reset_stop {
+            self.stop.reset();
+        }
     }
 
     /// Convert internal score to Score enum (Cp or Mate).
-    fn score_to_protocol(&self, score: i32) -> Score {
+    pub fn score_to_protocol(&self, score: i32) -> Score {
         // Check if this is a mate score
         if score.abs() >= MATE_SCORE - MAX_DEPTH as i32 {
             // Mate score: convert to plies until mate
@@ -125,6 +515,23 @@ impl Searcher {
         }
     }
 
+    /// The book move for `board` as a finished search, unless the book is
+    /// off or the caller restricted the search (search moves, mate search).
+    fn book_result(&self, board: &Board, time_control: &TimeControl) -> Option<SearchResult> {
+        let use_book = self.use_book
+            && self.search_moves.is_none()
+            && !matches!(time_control, TimeControl::Mate { .. });
+        let book_move = self.opening_book.probe(board).filter(|_| use_book)?;
+        Some(SearchResult {
+            best_move: book_move,
+            score: 0, // Book moves don't have scores
+            depth: 0,
+            nodes: 0,
+            pv: vec![book_move],
+            multi_pv: vec![],
+        })
+    }
+
     /// Iterative deepening search.
     ///
     /// Searches from depth 1 to max_depth, using results from shallower
@@ -148,27 +555,25 @@ impl Searcher {
     where
         F: FnMut(SearchInfo),
     {
-        self.nodes = 0;
-        self.tt.new_search();
-        self.move_order.clear();
-        self.stopped.store(false, Ordering::Relaxed);
+        // Setup-mode boards (missing kings etc.) can't be searched
+        if !board.is_searchable() {
+            return SearchResult::no_move(0);
+        }
+
+        self.begin_search(board);
 
         // Check opening book first
-        if let Some(book_move) = self.opening_book.probe(board) {
-            // Book hit! Return immediately without searching
-            return SearchResult {
-                best_move: book_move,
-                score: 0, // Book moves don't have scores
-                depth: 0,
-                nodes: 0,
-                pv: vec![book_move],
-                multi_pv: vec![],
-            };
+        if let Some(result) = self.book_result(board, &time_control) {
+            return result;
         }
 
         // Initialize time manager
         let is_white = board.side_to_move() == crate::piece::Color::White;
-        self.time_manager = Some(TimeManager::new(time_control, is_white));
+        self.time_manager = Some(TimeManager::with_clock(
+            time_control,
+            is_white,
+            self.clock.clone(),
+        ));
 
         let mut best_move = Move::new(
             crate::square::Square::A1,
@@ -177,6 +582,9 @@ impl Searcher {
         );
         let mut best_score = 0;
         let mut completed_depth = 0;
+        // PV of the last completed iteration
+        let mut pv = Vec::new();
+        let mut pv_end = None;
 
         // Track start time for NPS calculation (not available in WASM)
         #[cfg(not(target_arch = "wasm32"))]
@@ -184,6 +592,28 @@ impl Searcher {
         #[cfg(target_arch = "wasm32")]
         let start_nodes = self.nodes;
 
+        // Calculate time and NPS (platform-specific)
+        let timing = |nodes: u64| -> (u64, u64) {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let time_ms = start_time.elapsed().as_millis() as u64;
+                let nps = if time_ms > 0 {
+                    (nodes as u128 * 1000 / time_ms as u128) as u64
+                } else {
+                    nodes
+                };
+                (time_ms, nps)
+            }
+
+            // WASM: approximate timing based on nodes (~1M nodes per second)
+            #[cfg(target_arch = "wasm32")]
+            {
+                let nodes_searched = nodes - start_nodes;
+                let time_ms = (nodes_searched / 1000).max(1);
+                (time_ms, nodes_searched * 1000 / time_ms)
+            }
+        };
+
         // Iterative deepening with aspiration windows
         for depth in 1..=max_depth {
             // Check if we should stop (time, depth, or node limits)
@@ -199,99 +629,99 @@ impl Searcher {
                 let params = search_params::get_search_params();
                 let aspiration_delta = params.aspiration_delta;
 
-                let mut alpha = best_score - aspiration_delta;
-                let mut beta = best_score + aspiration_delta;
+                let mut alpha = (best_score - aspiration_delta).max(-INFINITY);
+                let mut beta = (best_score + aspiration_delta).min(INFINITY);
                 let mut delta = aspiration_delta;
 
                 loop {
                     let score = self.search_root_window(board, depth, alpha, beta);
+                    if self.is_stopped() {
+                        break score;
+                    }
 
-                    if score <= alpha {
-                        // Fail low: widen window downward
-                        alpha -= delta;
-                        // Cap delta to prevent overflow
-                        if delta >= 500 {
-                            alpha = -INFINITY;
-                            beta = INFINITY;
-                            delta = aspiration_delta; // Reset for next iteration
+                    // On a failure only the failing side moves: it restarts
+                    // from the fail score by the current margin, which then
+                    // doubles, and opens fully once the margin passes 500.
+                    // The other bound still holds and is kept.
+                    let bound = if score <= alpha {
+                        alpha = if delta > 500 {
+                            -INFINITY
                         } else {
-                            delta = delta.saturating_mul(2);
-                        }
+                            (score - delta).max(-INFINITY)
+                        };
+                        delta = delta.saturating_mul(2);
+                        ScoreBound::Upper
                     } else if score >= beta {
-                        // Fail high: widen window upward
-                        beta += delta;
-                        // Cap delta to prevent overflow
-                        if delta >= 500 {
-                            alpha = -INFINITY;
-                            beta = INFINITY;
-                            delta = aspiration_delta; // Reset for next iteration
+                        beta = if delta > 500 {
+                            INFINITY
                         } else {
-                            delta = delta.saturating_mul(2);
-                        }
+                            (score + delta).min(INFINITY)
+                        };
+                        delta = delta.saturating_mul(2);
+                        ScoreBound::Lower
                     } else {
                         // Success: score within window
                         break score;
-                    }
+                    };
+
+                    // Tell the GUI about the failure before re-searching
+                    let (time_ms, nps) = timing(self.nodes);
+                    let (pv, pv_end) = self.extract_pv(board, depth);
+                    callback(SearchInfo {
+                        pv_terminated: Some(pv_end),
+                        ..self.search_info(depth, score, &pv, time_ms, nps, Some(bound))
+                    });
                 }
             };
 
+            // An interrupted iteration's score and PV can't be trusted:
+            // conclude with the last completed one
+            if self.is_stopped() {
+                break;
+            }
+
             best_score = score;
             completed_depth = depth;
 
             // Extract PV from TT
-            let pv = self.extract_pv(board, depth);
+            let end;
+            (pv, end) = self.extract_pv(board, depth);
+            pv_end = Some(end);
 
             if let Some(&first_move) = pv.first() {
                 best_move = first_move;
             }
 
-            // Calculate time and NPS (platform-specific)
-            #[cfg(not(target_arch = "wasm32"))]
-            let (time_ms, nps) = {
-                let elapsed = start_time.elapsed();
-                let time_ms = elapsed.as_millis() as u64;
-                let nps = if time_ms > 0 {
-                    (self.nodes as u128 * 1000 / time_ms as u128) as u64
-                } else {
-                    self.nodes
-                };
-                (time_ms, nps)
-            };
+            let (time_ms, nps) = timing(self.nodes);
+            callback(SearchInfo {
+                pv_terminated: pv_end,
+                ..self.search_info(depth, score, &pv, time_ms, nps, None)
+            });
 
-            // WASM: approximate timing based on nodes
-            #[cfg(target_arch = "wasm32")]
-            let (time_ms, nps) = {
-                let nodes_searched = self.nodes - start_nodes;
-                // Estimate ~1M nodes per second
-                let time_ms = (nodes_searched / 1000).max(1);
-                let nps = if time_ms > 0 {
-                    nodes_searched * 1000 / time_ms
-                } else {
-                    nodes_searched
-                };
-                (time_ms, nps)
-            };
+            // "go mate N": stop as soon as a short enough mate is proven
+            if self.mate_limit_reached(score) {
+                break;
+            }
+        }
 
-            // Convert PV to UCI strings
-            let pv_strings: Vec<String> = pv.iter().map(|m| format!("{}", m)).collect();
+        if completed_depth == 0 {
+            // Stopped before depth 1 completed: still answer with a legal move
+            if let Some(mv) = self.fallback_move(board) {
+                best_move = mv;
+                best_score = self.evaluate(board);
+                pv = vec![mv];
+            }
+        }
 
-            // Call callback with SearchInfo
+        if self.is_stopped() {
+            let (time_ms, nps) = timing(self.nodes);
             callback(SearchInfo {
-                id: String::new(), // ID will be set by EngineImpl
-                depth,
-                seldepth: Some(depth), // TODO: track actual selective depth
-                nodes: self.nodes,
-                nps,
-                time_ms,
-                score: self.score_to_protocol(score),
-                pv: pv_strings,
-                hashfull: Some(self.tt.hashfull() as u32),
-                tb_hits: None, // TODO: add when tablebases are implemented
+                partial: true,
+                pv_terminated: pv_end,
+                ..self.search_info(completed_depth, best_score, &pv, time_ms, nps, None)
             });
         }
 
-        let pv = self.extract_pv(board, completed_depth);
-
         SearchResult {
             best_move,
             score: best_score,
@@ -302,6 +732,48 @@ impl Searcher {
         }
     }
 
+    /// SearchInfo for the single-PV line at `depth`; `bound` is set for
+    /// aspiration failures.
+    fn search_info(
+        &self,
+        depth: u32,
+        score: i32,
+        pv: &[Move],
+        time_ms: u64,
+        nps: u64,
+        bound: Option<ScoreBound>,
+    ) -> SearchInfo {
+        SearchInfo {
+            id: String::new(), // ID will be set by EngineImpl
+            depth,
+            seldepth: Some(self.seldepth.max(depth)),
+            nodes: self.nodes,
+            nps,
+            time_ms,
+            score: self.score_to_protocol(score),
+            pv: pv.iter().map(|m| format!("{}", m)).collect(),
+            hashfull: Some(self.tt.hashfull() as u32),
+            tb_hits: None, // TODO: add when tablebases are implemented
+            multipv: None,
+            bound,
+            score_pov: ScorePov::SideToMove,
+            wdl: None,
+            partial: false,
+            pv_terminated: None,
+        }
+    }
+
+    /// The move to play when a search is stopped before completing depth 1:
+    /// the transposition table move if there is one, otherwise the first
+    /// root move in move ordering. `None` without legal moves.
+    fn fallback_move(&mut self, board: &Board) -> Option<Move> {
+        let mut legal_moves = self.root_legal_moves(board);
+        let tt_move = self.tt.probe(board.hash()).map(|e| e.best_move);
+        self.move_order
+            .order_moves(board, &mut legal_moves, 0, tt_move, None);
+        legal_moves.as_slice().first().copied()
+    }
+
     /// Convenience method without callback (backward compatibility).
     pub fn search_with_limit(
         &mut self,
@@ -313,6 +785,22 @@ impl Searcher {
         self.search_with_limit_callback(board, max_depth, time_control, |_| {})
     }
 
+    /// Search a fixed number of nodes, for reproducible tests and for
+    /// comparing engines at equal effort.
+    ///
+    /// A [`TimeControl::Nodes`] search without a depth cap that ignores the
+    /// opening book. The node limit is checked on every node, main search
+    /// and quiescence alike, so `result.nodes` never exceeds `max_nodes` and
+    /// only falls short of it when the search runs out of work first (say,
+    /// after proving a mate).
+    pub fn search_fixed_nodes(&mut self, board: &Board, max_nodes: u64) -> SearchResult {
+        let use_book = std::mem::replace(&mut self.use_book, false);
+        let result =
+            self.search_with_limit(board, MAX_DEPTH, TimeControl::Nodes { nodes: max_nodes });
+        self.use_book = use_book;
+        result
+    }
+
     /// Convenience method for unlimited search (backward compatibility).
     pub fn search(&mut self, board: &Board, max_depth: u32) -> SearchResult {
         self.search_with_limit(board, max_depth, TimeControl::Infinite)
@@ -320,7 +808,7 @@ impl Searcher {
 
     /// Check if search should stop due to time/depth/node limits.
     fn should_stop(&self, current_depth: u32) -> bool {
-        if self.stopped.load(Ordering::Relaxed) {
+        if self.is_stopped() {
             return true;
         }
 
@@ -352,8 +840,7 @@ impl Searcher {
 
     /// Multi-PV search: find top N best moves.
     ///
-    /// Searches the position N times, excluding previously found best moves
-    /// each iteration. Useful for analysis mode.
+    /// Convenience wrapper without time control or callback.
     ///
     /// # Arguments
     /// * `board` - The position to search
@@ -368,141 +855,350 @@ impl Searcher {
         max_depth: u32,
         num_pv: usize,
     ) -> SearchResult {
-        if num_pv <= 1 {
+        self.search_multi_pv_with_limit_callback(
+            board,
+            max_depth,
+            num_pv,
+            TimeControl::Infinite,
+            |_| {},
+        )
+    }
+
+    /// Multi-PV iterative deepening search.
+    ///
+    /// Runs a single iterative deepening loop over the root moves. At each
+    /// depth every root move is searched once: until N lines are known each
+    /// move gets a full window, afterwards a move is first searched with a
+    /// null window at the score of the current N-th best line and only
+    /// re-searched with a full window if it enters the top N. The callback
+    /// receives one SearchInfo per line (with `multipv` set) for every
+    /// completed depth.
+    ///
+    /// With a reduced skill level this path is also used for `num_pv == 1`:
+    /// enough extra lines are searched for the skill to pick from, and the
+    /// returned best move is the one the skill picked. The same goes for
+    /// move variety in the opening, which picks among the exactly scored
+    /// lines. Of the two, only a variety search of a single line probes the
+    /// opening book first, as the regular search does.
+    ///
+    /// # Arguments
+    /// * `board` - The position to search
+    /// * `max_depth` - Maximum search depth in plies
+    /// * `num_pv` - Number of principal variations to find
+    /// * `time_control` - Time control for the search
+    /// * `callback` - Receives SearchInfo for each PV line after each depth
+    ///
+    /// # Returns
+    /// SearchResult with best move and multi_pv containing all PV lines
+    pub fn search_multi_pv_with_limit_callback<F>(
+        &mut self,
+        board: &Board,
+        max_depth: u32,
+        num_pv: usize,
+        time_control: TimeControl,
+        mut callback: F,
+    ) -> SearchResult
+    where
+        F: FnMut(SearchInfo),
+    {
+        let use_variety = self.variety.applies(board);
+        if num_pv <= 1 && !self.skill.enabled() && !use_variety {
             // Single PV: use regular search
-            return self.search(board, max_depth);
+            return self.search_with_limit_callback(board, max_depth, time_control, callback);
+        }
+        if !board.is_searchable() {
+            return SearchResult::no_move(0);
         }
 
-        self.nodes = 0;
-        self.tt.new_search();
-        self.move_order.clear();
-
-        let mut multi_pv = Vec::new();
-        let mut excluded_moves = Vec::new();
-
-        for _pv_num in 0..num_pv {
-            // Search with excluded moves
-            let result = self.search_excluding(board, max_depth, &excluded_moves);
+        self.begin_search(board);
 
-            if result.pv.is_empty() {
-                // No more legal moves
-                break;
+        if num_pv <= 1 && !self.skill.enabled() {
+            if let Some(result) = self.book_result(board, &time_control) {
+                return result;
             }
+        }
 
-            multi_pv.push(PVLine {
-                score: result.score,
-                pv: result.pv.clone(),
-            });
+        let is_white = board.side_to_move() == crate::piece::Color::White;
+        self.time_manager = Some(TimeManager::with_clock(
+            time_control,
+            is_white,
+            self.clock.clone(),
+        ));
 
-            // Exclude this PV's first move from next iteration
-            if let Some(&first_move) = result.pv.first() {
-                excluded_moves.push(first_move);
-            }
+        let mut legal_moves = self.root_legal_moves(board);
+        if legal_moves.is_empty() {
+            return SearchResult::no_move(if board.is_in_check() { -MATE_SCORE } else { 0 });
         }
 
-        // Return best as primary result
-        let best = &multi_pv[0];
+        let tt_move = self.tt.probe(board.hash()).map(|e| e.best_move);
+        self.move_order
+            .order_moves(board, &mut legal_moves, 0, tt_move, None);
 
-        SearchResult {
-            best_move: best.pv[0],
-            score: best.score,
-            depth: max_depth,
-            nodes: self.nodes,
-            pv: best.pv.clone(),
-            multi_pv,
+        let mut root_moves = RootMoves::from_moves(&legal_moves);
+        let num_pv = num_pv.max(1).min(root_moves.len());
+        let mut search_lines = num_pv.max(self.skill.search_lines(root_moves.len()));
+        if use_variety {
+            search_lines = search_lines.max(VARIETY_SEARCH_LINES.min(root_moves.len()));
         }
-    }
+        let mut completed_depth = 0;
+        // Change of the best score over the last completed iteration
+        let mut swing = 0;
 
-    /// Search with a set of excluded moves (for multi-PV).
-    fn search_excluding(
-        &mut self,
-        board: &Board,
-        max_depth: u32,
-        excluded: &[Move],
-    ) -> SearchResult {
-        let mut best_move = Move::new(
-            crate::square::Square::A1,
-            crate::square::Square::A1,
-            crate::r#move::MoveFlags::QUIET,
-        );
-        let mut best_score = 0;
+        #[cfg(not(target_arch = "wasm32"))]
+        let start_time = std::time::Instant::now();
+
+        let timing = |nodes: u64| -> (u64, u64) {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let time_ms = start_time.elapsed().as_millis() as u64;
+                let nps = if time_ms > 0 {
+                    (nodes as u128 * 1000 / time_ms as u128) as u64
+                } else {
+                    nodes
+                };
+                (time_ms, nps)
+            }
+
+            // WASM: approximate timing based on nodes (~1M nodes per second)
+            #[cfg(target_arch = "wasm32")]
+            {
+                let time_ms = (nodes / 1000).max(1);
+                (time_ms, nodes * 1000 / time_ms)
+            }
+        };
 
-        // Iterative deepening (simplified - no aspiration windows for multi-PV)
         for depth in 1..=max_depth {
-            let score = self.search_root_excluding(board, depth, excluded);
+            if self.should_stop(depth) {
+                break;
+            }
 
-            best_score = score;
+            let previous = root_moves.clone();
+            self.search_root_multi_pv(board, depth, &mut root_moves, search_lines);
 
-            // Extract PV from TT
-            let pv = self.extract_pv(board, depth);
+            if self.is_stopped() {
+                // Partial iteration: keep the lines from the last completed depth
+                root_moves = previous;
+                break;
+            }
 
-            if let Some(&first_move) = pv.first() {
-                best_move = first_move;
+            if completed_depth > 0 {
+                swing = root_moves[0].score - previous[0].score;
             }
-        }
+            completed_depth = depth;
 
-        let pv = self.extract_pv(board, max_depth);
+            let (time_ms, nps) = timing(self.nodes);
 
-        SearchResult {
-            best_move,
-            score: best_score,
-            depth: max_depth,
-            nodes: self.nodes,
-            pv,
-            multi_pv: Vec::new(),
-        }
-    }
+            for (index, rm) in root_moves.iter().take(num_pv).enumerate() {
+                callback(SearchInfo {
+                    id: String::new(), // ID will be set by EngineImpl
+                    depth,
+                    seldepth: Some(self.seldepth.max(depth)),
+                    nodes: self.nodes,
+                    nps,
+                    time_ms,
+                    score: self.score_to_protocol(rm.score),
+                    pv: rm.pv.iter().map(|m| format!("{}", m)).collect(),
+                    hashfull: Some(self.tt.hashfull() as u32),
+                    tb_hits: None,
+                    multipv: Some(index as u32 + 1),
+                    bound: None,
+                    score_pov: ScorePov::SideToMove,
+                    wdl: None,
+                    partial: false,
+                    pv_terminated: rm.pv_end,
+                });
+            }
 
-    /// Search at the root with excluded moves (for multi-PV).
-    fn search_root_excluding(&mut self, board: &Board, depth: u32, excluded: &[Move]) -> i32 {
-        let all_moves = board.generate_legal_moves();
+            // "go mate N": stop as soon as a short enough mate is proven
+            if self.mate_limit_reached(root_moves[0].score) {
+                break;
+            }
+        }
 
-        // Filter out excluded moves
-        let mut legal_moves = crate::movelist::MoveList::new();
-        for m in all_moves.iter() {
-            if !excluded.contains(m) {
-                legal_moves.push(*m);
+        if self.is_stopped() {
+            let (time_ms, nps) = timing(self.nodes);
+            for (index, rm) in root_moves.iter().take(num_pv).enumerate() {
+                let score = if completed_depth > 0 { rm.score } else { 0 };
+                callback(SearchInfo {
+                    multipv: Some(index as u32 + 1),
+                    partial: true,
+                    pv_terminated: rm.pv_end,
+                    ..self.search_info(completed_depth, score, &rm.pv, time_ms, nps, None)
+                });
             }
         }
 
-        if legal_moves.is_empty() {
-            return if board.is_in_check() { -MATE_SCORE } else { 0 };
+        let multi_pv: Vec<PVLine> = root_moves
+            .iter()
+            .take(num_pv)
+            .map(|rm| PVLine {
+                // Nothing completed (stopped during depth 1): no real score yet
+                score: if completed_depth > 0 { rm.score } else { 0 },
+                pv: rm.pv.clone(),
+            })
+            .collect();
+
+        // Reduced skill: possibly play one of the other top moves
+        let mut index = self.skill.pick(board.hash(), root_moves.len());
+        // Opening variety: otherwise maybe one of the near-best lines
+        if index == 0 && use_variety && completed_depth > 0 {
+            let scores: Vec<i32> = root_moves
+                .iter()
+                .take(search_lines)
+                .map(|rm| rm.score)
+                .collect();
+            index = self.variety.pick(&scores, swing);
         }
+        let chosen = &root_moves[index];
 
-        // Order moves (using TT move from previous iteration if available)
-        let tt_move = self.tt.probe(board.hash()).map(|e| e.best_move);
-        self.move_order
-            .order_moves(board, &mut legal_moves, 0, tt_move, None);
+        SearchResult {
+            best_move: chosen.mv,
+            score: if completed_depth > 0 { chosen.score } else { 0 },
+            depth: completed_depth,
+            nodes: self.nodes,
+            pv: chosen.pv.clone(),
+            multi_pv,
+        }
+    }
 
-        let mut best_score = -INFINITY;
-        let mut best_move = legal_moves[0];
-        let mut alpha = -INFINITY;
-        let beta = INFINITY;
+    /// Search all root moves once at the given depth (for multi-PV).
+    ///
+    /// Leaves `root_moves` sorted best-first with exact scores and PVs for
+    /// the top `num_pv` entries. Moves outside the top N only carry an
+    /// upper bound. Returns early (leaving the list unsorted) if stopped.
+    fn search_root_multi_pv(
+        &mut self,
+        board: &Board,
+        depth: u32,
+        root_moves: &mut RootMoves,
+        num_pv: usize,
+    ) {
+        debug_assert!(board.is_searchable(), "{:?}", board.check_searchable());
+        root_moves.start_iteration();
 
-        for m in legal_moves.iter() {
+        // Exact scores of the best lines found so far, best first
+        let mut top_scores: Vec<i32> = Vec::with_capacity(num_pv + 1);
+        let child_depth = depth as i32 - 1;
+
+        for (index, rm) in root_moves.iter_mut().enumerate() {
             let mut new_board = board.clone();
-            new_board.make_move(*m);
+            new_board.make_move(rm.mv);
+            self.tt.prefetch(new_board.hash());
+
+            let score = if top_scores.len() < num_pv {
+                // Still filling the top N: every line needs an exact score.
+                // The first N moves were last iteration's exact lines, so
+                // past the first few depths their old score is a good guess.
+                let guess = (depth > 4 && index < num_pv).then_some(rm.previous_score);
+                self.search_root_line(&new_board, child_depth, rm.mv, guess, -INFINITY)
+            } else {
+                // Only need to know whether this move beats the N-th best line.
+                // Late quiet moves are first tried a ply shallower, as late
+                // moves are reduced inside the tree, and only go to full
+                // depth if that doesn't already rule them out.
+                let threshold = top_scores[num_pv - 1];
+                let mv = rm.mv;
+                let null_window = |searcher: &mut Self, depth: i32| {
+                    -searcher.negamax(
+                        &new_board,
+                        depth,
+                        -threshold - 1,
+                        -threshold,
+                        1,
+                        Some(mv),
+                        0,
+                        false,
+                    )
+                };
+                let reduce = index > num_pv
+                    && child_depth >= 3
+                    && !mv.is_capture()
+                    && !mv.is_promotion()
+                    && !board.is_in_check()
+                    && !new_board.is_in_check();
+                let mut score = threshold + 1;
+                if reduce {
+                    score = null_window(self, child_depth - 1);
+                }
+                if score > threshold {
+                    score = null_window(self, child_depth);
+                }
 
-            let score = -self.negamax(&new_board, depth as i32 - 1, -beta, -alpha, 1, Some(*m), 0, false);
+                if score > threshold {
+                    // Entered the top N: re-search for an exact score, which
+                    // is known to lie above the threshold
+                    self.search_root_line(&new_board, child_depth, rm.mv, Some(score), threshold)
+                } else {
+                    score
+                }
+            };
 
-            if score > best_score {
-                best_score = score;
-                best_move = *m;
+            if self.is_stopped() {
+                return;
             }
 
-            alpha = alpha.max(score);
+            rm.score = score;
+
+            if top_scores.len() < num_pv || score > top_scores[num_pv - 1] {
+                let pos = top_scores.partition_point(|&s| s >= score);
+                top_scores.insert(pos, score);
+                top_scores.truncate(num_pv);
+
+                let (pv, pv_end) = self.extract_pv(&new_board, depth.saturating_sub(1));
+                rm.pv = std::iter::once(rm.mv).chain(pv).collect();
+                rm.pv_end = Some(pv_end);
+            }
         }
 
-        // Store best move in TT
-        self.tt.store(
-            board.hash(),
-            best_move,
-            best_score,
-            depth as u8,
-            Bound::Exact,
-        );
+        root_moves.sort();
 
-        best_score
+        let best = &root_moves[0];
+        self.tt
+            .store(board.hash(), best.mv, best.score, depth as u8, Bound::Exact);
+    }
+
+    /// Exact score of the root move `mv`, which led to `child`.
+    ///
+    /// With a `guess`, the line is searched in an aspiration window around
+    /// it, widened on failure like the main search's; without one, in a
+    /// full window. Either way the window never opens below `floor`, a
+    /// score the line is already known to beat.
+    fn search_root_line(
+        &mut self,
+        child: &Board,
+        depth: i32,
+        mv: Move,
+        guess: Option<i32>,
+        floor: i32,
+    ) -> i32 {
+        let Some(guess) = guess else {
+            return -self.negamax(child, depth, -INFINITY, -floor, 1, Some(mv), 0, false);
+        };
+        let mut delta = search_params::get_search_params().aspiration_delta;
+        let mut alpha = (guess - delta).max(floor);
+        let mut beta = (guess + delta).min(INFINITY);
+        loop {
+            let score = -self.negamax(child, depth, -beta, -alpha, 1, Some(mv), 0, false);
+            if self.is_stopped() {
+                return score;
+            }
+            if score <= alpha && alpha > floor {
+                alpha = if delta > 500 {
+                    floor
+                } else {
+                    (score - delta).max(floor)
+                };
+            } else if score >= beta && beta < INFINITY {
+                beta = if delta > 500 {
+                    INFINITY
+                } else {
+                    (score + delta).min(INFINITY)
+                };
+            } else {
+                return score;
+            }
+            delta = delta.saturating_mul(2);
+        }
     }
 
     /// Search at the root (find best move at current depth).
@@ -513,8 +1209,9 @@ impl Searcher {
     /// Search at the root with custom alpha-beta window.
     /// Used for aspiration windows.
     fn search_root_window(&mut self, board: &Board, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+        debug_assert!(board.is_searchable(), "{:?}", board.check_searchable());
         let original_alpha = alpha;
-        let mut legal_moves = board.generate_legal_moves();
+        let mut legal_moves = self.root_legal_moves(board);
 
         if legal_moves.is_empty() {
             return if board.is_in_check() { -MATE_SCORE } else { 0 };
@@ -531,6 +1228,7 @@ impl Searcher {
         for m in legal_moves.iter() {
             let mut new_board = board.clone();
             new_board.make_move(*m);
+            self.tt.prefetch(new_board.hash());
 
             let score = -self.negamax(&new_board, depth as i32 - 1, -beta, -alpha, 1, Some(*m), 0, false);
 
@@ -563,49 +1261,58 @@ impl Searcher {
     }
 
     /// Verify if a move is singular (much better than all alternatives).
-    /// Used for singular extensions.
-    fn verify_singular(&mut self, board: &Board, tt_move: Move, beta: i32, depth: i32, extensions_used: i32) -> bool {
+    /// Used for singular extensions, at the node `ply` from the root.
+    fn verify_singular(
+        &mut self,
+        board: &Board,
+        tt_move: Move,
+        beta: i32,
+        depth: i32,
+        ply: u32,
+        extensions_used: i32,
+    ) -> bool {
         // Search at reduced depth excluding the TT move
-        let all_moves = board.generate_legal_moves();
-
-        // Build a new list excluding the TT move
-        let mut legal_moves = crate::movelist::MoveList::new();
-        for m in all_moves.iter() {
-            if *m != tt_move {
-                legal_moves.push(*m);
-            }
-        }
+        let mut legal_moves = board.generate_legal_moves();
+        legal_moves.retain(|&m| m != tt_move);
 
         if legal_moves.is_empty() {
             return true; // Only move available is singular by definition
         }
 
         // Order remaining moves
-        self.move_order.order_moves(board, &mut legal_moves, 0, None, None);
+        self.move_order
+            .order_moves(board, &mut legal_moves, ply as usize, None, None);
 
         let mut alpha = beta - 1;
 
         // Search all other moves at reduced depth
+        let mut singular = true;
         for m in legal_moves.iter() {
             let mut new_board = board.clone();
             new_board.make_move(*m);
+            self.tt.prefetch(new_board.hash());
 
-            let score = -self.negamax(&new_board, depth - 1, -beta, -alpha, 0, Some(*m), extensions_used, true);
+            let score = -self.negamax(&new_board, depth - 1, -beta, -alpha, ply + 1, Some(*m), extensions_used, true);
 
             // If any move reaches beta, TT move is not singular
             if score >= beta {
-                return false;
+                singular = false;
+                break;
             }
 
             alpha = alpha.max(score);
         }
 
-        // All other moves failed low - TT move is singular
-        true
+        // Singular if all other moves failed low
+        singular
     }
 
-    /// Extract principal variation from transposition table.
-    fn extract_pv(&self, board: &Board, max_depth: u32) -> Vec<Move> {
+    /// Extract principal variation from transposition table, and why it
+    /// ends.
+    ///
+    /// A line that returns to one of its positions is kept up to the
+    /// repeated position, so a perpetual shows its whole cycle.
+    fn extract_pv(&self, board: &Board, max_depth: u32) -> (Vec<Move>, PvEnd) {
         let mut pv = Vec::new();
         let mut current_board = board.clone();
         let mut seen_positions = std::collections::HashSet::new();
@@ -615,26 +1322,28 @@ impl Searcher {
 
             // Avoid cycles
             if !seen_positions.insert(hash) {
-                break;
+                return (pv, PvEnd::Repetition);
             }
 
             // Probe TT for best move
-            if let Some(entry) = self.tt.probe(hash) {
-                let m = entry.best_move;
-
-                // Verify move is legal
-                if !current_board.is_legal(m) {
-                    break;
-                }
+            let Some(entry) = self.tt.probe(hash) else {
+                return (pv, PvEnd::TTMiss);
+            };
+            let m = entry.best_move;
 
-                pv.push(m);
-                current_board.make_move(m);
-            } else {
-                break;
+            // Verify move is legal (quiescence entries may have no move)
+            if current_board.make_move_if_legal(m).is_none() {
+                return (pv, PvEnd::IllegalTTMove);
             }
+
+            pv.push(m);
         }
 
-        pv
+        // The last move may still have closed a cycle
+        if seen_positions.contains(&current_board.hash()) {
+            return (pv, PvEnd::Repetition);
+        }
+        (pv, PvEnd::MaxLen)
     }
 
     /// Negamax search with alpha-beta pruning.
@@ -659,43 +1368,56 @@ impl Searcher {
         extensions_used: i32,
         in_singular_verification: bool,
     ) -> i32 {
-        self.nodes += 1;
-
-        // Check time limits periodically (every 1024 nodes)
-        if self.nodes % 1024 == 0 {
-            if let Some(tm) = &self.time_manager {
-                if tm.must_stop() {
-                    self.stopped.store(true, Ordering::Relaxed);
-                    return 0; // Return early with neutral score
-                }
-            }
+        // If we've been stopped, return immediately
+        if self.enter_node() {
+            return 0;
         }
 
-        // If we've been stopped, return immediately
-        if self.stopped.load(Ordering::Relaxed) {
+        // Repetitions and the 50-move rule end the game in a draw
+        if ply > 0
+            && (self.is_repetition(board)
+                || (board.halfmove_clock() >= 100 && !board.is_in_check()))
+        {
             return 0;
         }
 
+        // Too deep for the per-ply tables (and for small WASM stacks)
+        self.seldepth = self.seldepth.max(ply);
+        if ply as usize >= MAX_PLY - 1 {
+            let eval = self.evaluate(board);
+            return self.apply_contempt(eval);
+        }
+
+        // Mate distance pruning: being mated here is no worse than
+        // -MATE_SCORE + ply, and mating takes at least one more ply, so
+        // nothing can beat a shorter mate already found nearer the root
+        alpha = alpha.max(-MATE_SCORE + ply as i32);
+        beta = beta.min(MATE_SCORE - ply as i32 - 1);
+        if alpha >= beta {
+            return alpha;
+        }
+
         let original_alpha = alpha;
         let hash = board.hash();
 
         // Probe transposition table
         let mut tt_move = if let Some(tt_entry) = self.tt.probe(hash) {
             if tt_entry.depth >= depth as u8 {
+                let tt_score = score_from_tt(tt_entry.score, ply);
                 match tt_entry.bound {
-                    Bound::Exact => return tt_entry.score,
+                    Bound::Exact => return tt_score,
                     Bound::Lower => {
-                        alpha = alpha.max(tt_entry.score);
+                        alpha = alpha.max(tt_score);
                     }
                     Bound::Upper => {
-                        if tt_entry.score <= alpha {
-                            return tt_entry.score;
+                        if tt_score <= alpha {
+                            return tt_score;
                         }
-                        beta = beta.min(tt_entry.score);
+                        beta = beta.min(tt_score);
                     }
                 }
                 if alpha >= beta {
-                    return tt_entry.score;
+                    return tt_score;
                 }
             }
             Some(tt_entry.best_move)
@@ -713,12 +1435,12 @@ impl Searcher {
             if is_pv {
                 // IID: Do shallow search to populate TT with a good move
                 let iid_depth = depth - params.iid_depth_reduction;
-                self.negamax(board, iid_depth, alpha, beta, ply, None, extensions_used, false);
-
-                // Re-probe TT to get the move
-                if let Some(tt_entry) = self.tt.probe(hash) {
-                    tt_move = Some(tt_entry.best_move);
-                }
+                tt_move = self.iid_search(board, iid_depth, alpha, beta, ply, extensions_used);
+            } else if depth >= params.iid_cut_min_depth && self.evaluate(board) >= beta {
+                // Expected cut node: a good first move fails high at once, so
+                // finding one with a much shallower search pays for itself
+                let iid_depth = depth - params.iid_cut_depth_reduction;
+                tt_move = self.iid_search(board, iid_depth, alpha, beta, ply, extensions_used);
             } else {
                 // IIR: Reduce depth when we have no TT move in non-PV nodes
                 depth -= params.iir_depth_reduction;
@@ -728,29 +1450,34 @@ impl Searcher {
         // Null move pruning
         // Try "passing" the turn - if position is still winning, we can skip full search
         // Conditions:
-        // - Not in check (zugzwang risk)
+        // - Not in check (checked first: cheaper than cloning the board for
+        //   try_make_null_move, which would refuse anyway)
         // - Sufficient depth (need depth for reduced search)
         // - Not in endgame (zugzwang risk)
         // - Beta is not a mate score (avoid mate score distortion)
         let in_check = board.is_in_check();
+        let improving = self.update_improving(board, ply, in_check);
         let params = search_params::get_search_params();
-        if depth >= params.null_move_min_depth
-            && !in_check
+        if !in_check
+            && depth >= params.null_move_min_depth
             && !crate::eval::is_endgame(board)
             && beta.abs() < MATE_SCORE - MAX_DEPTH as i32
         {
             let r = params.null_move_r; // Reduction factor
 
             let mut null_board = board.clone();
-            null_board.make_null_move();
-
-            // Search with reduced depth and null window around beta
-            let null_score =
-                -self.negamax(&null_board, depth - 1 - r, -beta, -beta + 1, ply + 1, None, extensions_used, false);
-
-            // If null move fails high, position is too good - prune this branch
-            if null_score >= beta {
-                return beta;
+            if null_board.try_make_null_move().is_some() {
+                // Search with reduced depth and null window around beta, with
+                // this node on the path so lines below can repeat it
+                self.path.push(hash);
+                let null_score =
+                    -self.negamax(&null_board, depth - 1 - r, -beta, -beta + 1, ply + 1, None, extensions_used, false);
+                self.path.pop();
+
+                // If null move fails high, position is too good - prune this branch
+                if null_score >= beta {
+                    return beta;
+                }
             }
         }
 
@@ -758,7 +1485,7 @@ impl Searcher {
         // If our position is so good that even with a margin we're above beta, prune
         let is_pv = beta - alpha > 1;
         if !in_check && !is_pv && depth <= 5 {
-            let eval = self.evaluator.evaluate(board);
+            let eval = self.evaluate(board);
             let eval = self.apply_contempt(eval);
             let (can_prune, score) = crate::search::pruning::can_reverse_futility_prune(
                 depth, in_check, is_pv, eval, beta,
@@ -771,10 +1498,10 @@ impl Searcher {
         // M7: Razoring
         // If position is hopeless even with a margin, drop into qsearch
         if !in_check && !is_pv && (1..=3).contains(&depth) {
-            let eval = self.evaluator.evaluate(board);
+            let eval = self.evaluate(board);
             let eval = self.apply_contempt(eval);
             if crate::search::pruning::can_razor(depth, in_check, is_pv, eval, alpha) {
-                let q_score = self.quiesce(board, alpha, beta);
+                let q_score = self.quiesce(board, alpha, beta, ply, 0);
                 if q_score < alpha {
                     return q_score;
                 }
@@ -783,7 +1510,7 @@ impl Searcher {
 
         // Leaf node: enter quiescence search
         if depth <= 0 {
-            return self.quiesce(board, alpha, beta);
+            return self.quiesce(board, alpha, beta, ply, 0);
         }
 
         let mut legal_moves = board.generate_legal_moves();
@@ -817,7 +1544,7 @@ impl Searcher {
                     let singular_beta = beta - 100; // Margin for singularity
                     let singular_depth = depth - 4; // Reduced depth for verification
 
-                    if self.verify_singular(board, tt_mv, singular_beta, singular_depth, extensions_used) {
+                    if self.verify_singular(board, tt_mv, singular_beta, singular_depth, ply, extensions_used) {
                         singular_ext = 1;
                     }
                 }
@@ -827,16 +1554,31 @@ impl Searcher {
         // M7: Futility pruning - skip quiet moves if position is hopeless
         let futility_prune = !in_check && !is_pv && depth <= 3;
         let futility_eval = if futility_prune {
-            let eval = self.evaluator.evaluate(board);
+            let eval = self.evaluate(board);
             self.apply_contempt(eval)
         } else {
             0
         };
 
+        // Checks are what the check extension extends: keep them out of the
+        // late move and futility pruning below
+        let checking_moves = if !in_check && depth <= 3 {
+            generate_checking_moves(board)
+        } else {
+            MoveList::new()
+        };
+
+        self.path.push(hash);
         for (move_count, m) in legal_moves.iter().enumerate() {
+            let gives_check = checking_moves.iter().any(|c| c == m);
+
             // M7: Late Move Pruning (LMP)
             // Skip late quiet moves at low depths
-            if crate::search::pruning::can_late_move_prune(depth, in_check, move_count, *m) {
+            if !gives_check
+                && crate::search::pruning::can_late_move_prune(
+                    depth, in_check, move_count, *m, improving,
+                )
+            {
                 continue;
             }
 
@@ -849,6 +1591,7 @@ impl Searcher {
             // M7: Futility Pruning
             // Skip quiet moves when position is hopeless
             if futility_prune
+                && !gives_check
                 && !m.is_capture()
                 && !m.is_promotion()
                 && crate::search::pruning::can_futility_prune(
@@ -857,6 +1600,7 @@ impl Searcher {
                     is_pv,
                     futility_eval,
                     alpha,
+                    improving,
                 )
             {
                 continue;
@@ -864,6 +1608,7 @@ impl Searcher {
 
             let mut new_board = board.clone();
             new_board.make_move(*m);
+            self.tt.prefetch(new_board.hash());
 
             // M7: Calculate extensions
             let in_check_after = new_board.is_in_check();
@@ -891,26 +1636,51 @@ impl Searcher {
             // Conditions:
             // 1. Not the first few moves (move_count >= 3)
             // 2. Sufficient depth (depth >= 3)
-            // 3. Not a tactical move (capture, promotion, gives check)
+            // 3. Not a tactical move (capture that doesn't lose material,
+            //    promotion, gives check)
             // 4. Not currently in check
             // 5. No extension applied (don't reduce extended moves)
+            // 6. Not the TT move, when the log table or history adjustment
+            //    is on (they can reduce more, so never apply them to it)
+            let refined_lmr = params.lmr_log_table != 0 || params.lmr_history_adjust != 0;
             let can_reduce = move_count >= 3
                 && depth >= 3
-                && !m.is_capture()
+                && !(m.is_capture() && board.see_sign(*m) >= 0)
                 && !m.is_promotion()
                 && !in_check_after
                 && !in_check
-                && extension == 0;
+                && extension == 0
+                && !(refined_lmr && Some(*m) == tt_move);
 
-            if can_reduce {
+            let reduction = if can_reduce {
                 // Calculate reduction amount using tunable parameters
-                let params = search_params::get_search_params();
-                let reduction = if move_count >= params.lmr_move_threshold && depth >= params.lmr_depth_threshold {
+                let mut reduction = if params.lmr_log_table != 0 {
+                    crate::search::lmr::get_reduction(depth, move_count).max(1)
+                } else if move_count >= params.lmr_move_threshold && depth >= params.lmr_depth_threshold {
                     params.lmr_base_reduction
                 } else {
                     1 // Reduce by 1 ply
                 };
+                if params.lmr_history_adjust != 0 {
+                    reduction += self.move_order.lmr_adjustment(
+                        board,
+                        *m,
+                        ply as usize,
+                        prev_move,
+                        params.lmr_history_divisor,
+                    );
+                }
+                if refined_lmr {
+                    // Always leave at least one ply to search
+                    reduction.clamp(0, next_depth - 1)
+                } else {
+                    reduction
+                }
+            } else {
+                0
+            };
 
+            if reduction > 0 {
                 // Search at reduced depth with null window
                 score = -self.negamax(
                     &new_board,
@@ -980,56 +1750,165 @@ impl Searcher {
 
                     // M7: Update continuation history if we have a previous move
                     if let Some(pm) = prev_move {
-                        self.move_order.update_continuation_history(pm, *m, depth);
+                        self.move_order
+                            .update_continuation_history(board, pm, *m, depth);
                     }
                 }
                 break;
             }
         }
 
-        // Store in transposition table
-        let bound = if best_score >= beta {
-            Bound::Lower // Beta cutoff
-        } else if best_score > original_alpha {
-            Bound::Exact // PV node
-        } else {
-            Bound::Upper // All-node (fail-low)
-        };
+        self.path.pop();
+
+        // Store in transposition table
+        let bound = if best_score >= beta {
+            Bound::Lower // Beta cutoff
+        } else if best_score > original_alpha {
+            Bound::Exact // PV node
+        } else {
+            Bound::Upper // All-node (fail-low)
+        };
+
+        self.tt.store(
+            hash,
+            best_move,
+            score_to_tt(best_score, ply),
+            depth as u8,
+            bound,
+        );
+
+        best_score
+    }
+
+    /// Whether `board` repeats a position of the game history or of the
+    /// current line. Only positions since the last irreversible move with
+    /// the same side to move can match.
+    fn is_repetition(&self, board: &Board) -> bool {
+        let hash = board.hash();
+        self.path
+            .iter()
+            .rev()
+            .take(board.halfmove_clock() as usize)
+            .skip(1)
+            .step_by(2)
+            .any(|&h| h == hash)
+    }
+
+    /// Internal iterative deepening: search this node at a reduced `depth`
+    /// to fill the TT, and return the best move it found (if the search
+    /// wasn't stopped) so the full-depth search can try it first.
+    fn iid_search(
+        &mut self,
+        board: &Board,
+        depth: i32,
+        alpha: i32,
+        beta: i32,
+        ply: u32,
+        extensions_used: i32,
+    ) -> Option<Move> {
+        self.negamax(board, depth, alpha, beta, ply, None, extensions_used, false);
+        if self.is_stopped() {
+            return None;
+        }
+        self.tt.probe(board.hash()).map(|entry| entry.best_move)
+    }
+
+    /// Count a node and check the search limits.
+    ///
+    /// Shared by `negamax` and `quiesce`, so quiescence nodes are counted and
+    /// limited exactly like main search nodes. Returns true if the search has
+    /// been stopped and the node must return immediately without being counted.
+    ///
+    /// The clock is read every 1024 nodes; the node limit is a plain compare
+    /// and is checked on every node. Once the limit is hit the stop flag is set
+    /// and no further nodes are counted, so a `TimeControl::Nodes` search ends
+    /// with `nodes <= limit` (epsilon 0).
+    #[inline]
+    fn enter_node(&mut self) -> bool {
+        if self.is_stopped() {
+            return true;
+        }
+
+        self.nodes += 1;
 
-        self.tt
-            .store(hash, best_move, best_score, depth as u8, bound);
+        if let Some(tm) = &self.time_manager {
+            let out_of_time = self.nodes.is_multiple_of(1024) && tm.must_stop();
+            if out_of_time || tm.node_limit_reached(self.nodes) {
+                self.aborted = true;
+            }
+        }
 
-        best_score
+        false
     }
 
     /// Quiescence search to avoid horizon effect.
     ///
-    /// Only searches tactical moves (captures) to reach a quiet position.
-    fn quiesce(&mut self, board: &Board, mut alpha: i32, beta: i32) -> i32 {
-        self.nodes += 1;
-
+    /// Only searches tactical moves (queen promotions and captures) to reach
+    /// a quiet position. Queen promotions go first, then captures ordered by
+    /// SEE with the TT move first, and results are
+    /// stored in the TT as depth 0 entries, which only ever satisfy other
+    /// quiescence (depth 0) probes.
+    ///
+    /// `ply` is the distance from the root and `qply` the distance from the
+    /// main search's leaf. Long capture chains stop at the stand pat once
+    /// either reaches its cap ([`MAX_PLY`], `max_qsearch_ply`).
+    fn quiesce(&mut self, board: &Board, mut alpha: i32, beta: i32, ply: u32, qply: u32) -> i32 {
         // If we've been stopped, return immediately
-        if self.stopped.load(Ordering::Relaxed) {
+        if self.enter_node() {
             return 0;
         }
+        self.seldepth = self.seldepth.max(ply);
+
+        // Any entry is deep enough for a quiescence probe
+        let hash = board.hash();
+        let tt_move = match self.tt.probe(hash) {
+            Some(entry) => {
+                let tt_score = score_from_tt(entry.score, ply);
+                match entry.bound {
+                    Bound::Exact => return tt_score.clamp(alpha, beta),
+                    Bound::Lower if tt_score >= beta => return beta,
+                    Bound::Upper if tt_score <= alpha => return alpha,
+                    _ => {}
+                }
+                Some(entry.best_move)
+            }
+            None => None,
+        };
 
         // Stand pat: assume we can maintain current evaluation
-        let stand_pat = self.evaluator.evaluate(board);
+        let stand_pat = self.evaluate_lazy(board, alpha, beta);
         let stand_pat = self.apply_contempt(stand_pat);
 
         if stand_pat >= beta {
             return beta;
         }
 
+        if qply >= self.max_qsearch_ply || ply as usize >= MAX_PLY - 1 {
+            return stand_pat;
+        }
+
+        let original_alpha = alpha;
         if stand_pat > alpha {
             alpha = stand_pat;
         }
 
-        // Generate and search only captures
-        let moves = board.generate_legal_moves();
-        let captures: Vec<Move> = moves.iter().filter(|m| m.is_capture()).copied().collect();
+        let mut moves = board.generate_legal_moves();
+
+        // Stalemated: a draw, however far behind the stand pat says we are.
+        // Keeps a capture that stalemates a lone king from looking winning.
+        if moves.is_empty() && !board.is_in_check() {
+            return 0;
+        }
+
+        // Queen promotions first, quiet ones included so a pawn about to
+        // queen is not left to the horizon, then the other captures best first
+        let mut promotions = generate_queen_promotions(board);
+        promotions.retain(|&m| board.is_legal(m));
+        moves.retain(|m| m.is_capture() && m.promotion_piece() != Some(PieceType::Queen));
+        self.move_order.order_captures(board, &mut moves, tt_move);
 
-        for m in captures {
+        let mut best_move = Move::null();
+        for m in promotions.into_iter().chain(moves) {
             // M7: SEE Pruning in qsearch - skip clearly bad captures
             if crate::search::pruning::can_see_prune(board, m, true) {
                 continue;
@@ -1038,17 +1917,43 @@ impl Searcher {
             let mut new_board = board.clone();
             new_board.make_move(m);
 
-            let score = -self.quiesce(&new_board, -beta, -alpha);
+            let score = -self.quiesce(&new_board, -beta, -alpha, ply + 1, qply + 1);
 
             if score >= beta {
+                self.store_quiesce(hash, m, beta, Bound::Lower, ply);
                 return beta;
             }
 
-            alpha = alpha.max(score);
+            if score > alpha {
+                alpha = score;
+                best_move = m;
+            }
         }
 
+        let bound = if alpha > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.store_quiesce(hash, best_move, alpha, bound, ply);
+
         alpha
     }
+
+    /// Store a quiescence result found at `ply` as a depth 0 TT entry.
+    ///
+    /// Never overwrites a main search entry for the same position, since
+    /// that one is more valuable and also serves quiescence probes.
+    fn store_quiesce(&mut self, hash: u64, best_move: Move, score: i32, bound: Bound, ply: u32) {
+        if self.is_stopped() {
+            return;
+        }
+        if self.tt.probe(hash).is_some_and(|entry| entry.depth > 0) {
+            return;
+        }
+        self.tt
+            .store(hash, best_move, score_to_tt(score, ply), 0, bound);
+    }
 }
 
 impl Default for Searcher {
@@ -1061,6 +1966,7 @@ impl Default for Searcher {
 mod tests {
     use super::*;
     use crate::io::parse_fen;
+    use crate::time::ManualClock;
 
     #[test]
     fn test_search_startpos() {
@@ -1386,6 +2292,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_aspiration_fail_high_reports_lower_bound() {
+        // WAC.009: the winning Rh1+ shows up at depth 5, well above the
+        // depth 4 score
+        let board =
+            parse_fen("3q1rk1/p4pp1/2pb3p/3p4/6Pr/1PNQ4/P1PB1PP1/4RRK1 b - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let mut infos = Vec::new();
+        searcher.search_with_limit_callback(&board, 6, TimeControl::Infinite, |info| {
+            infos.push(info)
+        });
+
+        let lower = infos
+            .iter()
+            .position(|info| info.bound == Some(ScoreBound::Lower))
+            .expect("no lowerbound info");
+        let exact = infos[lower + 1..]
+            .iter()
+            .find(|info| info.bound.is_none())
+            .expect("no exact score after the lowerbound");
+        assert_eq!(exact.depth, infos[lower].depth);
+
+        // Every depth ends with exactly one exact score
+        let exact_depths: Vec<u32> = infos
+            .iter()
+            .filter(|info| info.bound.is_none())
+            .map(|info| info.depth)
+            .collect();
+        assert_eq!(exact_depths, (1..=6).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_multi_pv_basic() {
         // Test Multi-PV finds multiple best moves
@@ -1468,6 +2406,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_pv_scores_ordered_every_depth() {
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let board = parse_fen(fen).unwrap();
+
+        let mut searcher = Searcher::new();
+        let mut infos = Vec::new();
+        let result = searcher.search_multi_pv_with_limit_callback(
+            &board,
+            5,
+            3,
+            TimeControl::Infinite,
+            |info| infos.push(info),
+        );
+
+        assert_eq!(result.depth, 5);
+        assert_eq!(infos.len(), 5 * 3, "Three lines per depth");
+
+        let as_cp = |score: &Score| match score {
+            Score::Cp { value } => *value,
+            Score::Mate { plies } if *plies > 0 => MATE_SCORE - plies,
+            Score::Mate { plies } => -MATE_SCORE - plies,
+        };
+
+        for lines in infos.chunks(3) {
+            let depth = lines[0].depth;
+            for (i, info) in lines.iter().enumerate() {
+                assert_eq!(info.depth, depth);
+                assert_eq!(info.multipv, Some(i as u32 + 1));
+                assert!(!info.pv.is_empty());
+            }
+            assert!(as_cp(&lines[0].score) >= as_cp(&lines[1].score));
+            assert!(as_cp(&lines[1].score) >= as_cp(&lines[2].score));
+        }
+
+        // Final lines match the last reported depth
+        for (line, info) in result.multi_pv.iter().zip(&infos[12..]) {
+            assert_eq!(format!("{}", line.pv[0]), info.pv[0]);
+        }
+    }
+
+    #[test]
+    fn test_multi_pv_cheaper_than_repeated_search() {
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let board = parse_fen(fen).unwrap();
+
+        let mut single = Searcher::new();
+        let single_result = single.search(&board, 6);
+
+        let mut multi = Searcher::new();
+        let multi_result = multi.search_multi_pv(&board, 6, 3);
+
+        assert_eq!(multi_result.multi_pv.len(), 3);
+        assert!(
+            multi_result.nodes < 2 * single_result.nodes,
+            "MultiPV=3 used {} nodes, single PV {}",
+            multi_result.nodes,
+            single_result.nodes
+        );
+    }
+
+    /// A searcher without the opening book, reading time from `clock`.
+    fn searcher_on(clock: &Arc<ManualClock>) -> Searcher {
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        searcher.set_clock(clock.clone());
+        searcher
+    }
+
+    #[test]
+    fn test_multi_pv_respects_time_control() {
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let board = parse_fen(fen).unwrap();
+
+        // The soft limit (50ms) is reached while depth 2 completes
+        let clock = Arc::new(ManualClock::new());
+        let mut searcher = searcher_on(&clock);
+        let result = searcher.search_multi_pv_with_limit_callback(
+            &board,
+            MAX_DEPTH,
+            3,
+            TimeControl::MoveTime { millis: 100 },
+            |info| {
+                if info.depth == 2 {
+                    clock.set(50);
+                }
+            },
+        );
+
+        assert_eq!(result.depth, 2);
+        assert_eq!(result.multi_pv.len(), 3);
+        for line in &result.multi_pv {
+            assert!(board.is_legal(line.pv[0]));
+        }
+    }
+
     #[test]
     fn test_multi_pv_single_pv_fallback() {
         // Test that requesting 1 PV falls back to regular search
@@ -1486,17 +2520,52 @@ mod tests {
 
     #[test]
     fn test_time_limited_search_move_time() {
-        // Test fixed time per move
+        // Fixed time per move: the soft limit is 50ms before the hard one
         let board = Board::startpos();
-        let mut searcher = Searcher::new();
-
-        let time_control = TimeControl::MoveTime { millis: 100 };
-        let result = searcher.search_with_limit(&board, 10, time_control);
+        let time_control = TimeControl::MoveTime { millis: 1000 };
+        let search_until = |soft_limit_at: u32, clock_ms: u64| {
+            let clock = Arc::new(ManualClock::new());
+            let mut searcher = searcher_on(&clock);
+            searcher.search_with_limit_callback(&board, 10, time_control.clone(), |info| {
+                if info.depth == soft_limit_at {
+                    clock.set(clock_ms);
+                }
+            })
+        };
 
-        // Should find a legal move
+        // Once the soft budget is used up, the next depth is not started
+        let result = search_until(3, 950);
+        assert_eq!(result.depth, 3);
         assert!(board.is_legal(result.best_move));
-        // Should have stopped before reaching depth 10 (100ms is too short for depth 10)
-        assert!(result.depth < 10);
+
+        // One millisecond short of it, depth 4 still runs
+        let result = search_until(3, 949);
+        assert!(result.depth > 3);
+    }
+
+    #[test]
+    fn test_hard_limit_stops_within_one_check_interval() {
+        let clock = Arc::new(ManualClock::new());
+        let mut searcher = searcher_on(&clock);
+        searcher.time_manager = Some(TimeManager::with_clock(
+            TimeControl::MoveTime { millis: 100 },
+            true,
+            clock.clone(),
+        ));
+
+        for _ in 0..1500 {
+            assert!(!searcher.enter_node());
+        }
+        assert!(!searcher.is_stopped());
+
+        // The clock is next read at node 2048, which is the last one counted
+        clock.set(100);
+        while !searcher.is_stopped() {
+            searcher.enter_node();
+        }
+        assert_eq!(searcher.nodes, 2048);
+        assert!(searcher.enter_node());
+        assert_eq!(searcher.nodes, 2048);
     }
 
     #[test]
@@ -1522,13 +2591,61 @@ mod tests {
         let time_control = TimeControl::Nodes { nodes: 1000 };
         let result = searcher.search_with_limit(&board, 10, time_control);
 
-        // Should stop when nodes exceeded
-        assert!(result.nodes >= 1000);
+        // Should stop exactly at the node limit
+        assert_eq!(result.nodes, 1000);
         // Should not search to full depth
         assert!(result.depth < 10);
         assert!(board.is_legal(result.best_move));
     }
 
+    #[test]
+    fn test_node_limit_includes_quiescence() {
+        // Tactical position with lots of captures for quiescence to chew on
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let board = parse_fen(fen).unwrap();
+        let mut searcher = Searcher::new();
+
+        let result =
+            searcher.search_with_limit(&board, MAX_DEPTH, TimeControl::Nodes { nodes: 10_000 });
+
+        assert!(
+            result.nodes <= 10_500,
+            "Node limit overshot: {} nodes",
+            result.nodes
+        );
+        assert!(board.is_legal(result.best_move));
+    }
+
+    #[test]
+    fn test_search_fixed_nodes_exhausts_budget() {
+        // The start position is a book hit for a normal search
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ];
+        for fen in fens {
+            let board = parse_fen(fen).unwrap();
+            for max_nodes in [500, 5_000, 20_000] {
+                let mut searcher = Searcher::new();
+                let result = searcher.search_fixed_nodes(&board, max_nodes);
+                assert!(
+                    result.nodes <= max_nodes && result.nodes >= max_nodes - 100,
+                    "{fen}: {} nodes for a budget of {max_nodes}",
+                    result.nodes
+                );
+                assert!(board.is_legal(result.best_move), "{fen}");
+
+                // Same budget on a fresh searcher, same search
+                let again = Searcher::new().search_fixed_nodes(&board, max_nodes);
+                assert_eq!(
+                    (again.best_move, again.nodes),
+                    (result.best_move, result.nodes)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_infinite_time_control() {
         // Test that infinite time control searches to full depth
@@ -1545,9 +2662,10 @@ mod tests {
 
     #[test]
     fn test_clock_time_control() {
-        // Test clock-based time control
+        // 10s for 20 moves: (10000 - 200) / 20 = 490ms soft, 3x that hard
         let board = Board::startpos();
-        let mut searcher = Searcher::new();
+        let clock = Arc::new(ManualClock::new());
+        let mut searcher = searcher_on(&clock);
 
         let time_control = TimeControl::Clock {
             wtime: 10000, // 10 seconds
@@ -1556,12 +2674,26 @@ mod tests {
             binc: 0,
             movestogo: Some(20),
         };
-        let result = searcher.search_with_limit(&board, 10, time_control);
+        let result = searcher.search_with_limit_callback(&board, 10, time_control, |info| {
+            if info.depth == 2 {
+                clock.set(490);
+            }
+        });
 
-        // Should find a legal move
         assert!(board.is_legal(result.best_move));
-        // Should stop before reaching depth 10 (not enough time)
-        assert!(result.depth < 10);
+        assert_eq!(result.depth, 2);
+    }
+
+    #[test]
+    fn test_tt_score_reports_exact_root_entry() {
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let board = parse_fen(fen).unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        assert_eq!(searcher.tt_score(&board), None);
+
+        let result = searcher.search(&board, 4);
+        assert_eq!(searcher.tt_score(&board), Some(result.score));
     }
 
     #[test]
@@ -1676,4 +2808,446 @@ mod tests {
         assert!(result1.nodes > 100);
         assert!(result2.nodes > 100);
     }
+
+    #[test]
+    fn test_iid_search_returns_tt_move() {
+        let board =
+            parse_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4")
+                .unwrap();
+        let mut searcher = Searcher::new();
+        assert!(searcher.tt.probe(board.hash()).is_none());
+
+        let iid_move = searcher
+            .iid_search(&board, 3, -INFINITY, INFINITY, 0, 0)
+            .unwrap();
+        assert!(board.is_legal(iid_move));
+        assert_eq!(searcher.tt.probe(board.hash()).unwrap().best_move, iid_move);
+
+        // A stopped search has nothing trustworthy to offer
+        searcher.stop();
+        assert_eq!(
+            searcher.iid_search(&board, 3, -INFINITY, INFINITY, 0, 0),
+            None
+        );
+    }
+
+    #[test]
+    #[ignore] // Slow test - run with --ignored
+    fn test_cut_node_iid_changes_best_move_with_fewer_nodes() {
+        // Cut node IID is off by default; at depth 9 it finds d3 instead of
+        // Nc3 here with fewer nodes
+        let board =
+            parse_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4")
+                .unwrap();
+        let search = || {
+            let mut searcher = Searcher::with_tt_size(16);
+            searcher.set_use_book(false);
+            searcher.search(&board, 9)
+        };
+
+        let without = search();
+        search_params::set_param("iid_cut_min_depth", 6).unwrap();
+        let with = search();
+        search_params::set_search_params(search_params::SearchParams::default());
+
+        assert!(
+            with.nodes < without.nodes,
+            "{} >= {}",
+            with.nodes,
+            without.nodes
+        );
+        assert_ne!(with.best_move, without.best_move);
+        assert!(board.is_legal(with.best_move));
+    }
+
+    #[test]
+    fn test_tt_mate_scores_are_node_relative() {
+        // Mate in 3 plies found 5 plies from the root: 8 plies from the root
+        // here, 3 from the node wherever it is reached again
+        let stored = score_to_tt(MATE_SCORE - 8, 5);
+        assert_eq!(stored, MATE_SCORE - 3);
+        assert_eq!(score_from_tt(stored, 2), MATE_SCORE - 5);
+        assert_eq!(
+            score_from_tt(score_to_tt(-MATE_SCORE + 8, 5), 2),
+            -MATE_SCORE + 5
+        );
+        assert_eq!(score_to_tt(250, 7), 250);
+        assert_eq!(score_from_tt(-250, 7), -250);
+    }
+
+    #[test]
+    fn test_game_history_repetition_is_avoided() {
+        let board = parse_fen("7k/8/8/8/8/8/8/K5R1 w - - 10 40").unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let first = searcher.search(&board, 5);
+
+        // Pretend the position after the chosen move was already played
+        let mut after = board.clone();
+        after.make_move(first.best_move);
+        searcher.reset_for_new_game();
+        searcher.set_game_history(vec![after.hash(), 1, 2]);
+        let second = searcher.search(&board, 5);
+        assert_ne!(second.best_move, first.best_move);
+        assert!(second.score > 300, "{}", second.score);
+    }
+
+    #[test]
+    fn test_fifty_move_rule_is_a_draw() {
+        // Queen up, but every move but a check ends the game on move 50
+        // and Black always has a reply to the checks
+        let board = parse_fen("7k/8/8/8/8/8/8/KQ6 w - - 99 80").unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let result = searcher.search(&board, 4);
+        assert_eq!(result.score, 0);
+
+        // With a fresh clock the same position is winning
+        let board = parse_fen("7k/8/8/8/8/8/8/KQ6 w - - 0 80").unwrap();
+        assert!(searcher.search(&board, 4).score > 500);
+    }
+
+    #[test]
+    fn test_perpetual_check_pv_ends_in_repetition() {
+        // Black is a queen and two rooks up and mates on the back rank,
+        // but Qe8+ Kh7 Qh5+ Kg8 repeats
+        let board = parse_fen("6k1/3Q2p1/8/8/8/8/qrr2PPP/6K1 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let mut infos = Vec::new();
+        let result =
+            searcher.search_with_limit_callback(&board, 8, TimeControl::Infinite, |info| {
+                infos.push(info)
+            });
+        assert_eq!(result.score, 0);
+
+        let last = infos.last().unwrap();
+        assert_eq!(last.pv_terminated, Some(PvEnd::Repetition));
+
+        // The line is kept up to the repeated position
+        let mut replay = board.clone();
+        let mut seen = vec![replay.hash()];
+        for m in &result.pv {
+            replay.make_move(*m);
+            seen.push(replay.hash());
+        }
+        let end = seen.pop().unwrap();
+        let start = seen.iter().position(|&h| h == end).expect("no repetition");
+        assert!(result.pv.len() - start >= 4, "{:?}", last.pv);
+    }
+
+    #[test]
+    fn test_quiesce_stores_depth_zero_entries() {
+        // White can win the d5 pawn
+        let board = parse_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+
+        let score = searcher.quiesce(&board, -INFINITY, INFINITY, 0, 0);
+        let entry = searcher.tt.probe(board.hash()).copied().unwrap();
+        assert_eq!(entry.depth, 0);
+        assert_eq!(entry.bound, Bound::Exact);
+        assert_eq!(entry.score, score);
+        assert_eq!(entry.best_move.to_uci(), "c3d5");
+
+        // Cached result is returned without re-searching
+        let nodes = searcher.nodes;
+        assert_eq!(searcher.quiesce(&board, -INFINITY, INFINITY, 0, 0), score);
+        assert_eq!(searcher.nodes, nodes + 1);
+    }
+
+    #[test]
+    fn test_quiesce_mate_scores_are_node_relative() {
+        let board = parse_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+
+        // Mate 10 plies from the root, found 4 plies in: 6 plies from here
+        searcher.store_quiesce(board.hash(), Move::null(), MATE_SCORE - 10, Bound::Exact, 4);
+        let entry = searcher.tt.probe(board.hash()).copied().unwrap();
+        assert_eq!(entry.score, MATE_SCORE - 6);
+
+        // Reached 2 plies from the root, the mate is 8 plies away
+        let score = searcher.quiesce(&board, -INFINITY, INFINITY, 2, 0);
+        assert_eq!(score, MATE_SCORE - 8);
+    }
+
+    #[test]
+    fn test_quiesce_keeps_main_search_entries() {
+        let board = parse_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        let quiet = Move::new(
+            crate::square::Square::E1,
+            crate::square::Square::E2,
+            crate::r#move::MoveFlags::QUIET,
+        );
+        searcher
+            .tt
+            .store(board.hash(), quiet, 1500, 5, Bound::Upper);
+
+        // The depth 5 entry cannot cut this window, so qsearch runs...
+        searcher.quiesce(&board, 1000, 2000, 0, 0);
+
+        // ...but must not replace it
+        let entry = searcher.tt.probe(board.hash()).unwrap();
+        assert_eq!(entry.depth, 5);
+        assert_eq!(entry.best_move, quiet);
+    }
+
+    #[test]
+    fn test_lmr_history_adjustment_saves_nodes() {
+        let board =
+            parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        let search = |lmr_history_adjust| {
+            search_params::set_search_params(search_params::SearchParams {
+                lmr_history_adjust,
+                ..Default::default()
+            });
+            let mut searcher = Searcher::with_tt_size(16);
+            searcher.set_use_book(false);
+            let result = searcher.search(&board, 8);
+            search_params::set_search_params(Default::default());
+            result
+        };
+
+        let without = search(0);
+        let with = search(1);
+        assert!(board.is_legal(with.best_move));
+        assert!(with.nodes < without.nodes, "{} >= {}", with.nodes, without.nodes);
+    }
+
+    #[test]
+    fn test_lazy_eval_keeps_search_result() {
+        let board =
+            parse_fen("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10")
+                .unwrap();
+        let search = |lazy_eval_margin| {
+            search_params::set_search_params(search_params::SearchParams {
+                lazy_eval_margin,
+                ..Default::default()
+            });
+            let mut searcher = Searcher::with_tt_size(16);
+            searcher.set_use_book(false);
+            let result = searcher.search(&board, 6);
+            search_params::set_search_params(Default::default());
+            result
+        };
+
+        let full = search(0);
+        let lazy = search(350);
+        assert_eq!(lazy.best_move, full.best_move);
+        assert_eq!(lazy.score, full.score);
+    }
+
+    #[test]
+    fn test_evaluate_lazy_only_skips_far_outside_window() {
+        // White is a rook up
+        let board = parse_fen("4k3/pppp4/8/8/8/8/PPPP4/R3K3 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        searcher.lazy_eval_margin = 300;
+        let lazy = searcher.evaluator.evaluate_lazy(&board);
+        let full = searcher.evaluator.evaluate(&board);
+        assert_ne!(lazy, full);
+
+        assert_eq!(searcher.evaluate_lazy(&board, -100, 100), lazy);
+        assert_eq!(searcher.evaluate_lazy(&board, lazy - 100, lazy + 100), full);
+        assert_eq!(searcher.evaluate_lazy(&board, -MATE_SCORE, 100), full);
+
+        searcher.lazy_eval_margin = 0;
+        searcher.clear_eval_cache();
+        assert_eq!(searcher.evaluate_lazy(&board, -100, 100), full);
+    }
+
+    /// Batteries on both sides of d5, so captures there can go on and on.
+    const CAPTURE_CHAIN_FEN: &str = "3qk3/3r4/1n1rbn2/2bp4/5N2/1BNR4/3R4/3QK3 w - - 0 1";
+
+    #[test]
+    fn test_quiesce_respects_qsearch_ply_cap() {
+        let board = parse_fen(CAPTURE_CHAIN_FEN).unwrap();
+        let seldepth = |max_qsearch_ply| {
+            search_params::set_search_params(search_params::SearchParams {
+                max_qsearch_ply,
+                ..Default::default()
+            });
+            let mut searcher = Searcher::new();
+            searcher.search(&board, 1);
+            search_params::set_search_params(Default::default());
+            searcher.seldepth()
+        };
+
+        let capped = seldepth(2);
+        let uncapped = seldepth(32);
+        assert!(capped <= 3, "seldepth {}", capped);
+        assert!(uncapped > capped);
+    }
+
+    #[test]
+    fn test_negamax_stops_at_max_ply() {
+        let board = parse_fen(CAPTURE_CHAIN_FEN).unwrap();
+        let mut searcher = Searcher::new();
+        let static_eval = searcher.evaluate(&board);
+        let static_eval = searcher.apply_contempt(static_eval);
+
+        let ply = (MAX_PLY - 1) as u32;
+        let score = searcher.negamax(&board, 10, -INFINITY, INFINITY, ply, None, 0, false);
+        assert_eq!(score, static_eval);
+        assert_eq!(searcher.quiesce(&board, -INFINITY, INFINITY, ply, 0), static_eval);
+    }
+
+    #[test]
+    fn test_quiesce_sees_quiet_queen_promotion() {
+        // A rook down, but b8=Q+ captures nothing and still wins material
+        let board = parse_fen("7k/1P6/8/8/8/8/6r1/K7 w - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        let stand_pat = searcher.evaluate(&board);
+        assert!(stand_pat < 0, "{}", stand_pat);
+
+        let score = searcher.quiesce(&board, -INFINITY, INFINITY, 0, 0);
+        assert!(score > 300, "{}", score);
+        let entry = searcher.tt.probe(board.hash()).unwrap();
+        assert_eq!(entry.best_move.to_uci(), "b7b8q");
+    }
+
+    #[test]
+    fn test_quiesce_scores_stalemate_as_draw() {
+        // Black is a queen down but has no legal move
+        let board = parse_fen("7k/5Q2/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let mut searcher = Searcher::new();
+        assert_eq!(searcher.quiesce(&board, -INFINITY, INFINITY, 0, 0), 0);
+
+        // Taking the f7 pawn would stalemate; any depth keeps the win
+        let board = parse_fen("7k/5p2/8/8/8/8/8/5QK1 w - - 0 1").unwrap();
+        for depth in 1..=3 {
+            let result = Searcher::new().search(&board, depth);
+            assert_ne!(result.best_move.to_uci(), "f1f7", "depth {}", depth);
+            assert!(result.score > 500, "depth {}: {}", depth, result.score);
+        }
+    }
+
+    #[test]
+    fn test_eval_cache_hits_during_search() {
+        let fen = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+        let board = parse_fen(fen).unwrap();
+        let mut searcher = Searcher::with_tt_size(16);
+        searcher.search(&board, 5);
+
+        let cache = searcher.eval_cache();
+        assert!(cache.hits() > 0);
+        assert!(cache.hit_rate() > 0.1, "hit rate {:.3}", cache.hit_rate());
+        let probes = cache.probes();
+
+        // Each search starts from an empty cache
+        searcher.search(&board, 1);
+        assert!(searcher.eval_cache().probes() < probes);
+
+        searcher.clear_eval_cache();
+        assert_eq!(searcher.eval_cache().probes(), 0);
+    }
+
+    #[test]
+    fn test_pawn_hash_hits_during_search() {
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        searcher.search(&Board::startpos(), 7);
+
+        let stats = searcher.pawn_hash_stats();
+        assert!(stats.hit_rate() > 0.8, "{:?}", stats);
+    }
+
+    #[test]
+    fn test_decayed_history_guides_next_search() {
+        let board =
+            parse_fen("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10")
+                .unwrap();
+        let mut searcher = Searcher::new();
+        searcher.set_use_book(false);
+        let first = searcher.search(&board, 7).nodes;
+
+        // Without the TT only the history tables carry over
+        searcher.tt.clear();
+        let second = searcher.search(&board, 7).nodes;
+        assert!(second < first, "first {} second {}", first, second);
+    }
+
+    #[test]
+    fn test_previous_search_speeds_up_pv_continuation() {
+        // Play the expected PV move and reply, as in a game, and search
+        // again: the kept TT and history should save nodes compared with a
+        // searcher that starts over
+        let fens = [
+            "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ];
+        let (mut kept, mut fresh) = (0, 0);
+        for fen in fens {
+            let mut board = parse_fen(fen).unwrap();
+            let mut searcher = Searcher::with_tt_size(16);
+            searcher.set_use_book(false);
+            let pv = searcher.search(&board, 7).pv;
+            board.make_move(pv[0]);
+            board.make_move(pv[1]);
+
+            kept += searcher.search(&board, 7).nodes;
+            searcher.reset_for_new_game();
+            fresh += searcher.search(&board, 7).nodes;
+        }
+        assert!(kept * 10 < fresh * 9, "kept {} fresh {}", kept, fresh);
+    }
+
+    #[test]
+    fn test_reset_for_new_game_matches_fresh_searcher() {
+        let first =
+            parse_fen("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10")
+                .unwrap();
+        let second =
+            parse_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 8").unwrap();
+
+        let mut reused = Searcher::with_tt_size(16);
+        reused.set_contempt(0);
+        reused.search(&first, 6);
+        reused.reset_for_new_game();
+        let result = reused.search(&second, 6);
+
+        let mut fresh = Searcher::with_tt_size(16);
+        fresh.set_contempt(0);
+        let expected = fresh.search(&second, 6);
+
+        assert_eq!(result.best_move, expected.best_move);
+        assert_eq!(result.score, expected.score);
+        assert_eq!(result.nodes, expected.nodes);
+        assert_eq!(reused.contempt(), 0);
+    }
+
+    #[test]
+    fn test_continuation_history_node_counts() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+            "r2q1rk1/ppp2ppp/2n1bn2/2bpp3/4P3/2PP1N2/PPBN1PPP/R1BQ1RK1 w - - 0 1",
+            "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 8",
+        ];
+
+        let total_nodes = |enabled: bool| -> u64 {
+            fens.iter()
+                .map(|fen| {
+                    let board = parse_fen(fen).unwrap();
+                    let mut searcher = Searcher::new();
+                    searcher.move_order.set_continuation_history(enabled);
+                    let result = searcher.search(&board, 6);
+                    assert!(board.is_legal(result.best_move));
+                    result.nodes
+                })
+                .sum()
+        };
+
+        // Continuation history should not blow up the tree on this suite
+        let without = total_nodes(false);
+        let with = total_nodes(true);
+        assert!(
+            with * 10 <= without * 11,
+            "Continuation history searched {} nodes vs {} without",
+            with,
+            without
+        );
+    }
 }
@@ -89,7 +89,7 @@ pub fn calculate_extension(
 
 /// Check if a move is a recapture (captures on the same square as previous move)
 fn is_recapture(mv: Move, prev_move: Move) -> bool {
-    mv.is_capture() && mv.to() == prev_move.to()
+    mv.is_capture() && mv.to() == prev_move.destination()
 }
 
 /// Check if a move is a passed pawn push to the 6th or 7th rank
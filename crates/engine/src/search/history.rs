@@ -32,7 +32,7 @@ impl CountermoveTable {
     /// * `countermove` - The move that refuted it
     pub fn store(&mut self, prev_move: Move, countermove: Move) {
         let from = prev_move.from().index() as usize;
-        let to = prev_move.to().index() as usize;
+        let to = prev_move.destination().index() as usize;
         self.table[from][to] = Some(countermove);
     }
 
@@ -45,7 +45,7 @@ impl CountermoveTable {
     /// The countermove, or None if there isn't one
     pub fn get(&self, prev_move: Move) -> Option<Move> {
         let from = prev_move.from().index() as usize;
-        let to = prev_move.to().index() as usize;
+        let to = prev_move.destination().index() as usize;
         self.table[from][to]
     }
 
@@ -61,98 +61,106 @@ impl Default for CountermoveTable {
     }
 }
 
+/// Maximum magnitude of a continuation history entry.
+const CONTINUATION_HISTORY_MAX: i16 = 16000;
+
 /// Continuation history tracks move pairs
 ///
 /// Scores moves based on what move was played before them.
 /// This captures tactical and positional patterns.
+///
+/// Indexed by `[prev_piece][prev_to][cur_piece][cur_to]`: 6×64×6×64 entries
+/// of i16 (about 288 KB), so a pair is recognized no matter where the pieces
+/// came from.
 #[derive(Clone)]
 pub struct ContinuationHistory {
-    /// [from1][to1][from2][to2] -> score
-    /// Indexed by: previous move's from/to, current move's from/to
-    table: Box<[[[[i16; 64]; 64]; 64]; 64]>,
+    /// [prev_piece][prev_to][cur_piece][cur_to] -> score
+    table: Box<[[[[i16; 64]; 6]; 64]; 6]>,
 }
 
 impl ContinuationHistory {
     pub fn new() -> Self {
-        // Allocate on heap directly to avoid stack overflow
-        // Create with vec and convert to box
         Self {
-            table: unsafe {
-                let layout = std::alloc::Layout::new::<[[[[i16; 64]; 64]; 64]; 64]>();
-                let ptr = std::alloc::alloc_zeroed(layout) as *mut [[[[i16; 64]; 64]; 64]; 64];
-                Box::from_raw(ptr)
-            },
+            // Built on the heap: a `Box::new` of the array literal would
+            // first put all 288 KB on the stack
+            table: vec![[[[0; 64]; 6]; 64]; 6]
+                .into_boxed_slice()
+                .try_into()
+                .expect("six rows"),
         }
     }
 
     /// Update continuation history for a move pair
     ///
     /// # Arguments
+    /// * `prev_piece` - Piece type that made the previous move
     /// * `prev_move` - The previous move
+    /// * `cur_piece` - Piece type making the current move
     /// * `current_move` - The current move that caused a cutoff
     /// * `depth` - Depth of the cutoff
-    pub fn update(&mut self, prev_move: Move, current_move: Move, depth: i32) {
-        let from1 = prev_move.from().index() as usize;
-        let to1 = prev_move.to().index() as usize;
-        let from2 = current_move.from().index() as usize;
-        let to2 = current_move.to().index() as usize;
-
+    pub fn update(
+        &mut self,
+        prev_piece: PieceType,
+        prev_move: Move,
+        cur_piece: PieceType,
+        current_move: Move,
+        depth: i32,
+    ) {
         // Bonus proportional to depth squared, but capped to avoid overflow
         let bonus = (depth * depth).min(400) as i16;
 
-        // Update with saturation arithmetic
-        self.table[from1][to1][from2][to2] = self.table[from1][to1][from2][to2]
-            .saturating_add(bonus)
-            .min(16000);
+        let entry = self.entry_mut(prev_piece, prev_move, cur_piece, current_move);
+        *entry = entry.saturating_add(bonus).min(CONTINUATION_HISTORY_MAX);
 
-        // Age down if getting too large
-        if self.table[from1][to1][from2][to2] > 16000 {
-            self.age_down();
+        // Age everything once an entry saturates, keeping relative order
+        if *entry >= CONTINUATION_HISTORY_MAX {
+            self.age();
         }
     }
 
     /// Get continuation history score for a move pair
     ///
-    /// # Arguments
-    /// * `prev_move` - The previous move
-    /// * `current_move` - The current move
-    ///
     /// # Returns
     /// Continuation history score (0-16000)
-    pub fn get(&self, prev_move: Move, current_move: Move) -> i32 {
-        let from1 = prev_move.from().index() as usize;
-        let to1 = prev_move.to().index() as usize;
-        let from2 = current_move.from().index() as usize;
-        let to2 = current_move.to().index() as usize;
+    pub fn get(
+        &self,
+        prev_piece: PieceType,
+        prev_move: Move,
+        cur_piece: PieceType,
+        current_move: Move,
+    ) -> i32 {
+        self.table[prev_piece.index()][prev_move.destination().index() as usize][cur_piece.index()]
+            [current_move.destination().index() as usize] as i32
+    }
 
-        self.table[from1][to1][from2][to2] as i32
+    fn entry_mut(
+        &mut self,
+        prev_piece: PieceType,
+        prev_move: Move,
+        cur_piece: PieceType,
+        current_move: Move,
+    ) -> &mut i16 {
+        &mut self.table[prev_piece.index()][prev_move.destination().index() as usize]
+            [cur_piece.index()][current_move.destination().index() as usize]
     }
 
-    /// Age down all scores (divide by 2)
-    fn age_down(&mut self) {
-        for i in 0..64 {
-            for j in 0..64 {
-                for k in 0..64 {
-                    for l in 0..64 {
-                        self.table[i][j][k][l] /= 2;
-                    }
-                }
-            }
-        }
+    /// Age all scores (divide by 2)
+    pub fn age(&mut self) {
+        self.for_each_entry(|score| *score /= 2);
     }
 
     /// Clear all continuation history
     pub fn clear(&mut self) {
-        // Zero out in place to avoid stack overflow
-        for i in 0..64 {
-            for j in 0..64 {
-                for k in 0..64 {
-                    for l in 0..64 {
-                        self.table[i][j][k][l] = 0;
-                    }
-                }
-            }
-        }
+        self.for_each_entry(|score| *score = 0);
+    }
+
+    fn for_each_entry(&mut self, mut f: impl FnMut(&mut i16)) {
+        self.table
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .flatten()
+            .for_each(&mut f);
     }
 }
 
@@ -200,7 +208,7 @@ impl CaptureHistory {
 
         // Age down if too large
         if self.table[from][to][piece_idx] > 16000 {
-            self.age_down();
+            self.age();
         }
     }
 
@@ -220,8 +228,8 @@ impl CaptureHistory {
         self.table[from][to][piece_idx] as i32
     }
 
-    /// Age down all scores (divide by 2)
-    fn age_down(&mut self) {
+    /// Age all scores (divide by 2)
+    pub fn age(&mut self) {
         for i in 0..64 {
             for j in 0..64 {
                 for k in 0..6 {
@@ -249,6 +257,8 @@ mod tests {
     use crate::r#move::MoveFlags;
     use crate::square::Square;
 
+    const PAWN: PieceType = PieceType::Pawn;
+
     #[test]
     fn test_countermove_basic() {
         let mut cm = CountermoveTable::new();
@@ -306,15 +316,15 @@ mod tests {
         let current = Move::new(Square::E7, Square::E5, MoveFlags::QUIET);
 
         // Initially 0
-        assert_eq!(ch.get(prev, current), 0);
+        assert_eq!(ch.get(PAWN, prev, PAWN, current), 0);
 
         // Update with depth 3: bonus = 9
-        ch.update(prev, current, 3);
-        assert_eq!(ch.get(prev, current), 9);
+        ch.update(PAWN, prev, PAWN, current, 3);
+        assert_eq!(ch.get(PAWN, prev, PAWN, current), 9);
 
         // Update again with depth 5: bonus = 25
-        ch.update(prev, current, 5);
-        assert_eq!(ch.get(prev, current), 34);
+        ch.update(PAWN, prev, PAWN, current, 5);
+        assert_eq!(ch.get(PAWN, prev, PAWN, current), 34);
     }
 
     #[test]
@@ -325,12 +335,12 @@ mod tests {
         let prev2 = Move::new(Square::D2, Square::D4, MoveFlags::QUIET);
         let current = Move::new(Square::E7, Square::E5, MoveFlags::QUIET);
 
-        ch.update(prev1, current, 5);
-        ch.update(prev2, current, 3);
+        ch.update(PAWN, prev1, PAWN, current, 5);
+        ch.update(PAWN, prev2, PAWN, current, 3);
 
         // Different previous moves should have different scores
-        assert_eq!(ch.get(prev1, current), 25);
-        assert_eq!(ch.get(prev2, current), 9);
+        assert_eq!(ch.get(PAWN, prev1, PAWN, current), 25);
+        assert_eq!(ch.get(PAWN, prev2, PAWN, current), 9);
     }
 
     #[test]
@@ -342,10 +352,10 @@ mod tests {
 
         // Update many times to test saturation
         for _ in 0..100 {
-            ch.update(prev, current, 20); // bonus = 400 each time
+            ch.update(PAWN, prev, PAWN, current, 20); // bonus = 400 each time
         }
 
-        let score = ch.get(prev, current);
+        let score = ch.get(PAWN, prev, PAWN, current);
         assert!(
             score <= 16000,
             "Score should be capped at 16000, got {}",
@@ -434,7 +444,7 @@ mod tests {
         let mv2 = Move::new(Square::E7, Square::E5, MoveFlags::QUIET);
 
         cm.store(mv1, mv2);
-        ch.update(mv1, mv2, 5);
+        ch.update(PAWN, mv1, PAWN, mv2, 5);
         cap_hist.update(mv1, PieceType::Pawn, 5);
 
         // Clear all
@@ -444,7 +454,7 @@ mod tests {
 
         // Everything should be reset
         assert_eq!(cm.get(mv1), None);
-        assert_eq!(ch.get(mv1, mv2), 0);
+        assert_eq!(ch.get(PAWN, mv1, PAWN, mv2), 0);
         assert_eq!(cap_hist.get(mv1, PieceType::Pawn), 0);
     }
 
@@ -455,11 +465,45 @@ mod tests {
         let prev = Move::new(Square::E2, Square::E4, MoveFlags::QUIET);
         let current = Move::new(Square::E7, Square::E5, MoveFlags::QUIET);
 
-        ch.update(prev, current, 10);
-        assert!(ch.get(prev, current) > 0);
+        ch.update(PAWN, prev, PAWN, current, 10);
+        assert!(ch.get(PAWN, prev, PAWN, current) > 0);
 
         ch.clear();
-        assert_eq!(ch.get(prev, current), 0);
+        assert_eq!(ch.get(PAWN, prev, PAWN, current), 0);
+    }
+
+    #[test]
+    fn test_continuation_history_indexed_by_piece_and_target() {
+        let mut ch = ContinuationHistory::new();
+
+        let prev = Move::new(Square::E2, Square::E4, MoveFlags::QUIET);
+        let knight_to_f6 = Move::new(Square::G8, Square::from_coords(5, 5), MoveFlags::QUIET);
+        let queen_to_f6 = Move::new(Square::D8, Square::from_coords(5, 5), MoveFlags::QUIET);
+
+        ch.update(PAWN, prev, PieceType::Knight, knight_to_f6, 4);
+        assert_eq!(ch.get(PAWN, prev, PieceType::Knight, knight_to_f6), 16);
+        // Different piece to the same square is a different pair
+        assert_eq!(ch.get(PAWN, prev, PieceType::Queen, queen_to_f6), 0);
+
+        // Same pieces and targets share an entry, wherever they came from
+        let other_prev = Move::new(Square::E3, Square::E4, MoveFlags::QUIET);
+        let other_knight = Move::new(Square::E4, Square::from_coords(5, 5), MoveFlags::QUIET);
+        assert_eq!(
+            ch.get(PAWN, other_prev, PieceType::Knight, other_knight),
+            16
+        );
+    }
+
+    #[test]
+    fn test_continuation_history_age() {
+        let mut ch = ContinuationHistory::new();
+
+        let prev = Move::new(Square::E2, Square::E4, MoveFlags::QUIET);
+        let current = Move::new(Square::E7, Square::E5, MoveFlags::QUIET);
+
+        ch.update(PAWN, prev, PAWN, current, 10);
+        ch.age();
+        assert_eq!(ch.get(PAWN, prev, PAWN, current), 50);
     }
 
     #[test]
@@ -5,11 +5,19 @@ pub mod extensions;
 pub mod history;
 pub mod lmr;
 pub mod pruning;
+pub mod root;
 pub mod see;
+pub mod skill;
+pub mod stop;
+pub mod variety;
 
 pub use core::*;
 pub use extensions::*;
 pub use history::*;
 pub use lmr::*;
 pub use pruning::*;
+pub use root::*;
 pub use see::*;
+pub use skill::*;
+pub use stop::*;
+pub use variety::*;
@@ -51,6 +51,33 @@ pub const MULTI_CUT_THRESHOLD: usize = 3;
 /// Multi-cut depth reduction
 pub const MULTI_CUT_DEPTH_REDUCTION: i32 = 3;
 
+/// How much smaller the futility margin is when the position is improving
+pub const IMPROVING_MARGIN_REDUCTION: i32 = 20;
+
+/// Futility margin for a depth
+///
+/// The tuned per-depth margins (by default `depth * 100`), less
+/// [`IMPROVING_MARGIN_REDUCTION`] when our static eval is rising: an
+/// improving side is trusted to need a smaller margin.
+///
+/// # Arguments
+/// * `depth` - Current search depth
+/// * `improving` - Whether the static eval beats the one two plies ago
+pub fn futility_margin(depth: i32, improving: bool) -> i32 {
+    let params = search_params::get_search_params();
+    let margin = match depth {
+        1 => params.futility_margin_d1,
+        2 => params.futility_margin_d2,
+        3 => params.futility_margin_d3,
+        _ => depth * 100,
+    };
+    if improving {
+        margin - IMPROVING_MARGIN_REDUCTION
+    } else {
+        margin
+    }
+}
+
 /// Check if futility pruning can be applied
 ///
 /// Futility pruning skips quiet moves at shallow depths when the static
@@ -68,22 +95,23 @@ pub const MULTI_CUT_DEPTH_REDUCTION: i32 = 3;
 /// * `is_pv` - Whether this is a PV node
 /// * `eval` - Static evaluation of current position
 /// * `alpha` - Alpha bound
+/// * `improving` - Whether the static eval beats the one two plies ago
 ///
 /// # Returns
 /// true if futility pruning can skip quiet moves
-pub fn can_futility_prune(depth: i32, in_check: bool, is_pv: bool, eval: i32, alpha: i32) -> bool {
+pub fn can_futility_prune(
+    depth: i32,
+    in_check: bool,
+    is_pv: bool,
+    eval: i32,
+    alpha: i32,
+    improving: bool,
+) -> bool {
     if depth > 3 || in_check || is_pv {
         return false;
     }
 
-    let params = search_params::get_search_params();
-    let margin = match depth {
-        1 => params.futility_margin_d1,
-        2 => params.futility_margin_d2,
-        3 => params.futility_margin_d3,
-        _ => 0,
-    };
-    eval + margin < alpha
+    eval + futility_margin(depth, improving) < alpha
 }
 
 /// Check if reverse futility pruning can be applied
@@ -174,6 +202,30 @@ pub fn can_razor(depth: i32, in_check: bool, is_pv: bool, eval: i32, alpha: i32)
     eval + margin < alpha
 }
 
+/// Number of quiet moves searched before late move pruning starts
+///
+/// Around the tuned per-depth thresholds: `depth` more moves are searched
+/// when the position is improving and one fewer when it is not, since late
+/// quiet moves are unlikely to rescue a falling eval.
+///
+/// # Arguments
+/// * `depth` - Current search depth
+/// * `improving` - Whether the static eval beats the one two plies ago
+pub fn lmp_threshold(depth: i32, improving: bool) -> usize {
+    let params = search_params::get_search_params();
+    let threshold = match depth {
+        1 => params.lmp_threshold_d1,
+        2 => params.lmp_threshold_d2,
+        3 => params.lmp_threshold_d3,
+        _ => return 99,
+    };
+    if improving {
+        threshold + depth as usize
+    } else {
+        threshold.saturating_sub(1)
+    }
+}
+
 /// Check if late move pruning can be applied
 ///
 /// LMP skips remaining quiet moves after searching a certain number
@@ -190,10 +242,17 @@ pub fn can_razor(depth: i32, in_check: bool, is_pv: bool, eval: i32, alpha: i32)
 /// * `in_check` - Whether current side is in check
 /// * `move_count` - Number of moves searched so far
 /// * `mv` - The move to check
+/// * `improving` - Whether the static eval beats the one two plies ago
 ///
 /// # Returns
 /// true if this move should be pruned
-pub fn can_late_move_prune(depth: i32, in_check: bool, move_count: usize, mv: Move) -> bool {
+pub fn can_late_move_prune(
+    depth: i32,
+    in_check: bool,
+    move_count: usize,
+    mv: Move,
+    improving: bool,
+) -> bool {
     if depth > 3 || in_check {
         return false;
     }
@@ -203,14 +262,7 @@ pub fn can_late_move_prune(depth: i32, in_check: bool, move_count: usize, mv: Mo
         return false;
     }
 
-    let params = search_params::get_search_params();
-    let threshold = match depth {
-        1 => params.lmp_threshold_d1,
-        2 => params.lmp_threshold_d2,
-        3 => params.lmp_threshold_d3,
-        _ => 99,
-    };
-    move_count > threshold
+    move_count > lmp_threshold(depth, improving)
 }
 
 /// Check if a move should be pruned based on SEE
@@ -296,6 +348,32 @@ pub fn is_endgame(_board: &Board) -> bool {
     false
 }
 
+/// Minimum material lead before stalemate avoidance kicks in (a minor piece).
+pub const STALEMATE_RISK_LEAD: i32 = 300;
+
+/// Check if the side to move could stalemate the opponent by accident
+///
+/// Stalemate is only a realistic danger (and a defensive resource worth
+/// checking for) when the opponent is down to king and pawns and we are
+/// clearly ahead. Outside such positions the costly stalemate test is skipped.
+///
+/// # Arguments
+/// * `board` - Current board position
+///
+/// # Returns
+/// true if moves should be checked with `Board::is_stalemate_after`
+pub fn stalemate_risk(board: &Board) -> bool {
+    use crate::piece::PieceType;
+
+    let us = board.side_to_move();
+    let them = us.opponent();
+    let their_pieces = board.color_bb(them)
+        & !board.piece_bb(PieceType::Pawn, them)
+        & !board.piece_bb(PieceType::King, them);
+
+    their_pieces.is_empty() && board.material(us) - board.material(them) >= STALEMATE_RISK_LEAD
+}
+
 /// Pruning safety check
 ///
 /// Some conditions universally disable all forward pruning:
@@ -336,25 +414,25 @@ mod tests {
     #[test]
     fn test_futility_pruning_depth() {
         // Should work at shallow depths
-        assert!(can_futility_prune(1, false, false, -200, 0));
-        assert!(can_futility_prune(2, false, false, -300, 0));
-        assert!(can_futility_prune(3, false, false, -400, 0));
+        assert!(can_futility_prune(1, false, false, -200, 0, false));
+        assert!(can_futility_prune(2, false, false, -300, 0, false));
+        assert!(can_futility_prune(3, false, false, -400, 0, false));
 
         // Should not work at higher depths
-        assert!(!can_futility_prune(4, false, false, -500, 0));
-        assert!(!can_futility_prune(5, false, false, -600, 0));
+        assert!(!can_futility_prune(4, false, false, -500, 0, false));
+        assert!(!can_futility_prune(5, false, false, -600, 0, false));
     }
 
     #[test]
     fn test_futility_pruning_conditions() {
         // Should not work in check
-        assert!(!can_futility_prune(1, true, false, -200, 0));
+        assert!(!can_futility_prune(1, true, false, -200, 0, false));
 
         // Should not work in PV
-        assert!(!can_futility_prune(1, false, true, -200, 0));
+        assert!(!can_futility_prune(1, false, true, -200, 0, false));
 
         // Should not work if eval + margin >= alpha
-        assert!(!can_futility_prune(1, false, false, 50, 0));
+        assert!(!can_futility_prune(1, false, false, 50, 0, false));
     }
 
     #[test]
@@ -422,15 +500,15 @@ mod tests {
         );
 
         // Should prune late quiet moves
-        assert!(can_late_move_prune(1, false, 5, quiet_move));
-        assert!(can_late_move_prune(2, false, 10, quiet_move));
+        assert!(can_late_move_prune(1, false, 5, quiet_move, false));
+        assert!(can_late_move_prune(2, false, 10, quiet_move, false));
 
         // Should not prune early moves
-        assert!(!can_late_move_prune(1, false, 2, quiet_move));
-        assert!(!can_late_move_prune(2, false, 4, quiet_move));
+        assert!(!can_late_move_prune(1, false, 2, quiet_move, false));
+        assert!(!can_late_move_prune(2, false, 4, quiet_move, false));
 
         // Should not prune in check
-        assert!(!can_late_move_prune(1, true, 10, quiet_move));
+        assert!(!can_late_move_prune(1, true, 10, quiet_move, false));
     }
 
     #[test]
@@ -448,8 +526,37 @@ mod tests {
         );
 
         // Should not prune captures or promotions
-        assert!(!can_late_move_prune(1, false, 10, capture));
-        assert!(!can_late_move_prune(1, false, 10, promotion));
+        assert!(!can_late_move_prune(1, false, 10, capture, false));
+        assert!(!can_late_move_prune(1, false, 10, promotion, false));
+    }
+
+    #[test]
+    fn test_futility_margin_improving() {
+        assert_eq!(futility_margin(1, false), 100);
+        assert_eq!(futility_margin(2, false), 200);
+        assert_eq!(futility_margin(3, true), 280);
+        assert_eq!(futility_margin(4, false), 400);
+
+        // eval + 100 = alpha: only the smaller improving margin prunes
+        assert!(!can_futility_prune(1, false, false, -100, 0, false));
+        assert!(can_futility_prune(1, false, false, -100, 0, true));
+    }
+
+    #[test]
+    fn test_lmp_threshold_improving() {
+        for depth in 1..=3 {
+            assert!(lmp_threshold(depth, false) < lmp_threshold(depth, true));
+        }
+        assert_eq!(lmp_threshold(4, false), 99);
+
+        // Not improving prunes the fourth move at depth 1, improving keeps it
+        let quiet_move = Move::new(
+            Square::from_algebraic("g1").unwrap(),
+            Square::from_algebraic("f3").unwrap(),
+            MoveFlags::QUIET,
+        );
+        assert!(can_late_move_prune(1, false, 3, quiet_move, false));
+        assert!(!can_late_move_prune(1, false, 3, quiet_move, true));
     }
 
     #[test]
@@ -537,4 +644,25 @@ mod tests {
         assert!(LMP_THRESHOLDS[1] < LMP_THRESHOLDS[2]);
         assert!(LMP_THRESHOLDS[2] < LMP_THRESHOLDS[3]);
     }
+
+    #[test]
+    fn test_stalemate_risk() {
+        use crate::io::parse_fen;
+
+        // Queen against a lone king
+        let board = parse_fen("7k/8/8/8/8/8/8/5QK1 w - - 0 1").unwrap();
+        assert!(stalemate_risk(&board));
+
+        // The defending side itself is not at risk of stalemating anyone
+        let board = parse_fen("7k/8/8/8/8/8/8/5QK1 b - - 0 1").unwrap();
+        assert!(!stalemate_risk(&board));
+
+        // Opponent still has a piece
+        let board = parse_fen("7k/8/8/8/8/8/n7/5QK1 w - - 0 1").unwrap();
+        assert!(!stalemate_risk(&board));
+
+        // Material is level
+        let board = parse_fen("7k/7p/8/8/8/8/7P/6K1 w - - 0 1").unwrap();
+        assert!(!stalemate_risk(&board));
+    }
 }
@@ -0,0 +1,160 @@
+//! Root move bookkeeping
+//!
+//! Keeps every legal root move together with the score and PV from the
+//! last iteration that searched it. The list is re-sorted after each
+//! iteration so the next one starts with the best moves first, and the
+//! top N entries double as the lines reported in multi-PV mode.
+
+use crate::board::Board;
+use crate::movelist::MoveList;
+use crate::r#move::Move;
+use crate::types::PvEnd;
+
+/// Score used for root moves that have not been searched yet.
+pub const UNSEARCHED_SCORE: i32 = i32::MIN / 2;
+
+/// A legal root move and the result of its last search.
+#[derive(Debug, Clone)]
+pub struct RootMove {
+    pub mv: Move,
+    /// Score from the current iteration (UNSEARCHED_SCORE if not searched)
+    pub score: i32,
+    /// Score from the previous completed iteration
+    pub previous_score: i32,
+    /// Principal variation starting with `mv`
+    pub pv: Vec<Move>,
+    /// Why `pv` ends (`None` until the move enters the top lines)
+    pub pv_end: Option<PvEnd>,
+}
+
+impl RootMove {
+    pub fn new(mv: Move) -> Self {
+        Self {
+            mv,
+            score: UNSEARCHED_SCORE,
+            previous_score: UNSEARCHED_SCORE,
+            pv: vec![mv],
+            pv_end: None,
+        }
+    }
+}
+
+/// All legal root moves, ordered best-first after each iteration.
+#[derive(Debug, Clone, Default)]
+pub struct RootMoves {
+    moves: Vec<RootMove>,
+}
+
+impl RootMoves {
+    /// Build the root move list from an already ordered move list.
+    pub fn from_moves(moves: &MoveList) -> Self {
+        Self {
+            moves: moves.iter().map(|&m| RootMove::new(m)).collect(),
+        }
+    }
+
+    /// Build the root move list from all legal moves in a position.
+    pub fn from_board(board: &Board) -> Self {
+        Self::from_moves(&board.generate_legal_moves())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, RootMove> {
+        self.moves.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, RootMove> {
+        self.moves.iter_mut()
+    }
+
+    /// Get the root move at an index.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&RootMove> {
+        self.moves.get(index)
+    }
+
+    /// The best root move of the last completed iteration.
+    #[inline]
+    pub fn best(&self) -> Option<&RootMove> {
+        self.moves.first()
+    }
+
+    /// Remember the current scores as previous scores before a new iteration.
+    pub fn start_iteration(&mut self) {
+        for rm in &mut self.moves {
+            rm.previous_score = rm.score;
+            rm.score = UNSEARCHED_SCORE;
+        }
+    }
+
+    /// Sort moves best-first by score.
+    ///
+    /// The sort is stable, so moves with equal scores (including all moves
+    /// that failed low) keep their relative order from the previous iteration.
+    pub fn sort(&mut self) {
+        self.moves.sort_by_key(|rm| std::cmp::Reverse(rm.score));
+    }
+}
+
+impl std::ops::Index<usize> for RootMoves {
+    type Output = RootMove;
+
+    fn index(&self, index: usize) -> &RootMove {
+        &self.moves[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_moves_from_board() {
+        let board = Board::startpos();
+        let root_moves = RootMoves::from_board(&board);
+
+        assert_eq!(root_moves.len(), 20);
+        for rm in root_moves.iter() {
+            assert_eq!(rm.score, UNSEARCHED_SCORE);
+            assert_eq!(rm.pv, vec![rm.mv]);
+        }
+    }
+
+    #[test]
+    fn test_root_moves_sort_is_stable() {
+        let board = Board::startpos();
+        let mut root_moves = RootMoves::from_board(&board);
+        let original: Vec<Move> = root_moves.iter().map(|rm| rm.mv).collect();
+
+        root_moves.iter_mut().nth(5).unwrap().score = 50;
+        root_moves.iter_mut().nth(7).unwrap().score = 30;
+        root_moves.sort();
+
+        assert_eq!(root_moves[0].mv, original[5]);
+        assert_eq!(root_moves[1].mv, original[7]);
+        // Unsearched moves keep their relative order
+        assert_eq!(root_moves[2].mv, original[0]);
+        assert_eq!(root_moves[3].mv, original[1]);
+    }
+
+    #[test]
+    fn test_start_iteration_keeps_previous_score() {
+        let board = Board::startpos();
+        let mut root_moves = RootMoves::from_board(&board);
+
+        root_moves.iter_mut().next().unwrap().score = 25;
+        root_moves.start_iteration();
+
+        assert_eq!(root_moves[0].previous_score, 25);
+        assert_eq!(root_moves[0].score, UNSEARCHED_SCORE);
+    }
+}
@@ -33,7 +33,7 @@ fn piece_value(piece: PieceType) -> i32 {
 
 /// Find least valuable attacker of a square
 /// Returns (attacker_square, piece_type) or None
-fn least_valuable_attacker(
+pub(crate) fn least_valuable_attacker(
     board: &Board,
     target: Square,
     by_color: Color,
@@ -107,13 +107,74 @@ fn least_valuable_attacker(
 /// - If recapture: -330 (lose bishop)
 /// - Net: -10 (losing capture)
 pub fn see(board: &Board, mv: Move, threshold: i32) -> bool {
-    see_value(board, mv) >= threshold
+    see_beats_threshold(board, mv, threshold)
+}
+
+/// Threshold-only SEE: returns `see_value(board, mv) >= threshold`.
+///
+/// Instead of building the full gain list, this tracks a running swap
+/// value and stops as soon as the side to move can no longer change the
+/// outcome. Typical cases (e.g. PxP with a queen behind) resolve after one
+/// or two attacker lookups instead of walking the whole exchange.
+pub fn see_beats_threshold(board: &Board, mv: Move, threshold: i32) -> bool {
+    let from = mv.from();
+    let to = mv.destination();
+
+    let attacker = board.piece_at(from).unwrap();
+    let mut victim = board.piece_at(to).map(|p| p.piece_type);
+
+    // Handle en passant
+    if attacker.piece_type == PieceType::Pawn && victim.is_none() && from.file() != to.file() {
+        victim = Some(PieceType::Pawn);
+    }
+
+    // Non-captures have SEE of 0
+    let victim = match victim {
+        Some(v) => v,
+        None => return threshold <= 0,
+    };
+
+    // Even if nobody recaptures, the capture doesn't reach the threshold
+    let mut swap = piece_value(victim) - threshold;
+    if swap < 0 {
+        return false;
+    }
+
+    // Even if we lose the capturing piece, we still reach the threshold
+    swap = piece_value(attacker.piece_type) - swap;
+    if swap <= 0 {
+        return true;
+    }
+
+    let mut occupied = board.occupied().clear(from);
+    let mut side = board.side_to_move().opponent();
+    let mut result = true;
+
+    while let Some((attacker_sq, attacker_type)) =
+        least_valuable_attacker(board, to, side, occupied)
+    {
+        occupied = occupied.clear(attacker_sq);
+        side = side.opponent();
+        result = !result;
+
+        // Same as see_value: a king capture always ends the exchange
+        if attacker_type == PieceType::King {
+            return result;
+        }
+
+        swap = piece_value(attacker_type) - swap;
+        if swap < result as i32 {
+            break;
+        }
+    }
+
+    result
 }
 
 /// Calculate the SEE value of a move
 pub fn see_value(board: &Board, mv: Move) -> i32 {
     let from = mv.from();
-    let to = mv.to();
+    let to = mv.destination();
 
     // Get the pieces involved
     let attacker = board.piece_at(from).unwrap();
@@ -185,6 +246,107 @@ pub fn see_value(board: &Board, mv: Move) -> i32 {
     score
 }
 
+/// Sign of the SEE value of a move: +1 if it wins material, 0 if it
+/// breaks even, -1 if it loses material.
+///
+/// Uses the gain-array form of SEE: attackers are taken in ascending value
+/// order (x-rays included) and each entry holds the speculative balance if
+/// the exchange stopped there, all in a fixed array rather than a `Vec`.
+/// The array is then folded back from the end, letting either side stand
+/// pat. Cheaper than [`see_value`] when a prune/don't-prune decision only
+/// needs the sign, and always agrees with it.
+pub fn see_sign(board: &Board, mv: Move) -> i32 {
+    let from = mv.from();
+    let to = mv.destination();
+
+    let attacker = board.piece_at(from).unwrap();
+    let victim = match board.piece_at(to) {
+        Some(piece) => piece.piece_type,
+        // En passant
+        None if attacker.piece_type == PieceType::Pawn && from.file() != to.file() => {
+            PieceType::Pawn
+        }
+        None => return 0,
+    };
+
+    // A capture can't lose material if the piece it risks is worth no
+    // more than the one it takes
+    if piece_value(attacker.piece_type) < piece_value(victim) {
+        return 1;
+    }
+
+    // gain[d]: balance for the side making capture d if the exchange
+    // stopped after it
+    let mut gain = [0i32; 32];
+    let mut depth = 0;
+    gain[0] = piece_value(victim);
+
+    let mut on_square = attacker.piece_type;
+    let mut occupied = board.occupied().clear(from);
+    let mut side = board.side_to_move().opponent();
+
+    while depth + 1 < gain.len() {
+        let Some((sq, next)) = least_valuable_attacker(board, to, side, occupied) else {
+            break;
+        };
+        depth += 1;
+        gain[depth] = piece_value(on_square) - gain[depth - 1];
+        occupied = occupied.clear(sq);
+        on_square = next;
+        side = side.opponent();
+
+        // Same as see_value: the king's capture is the last one
+        if next == PieceType::King && least_valuable_attacker(board, to, side, occupied).is_some() {
+            break;
+        }
+    }
+
+    while depth > 0 {
+        gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        depth -= 1;
+    }
+
+    gain[0].signum()
+}
+
+/// Material `by_color` wins by starting an exchange on `target`.
+///
+/// Captures are made with the least valuable attacker each time, sliders
+/// behind the capturing piece join in (x-rays), and either side may stop
+/// capturing when continuing would lose material. Returns 0 if `target`
+/// holds no enemy piece of `by_color`, or if capturing doesn't pay.
+///
+/// Pins are ignored, as in the rest of SEE. A king never captures onto a
+/// square that is still attacked.
+pub fn see_on_square(board: &Board, target: Square, by_color: Color) -> i32 {
+    let victim = match board.piece_at(target) {
+        Some(piece) if piece.color != by_color => piece.piece_type,
+        _ => return 0,
+    };
+
+    // Value captured by each capture in the sequence
+    let mut captured = Vec::with_capacity(32);
+    let mut on_square = piece_value(victim);
+    let mut occupied = board.occupied();
+    let mut side = by_color;
+
+    while let Some((sq, attacker)) = least_valuable_attacker(board, target, side, occupied) {
+        occupied = occupied.clear(sq);
+        if attacker == PieceType::King
+            && least_valuable_attacker(board, target, side.opponent(), occupied).is_some()
+        {
+            break;
+        }
+
+        captured.push(on_square);
+        on_square = piece_value(attacker);
+        side = side.opponent();
+    }
+
+    // Each side only captures if it comes out ahead
+    captured.iter().rev().fold(0, |score, &gain| (gain - score).max(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +434,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_see_beats_threshold_matches_see_value() {
+        let fens = [
+            "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "rnbqkbnr/ppp1pppp/8/3p2N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 1",
+            "2r1r1k1/pp1q1ppp/2n1b3/3pP3/3P4/2PB1N2/P4PPP/R2Q1RK1 w - - 0 1",
+            "1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1",
+            "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1",
+            "4k3/8/8/3p4/4K3/8/8/8 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let board = parse_fen(fen).unwrap();
+            for mv in generate_moves(&board).iter().filter(|m| m.is_capture()) {
+                let value = see_value(&board, *mv);
+                for threshold in (-1000..=1000).step_by(10) {
+                    assert_eq!(
+                        see_beats_threshold(&board, *mv, threshold),
+                        value >= threshold,
+                        "{} {} threshold {} (see_value {})",
+                        fen,
+                        mv,
+                        threshold,
+                        value
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_see_beats_threshold_non_capture() {
+        let board = Board::startpos();
+        let moves = generate_moves(&board);
+        let quiet = moves.iter()
+            .find(|m| m.from().to_string() == "g1" && m.to().to_string() == "f3")
+            .unwrap();
+
+        assert!(see_beats_threshold(&board, *quiet, 0));
+        assert!(see_beats_threshold(&board, *quiet, -50));
+        assert!(!see_beats_threshold(&board, *quiet, 1));
+    }
+
+    #[test]
+    fn test_see_sign_matches_see_value() {
+        let fens = [
+            "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "rnbqkbnr/ppp1pppp/8/3p2N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 1",
+            "2r1r1k1/pp1q1ppp/2n1b3/3pP3/3P4/2PB1N2/P4PPP/R2Q1RK1 w - - 0 1",
+            "1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1",
+            "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1",
+            "4k3/8/8/3p4/4K3/8/8/8 w - - 0 1",
+            "4k3/8/8/3p4/4K3/8/8/3r4 w - - 0 1",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+            "rnbqkbnr/ppp2ppp/8/3pp3/4P3/2N5/PPPP1PPP/R1BQKBNR w KQkq - 0 3",
+            "rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+            "3r2k1/3r4/8/3p4/8/3R4/3R4/3QK3 w - - 0 1",
+        ];
+
+        let mut signs = [0; 3];
+        for fen in fens {
+            let board = parse_fen(fen).unwrap();
+            for mv in generate_moves(&board).iter().filter(|m| m.is_capture()) {
+                let value = see_value(&board, *mv);
+                let sign = see_sign(&board, *mv);
+                assert_eq!(sign, value.signum(), "{} {} (see_value {})", fen, mv, value);
+                signs[(sign + 1) as usize] += 1;
+            }
+        }
+
+        // Losing, even and winning captures are all covered
+        assert!(signs.iter().all(|&n| n > 0), "{:?}", signs);
+    }
+
     #[test]
     fn test_see_king_wont_walk_into_check() {
         // King shouldn't capture if it would be in check
@@ -0,0 +1,170 @@
+//! Skill level (strength reduction)
+//!
+//! Below the maximum level the engine sometimes plays a random move from the
+//! top N root moves instead of the best one. Both the chance of deviating and
+//! N grow as the level drops: level 20 always plays the best move, level 0
+//! plays a uniformly random legal move.
+//!
+//! The choice is driven by a PRNG seeded from the position hash, so the same
+//! position at the same level always gets the same move.
+
+/// Highest skill level (full strength).
+pub const MAX_SKILL_LEVEL: u32 = 20;
+
+/// Maximum number of lines searched with exact scores when skill is enabled.
+///
+/// Candidates beyond these lines are ordered by their upper bounds only,
+/// which is plenty for the weak levels that reach that far down the list.
+pub const SKILL_SEARCH_LINES: usize = 4;
+
+/// Strength reduction settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Skill {
+    level: u32,
+}
+
+impl Skill {
+    /// Create a skill setting (levels above 20 are clamped to 20).
+    pub fn new(level: u32) -> Self {
+        Self {
+            level: level.min(MAX_SKILL_LEVEL),
+        }
+    }
+
+    #[inline]
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Whether the engine plays below full strength.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.level < MAX_SKILL_LEVEL
+    }
+
+    /// Number of best root moves a weakened move is picked from.
+    ///
+    /// Scales from 1 at level 20 to every legal move at level 0.
+    pub fn candidate_count(&self, legal_moves: usize) -> usize {
+        if !self.enabled() || legal_moves <= 1 {
+            return legal_moves.min(1);
+        }
+
+        let weakness = (MAX_SKILL_LEVEL - self.level) as usize;
+        (legal_moves * weakness)
+            .div_ceil(MAX_SKILL_LEVEL as usize)
+            .clamp(2, legal_moves)
+    }
+
+    /// Number of lines the search should score exactly for this level.
+    pub fn search_lines(&self, legal_moves: usize) -> usize {
+        self.candidate_count(legal_moves).min(SKILL_SEARCH_LINES)
+    }
+
+    /// Pick the index of the move to play among `legal_moves` root moves
+    /// sorted best-first.
+    ///
+    /// Returns 0 (the best move) unless the level decides to deviate.
+    pub fn pick(&self, hash: u64, legal_moves: usize) -> usize {
+        if !self.enabled() || legal_moves <= 1 {
+            return 0;
+        }
+
+        let mut rng = SkillRng::new(hash ^ self.level as u64);
+
+        // Deviate with probability (20 - level) / 20: always at level 0
+        if rng.next_below(MAX_SKILL_LEVEL as u64) < self.level as u64 {
+            return 0;
+        }
+
+        rng.next_below(self.candidate_count(legal_moves) as u64) as usize
+    }
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self::new(MAX_SKILL_LEVEL)
+    }
+}
+
+/// SplitMix64 generator: tiny, fast and well mixed even for similar seeds.
+#[derive(Debug, Clone)]
+pub(crate) struct SkillRng {
+    state: u64,
+}
+
+impl SkillRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_strength_always_best() {
+        let skill = Skill::new(MAX_SKILL_LEVEL);
+        assert!(!skill.enabled());
+        for hash in 0..1000u64 {
+            assert_eq!(skill.pick(hash.wrapping_mul(0x9E37_79B9), 30), 0);
+        }
+        assert_eq!(Skill::new(50), skill);
+    }
+
+    #[test]
+    fn test_candidate_count_scales_with_level() {
+        assert_eq!(Skill::new(0).candidate_count(30), 30);
+        assert_eq!(Skill::new(10).candidate_count(30), 15);
+        assert_eq!(Skill::new(19).candidate_count(30), 2);
+        assert_eq!(Skill::new(20).candidate_count(30), 1);
+        assert_eq!(Skill::new(5).candidate_count(1), 1);
+
+        assert_eq!(Skill::new(0).search_lines(30), SKILL_SEARCH_LINES);
+        assert_eq!(Skill::new(19).search_lines(30), 2);
+    }
+
+    #[test]
+    fn test_pick_is_deterministic_and_in_range() {
+        let skill = Skill::new(0);
+        for hash in 0..1000u64 {
+            let index = skill.pick(hash, 20);
+            assert!(index < 20);
+            assert_eq!(skill.pick(hash, 20), index);
+        }
+    }
+
+    #[test]
+    fn test_level_zero_spreads_over_all_moves() {
+        let skill = Skill::new(0);
+        let mut seen = [false; 20];
+        for hash in 0..1000u64 {
+            seen[skill.pick(hash, 20)] = true;
+        }
+        assert!(seen.iter().all(|&s| s), "Every move should get picked");
+    }
+
+    #[test]
+    fn test_higher_levels_deviate_less() {
+        let deviations = |level: u32| {
+            let skill = Skill::new(level);
+            (0..1000u64).filter(|&h| skill.pick(h, 20) != 0).count()
+        };
+
+        assert!(deviations(0) > deviations(10));
+        assert!(deviations(10) > deviations(19));
+    }
+}
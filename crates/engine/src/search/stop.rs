@@ -0,0 +1,71 @@
+//! Search cancellation
+//!
+//! A [`StopHandle`] cancels one search (or one batch of searches, such as a
+//! game analysis). Each search gets its own handle, so a stop aimed at an
+//! earlier search can never cancel a later one, and a stop sent before the
+//! search has started still takes effect: the search returns immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared cancellation flag for a search.
+///
+/// Clones share the flag, so one clone can be handed to the searcher and
+/// another kept by whoever may want to stop it, on any thread.
+#[derive(Debug, Clone, Default)]
+pub struct StopHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl StopHandle {
+    /// Create a handle that has not been stopped.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap an existing flag.
+    pub fn from_flag(flag: Arc<AtomicBool>) -> Self {
+        Self { flag }
+    }
+
+    /// Ask the search to stop. Stopping is permanent for this handle.
+    pub fn stop(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`StopHandle::stop`] has been called.
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Clear the flag so the handle can be used for another search.
+    pub(crate) fn reset(&self) {
+        self.flag.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether two handles share the same flag.
+    pub fn same_as(&self, other: &StopHandle) -> bool {
+        Arc::ptr_eq(&self.flag, &other.flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_handle_clones_share_flag() {
+        let handle = StopHandle::new();
+        let clone = handle.clone();
+        assert!(!clone.is_stopped());
+
+        handle.stop();
+        assert!(clone.is_stopped());
+        assert!(handle.same_as(&clone));
+
+        let other = StopHandle::new();
+        assert!(!other.is_stopped());
+        assert!(!other.same_as(&handle));
+    }
+}
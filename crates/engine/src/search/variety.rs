@@ -0,0 +1,213 @@
+//! Move variety for casual games
+//!
+//! Early in the game the engine may play any root move scoring close to the
+//! best one instead of always the best, so casual opponents don't see the
+//! same line every game. Candidates within `variety * VARIETY_CP_PER_POINT`
+//! centipawns of the best move are weighted by how close they are
+//! (a softmax with a temperature of half that window) and one is drawn at
+//! random.
+//!
+//! Unlike [`Skill`](super::Skill) the choice comes from a seedable PRNG that
+//! advances with every pick: a fixed seed replays the same choices, and
+//! without one successive games differ.
+
+use super::core::{MATE_SCORE, MAX_DEPTH};
+use super::skill::SkillRng;
+use crate::board::Board;
+
+/// Highest variety setting.
+pub const MAX_MOVE_VARIETY: u32 = 100;
+
+/// Width of the candidate window per variety point, in centipawns.
+pub const VARIETY_CP_PER_POINT: i32 = 2;
+
+/// Candidates are never more than this much worse than the best move.
+pub const VARIETY_MAX_WINDOW: i32 = 150;
+
+/// Variety only applies up to this full move number.
+pub const VARIETY_LAST_FULLMOVE: u32 = 12;
+
+/// Number of lines searched with exact scores when variety applies.
+pub const VARIETY_SEARCH_LINES: usize = 4;
+
+/// Move variety settings and random state.
+#[derive(Debug, Clone)]
+pub struct MoveVariety {
+    amount: u32,
+    rng: SkillRng,
+}
+
+impl MoveVariety {
+    /// Create a variety setting (values above 100 are clamped), seeded from
+    /// the process' random hasher keys.
+    ///
+    /// Those keys are fixed on wasm32, so there every game replays the same
+    /// choices unless the host sets a seed (see [`Self::set_seed`]).
+    pub fn new(amount: u32) -> Self {
+        Self::with_seed(amount, random_seed())
+    }
+
+    /// Create a variety setting whose choices are fully determined by `seed`.
+    pub fn with_seed(amount: u32, seed: u64) -> Self {
+        Self {
+            amount: amount.min(MAX_MOVE_VARIETY),
+            rng: SkillRng::new(seed),
+        }
+    }
+
+    #[inline]
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    /// Change the amount, keeping the random state.
+    pub fn set_amount(&mut self, amount: u32) {
+        self.amount = amount.min(MAX_MOVE_VARIETY);
+    }
+
+    /// Restart the random sequence from `seed`.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SkillRng::new(seed);
+    }
+
+    /// Whether variety is switched on at all.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.amount > 0
+    }
+
+    /// Whether variety is on and `board` is still in the opening.
+    pub fn applies(&self, board: &Board) -> bool {
+        self.enabled() && board.fullmove_number() <= VARIETY_LAST_FULLMOVE
+    }
+
+    /// Maximum score loss of a candidate move, in centipawns.
+    pub fn window(&self) -> i32 {
+        (self.amount as i32 * VARIETY_CP_PER_POINT).min(VARIETY_MAX_WINDOW)
+    }
+
+    /// Pick the index of the move to play among root moves with exact
+    /// `scores`, sorted best-first. `swing` is the change of the best score
+    /// over the last search iteration.
+    ///
+    /// Returns 0 (the best move) when variety is off, any mate score is
+    /// involved or the score swung by more than [`VARIETY_MAX_WINDOW`];
+    /// otherwise only moves within [`Self::window`] of the best are
+    /// considered.
+    pub fn pick(&mut self, scores: &[i32], swing: i32) -> usize {
+        let is_mate = |score: i32| score.abs() >= MATE_SCORE - MAX_DEPTH as i32;
+        if !self.enabled()
+            || scores.len() <= 1
+            || swing.abs() > VARIETY_MAX_WINDOW
+            || scores.iter().any(|&s| is_mate(s))
+        {
+            return 0;
+        }
+
+        let window = self.window();
+        let temperature = (window as f64 / 2.0).max(1.0);
+        let weights: Vec<f64> = scores
+            .iter()
+            .take_while(|&&score| scores[0] - score <= window)
+            .map(|&score| (-(scores[0] - score) as f64 / temperature).exp())
+            .collect();
+
+        // Uniform in [0, total): 53 random bits are exact in an f64
+        let total: f64 = weights.iter().sum();
+        let mut target = (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 * total;
+        for (index, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return index;
+            }
+            target -= weight;
+        }
+        weights.len() - 1
+    }
+}
+
+impl Default for MoveVariety {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Seed from the standard library's per-process random hasher keys (a
+/// constant on wasm32, which has no entropy source for them).
+fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCORES: [i32; 5] = [30, 25, 10, -40, -200];
+
+    #[test]
+    fn test_zero_variety_always_best() {
+        let mut variety = MoveVariety::with_seed(0, 7);
+        assert!(!variety.enabled());
+        for _ in 0..1000 {
+            assert_eq!(variety.pick(&SCORES, 0), 0);
+        }
+    }
+
+    #[test]
+    fn test_pick_stays_in_window() {
+        // 20 points: 40cp window, so only the first three moves qualify
+        let mut variety = MoveVariety::with_seed(20, 1);
+        let mut seen = [0; 5];
+        for _ in 0..1000 {
+            seen[variety.pick(&SCORES, 0)] += 1;
+        }
+        assert!(seen[0] > seen[1] && seen[1] > seen[2] && seen[2] > 0);
+        assert_eq!(seen[3] + seen[4], 0);
+
+        // The window is capped even at full variety
+        let mut variety = MoveVariety::with_seed(MAX_MOVE_VARIETY, 1);
+        assert_eq!(variety.window(), VARIETY_MAX_WINDOW);
+        assert!((0..1000).all(|_| variety.pick(&SCORES, 0) != 4));
+    }
+
+    #[test]
+    fn test_seed_replays_choices() {
+        let picks = |seed| {
+            let mut variety = MoveVariety::with_seed(50, seed);
+            (0..100)
+                .map(|_| variety.pick(&SCORES, 0))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picks(42), picks(42));
+        assert_ne!(picks(42), picks(43));
+    }
+
+    #[test]
+    fn test_mate_scores_disable_variety() {
+        let mut variety = MoveVariety::with_seed(MAX_MOVE_VARIETY, 3);
+        let scores = [MATE_SCORE - 5, MATE_SCORE - 7, 0];
+        assert!((0..100).all(|_| variety.pick(&scores, 0) == 0));
+        let scores = [20, 10, -MATE_SCORE + 4];
+        assert!((0..100).all(|_| variety.pick(&scores, 0) == 0));
+    }
+
+    #[test]
+    fn test_score_swing_disables_variety() {
+        let mut variety = MoveVariety::with_seed(MAX_MOVE_VARIETY, 5);
+        let scores = [0, 0, 0, 0];
+        assert!((0..100).all(|_| variety.pick(&scores, -200) == 0));
+        assert!((0..100).any(|_| variety.pick(&scores, 100) != 0));
+    }
+
+    #[test]
+    fn test_applies_only_in_opening() {
+        let variety = MoveVariety::with_seed(30, 0);
+        let mut board = Board::startpos();
+        assert!(variety.applies(&board));
+        board.set_fullmove_number(VARIETY_LAST_FULLMOVE + 1);
+        assert!(!variety.applies(&board));
+        assert!(!MoveVariety::with_seed(0, 0).applies(&Board::startpos()));
+    }
+}
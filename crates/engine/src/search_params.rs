@@ -47,6 +47,9 @@ pub struct SearchParams {
     pub lmr_base_reduction: i32,           // Base reduction for LMR (1-3)
     pub lmr_move_threshold: usize,         // Moves before LMR kicks in (3-6)
     pub lmr_depth_threshold: i32,          // Depth before LMR kicks in (2-4)
+    pub lmr_log_table: i32,                // Base reduction from the ln(depth) * ln(moves) table (0 = off, 1 = on)
+    pub lmr_history_adjust: i32,           // Adjust reductions by killers/countermoves/history (0 = off, 1 = on)
+    pub lmr_history_divisor: i32,          // History points per ply of reduction removed (4000-16000)
 
     // Null Move Pruning
     pub null_move_r: i32,                  // Null move reduction factor (2-3)
@@ -81,12 +84,22 @@ pub struct SearchParams {
     pub iid_depth_reduction: i32,          // IID depth reduction (1-3)
     pub iir_depth_reduction: i32,          // IIR depth reduction (1-2)
     pub iid_min_depth: i32,                // Minimum depth for IID (3-5)
+    pub iid_cut_depth_reduction: i32,      // IID depth reduction at cut nodes (3-5)
+    pub iid_cut_min_depth: i32,            // Minimum depth for IID at cut nodes (5-8, 99 = off)
 
     // Singular Extensions
     pub singular_margin: i32,              // Margin for singularity (50-150)
     pub singular_depth_reduction: i32,     // Depth reduction for verification (2-4)
     pub singular_min_depth: i32,           // Minimum depth for singular (6-10)
 
+    // Quiescence
+    pub max_qsearch_ply: i32,              // Quiescence plies below the leaves (16-64)
+    pub lazy_eval_margin: i32,             // Stand pat lazy eval margin (300-400, 0 = off)
+    pub lazy_eval_king_attack: i32,        // King attack weight that forces a full eval (6-16)
+
+    // Move ordering
+    pub bad_captures_before_quiets: i32,   // Bad captures before quiets (0 = after, 1 = before)
+
     // Evaluation scaling
     pub king_safety_divisor: i32,          // King safety scaling (8-16)
 }
@@ -98,6 +111,10 @@ impl Default for SearchParams {
             lmr_base_reduction: 2,
             lmr_move_threshold: 6,
             lmr_depth_threshold: 6,
+            // LMR refinements: the log table and history adjustment
+            lmr_log_table: 0,
+            lmr_history_adjust: 0,
+            lmr_history_divisor: 8000,
 
             // Null move
             null_move_r: 2,
@@ -132,12 +149,28 @@ impl Default for SearchParams {
             iid_depth_reduction: 2,
             iir_depth_reduction: 1,
             iid_min_depth: 4,
+            // Cut node IID costs ~10% more nodes at depth 9 on the bench
+            // positions, so it is off until tuned
+            iid_cut_depth_reduction: 4,
+            iid_cut_min_depth: 99,
 
             // Singular extensions
             singular_margin: 100,
             singular_depth_reduction: 4,
             singular_min_depth: 8,
 
+            // Quiescence: capture chains rarely run past 20 plies
+            max_qsearch_ply: 32,
+            // Lazy stand pat at a 350 margin searches the same nodes on the
+            // bench positions and skips the full eval at 38% of the stand
+            // pats, for about 5% less time at depth 8
+            lazy_eval_margin: 350,
+            lazy_eval_king_attack: 10,
+
+            // Move ordering: before quiets saves 13% of the nodes on WAC at
+            // depth 8 but costs 44% on the bench positions at depth 10
+            bad_captures_before_quiets: 0,
+
             // Evaluation
             king_safety_divisor: 12,
         }
@@ -151,6 +184,9 @@ impl SearchParams {
             "lmr_base_reduction" => self.lmr_base_reduction = value,
             "lmr_move_threshold" => self.lmr_move_threshold = value as usize,
             "lmr_depth_threshold" => self.lmr_depth_threshold = value,
+            "lmr_log_table" => self.lmr_log_table = value,
+            "lmr_history_adjust" => self.lmr_history_adjust = value,
+            "lmr_history_divisor" => self.lmr_history_divisor = value,
 
             "null_move_r" => self.null_move_r = value,
             "null_move_min_depth" => self.null_move_min_depth = value,
@@ -178,11 +214,19 @@ impl SearchParams {
             "iid_depth_reduction" => self.iid_depth_reduction = value,
             "iir_depth_reduction" => self.iir_depth_reduction = value,
             "iid_min_depth" => self.iid_min_depth = value,
+            "iid_cut_depth_reduction" => self.iid_cut_depth_reduction = value,
+            "iid_cut_min_depth" => self.iid_cut_min_depth = value,
 
             "singular_margin" => self.singular_margin = value,
             "singular_depth_reduction" => self.singular_depth_reduction = value,
             "singular_min_depth" => self.singular_min_depth = value,
 
+            "max_qsearch_ply" => self.max_qsearch_ply = value,
+            "lazy_eval_margin" => self.lazy_eval_margin = value,
+            "lazy_eval_king_attack" => self.lazy_eval_king_attack = value,
+
+            "bad_captures_before_quiets" => self.bad_captures_before_quiets = value,
+
             "king_safety_divisor" => self.king_safety_divisor = value,
 
             _ => return Err(format!("Unknown parameter: {}", name)),
@@ -196,6 +240,9 @@ impl SearchParams {
             "lmr_base_reduction" => Ok(self.lmr_base_reduction),
             "lmr_move_threshold" => Ok(self.lmr_move_threshold as i32),
             "lmr_depth_threshold" => Ok(self.lmr_depth_threshold),
+            "lmr_log_table" => Ok(self.lmr_log_table),
+            "lmr_history_adjust" => Ok(self.lmr_history_adjust),
+            "lmr_history_divisor" => Ok(self.lmr_history_divisor),
 
             "null_move_r" => Ok(self.null_move_r),
             "null_move_min_depth" => Ok(self.null_move_min_depth),
@@ -223,11 +270,19 @@ impl SearchParams {
             "iid_depth_reduction" => Ok(self.iid_depth_reduction),
             "iir_depth_reduction" => Ok(self.iir_depth_reduction),
             "iid_min_depth" => Ok(self.iid_min_depth),
+            "iid_cut_depth_reduction" => Ok(self.iid_cut_depth_reduction),
+            "iid_cut_min_depth" => Ok(self.iid_cut_min_depth),
 
             "singular_margin" => Ok(self.singular_margin),
             "singular_depth_reduction" => Ok(self.singular_depth_reduction),
             "singular_min_depth" => Ok(self.singular_min_depth),
 
+            "max_qsearch_ply" => Ok(self.max_qsearch_ply),
+            "lazy_eval_margin" => Ok(self.lazy_eval_margin),
+            "lazy_eval_king_attack" => Ok(self.lazy_eval_king_attack),
+
+            "bad_captures_before_quiets" => Ok(self.bad_captures_before_quiets),
+
             "king_safety_divisor" => Ok(self.king_safety_divisor),
 
             _ => Err(format!("Unknown parameter: {}", name)),
@@ -240,6 +295,9 @@ impl SearchParams {
             "lmr_base_reduction",
             "lmr_move_threshold",
             "lmr_depth_threshold",
+            "lmr_log_table",
+            "lmr_history_adjust",
+            "lmr_history_divisor",
             "null_move_r",
             "null_move_min_depth",
             "futility_margin_d1",
@@ -260,9 +318,15 @@ impl SearchParams {
             "iid_depth_reduction",
             "iir_depth_reduction",
             "iid_min_depth",
+            "iid_cut_depth_reduction",
+            "iid_cut_min_depth",
             "singular_margin",
             "singular_depth_reduction",
             "singular_min_depth",
+            "max_qsearch_ply",
+            "lazy_eval_margin",
+            "lazy_eval_king_attack",
+            "bad_captures_before_quiets",
             "king_safety_divisor",
         ]
     }
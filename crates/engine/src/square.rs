@@ -1,3 +1,6 @@
+use crate::bitboard::Bitboard;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// Represents a square on the chessboard (0-63)
 ///
 /// Layout (little-endian rank-file mapping):
@@ -46,6 +49,65 @@ impl Square {
         self.0 >> 3
     }
 
+    /// Mirror across the d/e file boundary (a1 <-> h1, e4 <-> d4)
+    #[inline]
+    pub const fn mirror_horizontal(self) -> Self {
+        Square(self.0 ^ 7)
+    }
+
+    /// Mirror across the 4th/5th rank boundary (a1 <-> a8, e4 <-> e5)
+    ///
+    /// This is the flip used to look up White-relative tables for Black.
+    #[inline]
+    pub const fn mirror_vertical(self) -> Self {
+        Square(self.0 ^ 56)
+    }
+
+    /// King steps to `other`: the larger of the file and rank distances (0-7)
+    #[inline]
+    pub const fn chebyshev_distance(self, other: Square) -> u8 {
+        let file_dist = self.file().abs_diff(other.file());
+        let rank_dist = self.rank().abs_diff(other.rank());
+        if file_dist > rank_dist {
+            file_dist
+        } else {
+            rank_dist
+        }
+    }
+
+    /// Orthogonal steps to `other`: file plus rank distance (0-14)
+    #[inline]
+    pub const fn manhattan_distance(self, other: Square) -> u8 {
+        self.file().abs_diff(other.file()) + self.rank().abs_diff(other.rank())
+    }
+
+    /// The 3x3 block centred on this square, the square itself included
+    /// (4 squares in a corner, 6 on an edge); the king zone
+    #[inline]
+    pub fn adjacent(self) -> Bitboard {
+        crate::attacks::king_attacks(self).set(self)
+    }
+
+    /// Whether both squares are on the same rank
+    #[inline]
+    pub const fn same_rank(self, other: Square) -> bool {
+        self.rank() == other.rank()
+    }
+
+    /// Whether both squares are on the same file
+    #[inline]
+    pub const fn same_file(self, other: Square) -> bool {
+        self.file() == other.file()
+    }
+
+    /// Whether both squares are on one diagonal or anti-diagonal, i.e. a
+    /// bishop on an empty board could go from one to the other (or they
+    /// are the same square)
+    #[inline]
+    pub const fn same_diagonal(self, other: Square) -> bool {
+        self.file().abs_diff(other.file()) == self.rank().abs_diff(other.rank())
+    }
+
     /// Convert to algebraic notation (e.g., "e4")
     pub fn to_algebraic(self) -> String {
         let file_char = (b'a' + self.file()) as char;
@@ -134,6 +196,55 @@ impl std::fmt::Display for Square {
     }
 }
 
+/// Error for a string or index that isn't a square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquareError(pub String);
+
+impl std::fmt::Display for SquareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid square: {}", self.0)
+    }
+}
+
+impl std::error::Error for SquareError {}
+
+impl std::str::FromStr for Square {
+    type Err = SquareError;
+
+    /// Parse algebraic notation, e.g. `"e4".parse::<Square>()`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Square::from_algebraic(s).ok_or_else(|| SquareError(s.to_string()))
+    }
+}
+
+impl TryFrom<u8> for Square {
+    type Error = SquareError;
+
+    /// Convert an index, rejecting anything outside 0-63.
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        if index < 64 {
+            Ok(Square(index))
+        } else {
+            Err(SquareError(index.to_string()))
+        }
+    }
+}
+
+/// Serialized in algebraic notation (`"e4"`).
+impl Serialize for Square {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Square {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +303,113 @@ mod tests {
         assert_eq!(all_squares[0], Square::A1);
         assert_eq!(all_squares[63], Square::H8);
     }
+
+    #[test]
+    fn square_mirrors() {
+        assert_eq!(Square::A1.mirror_horizontal(), Square::H1);
+        assert_eq!(Square::E4.mirror_horizontal(), Square::D4);
+        assert_eq!(Square::A1.mirror_vertical(), Square::A8);
+        assert_eq!(Square::E4.mirror_vertical(), Square::E5);
+
+        for sq in Square::all() {
+            assert_eq!(sq.mirror_horizontal().mirror_horizontal(), sq);
+            assert_eq!(sq.mirror_vertical().mirror_vertical(), sq);
+            assert_eq!(sq.mirror_horizontal().rank(), sq.rank());
+            assert_eq!(sq.mirror_vertical().file(), sq.file());
+        }
+    }
+
+    #[test]
+    fn square_distances() {
+        let sq = |s| Square::from_algebraic(s).unwrap();
+
+        assert_eq!(Square::E4.chebyshev_distance(Square::E5), 1);
+        assert_eq!(Square::A1.chebyshev_distance(Square::H8), 7);
+        assert_eq!(Square::H1.chebyshev_distance(Square::A8), 7);
+        assert_eq!(Square::E4.chebyshev_distance(sq("g6")), 2);
+        assert_eq!(Square::E4.chebyshev_distance(sq("f7")), 3);
+
+        assert_eq!(Square::A1.manhattan_distance(Square::H8), 14);
+        assert_eq!(Square::H1.manhattan_distance(Square::A8), 14);
+        assert_eq!(Square::E2.manhattan_distance(Square::E4), 2);
+        assert_eq!(Square::E4.manhattan_distance(sq("f7")), 4);
+
+        for a in Square::all() {
+            assert_eq!(a.chebyshev_distance(a), 0);
+            assert_eq!(a.manhattan_distance(a), 0);
+            for b in Square::all() {
+                assert_eq!(a.chebyshev_distance(b), b.chebyshev_distance(a));
+                assert_eq!(a.manhattan_distance(b), b.manhattan_distance(a));
+                assert!(a.chebyshev_distance(b) <= a.manhattan_distance(b));
+                assert!(a.manhattan_distance(b) <= 2 * a.chebyshev_distance(b));
+            }
+        }
+    }
+
+    #[test]
+    fn square_adjacent() {
+        let sq = |s| Square::from_algebraic(s).unwrap();
+
+        let zone = Square::E4.adjacent();
+        assert_eq!(zone.count(), 9);
+        for s in ["d3", "e3", "f3", "d4", "e4", "f4", "d5", "e5", "f5"] {
+            assert!(zone.contains(sq(s)), "{s}");
+        }
+
+        assert_eq!(Square::A1.adjacent().count(), 4);
+        assert_eq!(Square::H8.adjacent().count(), 4);
+        assert_eq!(Square::E1.adjacent().count(), 6);
+        assert_eq!(sq("a5").adjacent().count(), 6);
+
+        for a in Square::all() {
+            for b in Square::all() {
+                assert_eq!(a.adjacent().contains(b), a.chebyshev_distance(b) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn square_lines() {
+        let sq = |s| Square::from_algebraic(s).unwrap();
+
+        assert!(Square::A1.same_rank(Square::H1));
+        assert!(!Square::A1.same_rank(Square::A2));
+        assert!(Square::E2.same_file(Square::E8));
+        assert!(!Square::E2.same_file(Square::D2));
+
+        assert!(Square::A1.same_diagonal(Square::H8));
+        assert!(Square::H1.same_diagonal(Square::A8));
+        assert!(Square::E4.same_diagonal(sq("b7")));
+        assert!(Square::E4.same_diagonal(sq("h1")));
+        assert!(!Square::E4.same_diagonal(Square::E5));
+        assert!(!Square::E4.same_diagonal(sq("f6")));
+        // a1-h8 and h1-a8 meet only where the board would wrap
+        assert!(!Square::H1.same_diagonal(sq("a2")));
+
+        for a in Square::all() {
+            assert!(a.same_rank(a) && a.same_file(a) && a.same_diagonal(a));
+        }
+    }
+
+    #[test]
+    fn square_from_str_and_try_from_round_trip() {
+        for sq in Square::all() {
+            assert_eq!(sq.to_string().parse::<Square>(), Ok(sq));
+            assert_eq!(Square::try_from(sq.index()), Ok(sq));
+        }
+        assert!("e9".parse::<Square>().is_err());
+        assert!("".parse::<Square>().is_err());
+        assert_eq!(Square::try_from(64), Err(SquareError("64".to_string())));
+    }
+
+    #[test]
+    fn square_serde_round_trip() {
+        for sq in Square::all() {
+            let json = serde_json::to_string(&sq).unwrap();
+            assert_eq!(json, format!("\"{}\"", sq));
+            assert_eq!(serde_json::from_str::<Square>(&json).unwrap(), sq);
+        }
+        assert!(serde_json::from_str::<Square>("\"z1\"").is_err());
+        assert!(serde_json::from_str::<Square>("28").is_err());
+    }
 }
@@ -1,52 +1,104 @@
-// Time types - use dummy implementations for WASM
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
-use std::time::{Duration, Instant};
-
-#[cfg(target_arch = "wasm32")]
-use std::time::Duration;
+use std::time::Instant;
+
+/// Source of the current time for a [`TimeManager`].
+///
+/// Searches read the time through this trait so tests can drive a
+/// [`ManualClock`] instead of sleeping on the wall clock.
+pub trait Clock: Send + Sync {
+    /// Milliseconds since an arbitrary origin fixed for this clock.
+    fn now_ms(&self) -> u64;
+}
 
-#[cfg(target_arch = "wasm32")]
-#[derive(Debug, Clone, Copy, Eq)]
-struct Instant;
+/// The wall clock, counting from its creation.
+///
+/// There is no usable clock on wasm32: it stays at 0 ms, so only zero
+/// budgets ever run out there.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    origin: Instant,
+}
 
-#[cfg(target_arch = "wasm32")]
-impl Instant {
-    fn now() -> Self {
-        Instant
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            #[cfg(not(target_arch = "wasm32"))]
+            origin: Instant::now(),
+        }
     }
+}
 
-    fn elapsed(&self) -> Duration {
-        Duration::from_millis(0)
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-impl std::ops::Add<Duration> for Instant {
-    type Output = Instant;
-    fn add(self, _: Duration) -> Self {
-        Instant
+impl Clock for SystemClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn now_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
     }
-}
 
-#[cfg(target_arch = "wasm32")]
-impl PartialOrd for Instant {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    #[cfg(target_arch = "wasm32")]
+    fn now_ms(&self) -> u64 {
+        0
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-impl Ord for Instant {
-    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
-        // Always return Less so time checks always return false
-        std::cmp::Ordering::Less
+/// A clock that only moves when told to, for deterministic time tests.
+///
+/// A ticking clock also advances by a fixed step after every read, so time
+/// passes in proportion to how often the search looks at it.
+///
+/// # Example
+/// ```
+/// use engine::time::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// clock.advance(250);
+/// assert_eq!(clock.now_ms(), 250);
+///
+/// let ticking = ManualClock::ticking(10);
+/// assert_eq!((ticking.now_ms(), ticking.now_ms()), (0, 10));
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: AtomicU64,
+    step: u64,
+}
+
+impl ManualClock {
+    /// A clock standing at 0 ms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A clock at 0 ms that advances `step_ms` after each read.
+    pub fn ticking(step_ms: u64) -> Self {
+        ManualClock {
+            now: AtomicU64::new(0),
+            step: step_ms,
+        }
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, ms: u64) {
+        self.now.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    /// Set the clock to an absolute time.
+    pub fn set(&self, ms: u64) {
+        self.now.store(ms, Ordering::Relaxed);
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-impl PartialEq for Instant {
-    fn eq(&self, _: &Self) -> bool {
-        true
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now.fetch_add(self.step, Ordering::Relaxed)
     }
 }
 
@@ -79,24 +131,35 @@ pub enum TimeControl {
 
     /// Search a fixed number of nodes
     Nodes { nodes: u64 },
+
+    /// Search for a forced mate in the given number of moves
+    /// (no time limit; stops as soon as such a mate is proven)
+    Mate { moves: u32 },
 }
 
 /// Manages time allocation during search
 pub struct TimeManager {
-    /// Soft time limit - should stop searching after this
-    soft_limit: Option<Instant>,
-    /// Hard time limit - must stop searching after this
-    hard_limit: Option<Instant>,
-    /// When search started
-    start_time: Instant,
+    /// Soft time limit (ms on the clock) - should stop searching after this
+    soft_limit: Option<u64>,
+    /// Hard time limit (ms on the clock) - must stop searching after this
+    hard_limit: Option<u64>,
+    /// When search started (ms on the clock)
+    start_time: u64,
     /// Time control mode
     time_control: TimeControl,
+    /// Where the time is read from
+    clock: Arc<dyn Clock>,
 }
 
 impl TimeManager {
     /// Create a new time manager with given time control
     pub fn new(time_control: TimeControl, is_white: bool) -> Self {
-        let start_time = Instant::now();
+        Self::with_clock(time_control, is_white, Arc::new(SystemClock::new()))
+    }
+
+    /// Create a time manager that reads the time from `clock`
+    pub fn with_clock(time_control: TimeControl, is_white: bool, clock: Arc<dyn Clock>) -> Self {
+        let start_time = clock.now_ms();
         let (soft_limit, hard_limit) = Self::calculate_limits(&time_control, is_white, start_time);
 
         TimeManager {
@@ -104,6 +167,7 @@ impl TimeManager {
             hard_limit,
             start_time,
             time_control,
+            clock,
         }
     }
 
@@ -111,16 +175,17 @@ impl TimeManager {
     fn calculate_limits(
         time_control: &TimeControl,
         is_white: bool,
-        start_time: Instant,
-    ) -> (Option<Instant>, Option<Instant>) {
+        start_time: u64,
+    ) -> (Option<u64>, Option<u64>) {
         match time_control {
-            TimeControl::Infinite | TimeControl::Depth { .. } | TimeControl::Nodes { .. } => {
-                (None, None)
-            }
+            TimeControl::Infinite
+            | TimeControl::Depth { .. }
+            | TimeControl::Nodes { .. }
+            | TimeControl::Mate { .. } => (None, None),
 
             TimeControl::MoveTime { millis } => {
-                let hard = start_time + Duration::from_millis(*millis);
-                let soft = start_time + Duration::from_millis(millis.saturating_sub(50)); // Leave 50ms buffer
+                let hard = start_time + millis;
+                let soft = start_time + millis.saturating_sub(50); // Leave 50ms buffer
                 (Some(soft), Some(hard))
             }
 
@@ -140,8 +205,8 @@ impl TimeManager {
                 // Time allocation strategy
                 let allocated = Self::allocate_time(my_time, my_inc, *movestogo);
 
-                let hard = start_time + Duration::from_millis(allocated.hard);
-                let soft = start_time + Duration::from_millis(allocated.soft);
+                let hard = start_time + allocated.hard;
+                let soft = start_time + allocated.soft;
 
                 (Some(soft), Some(hard))
             }
@@ -194,7 +259,7 @@ impl TimeManager {
     /// Check if we should stop searching (soft limit exceeded)
     pub fn should_stop(&self) -> bool {
         if let Some(soft) = self.soft_limit {
-            Instant::now() >= soft
+            self.clock.now_ms() >= soft
         } else {
             false
         }
@@ -203,7 +268,7 @@ impl TimeManager {
     /// Check if we must stop searching (hard limit exceeded)
     pub fn must_stop(&self) -> bool {
         if let Some(hard) = self.hard_limit {
-            Instant::now() >= hard
+            self.clock.now_ms() >= hard
         } else {
             false
         }
@@ -211,7 +276,7 @@ impl TimeManager {
 
     /// Get elapsed time since search started (in milliseconds)
     pub fn elapsed_ms(&self) -> u64 {
-        self.start_time.elapsed().as_millis() as u64
+        self.clock.now_ms().saturating_sub(self.start_time)
     }
 
     /// Get time control
@@ -228,6 +293,11 @@ impl TimeManager {
     pub fn node_limit_reached(&self, current_nodes: u64) -> bool {
         matches!(self.time_control, TimeControl::Nodes { nodes } if current_nodes >= nodes)
     }
+
+    /// Check if a mate found in `mate_plies` plies satisfies a mate search
+    pub fn mate_limit_reached(&self, mate_plies: u32) -> bool {
+        matches!(self.time_control, TimeControl::Mate { moves } if mate_plies < 2 * moves)
+    }
 }
 
 /// Time allocation result
@@ -252,34 +322,69 @@ mod tests {
         assert!(!tm.node_limit_reached(1_000_000));
     }
 
+    /// A time manager on a manual clock that already reads `start` ms.
+    fn manual(
+        time_control: TimeControl,
+        is_white: bool,
+        start: u64,
+    ) -> (TimeManager, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new());
+        clock.set(start);
+        (
+            TimeManager::with_clock(time_control, is_white, clock.clone()),
+            clock,
+        )
+    }
+
     #[test]
     fn test_move_time() {
-        let tm = TimeManager::new(TimeControl::MoveTime { millis: 1000 }, true);
+        let (tm, clock) = manual(TimeControl::MoveTime { millis: 1000 }, true, 5_000);
 
         // Immediately after creation, should not stop
         assert!(!tm.should_stop());
         assert!(!tm.must_stop());
 
-        // Should have limits set
-        assert!(tm.soft_limit.is_some());
-        assert!(tm.hard_limit.is_some());
+        // Soft limit 50ms before the hard one, both measured from the start
+        clock.set(5_949);
+        assert!(!tm.should_stop());
+        clock.set(5_950);
+        assert!(tm.should_stop());
+        assert!(!tm.must_stop());
+        clock.set(6_000);
+        assert!(tm.must_stop());
     }
 
     #[test]
     fn test_clock_time_control() {
-        let tm = TimeManager::new(
-            TimeControl::Clock {
-                wtime: 60000, // 1 minute
-                btime: 60000,
-                winc: 1000, // 1 second increment
-                binc: 1000,
-                movestogo: Some(20),
-            },
-            true,
-        );
+        let clock = TimeControl::Clock {
+            wtime: 60000, // 1 minute
+            btime: 6000,
+            winc: 1000, // 1 second increment
+            binc: 0,
+            movestogo: Some(20),
+        };
+
+        // 60000 - 1200 margin = 58800 available: 58800 / 20 + 750 soft, 5x hard
+        let (tm, time) = manual(clock.clone(), true, 0);
+        time.set(3_689);
+        assert!(!tm.should_stop());
+        time.set(3_690);
+        assert!(tm.should_stop());
+        time.set(18_449);
+        assert!(!tm.must_stop());
+        time.set(18_450);
+        assert!(tm.must_stop());
 
-        assert!(tm.soft_limit.is_some());
-        assert!(tm.hard_limit.is_some());
+        // Black budgets from its own clock: (6000 - 120) / 20 soft, 3x hard
+        let (tm, time) = manual(clock, false, 0);
+        time.set(293);
+        assert!(!tm.should_stop());
+        time.set(294);
+        assert!(tm.should_stop());
+        time.set(881);
+        assert!(!tm.must_stop());
+        time.set(882);
+        assert!(tm.must_stop());
     }
 
     #[test]
@@ -310,11 +415,38 @@ mod tests {
         assert!(!tm.must_stop());
     }
 
+    #[test]
+    fn test_mate_limit() {
+        let tm = TimeManager::new(TimeControl::Mate { moves: 2 }, true);
+
+        assert!(tm.mate_limit_reached(1)); // Mate in 1
+        assert!(tm.mate_limit_reached(3)); // Mate in 2
+        assert!(!tm.mate_limit_reached(5)); // Mate in 3 is too slow
+
+        // No time limits for mate search
+        assert!(!tm.should_stop());
+        assert!(!tm.must_stop());
+    }
+
     #[test]
     fn test_elapsed_time() {
-        let tm = TimeManager::new(TimeControl::Infinite, true);
-        std::thread::sleep(Duration::from_millis(10));
-        assert!(tm.elapsed_ms() >= 10);
+        let (tm, clock) = manual(TimeControl::Infinite, true, 700);
+        assert_eq!(tm.elapsed_ms(), 0);
+        clock.advance(10);
+        assert_eq!(tm.elapsed_ms(), 10);
+
+        // Infinite searches never run out, however long they take
+        clock.advance(u32::MAX as u64);
+        assert!(!tm.should_stop());
+        assert!(!tm.must_stop());
+    }
+
+    #[test]
+    fn test_system_clock_moves_forward() {
+        let clock = SystemClock::new();
+        let before = clock.now_ms();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(clock.now_ms() >= before + 5);
     }
 
     #[test]
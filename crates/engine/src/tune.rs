@@ -5,10 +5,13 @@
 
 use crate::eval::Evaluator;
 use crate::io::parse_fen;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::RwLock;
 
 thread_local! {
     /// Thread-local storage for tunable parameters during optimization.
@@ -16,6 +19,17 @@ thread_local! {
     pub static TUNING_PARAMS: RefCell<Option<TuningParams>> = RefCell::new(None);
 }
 
+/// Process-wide parameters loaded at runtime (e.g. from an EvalParams file).
+/// Thread-local tuning parameters take precedence over these.
+static GLOBAL_PARAMS: RwLock<Option<TuningParams>> = RwLock::new(None);
+
+/// Fast check so evaluation skips the lock when no file is loaded.
+static GLOBAL_PARAMS_SET: AtomicBool = AtomicBool::new(false);
+
+/// Bumped whenever the global parameters change, so evaluators can drop
+/// cached scores computed with the old values.
+static PARAMS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 /// Set the thread-local tuning parameters.
 /// Call this before evaluating positions during tuning.
 pub fn set_tuning_params(params: TuningParams) {
@@ -34,13 +48,128 @@ where
     F: FnOnce(&TuningParams) -> i32,
 {
     TUNING_PARAMS.with(|p| {
-        p.borrow()
-            .as_ref()
-            .map(tuning_getter)
+        if let Some(params) = p.borrow().as_ref() {
+            return tuning_getter(params);
+        }
+        if !GLOBAL_PARAMS_SET.load(Ordering::Acquire) {
+            return default;
+        }
+        GLOBAL_PARAMS
+            .read()
+            .ok()
+            .and_then(|params| params.as_ref().map(tuning_getter))
             .unwrap_or(default)
     })
 }
 
+/// Evaluation parameters that can be loaded from JSON at runtime.
+///
+/// The JSON object must contain every field of [`TuningParams`], using the
+/// field names as keys; the passed pawn tables are arrays of 8 values indexed
+/// by rank.
+pub type EvalParams = TuningParams;
+
+/// Error loading evaluation parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalParamsError {
+    /// The parameter file could not be read
+    Io(String),
+    /// The input is not valid JSON or a value has the wrong type
+    Json(String),
+    /// The JSON object has unknown fields or lacks required ones
+    Fields {
+        unknown: Vec<String>,
+        missing: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for EvalParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalParamsError::Io(e) => write!(f, "Cannot read eval params: {}", e),
+            EvalParamsError::Json(e) => write!(f, "Invalid eval params JSON: {}", e),
+            EvalParamsError::Fields { unknown, missing } => {
+                write!(f, "Invalid eval params")?;
+                if !unknown.is_empty() {
+                    write!(f, "; unknown fields: {}", unknown.join(", "))?;
+                }
+                if !missing.is_empty() {
+                    write!(f, "; missing fields: {}", missing.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalParamsError {}
+
+/// Load evaluation parameters from a JSON file, or from a JSON string if
+/// the argument starts with `{`.
+///
+/// Every field must be present and no unknown fields are allowed; the error
+/// lists all offending fields at once.
+pub fn load_params_from_json(path_or_str: &str) -> Result<EvalParams, EvalParamsError> {
+    let json = if path_or_str.trim_start().starts_with('{') {
+        path_or_str.to_string()
+    } else {
+        std::fs::read_to_string(path_or_str)
+            .map_err(|e| EvalParamsError::Io(format!("{}: {}", path_or_str, e)))?
+    };
+
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| EvalParamsError::Json(e.to_string()))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| EvalParamsError::Json("expected a JSON object".to_string()))?;
+
+    // Field names come from the serialized defaults
+    let defaults = serde_json::to_value(TuningParams::from_current_eval())
+        .map_err(|e| EvalParamsError::Json(e.to_string()))?;
+    let expected = defaults
+        .as_object()
+        .expect("TuningParams serializes to an object");
+
+    let unknown: Vec<String> = object
+        .keys()
+        .filter(|key| !expected.contains_key(*key))
+        .cloned()
+        .collect();
+    let missing: Vec<String> = expected
+        .keys()
+        .filter(|key| !object.contains_key(*key))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() || !missing.is_empty() {
+        return Err(EvalParamsError::Fields { unknown, missing });
+    }
+
+    serde_json::from_value(value).map_err(|e| EvalParamsError::Json(e.to_string()))
+}
+
+/// Use `params` for evaluation in every thread, until cleared.
+pub fn set_global_params(params: EvalParams) {
+    if let Ok(mut global) = GLOBAL_PARAMS.write() {
+        *global = Some(params);
+        GLOBAL_PARAMS_SET.store(true, Ordering::Release);
+        PARAMS_GENERATION.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Return to the built-in evaluation parameters.
+pub fn clear_global_params() {
+    if let Ok(mut global) = GLOBAL_PARAMS.write() {
+        *global = None;
+        GLOBAL_PARAMS_SET.store(false, Ordering::Release);
+        PARAMS_GENERATION.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Counter that changes whenever the global parameters are set or cleared.
+pub fn params_generation() -> u64 {
+    PARAMS_GENERATION.load(Ordering::Acquire)
+}
+
 /// A training position with its game outcome.
 #[derive(Debug, Clone)]
 pub struct TrainingPosition {
@@ -53,7 +182,7 @@ pub struct TrainingPosition {
 /// Tunable evaluation parameters.
 ///
 /// This structure contains all the weights that can be optimized.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TuningParams {
     // PST scaling
     pub pst_scale: i32,
@@ -80,11 +209,22 @@ pub struct TuningParams {
     // King safety (if we decide to re-enable it)
     pub king_safety_scale: i32,
 
+    // Material imbalance
+    pub rook_pair_mg: i32,
+    pub rook_pair_eg: i32,
+    pub knight_pawn_adjust: i32,
+    pub rook_pawn_adjust: i32,
+
     // Overall scaling divisors
     pub pawn_structure_divisor: i32,
     pub mobility_divisor: i32,
     pub king_safety_divisor: i32,
     pub threat_divisor: i32,
+    pub space_divisor: i32,
+
+    // Bonus for the side to move (middlegame, endgame)
+    pub tempo_mg: i32,
+    pub tempo_eg: i32,
 }
 
 impl TuningParams {
@@ -112,16 +252,28 @@ impl TuningParams {
             mobility_scale: 8,  // Current divisor is 8
             king_safety_scale: 0,  // Currently disabled
 
+            // Current imbalance values (from eval/imbalance.rs)
+            rook_pair_mg: -16,
+            rook_pair_eg: -8,
+            knight_pawn_adjust: 6,
+            rook_pawn_adjust: -12,
+
             pawn_structure_divisor: 4,
             mobility_divisor: 8,
             king_safety_divisor: 12,  // Optimal (50% vs SF1800, +65 ELO)
-            threat_divisor: 8,  // Initial value for threat evaluation
+            threat_divisor: 0,  // Off (see eval step 8); 1 = full weight
+            space_divisor: 4,
+
+            // Off: a bonus widens the odd/even depth swing (stand pat already
+            // favors the side to move at the leaves) and doubles bench nodes
+            tempo_mg: 0,
+            tempo_eg: 0,
         }
     }
 
     /// Get the number of tunable parameters.
     pub fn param_count() -> usize {
-        14 + 12  // 14 scalar params + 12 passed pawn params (2 per rank for ranks 2-7)
+        14 + 12 + 4  // 14 scalar params + 12 passed pawn params (2 per rank for ranks 2-7) + 4 imbalance params
     }
 
     /// Get a parameter value by index.
@@ -149,6 +301,10 @@ impl TuningParams {
                     self.passed_pawn_eg[rank_idx]
                 }
             }
+            26 => self.rook_pair_mg,
+            27 => self.rook_pair_eg,
+            28 => self.knight_pawn_adjust,
+            29 => self.rook_pawn_adjust,
             _ => panic!("Invalid parameter index"),
         }
     }
@@ -178,6 +334,10 @@ impl TuningParams {
                     self.passed_pawn_eg[rank_idx] = value;
                 }
             }
+            26 => self.rook_pair_mg = value,
+            27 => self.rook_pair_eg = value,
+            28 => self.knight_pawn_adjust = value,
+            29 => self.rook_pawn_adjust = value,
             _ => panic!("Invalid parameter index"),
         }
     }
@@ -207,6 +367,8 @@ impl TuningParams {
             names.push(Box::leak(format!("passed_pawn_eg_r{}", rank).into_boxed_str()));
         }
 
+        names.extend(["rook_pair_mg", "rook_pair_eg", "knight_pawn_adjust", "rook_pawn_adjust"]);
+
         names
     }
 
@@ -231,6 +393,11 @@ impl TuningParams {
                 self.passed_pawn_mg[rank], self.passed_pawn_eg[rank])?;
         }
         writeln!(file)?;
+        writeln!(file, "Material imbalance:")?;
+        writeln!(file, "  rook_pair: [{}, {}]", self.rook_pair_mg, self.rook_pair_eg)?;
+        writeln!(file, "  knight_pawn_adjust: {}", self.knight_pawn_adjust)?;
+        writeln!(file, "  rook_pawn_adjust: {}", self.rook_pawn_adjust)?;
+        writeln!(file)?;
         writeln!(file, "Mobility scale: {}", self.mobility_scale)?;
         writeln!(file, "Pawn structure divisor: {}", self.pawn_structure_divisor)?;
         writeln!(file, "Mobility divisor: {}", self.mobility_divisor)?;
@@ -453,6 +620,61 @@ mod tests {
         assert!((0.85..0.95).contains(&prob));
     }
 
+    #[test]
+    fn test_eval_params_json_roundtrip() {
+        let mut params = EvalParams::from_current_eval();
+        params.doubled_pawn_mg = -99;
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(load_params_from_json(&json), Ok(params));
+    }
+
+    #[test]
+    fn test_eval_params_json_field_errors() {
+        let json = r#"{"pst_scale": 4, "doubled_pawn": -15}"#;
+        let err = load_params_from_json(json).unwrap_err();
+        match &err {
+            EvalParamsError::Fields { unknown, missing } => {
+                assert_eq!(unknown, &vec!["doubled_pawn".to_string()]);
+                assert!(missing.contains(&"doubled_pawn_mg".to_string()));
+                assert!(missing.contains(&"passed_pawn_eg".to_string()));
+                assert!(!missing.contains(&"pst_scale".to_string()));
+            }
+            other => panic!("Expected field error, got {:?}", other),
+        }
+        let message = err.to_string();
+        assert!(message.contains("unknown fields: doubled_pawn"));
+        assert!(message.contains("missing fields:"));
+
+        assert!(matches!(
+            load_params_from_json("{not json"),
+            Err(EvalParamsError::Json(_))
+        ));
+        assert!(matches!(
+            load_params_from_json("/nonexistent/params.json"),
+            Err(EvalParamsError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_loaded_params_change_evaluation() {
+        use crate::board::Board;
+
+        // Doubled c-pawns for white
+        let board = parse_fen("4k3/8/8/8/8/2P5/2P5/4K3 w - - 0 1").unwrap();
+        let default_score = Evaluator::new().evaluate(&board);
+
+        let mut params = EvalParams::from_current_eval();
+        params.doubled_pawn_mg = -2000;
+        params.doubled_pawn_eg = -2000;
+        let json = serde_json::to_string(&params).unwrap();
+        set_tuning_params(load_params_from_json(&json).unwrap());
+        let tuned_score = Evaluator::new().evaluate(&board);
+        clear_tuning_params();
+
+        assert!(tuned_score < default_score - 100);
+        assert_eq!(Evaluator::new().evaluate(&Board::startpos()), 0);
+    }
+
     #[test]
     fn test_params_initialization() {
         let params = TuningParams::from_current_eval();
@@ -460,4 +682,18 @@ mod tests {
         assert_eq!(params.pawn_structure_divisor, 4);
         assert_eq!(params.mobility_divisor, 8);
     }
+
+    #[test]
+    fn test_param_indices_match_names() {
+        let names = TuningParams::param_names();
+        assert_eq!(names.len(), TuningParams::param_count());
+
+        let mut params = TuningParams::from_current_eval();
+        for i in 0..TuningParams::param_count() {
+            let value = params.get_param(i);
+            params.set_param(i, value + 1);
+            assert_eq!(params.get_param(i), value + 1, "{}", names[i]);
+        }
+        assert_eq!(params.knight_pawn_adjust, 7);
+    }
 }
@@ -11,6 +11,9 @@ pub struct EngineOptions {
     #[serde(rename = "multiPV")]
     pub multi_pv: Option<u32>,
     pub use_tablebases: Option<bool>,
+    /// Write castling as the king taking its own rook (`e1h1`) in best
+    /// moves and PVs, as with `UCI_Chess960`
+    pub chess960: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +27,9 @@ pub enum SearchLimit {
     Time { move_time_ms: u64 },
     #[serde(rename = "infinite")]
     Infinite,
+    /// Search for a forced mate in `moves` moves ("go mate N")
+    #[serde(rename = "mate")]
+    Mate { moves: u32 },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +41,101 @@ pub enum Score {
     Mate { plies: i32 },
 }
 
+/// Centipawn advantage at which the WDL model gives even chances of winning.
+const WDL_EVEN_CP: f64 = 100.0;
+/// Spread of the WDL model's logistic curve, in centipawns.
+const WDL_SCALE_CP: f64 = 60.0;
+
+impl Score {
+    /// The same score from the opponent's point of view.
+    pub fn negated(&self) -> Score {
+        match *self {
+            Score::Cp { value } => Score::Cp { value: -value },
+            Score::Mate { plies } => Score::Mate { plies: -plies },
+        }
+    }
+
+    /// The UCI form of the score: `cp <x>` or `mate <moves>`, followed by
+    /// `lowerbound` or `upperbound` when it is only a `bound`.
+    ///
+    /// ```
+    /// use engine::types::{Bound, Score};
+    ///
+    /// assert_eq!(Score::Cp { value: 35 }.to_uci(None), "cp 35");
+    /// assert_eq!(Score::Cp { value: 35 }.to_uci(Some(Bound::Lower)), "cp 35 lowerbound");
+    /// assert_eq!(Score::Mate { plies: -3 }.to_uci(Some(Bound::Upper)), "mate -2 upperbound");
+    /// ```
+    pub fn to_uci(&self, bound: Option<Bound>) -> String {
+        let score = match *self {
+            Score::Cp { value } => format!("cp {}", value),
+            // UCI counts mates in moves, not plies
+            Score::Mate { plies } => format!("mate {}", plies.signum() * ((plies.abs() + 1) / 2)),
+        };
+        match bound {
+            Some(Bound::Lower) => score + " lowerbound",
+            Some(Bound::Upper) => score + " upperbound",
+            None => score,
+        }
+    }
+
+    /// Estimated (win, draw, loss) chances in per mille, for the side this
+    /// score is from.
+    ///
+    /// A logistic model of the centipawn score: +100 cp is an even chance
+    /// of winning, +300 cp wins about 97% of the time, and the rest of the
+    /// probability not spent on either result is a draw. Mates are certain.
+    ///
+    /// ```
+    /// use engine::types::Score;
+    ///
+    /// let (win, draw, loss) = Score::Cp { value: 0 }.wdl();
+    /// assert_eq!(win, loss);
+    /// assert_eq!(win + draw + loss, 1000);
+    /// assert_eq!(Score::Mate { plies: -3 }.wdl(), (0, 0, 1000));
+    /// ```
+    pub fn wdl(&self) -> (u16, u16, u16) {
+        let cp = match *self {
+            Score::Cp { value } => value as f64,
+            Score::Mate { plies } if plies > 0 => return (1000, 0, 0),
+            Score::Mate { .. } => return (0, 0, 1000),
+        };
+        let chance = |cp: f64| 1000.0 / (1.0 + ((WDL_EVEN_CP - cp) / WDL_SCALE_CP).exp());
+        let win = chance(cp).round() as u16;
+        let loss = chance(-cp).round() as u16;
+        (win, 1000 - win - loss, loss)
+    }
+}
+
+/// Point of view of reported scores.
+///
+/// The search works with scores relative to the side to move; this only
+/// changes what [`SearchInfo`] and [`BestMove`] report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScorePov {
+    /// Positive is good for the side to move, as in UCI
+    #[default]
+    SideToMove,
+    /// Positive is good for White, as on an evaluation bar
+    White,
+}
+
+impl ScorePov {
+    fn is_side_to_move(&self) -> bool {
+        *self == ScorePov::SideToMove
+    }
+}
+
+/// Which side of the true score a reported score is on, when it is not exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bound {
+    /// The true score is at least this (fail high, UCI "lowerbound")
+    Lower,
+    /// The true score is at most this (fail low, UCI "upperbound")
+    Upper,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchInfo {
@@ -52,14 +153,86 @@ pub struct SearchInfo {
     pub hashfull: Option<u32>,
     #[serde(rename = "tbHits", skip_serializing_if = "Option::is_none")]
     pub tb_hits: Option<u64>,
+    /// 1-based line index in multi-PV mode (None for single-PV searches)
+    #[serde(rename = "multiPV", default, skip_serializing_if = "Option::is_none")]
+    pub multipv: Option<u32>,
+    /// Set while an aspiration search failed and will be repeated; `None`
+    /// for an exact score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound: Option<Bound>,
+    /// Point of view of `score` and `wdl`
+    #[serde(
+        rename = "scorePov",
+        default,
+        skip_serializing_if = "ScorePov::is_side_to_move"
+    )]
+    pub score_pov: ScorePov,
+    /// Win/draw/loss estimate in per mille (see [`Score::wdl`]), when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wdl: Option<(u16, u16, u16)>,
+    /// Set on the last info of a stopped search: nodes and time are up to
+    /// date, while depth, score and PV are those of the last completed
+    /// iteration (the best move played)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// Why the PV taken from the transposition table stops where it does,
+    /// for debugging short lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pv_terminated: Option<PvEnd>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Why a principal variation read back from the transposition table ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PvEnd {
+    /// The line returned to a position already on it, after a full cycle;
+    /// the search scores this as a draw (e.g. a perpetual check)
+    Repetition,
+    /// No table entry for the next position
+    #[serde(rename = "ttMiss")]
+    TTMiss,
+    /// The line reached the depth of the iteration
+    MaxLen,
+    /// The table move is not legal in its position (a quiescence entry
+    /// without a move, or a hash collision)
+    #[serde(rename = "illegalTtMove")]
+    IllegalTTMove,
+}
+
+/// Final result of an analysis.
+///
+/// Everything after `ponder` is a statistics snapshot of the last completed
+/// iteration. Those fields are optional on the wire so older clients keep
+/// working; they are `None`/empty when no search ran (e.g. a book move).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BestMove {
     pub id: String,
     pub best: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ponder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seldepth: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<u64>,
+    #[serde(rename = "timeMs", default, skip_serializing_if = "Option::is_none")]
+    pub time_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nps: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<Score>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pv: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hashfull: Option<u32>,
+    #[serde(rename = "tbHits", default, skip_serializing_if = "Option::is_none")]
+    pub tb_hits: Option<u64>,
+    /// Why no search was run (invalid FEN, unsearchable position), in which
+    /// case `best` is "0000"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,12 +241,27 @@ pub struct AnalyzeRequestContext {
     pub allow_ponder: Option<bool>,
 }
 
+/// Root move restrictions for an analysis, on top of its [`SearchLimit`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootMoveFilter {
+    /// Only consider these UCI moves ("go searchmoves"); `None` allows all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_moves: Option<Vec<String>>,
+    /// Never consider these UCI moves, e.g. to vary repeated engine matches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_moves: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyzeRequest {
     pub id: String,
     pub fen: String,
     pub moves: Option<Vec<String>>,
     pub limit: SearchLimit,
+    /// Restrict the root search to these moves ("go searchmoves")
+    #[serde(rename = "searchMoves", default, skip_serializing_if = "Option::is_none")]
+    pub search_moves: Option<Vec<String>>,
     pub options: Option<EngineOptions>,
     pub context: Option<AnalyzeRequestContext>,
 }
@@ -84,6 +272,149 @@ pub struct ErrorPayload {
     pub message: String,
 }
 
+/// Side of a castling move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CastleSide {
+    Kingside,
+    Queenside,
+}
+
+/// A legal move with the metadata a GUI needs to render it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveInfo {
+    /// Origin square (e.g. "e2")
+    pub from: String,
+    /// Destination square (e.g. "e4")
+    pub to: String,
+    pub uci: String,
+    pub san: String,
+    pub is_capture: bool,
+    pub is_castle: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub castle_side: Option<CastleSide>,
+    pub is_en_passant: bool,
+    /// Promotion piece as a lowercase letter ('q', 'r', 'b', 'n')
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promotion: Option<char>,
+    pub gives_check: bool,
+    #[serde(default)]
+    pub is_checkmate: bool,
+}
+
+/// A piece that can be won by capturing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HangingPiece {
+    /// Square of the piece (e.g. "d4")
+    pub square: String,
+    /// FEN character of the piece (uppercase for white)
+    pub piece: char,
+    /// Material the opponent wins by starting the exchange, in centipawns
+    pub value: i32,
+}
+
+/// Static hints about a position that need no search.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionHints {
+    pub white_hanging: Vec<HangingPiece>,
+    pub black_hanging: Vec<HangingPiece>,
+}
+
+/// Check state of the side to move, for highlighting the king and checkers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckInfo {
+    pub in_check: bool,
+    /// Square of the side to move's king (e.g. "e1")
+    pub king: Option<String>,
+    /// Squares of the pieces giving check; two for a double check
+    pub checkers: Vec<String>,
+}
+
+/// An enemy piece bearing on the side to move's king, for highlighting
+/// checkers and discovered-check batteries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KingThreat {
+    /// Square of the attacking piece (e.g. "e1")
+    pub square: String,
+    /// FEN character of the piece (uppercase for white)
+    pub piece: char,
+    /// "direct" for a piece giving check, "discovery" for a slider with one
+    /// piece between it and the king
+    pub attack_type: crate::board::AttackType,
+}
+
+/// A piece of the side to move that is absolutely pinned to its king.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinInfo {
+    /// Square of the pinned piece
+    pub square: String,
+    /// Square of the enemy slider pinning it
+    pub pinner: String,
+    /// Squares from the king (exclusive) to the pinner (inclusive): the only
+    /// squares the pinned piece may move to
+    pub ray: Vec<String>,
+}
+
+/// A piece and the square it stands on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PieceOnSquare {
+    /// Square of the piece (e.g. "e1")
+    pub square: String,
+    /// FEN character of the piece (uppercase for white)
+    pub piece: char,
+}
+
+/// A square whose contents differ between two positions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SquareDiff {
+    /// Square that changed (e.g. "e4")
+    pub square: String,
+    /// FEN character of the piece there before, if any
+    pub before: Option<char>,
+    /// FEN character of the piece there after, if any
+    pub after: Option<char>,
+}
+
+/// One move of a principal variation together with the position it leads to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PvNode {
+    /// Move in UCI notation (e.g. "e7e8q")
+    pub uci: String,
+    /// Move in SAN (e.g. "e8=Q+")
+    pub san: String,
+    /// FEN of the position after the move
+    pub fen_after: String,
+    /// Exact transposition table score of the position after the move, from
+    /// the side to move's point of view, if the last search stored one
+    pub score_at_node: Option<i32>,
+}
+
+/// Result for one position of [`crate::EngineImpl::analyze_batch`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchEvaluation {
+    /// The position as given
+    pub fen: String,
+    /// Best move in UCI notation, "0000" if the position was not searched
+    pub best_move: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<Score>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    /// Why the position was not searched (invalid FEN, unsearchable position)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum EngineEvent {
@@ -1,11 +1,74 @@
 //! UCI (Universal Chess Interface) protocol implementation.
 
 use crate::board::Board;
-use crate::io::parse_fen;
+use crate::io::{parse_position, play_uci_moves, uci_line_in};
 use crate::r#move::Move;
 use crate::search::{SearchResult, Searcher};
-use crate::square::Square;
 use crate::time::TimeControl;
+use crate::types::SearchInfo;
+use crate::version;
+
+/// Error from parsing a UCI command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UciError {
+    /// The command is not the expected one or its arguments are malformed
+    InvalidCommand(String),
+}
+
+impl std::fmt::Display for UciError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UciError::InvalidCommand(s) => write!(f, "Invalid command: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for UciError {}
+
+/// Split a full UCI `position` command into its position and moves.
+///
+/// The command is `position [startpos | fen <FEN>] [moves <m1> <m2> ...]`.
+/// The position comes back as `"startpos"` or the FEN fields, ready for
+/// [`parse_position`] or `EngineImpl::position`; the FEN itself and the
+/// moves are not validated here.
+///
+/// # Example
+/// ```
+/// use engine::uci::parse_uci_position;
+///
+/// let (position, moves) = parse_uci_position("position startpos moves e2e4 e7e5").unwrap();
+/// assert_eq!(position, "startpos");
+/// assert_eq!(moves, ["e2e4", "e7e5"]);
+/// assert!(parse_uci_position("go depth 5").is_err());
+/// ```
+pub fn parse_uci_position(cmd: &str) -> Result<(String, Vec<String>), UciError> {
+    let mut tokens = cmd.split_whitespace();
+    if tokens.next() != Some("position") {
+        return Err(UciError::InvalidCommand(format!(
+            "expected \"position\": {}",
+            cmd.trim()
+        )));
+    }
+
+    let args: Vec<&str> = tokens.collect();
+    let (spec, moves) = match args.iter().position(|&x| x == "moves") {
+        Some(idx) => (&args[..idx], &args[idx + 1..]),
+        None => (&args[..], &[][..]),
+    };
+    let position = match spec {
+        ["startpos"] => "startpos".to_string(),
+        ["fen"] => return Err(UciError::InvalidCommand("missing FEN".into())),
+        ["fen", fields @ ..] => fields.join(" "),
+        _ => {
+            return Err(UciError::InvalidCommand(format!(
+                "position needs \"startpos\" or \"fen\": {}",
+                cmd.trim()
+            )))
+        }
+    };
+
+    Ok((position, moves.iter().map(|m| m.to_string()).collect()))
+}
 
 /// UCI options configurable by GUI.
 #[derive(Debug, Clone)]
@@ -13,6 +76,8 @@ pub struct UciOptions {
     pub hash_size_mb: usize,
     pub threads: usize,
     pub multi_pv: usize,
+    /// `UCI_Chess960`: write castling as the king taking its own rook
+    pub chess960: bool,
 }
 
 impl Default for UciOptions {
@@ -21,6 +86,7 @@ impl Default for UciOptions {
             hash_size_mb: 64,
             threads: 1,
             multi_pv: 1,
+            chess960: false,
         }
     }
 }
@@ -28,6 +94,8 @@ impl Default for UciOptions {
 /// Main UCI protocol handler.
 pub struct UciHandler {
     board: Board,
+    /// Hashes of the positions before `board`, for repetition detection
+    history: Vec<u64>,
     searcher: Searcher,
     options: UciOptions,
 }
@@ -37,6 +105,7 @@ impl UciHandler {
     pub fn new() -> Self {
         Self {
             board: Board::startpos(),
+            history: Vec::new(),
             searcher: Searcher::new(),
             options: UciOptions::default(),
         }
@@ -57,10 +126,12 @@ impl UciHandler {
             "uci" => self.handle_uci(),
             "isready" => Some("readyok".to_string()),
             "ucinewgame" => self.handle_new_game(),
-            "position" => self.handle_position(&parts[1..]),
+            "position" => self.handle_position(cmd),
             "go" => self.handle_go(&parts[1..]),
             "stop" => Some("bestmove 0000".to_string()), // Placeholder for now
             "setoption" => self.handle_setoption(&parts[1..]),
+            "savehash" => self.handle_save_hash(&parts[1..]),
+            "loadhash" => self.handle_load_hash(&parts[1..]),
             "quit" => None,
             _ => None, // Ignore unknown commands
         }
@@ -69,11 +140,13 @@ impl UciHandler {
     /// Handle "uci" command - send identification and options.
     fn handle_uci(&self) -> Option<String> {
         let mut response = String::new();
-        response.push_str("id name ChessAI 0.1.0\n");
-        response.push_str("id author Chess Engine Developers\n");
+        response.push_str(&format!("id name {}\n", version::id_name()));
+        response.push_str(&format!("id author {}\n", version::AUTHOR));
         response.push_str("option name Hash type spin default 64 min 1 max 1024\n");
         response.push_str("option name Threads type spin default 1 min 1 max 1\n");
         response.push_str("option name MultiPV type spin default 1 min 1 max 10\n");
+        response.push_str("option name EvalParamsFile type string default <empty>\n");
+        response.push_str("option name UCI_Chess960 type check default false\n");
         response.push_str("uciok");
         Some(response)
     }
@@ -81,102 +154,49 @@ impl UciHandler {
     /// Handle "ucinewgame" command - reset state.
     fn handle_new_game(&mut self) -> Option<String> {
         self.board = Board::startpos();
-        self.searcher = Searcher::with_tt_size(self.options.hash_size_mb);
+        self.history.clear();
+        self.searcher.reset_for_new_game();
         None
     }
 
     /// Handle "position" command - set up position.
-    fn handle_position(&mut self, args: &[&str]) -> Option<String> {
-        if args.is_empty() {
-            return None;
-        }
-
-        match args[0] {
-            "startpos" => {
-                self.board = Board::startpos();
-                // Apply moves if present
-                if let Some(idx) = args.iter().position(|&x| x == "moves") {
-                    self.apply_moves(&args[idx + 1..]);
-                }
-            }
-            "fen" => {
-                // Find where FEN ends (either "moves" or end of args)
-                let moves_idx = args.iter().position(|&x| x == "moves");
-                let fen_end = moves_idx.unwrap_or(args.len());
-                let fen = args[1..fen_end].join(" ");
-
-                match parse_fen(&fen) {
-                    Ok(board) => {
-                        self.board = board;
-                        // Apply moves if present
-                        if let Some(idx) = moves_idx {
-                            self.apply_moves(&args[idx + 1..]);
-                        }
-                    }
-                    Err(_) => return None, // Invalid FEN, ignore
-                }
-            }
-            _ => return None,
-        }
-
-        None
-    }
+    ///
+    /// On a malformed command or invalid FEN the position is left unchanged;
+    /// on an illegal move the moves before it are kept. Either way the GUI is
+    /// told why.
+    fn handle_position(&mut self, cmd: &str) -> Option<String> {
+        let (spec, moves) = match parse_uci_position(cmd) {
+            Ok(position) => position,
+            Err(e) => return Some(format!("info string {}", e)),
+        };
 
-    /// Apply a sequence of moves in UCI format.
-    fn apply_moves(&mut self, moves: &[&str]) {
-        for move_str in moves {
-            if let Some(m) = self.parse_uci_move(move_str) {
-                if self.board.is_legal(m) {
-                    self.board.make_move(m);
-                }
-            }
-        }
+        let mut board = match parse_position(&spec) {
+            Ok(board) => board,
+            Err(e) => return Some(format!("info string invalid position: {}", e)),
+        };
+        self.history.clear();
+        let result = play_uci_moves(&mut board, &moves, &mut self.history);
+        self.board = board;
+        result.err().map(|e| format!("info string {}", e))
     }
 
-    /// Parse a UCI move string (e.g., "e2e4", "e7e8q").
+    /// Parse a UCI move string (e.g., "e2e4", "e7e8q") in the current
+    /// position; see [`Move::from_uci`].
     fn parse_uci_move(&self, move_str: &str) -> Option<Move> {
-        if move_str.len() < 4 {
-            return None;
-        }
-
-        let from = Square::from_algebraic(&move_str[0..2])?;
-        let to = Square::from_algebraic(&move_str[2..4])?;
-
-        // Check if it's a promotion
-        let promotion = if move_str.len() >= 5 {
-            match move_str.chars().nth(4)? {
-                'q' => Some(crate::piece::PieceType::Queen),
-                'r' => Some(crate::piece::PieceType::Rook),
-                'b' => Some(crate::piece::PieceType::Bishop),
-                'n' => Some(crate::piece::PieceType::Knight),
-                _ => None,
-            }
-        } else {
-            None
-        };
-
-        // Find matching legal move
-        let legal_moves = self.board.generate_legal_moves();
-        for m in legal_moves.iter() {
-            if m.from() == from && m.to() == to {
-                // Check promotion matches if applicable
-                if let Some(promo_type) = promotion {
-                    if m.is_promotion() && m.promotion_piece() == Some(promo_type) {
-                        return Some(*m);
-                    }
-                } else {
-                    // If no promotion specified, accept any move
-                    return Some(*m);
-                }
-            }
-        }
-
-        None
+        Move::from_uci(&self.board, move_str)
     }
 
     /// Handle "go" command - start searching.
     fn handle_go(&mut self, args: &[&str]) -> Option<String> {
+        if let Err(reason) = self.board.check_searchable() {
+            return Some(format!(
+                "info string position cannot be searched: {}\nbestmove 0000",
+                reason
+            ));
+        }
+
         let time_control = self.parse_time_control(args);
+        let search_moves = self.parse_search_moves(args);
 
         // Determine max depth
         let max_depth = match &time_control {
@@ -185,12 +205,51 @@ impl UciHandler {
         };
 
         // Run search
-        let result = self
-            .searcher
-            .search_with_limit(&self.board, max_depth, time_control);
+        self.searcher.set_game_history(self.history.clone());
+        self.searcher.set_search_moves(search_moves);
+        let mut lines = Vec::new();
+        let (board, chess960) = (&self.board, self.options.chess960);
+        let result =
+            self.searcher
+                .search_with_limit_callback(board, max_depth, time_control, |info| {
+                    lines.push(Self::format_info(&info, board, chess960))
+                });
+        self.searcher.set_search_moves(None);
+
+        // Info lines, then the bestmove response
+        lines.extend(self.format_bestmove(&result));
+        Some(lines.join("\n"))
+    }
 
-        // Format bestmove response
-        self.format_bestmove(&result)
+    /// Format one search progress report as a UCI "info" line.
+    ///
+    /// A score from a failed aspiration window carries its bound (see
+    /// [`Score::to_uci`](crate::types::Score::to_uci)), so GUIs do not show it as exact. The
+    /// PV is replayed from `board` to write castling in the notation of
+    /// the current `UCI_Chess960` setting.
+    fn format_info(info: &SearchInfo, board: &Board, chess960: bool) -> String {
+        let mut line = format!("info depth {}", info.depth);
+        if let Some(seldepth) = info.seldepth {
+            line.push_str(&format!(" seldepth {}", seldepth));
+        }
+        if let Some(multipv) = info.multipv {
+            line.push_str(&format!(" multipv {}", multipv));
+        }
+        line.push_str(&format!(
+            " score {} nodes {} nps {} time {}",
+            info.score.to_uci(info.bound),
+            info.nodes,
+            info.nps,
+            info.time_ms
+        ));
+        if let Some(hashfull) = info.hashfull {
+            line.push_str(&format!(" hashfull {}", hashfull));
+        }
+        let pv = uci_line_in(board, &info.pv, chess960);
+        if !pv.is_empty() {
+            line.push_str(&format!(" pv {}", pv.join(" ")));
+        }
+        line
     }
 
     /// Parse time control from "go" command arguments.
@@ -204,6 +263,7 @@ impl UciHandler {
         let mut movetime = None;
         let mut depth = None;
         let mut nodes = None;
+        let mut mate = None;
 
         while i < args.len() {
             match args[i] {
@@ -220,6 +280,10 @@ impl UciHandler {
                     nodes = args.get(i + 1).and_then(|s| s.parse().ok());
                     i += 2;
                 }
+                "mate" => {
+                    mate = args.get(i + 1).and_then(|s| s.parse().ok());
+                    i += 2;
+                }
                 "wtime" => {
                     wtime = args.get(i + 1).and_then(|s| s.parse().ok());
                     i += 2;
@@ -251,6 +315,8 @@ impl UciHandler {
             TimeControl::Depth { depth: d }
         } else if let Some(n) = nodes {
             TimeControl::Nodes { nodes: n }
+        } else if let Some(m) = mate {
+            TimeControl::Mate { moves: m }
         } else if let Some(wt) = wtime {
             let bt = btime.unwrap_or(wt);
             TimeControl::Clock {
@@ -265,22 +331,90 @@ impl UciHandler {
         }
     }
 
+    /// Parse "searchmoves" from "go" command arguments.
+    ///
+    /// The move list runs until the next "go" keyword. Returns None if
+    /// searchmoves is absent or lists no legal move.
+    fn parse_search_moves(&self, args: &[&str]) -> Option<Vec<Move>> {
+        const GO_KEYWORDS: [&str; 12] = [
+            "searchmoves",
+            "ponder",
+            "wtime",
+            "btime",
+            "winc",
+            "binc",
+            "movestogo",
+            "depth",
+            "nodes",
+            "mate",
+            "movetime",
+            "infinite",
+        ];
+
+        let start = args.iter().position(|&a| a == "searchmoves")? + 1;
+        let moves: Vec<Move> = args[start..]
+            .iter()
+            .take_while(|a| !GO_KEYWORDS.contains(a))
+            .filter_map(|a| self.parse_uci_move(a))
+            .filter(|&m| self.board.is_legal(m))
+            .collect();
+
+        if moves.is_empty() {
+            None
+        } else {
+            Some(moves)
+        }
+    }
+
     /// Format bestmove response.
     fn format_bestmove(&self, result: &SearchResult) -> Option<String> {
-        let bestmove = result.best_move.to_uci();
+        if result.best_move.is_null() {
+            return Some("bestmove 0000".to_string());
+        }
+        let bestmove = result.best_move.to_uci_in(self.options.chess960);
 
         // Check if we have a ponder move (second move in PV)
         if let Some(&ponder_move) = result.pv.get(1) {
             Some(format!(
                 "bestmove {} ponder {}",
                 bestmove,
-                ponder_move.to_uci()
+                ponder_move.to_uci_in(self.options.chess960)
             ))
         } else {
             Some(format!("bestmove {}", bestmove))
         }
     }
 
+    /// Handle "savehash <file>" (engine-specific) - write the hash table to a file.
+    fn handle_save_hash(&self, args: &[&str]) -> Option<String> {
+        let path = args.join(" ");
+        if path.is_empty() {
+            return Some("info string savehash requires a file name".to_string());
+        }
+
+        let result = std::fs::File::create(&path)
+            .and_then(|file| self.searcher.save_tt(std::io::BufWriter::new(file)));
+        Some(match result {
+            Ok(()) => format!("info string hash saved to {}", path),
+            Err(e) => format!("info string cannot save hash to {}: {}", path, e),
+        })
+    }
+
+    /// Handle "loadhash <file>" (engine-specific) - read a saved hash table.
+    fn handle_load_hash(&mut self, args: &[&str]) -> Option<String> {
+        let path = args.join(" ");
+        if path.is_empty() {
+            return Some("info string loadhash requires a file name".to_string());
+        }
+
+        let result = std::fs::File::open(&path)
+            .and_then(|file| self.searcher.load_tt(std::io::BufReader::new(file)));
+        Some(match result {
+            Ok(()) => format!("info string hash loaded from {}", path),
+            Err(e) => format!("info string cannot load hash from {}: {}", path, e),
+        })
+    }
+
     /// Handle "setoption" command.
     fn handle_setoption(&mut self, args: &[&str]) -> Option<String> {
         // Parse: setoption name <id> [value <x>]
@@ -334,11 +468,34 @@ impl UciHandler {
                     self.options.multi_pv = multi_pv.clamp(1, 10);
                 }
             }
+            "uci_chess960" => {
+                if let Ok(chess960) = value.parse::<bool>() {
+                    self.options.chess960 = chess960;
+                }
+            }
+            "evalparamsfile" => return Some(Self::load_eval_params(&value)),
             _ => {} // Ignore unknown options
         }
 
         None
     }
+
+    /// Load evaluation parameters from a JSON file, or restore the built-in
+    /// ones for an empty value.
+    fn load_eval_params(path: &str) -> String {
+        if path.is_empty() || path == "<empty>" {
+            crate::tune::clear_global_params();
+            return "info string eval params reset to defaults".to_string();
+        }
+
+        match crate::tune::load_params_from_json(path) {
+            Ok(params) => {
+                crate::tune::set_global_params(params);
+                format!("info string eval params loaded from {}", path)
+            }
+            Err(e) => format!("info string cannot load eval params from {}: {}", path, e),
+        }
+    }
 }
 
 impl Default for UciHandler {
@@ -350,6 +507,8 @@ impl Default for UciHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::setup_position;
+    use crate::piece::PieceType;
 
     #[test]
     fn test_uci_command() {
@@ -358,7 +517,8 @@ mod tests {
 
         assert!(response.is_some());
         let resp = response.unwrap();
-        assert!(resp.contains("id name"));
+        let id = format!("id name ChessAI {}\n", env!("CARGO_PKG_VERSION"));
+        assert!(resp.starts_with(&id));
         assert!(resp.contains("id author"));
         assert!(resp.contains("uciok"));
         assert!(resp.contains("option name Hash"));
@@ -389,6 +549,80 @@ mod tests {
         assert_ne!(handler.board, Board::startpos());
     }
 
+    #[test]
+    fn test_position_reports_illegal_move() {
+        let mut handler = UciHandler::new();
+        let resp = handler.handle_command("position startpos moves e2e4 e7e5 e4e5 g1f3");
+        assert_eq!(
+            resp.as_deref(),
+            Some("info string Illegal move at index 2: e4e5")
+        );
+
+        // The legal prefix is kept, with its repetition history
+        assert_eq!(
+            handler.board,
+            setup_position("startpos", &["e2e4", "e7e5"]).unwrap()
+        );
+        assert_eq!(handler.history.len(), 2);
+
+        let resp = handler.handle_command("position fen not a fen");
+        assert!(resp.unwrap().starts_with("info string invalid position"));
+        assert_eq!(handler.history.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_uci_position() {
+        const FEN: &str = "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1";
+        let parse = |cmd: &str| parse_uci_position(cmd).unwrap();
+        let moves = |list: &[&str]| list.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse("position startpos"), ("startpos".into(), vec![]));
+        assert_eq!(
+            parse("position startpos moves e2e4"),
+            ("startpos".into(), moves(&["e2e4"]))
+        );
+        assert_eq!(
+            parse(&format!("position fen {}", FEN)),
+            (FEN.into(), vec![])
+        );
+        assert_eq!(
+            parse(&format!("position  fen {}  moves e5d6 b7b8q\n", FEN)),
+            (FEN.into(), moves(&["e5d6", "b7b8q"]))
+        );
+        // A trailing "moves" with nothing after it plays no moves
+        assert_eq!(
+            parse("position startpos moves"),
+            ("startpos".into(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_parse_uci_position_errors() {
+        for cmd in [
+            "",
+            "go depth 5",
+            "position",
+            "position fen",
+            "position fen moves e2e4",
+            "position startpos e2e4",
+            "position kiwipete",
+        ] {
+            assert!(
+                matches!(parse_uci_position(cmd), Err(UciError::InvalidCommand(_))),
+                "{:?}",
+                cmd
+            );
+        }
+
+        // The handler reports a malformed command and keeps its position
+        let mut handler = UciHandler::new();
+        handler.handle_command("position startpos moves e2e4");
+        let before = handler.board.clone();
+        let resp = handler.handle_command("position startpos e2e4");
+        assert!(resp.unwrap().starts_with("info string Invalid command"));
+        assert_eq!(handler.board, before);
+    }
+
     #[test]
     fn test_position_fen() {
         let mut handler = UciHandler::new();
@@ -424,6 +658,27 @@ mod tests {
         // Invalid moves
         assert!(handler.parse_uci_move("e2e5").is_none()); // Illegal pawn move
         assert!(handler.parse_uci_move("xyz").is_none()); // Invalid format
+        assert!(handler.parse_uci_move("e7e5").is_none()); // Not our pawn
+        assert!(handler.parse_uci_move("e1e2").is_none()); // Own piece on e2
+    }
+
+    #[test]
+    fn test_parse_uci_move_fills_in_flags() {
+        let mut handler = UciHandler::new();
+
+        handler.handle_command("position fen r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1");
+        let parse = |s| handler.parse_uci_move(s).unwrap();
+        assert!(parse("e1g1").is_kingside_castle());
+        assert!(parse("e1c1").is_queenside_castle());
+        assert_eq!(parse("e1h1"), parse("e1g1"));
+        assert!(parse("e5d6").is_en_passant());
+        assert_eq!(parse("b7b8").promotion_piece(), Some(PieceType::Queen));
+        assert_eq!(parse("b7a8n").promotion_piece(), Some(PieceType::Knight));
+        assert!(parse("b7a8n").is_capture());
+        assert!(parse("a1a8").is_capture());
+
+        // Castling through the king's own rook is not castling
+        assert!(handler.parse_uci_move("e1b1").is_none());
     }
 
     #[test]
@@ -454,6 +709,52 @@ mod tests {
         assert!(matches!(tc, TimeControl::Nodes { nodes: 100000 }));
     }
 
+    #[test]
+    fn test_parse_time_control_mate() {
+        let handler = UciHandler::new();
+        let tc = handler.parse_time_control(&["mate", "3"]);
+        assert!(matches!(tc, TimeControl::Mate { moves: 3 }));
+    }
+
+    #[test]
+    fn test_parse_search_moves() {
+        let handler = UciHandler::new();
+
+        let moves = handler
+            .parse_search_moves(&["searchmoves", "e2e4", "d2d4", "depth", "5"])
+            .unwrap();
+        let uci: Vec<String> = moves.iter().map(|m| m.to_uci()).collect();
+        assert_eq!(uci, vec!["e2e4", "d2d4"]);
+
+        // Illegal moves are dropped, no searchmoves means no restriction
+        assert!(handler
+            .parse_search_moves(&["searchmoves", "e2e5"])
+            .is_none());
+        assert!(handler.parse_search_moves(&["depth", "5"]).is_none());
+    }
+
+    #[test]
+    fn test_go_searchmoves() {
+        let mut handler = UciHandler::new();
+        handler.handle_command("position startpos moves e2e4 e7e5");
+
+        let response = handler
+            .handle_command("go depth 3 searchmoves a2a3")
+            .unwrap();
+        let bestmove = response.lines().last().unwrap();
+        assert!(bestmove.starts_with("bestmove a2a3"), "got {}", response);
+    }
+
+    #[test]
+    fn test_go_mate() {
+        let mut handler = UciHandler::new();
+        handler.handle_command("position fen 6k1/5ppp/8/8/8/8/8/R6K w - - 0 1");
+
+        let response = handler.handle_command("go mate 1").unwrap();
+        let bestmove = response.lines().last().unwrap();
+        assert!(bestmove.starts_with("bestmove a1a8"), "got {}", response);
+    }
+
     #[test]
     fn test_parse_time_control_clock() {
         let handler = UciHandler::new();
@@ -493,6 +794,45 @@ mod tests {
         assert_eq!(handler.options.multi_pv, 3);
     }
 
+    #[test]
+    fn test_setoption_chess960_castling_notation() {
+        let mut handler = UciHandler::new();
+        handler.handle_command("position fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let response = handler
+            .handle_command("go depth 1 searchmoves e1g1")
+            .unwrap();
+        assert_eq!(response.lines().last(), Some("bestmove e1g1"));
+        assert!(response.contains(" pv e1g1"), "got {}", response);
+
+        handler.handle_command("setoption name UCI_Chess960 value true");
+        assert!(handler.options.chess960);
+        let response = handler
+            .handle_command("go depth 1 searchmoves e1h1")
+            .unwrap();
+        assert_eq!(response.lines().last(), Some("bestmove e1h1"));
+        assert!(response.contains(" pv e1h1"), "got {}", response);
+    }
+
+    #[test]
+    fn test_setoption_eval_params_file_error() {
+        let mut handler = UciHandler::new();
+        let response = handler
+            .handle_command("setoption name EvalParamsFile value /nonexistent/params.json")
+            .unwrap();
+        assert!(response.starts_with("info string cannot load eval params"));
+    }
+
+    #[test]
+    fn test_go_on_unsearchable_position() {
+        let mut handler = UciHandler::new();
+        handler.handle_command("position fen 8/8/8/8/8/8/8/R3K3 w - - 0 1");
+        let response = handler.handle_command("go depth 3").unwrap();
+        assert_eq!(
+            response,
+            "info string position cannot be searched: no black king\nbestmove 0000"
+        );
+    }
+
     #[test]
     fn test_go_command_returns_bestmove() {
         let mut handler = UciHandler::new();
@@ -501,6 +841,46 @@ mod tests {
 
         assert!(response.is_some());
         let resp = response.unwrap();
-        assert!(resp.starts_with("bestmove"));
+        assert!(resp.lines().last().unwrap().starts_with("bestmove"));
+    }
+
+    #[test]
+    fn test_go_reports_aspiration_bounds() {
+        // WAC.009: the depth 5 score fails high over the depth 4 window
+        let mut handler = UciHandler::new();
+        handler.handle_command(
+            "position fen 3q1rk1/p4pp1/2pb3p/3p4/6Pr/1PNQ4/P1PB1PP1/4RRK1 b - - 0 1",
+        );
+        let response = handler.handle_command("go depth 6").unwrap();
+
+        let lower = response
+            .lines()
+            .find(|line| line.contains(" lowerbound "))
+            .expect("no lowerbound info line");
+        assert!(lower.starts_with("info depth "), "got {}", lower);
+        assert!(lower.contains(" lowerbound nodes "), "got {}", lower);
+        assert!(response.starts_with("info depth 1 "), "got {}", response);
+    }
+
+    #[test]
+    fn test_savehash_loadhash() {
+        let path = std::env::temp_dir().join(format!("uci_hash_{}.bin", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        let mut handler = UciHandler::new();
+        handler.handle_command("setoption name Hash value 1");
+        handler.handle_command("position startpos moves e2e4 e7e5");
+        handler.handle_command("go depth 4");
+
+        let resp = handler.handle_command(&format!("savehash {}", path)).unwrap();
+        assert!(resp.contains("hash saved"), "{}", resp);
+
+        let mut fresh = UciHandler::new();
+        let resp = fresh.handle_command(&format!("loadhash {}", path)).unwrap();
+        assert!(resp.contains("hash loaded"), "{}", resp);
+
+        std::fs::remove_file(&path).unwrap();
+        let resp = fresh.handle_command(&format!("loadhash {}", path)).unwrap();
+        assert!(resp.contains("cannot load hash"), "{}", resp);
     }
 }
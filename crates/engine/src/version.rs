@@ -0,0 +1,155 @@
+//! Engine name, version and build information.
+//!
+//! Everything that identifies the engine to the outside world — the UCI
+//! `id` lines, the server's `/about` route, the WASM `version()` call —
+//! reads from here, so they can't drift apart.
+//!
+//! ```
+//! use engine::version;
+//!
+//! assert_eq!(version::VERSION, env!("CARGO_PKG_VERSION"));
+//! assert!(version::id_name().starts_with(version::NAME));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::BENCH_POSITIONS;
+use crate::io::parse_fen;
+use crate::search::Searcher;
+
+/// Engine name, as sent in the UCI `id name` line.
+pub const NAME: &str = "ChessAI";
+
+/// Engine author, as sent in the UCI `id author` line.
+pub const AUTHOR: &str = "Chess Engine Developers";
+
+/// Crate version from `Cargo.toml`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short git commit hash the engine was built from, when the build had
+/// access to the repository (see `build.rs`).
+pub const GIT_HASH: Option<&str> = option_env!("ENGINE_GIT_HASH");
+
+/// `"release"` or `"debug"`, from whether debug assertions are compiled in.
+pub const BUILD_PROFILE: &str = if cfg!(debug_assertions) {
+    "debug"
+} else {
+    "release"
+};
+
+/// Depth each [`BENCH_POSITIONS`] entry is searched to for
+/// [`bench_signature`].
+pub const BENCH_SIGNATURE_DEPTH: u32 = 5;
+
+/// Cargo and target features compiled into this build, e.g. `"popcnt"`
+/// when the target has a hardware population count.
+pub fn features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(target_feature = "popcnt") {
+        features.push("popcnt");
+    }
+    if cfg!(target_feature = "bmi2") {
+        features.push("pext");
+    }
+    if cfg!(target_feature = "simd128") {
+        features.push("simd128");
+    }
+    if cfg!(feature = "parallel") {
+        features.push("parallel");
+    }
+    features
+}
+
+/// The UCI `id name` value: name and version.
+pub fn id_name() -> String {
+    format!("{} {}", NAME, VERSION)
+}
+
+/// Total nodes searched over [`BENCH_POSITIONS`] at
+/// [`BENCH_SIGNATURE_DEPTH`], each with a fresh searcher.
+///
+/// Any change to search or evaluation behavior almost always changes this
+/// number, so two builds with the same signature search identically. The
+/// searches use this thread's search and tuning parameters, so a tuned
+/// engine reports a different signature than the defaults, and the value is
+/// computed on every call.
+pub fn bench_signature() -> u64 {
+    BENCH_POSITIONS
+        .iter()
+        .map(|fen| {
+            let board = parse_fen(fen).expect("bench positions are valid");
+            let mut searcher = Searcher::with_tt_size(1);
+            searcher.set_use_book(false);
+            searcher.search(&board, BENCH_SIGNATURE_DEPTH).nodes
+        })
+        .sum()
+}
+
+/// Everything that identifies an engine build, from
+/// [`EngineImpl::about`](crate::EngineImpl::about).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineAbout {
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    /// Git commit, when known
+    pub git_hash: Option<String>,
+    /// `"release"` or `"debug"`
+    pub profile: String,
+    /// See [`features`]
+    pub features: Vec<String>,
+    /// See [`bench_signature`]
+    pub bench_signature: u64,
+}
+
+impl EngineAbout {
+    /// Information about this build.
+    pub fn current() -> Self {
+        Self {
+            name: NAME.to_string(),
+            version: VERSION.to_string(),
+            author: AUTHOR.to_string(),
+            git_hash: GIT_HASH.map(str::to_string),
+            profile: BUILD_PROFILE.to_string(),
+            features: features().into_iter().map(str::to_string).collect(),
+            bench_signature: bench_signature(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn about_reports_this_build() {
+        let about = EngineAbout::current();
+        assert_eq!(about.name, NAME);
+        assert_eq!(about.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(about.profile, BUILD_PROFILE);
+        assert!(about.bench_signature > 0);
+        assert_eq!(about.bench_signature, bench_signature());
+
+        let json = serde_json::to_value(&about).unwrap();
+        assert_eq!(json["name"], "ChessAI");
+        assert!(json.get("gitHash").is_some());
+        assert!(json.get("benchSignature").is_some());
+    }
+
+    #[test]
+    fn bench_signature_follows_search_params() {
+        use crate::search_params::{set_search_params, SearchParams};
+
+        let default = bench_signature();
+        set_search_params(SearchParams {
+            null_move_r: 1,
+            ..SearchParams::default()
+        });
+        let tuned = bench_signature();
+        set_search_params(SearchParams::default());
+
+        assert_ne!(tuned, default);
+        assert_eq!(bench_signature(), default);
+    }
+}
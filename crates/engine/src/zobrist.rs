@@ -6,22 +6,83 @@
 /// - Transposition table implementation
 /// - Position repetition detection
 use crate::board::{Board, CastlingRights};
-use crate::piece::{Color, Piece};
+use crate::piece::{Color, Piece, PieceType};
 use crate::square::Square;
-use once_cell::sync::Lazy;
 
 /// Zobrist hash keys for all board elements.
+///
+/// # Key layout
+///
+/// A position hash is the XOR of:
+/// - `pieces[piece_index(piece_type, color)][square]` for every piece on the board
+/// - `side` when Black is to move (nothing when White is to move)
+/// - `castling[right]` for each castling right still available, where `right`
+///   is one of [`WHITE_KINGSIDE`], [`WHITE_QUEENSIDE`], [`BLACK_KINGSIDE`],
+///   [`BLACK_QUEENSIDE`]
+/// - `ep_file[file]` when an en passant square is set (nothing otherwise, so
+///   "no en passant" needs no key of its own)
+///
+/// Castling keys are per right rather than per rights combination, so the
+/// layout keeps working once rights carry rook files for Chess960: a right is
+/// still "the king-side (or queen-side) rook of one color".
+///
+/// # Stability
+///
+/// Keys are generated at compile time from a fixed seed in a fixed order
+/// (pieces, side, castling, en passant). Hashes are therefore identical
+/// across runs, platforms and releases, which persistent opening books and
+/// TT dumps rely on. Changing the seed, the generator, the order or the
+/// layout changes every hash and must be treated as a breaking change; the
+/// known-value tests in this module catch accidental changes.
 pub struct ZobristKeys {
-    /// Keys for each piece type, color, and square [piece_type][color][square]
-    pub pieces: [[[u64; 64]; 2]; 6],
-    /// Key for black to move (XOR this when it's black's turn)
-    pub black_to_move: u64,
-    /// Keys for castling rights [0-15] (4 bits for WK, WQ, BK, BQ)
-    pub castling: [u64; 16],
-    /// Keys for en passant file [0-7] (a-h files)
-    pub en_passant: [u64; 8],
+    /// Keys for each piece and square [piece_index][square]
+    pub pieces: [[u64; 64]; 12],
+    /// Key for Black to move
+    pub side: u64,
+    /// Key for each individual castling right
+    pub castling: [u64; 4],
+    /// Keys for the en passant file [0-7] (a-h files)
+    pub ep_file: [u64; 8],
 }
 
+/// Castling right index of the white king-side right.
+pub const WHITE_KINGSIDE: usize = 0;
+/// Castling right index of the white queen-side right.
+pub const WHITE_QUEENSIDE: usize = 1;
+/// Castling right index of the black king-side right.
+pub const BLACK_KINGSIDE: usize = 2;
+/// Castling right index of the black queen-side right.
+pub const BLACK_QUEENSIDE: usize = 3;
+
+/// Index into [`ZobristKeys::pieces`]: White pawn..king are 0-5, Black 6-11.
+#[inline(always)]
+pub const fn piece_index(piece_type: PieceType, color: Color) -> usize {
+    color.index() * 6 + piece_type.index()
+}
+
+impl ZobristKeys {
+    /// Key for a piece on a square.
+    #[inline(always)]
+    pub fn piece(&self, piece: Piece, square: Square) -> u64 {
+        self.pieces[piece_index(piece.piece_type, piece.color)][square.index() as usize]
+    }
+
+    /// Combined key of every right set in `rights`.
+    #[inline(always)]
+    pub fn castling_rights(&self, rights: CastlingRights) -> u64 {
+        let mut key = 0;
+        let mut bits = rights.bits();
+        while bits != 0 {
+            key ^= self.castling[bits.trailing_zeros() as usize];
+            bits &= bits - 1;
+        }
+        key
+    }
+}
+
+/// Seed for key generation. Part of the hash stability guarantee.
+const SEED: u64 = 0x1234_5678_9ABC_DEF0;
+
 /// Generate pseudorandom 64-bit numbers using a simple LCG.
 const fn prng(mut seed: u64) -> u64 {
     // LCG parameters from Numerical Recipes
@@ -32,38 +93,33 @@ const fn prng(mut seed: u64) -> u64 {
 /// Generate all Zobrist keys at compile time.
 const fn generate_zobrist_keys() -> ZobristKeys {
     let mut keys = ZobristKeys {
-        pieces: [[[0; 64]; 2]; 6],
-        black_to_move: 0,
-        castling: [0; 16],
-        en_passant: [0; 8],
+        pieces: [[0; 64]; 12],
+        side: 0,
+        castling: [0; 4],
+        ep_file: [0; 8],
     };
 
-    // Start with a fixed seed for reproducibility
-    let mut seed: u64 = 0x1234_5678_9ABC_DEF0;
+    let mut seed = SEED;
 
     // Generate piece keys
-    let mut piece_type = 0;
-    while piece_type < 6 {
-        let mut color = 0;
-        while color < 2 {
-            let mut square = 0;
-            while square < 64 {
-                seed = prng(seed);
-                keys.pieces[piece_type][color][square] = seed;
-                square += 1;
-            }
-            color += 1;
+    let mut piece = 0;
+    while piece < 12 {
+        let mut square = 0;
+        while square < 64 {
+            seed = prng(seed);
+            keys.pieces[piece][square] = seed;
+            square += 1;
         }
-        piece_type += 1;
+        piece += 1;
     }
 
     // Generate side to move key
     seed = prng(seed);
-    keys.black_to_move = seed;
+    keys.side = seed;
 
     // Generate castling keys
     let mut i = 0;
-    while i < 16 {
+    while i < 4 {
         seed = prng(seed);
         keys.castling[i] = seed;
         i += 1;
@@ -73,15 +129,15 @@ const fn generate_zobrist_keys() -> ZobristKeys {
     let mut i = 0;
     while i < 8 {
         seed = prng(seed);
-        keys.en_passant[i] = seed;
+        keys.ep_file[i] = seed;
         i += 1;
     }
 
     keys
 }
 
-/// Global Zobrist keys (initialized once at program start).
-pub static ZOBRIST: Lazy<ZobristKeys> = Lazy::new(generate_zobrist_keys);
+/// Global Zobrist keys (generated at compile time).
+pub static ZOBRIST: ZobristKeys = generate_zobrist_keys();
 
 /// Calculate the Zobrist hash for a board position.
 ///
@@ -98,64 +154,68 @@ pub fn zobrist_hash(board: &Board) -> u64 {
     let mut hash: u64 = 0;
 
     // Hash all pieces on the board
-    for square in Square::all() {
-        if let Some(piece) = board.piece_at(square) {
-            let piece_key = ZOBRIST.pieces[piece.piece_type.index()][piece.color.index()]
-                [square.index() as usize];
-            hash ^= piece_key;
-        }
+    for (square, piece) in board.pieces_iter() {
+        hash ^= ZOBRIST.piece(piece, square);
     }
 
     // Hash side to move
     if board.side_to_move() == Color::Black {
-        hash ^= ZOBRIST.black_to_move;
+        hash ^= ZOBRIST.side;
     }
 
     // Hash castling rights
-    let castling_bits = board.castling().bits();
-    hash ^= ZOBRIST.castling[castling_bits as usize];
+    hash ^= ZOBRIST.castling_rights(board.castling());
 
-    // Hash en passant file
-    if let Some(ep_square) = board.ep_square() {
-        hash ^= ZOBRIST.en_passant[ep_square.file() as usize];
+    // Hash en passant file, only if the capture can actually be made
+    if let Some(ep_square) = board.legal_ep_square() {
+        hash ^= ZOBRIST.ep_file[ep_square.file() as usize];
     }
 
     hash
 }
 
+/// Identifier of the key tables, stored alongside persisted hashes.
+///
+/// This is the start position hash, so it changes whenever the seed, the
+/// generator or the key layout changes.
+pub fn keys_fingerprint() -> u64 {
+    zobrist_hash(&Board::startpos())
+}
+
 /// Update a hash when a piece is added to a square.
 #[inline(always)]
-pub fn hash_piece(hash: u64, piece: Piece, square: Square) -> u64 {
-    hash ^ ZOBRIST.pieces[piece.piece_type.index()][piece.color.index()][square.index() as usize]
+pub(crate) fn hash_piece(hash: u64, piece: Piece, square: Square) -> u64 {
+    hash ^ ZOBRIST.piece(piece, square)
 }
 
 /// Update a hash when side to move changes.
 #[inline(always)]
-pub fn hash_side_to_move(hash: u64) -> u64 {
-    hash ^ ZOBRIST.black_to_move
+pub(crate) fn hash_side_to_move(hash: u64) -> u64 {
+    hash ^ ZOBRIST.side
 }
 
 /// Update a hash when castling rights change.
+///
+/// Only the rights that actually changed are XORed.
 #[inline(always)]
-pub fn hash_castling(hash: u64, old_rights: CastlingRights, new_rights: CastlingRights) -> u64 {
-    // XOR out old rights, XOR in new rights
-    hash ^ ZOBRIST.castling[old_rights.bits() as usize]
-        ^ ZOBRIST.castling[new_rights.bits() as usize]
+pub(crate) fn hash_castling(hash: u64, old_rights: CastlingRights, new_rights: CastlingRights) -> u64 {
+    let changed = CastlingRights::from_bits(old_rights.bits() ^ new_rights.bits());
+    hash ^ ZOBRIST.castling_rights(changed)
 }
 
 /// Update a hash when en passant square changes.
 #[inline(always)]
-pub fn hash_en_passant(hash: u64, old_ep: Option<Square>, new_ep: Option<Square>) -> u64 {
+pub(crate) fn hash_en_passant(hash: u64, old_ep: Option<Square>, new_ep: Option<Square>) -> u64 {
     let mut h = hash;
 
     // XOR out old en passant
     if let Some(sq) = old_ep {
-        h ^= ZOBRIST.en_passant[sq.file() as usize];
+        h ^= ZOBRIST.ep_file[sq.file() as usize];
     }
 
     // XOR in new en passant
     if let Some(sq) = new_ep {
-        h ^= ZOBRIST.en_passant[sq.file() as usize];
+        h ^= ZOBRIST.ep_file[sq.file() as usize];
     }
 
     h
@@ -165,7 +225,6 @@ pub fn hash_en_passant(hash: u64, old_ep: Option<Square>, new_ep: Option<Square>
 mod tests {
     use super::*;
     use crate::io::parse_fen;
-    use crate::piece::PieceType;
 
     #[test]
     fn test_zobrist_startpos_nonzero() {
@@ -209,8 +268,8 @@ mod tests {
         // Only difference is side to move
         assert_ne!(hash_white, hash_black);
 
-        // XOR with black_to_move should toggle
-        assert_eq!(hash_white ^ ZOBRIST.black_to_move, hash_black);
+        // XOR with the side key should toggle
+        assert_eq!(hash_white ^ ZOBRIST.side, hash_black);
     }
 
     #[test]
@@ -226,13 +285,29 @@ mod tests {
 
     #[test]
     fn test_zobrist_en_passant() {
-        let fen1 = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
-        let fen2 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        // The f4 pawn can take on e3
+        let fen1 = "rnbqkbnr/pppp1ppp/8/8/4Pp2/8/PPPP2PP/RNBQKBNR b KQkq - 0 3";
+        let fen2 = "rnbqkbnr/pppp1ppp/8/8/4Pp2/8/PPPP2PP/RNBQKBNR b KQkq e3 0 3";
 
         let board1 = parse_fen(fen1).unwrap();
         let board2 = parse_fen(fen2).unwrap();
 
         assert_ne!(zobrist_hash(&board1), zobrist_hash(&board2));
+        assert_eq!(
+            zobrist_hash(&board1) ^ zobrist_hash(&board2),
+            ZOBRIST.ep_file[4]
+        );
+    }
+
+    #[test]
+    fn test_zobrist_ignores_unusable_en_passant() {
+        let fen1 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+        let fen2 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+
+        let board1 = parse_fen(fen1).unwrap();
+        let board2 = parse_fen(fen2).unwrap();
+
+        assert_eq!(zobrist_hash(&board1), zobrist_hash(&board2));
     }
 
     #[test]
@@ -286,4 +361,68 @@ mod tests {
         // Hash should be back to initial
         assert_eq!(board.hash(), initial_hash);
     }
+
+    /// Pinned hash values. If this test fails, the key tables changed and
+    /// every persisted hash (opening books, TT dumps) is invalidated; only
+    /// update these values as a deliberate, documented breaking change.
+    #[test]
+    fn test_known_hash_values() {
+        let cases = [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                0xC2F7_A22F_9E3B_8288,
+            ),
+            // No black pawn can take on e3, so the square is not hashed
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                0x6C90_9DD7_6D4B_53C9,
+            ),
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", 0xA66F_930C_38DC_A000),
+            (
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                0x997D_49C3_D76C_CB32,
+            ),
+            (
+                "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+                0x19E8_453E_913F_32BE,
+            ),
+            ("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 0 1", 0x16C9_C0E2_E580_438A),
+        ];
+
+        for (fen, expected) in cases {
+            let board = parse_fen(fen).unwrap();
+            assert_eq!(zobrist_hash(&board), expected, "{}", fen);
+        }
+        assert_eq!(zobrist_hash(&Board::startpos()), 0xC2F7_A22F_9E3B_8288);
+    }
+
+    #[test]
+    fn test_castling_keys_per_right() {
+        let all = CastlingRights::all();
+        let none = CastlingRights::none();
+
+        // All rights together are the XOR of the individual right keys
+        let combined = ZOBRIST.castling[WHITE_KINGSIDE]
+            ^ ZOBRIST.castling[WHITE_QUEENSIDE]
+            ^ ZOBRIST.castling[BLACK_KINGSIDE]
+            ^ ZOBRIST.castling[BLACK_QUEENSIDE];
+        assert_eq!(ZOBRIST.castling_rights(all), combined);
+        assert_eq!(ZOBRIST.castling_rights(none), 0);
+
+        // Losing one right only touches that right's key
+        let without_wk = CastlingRights::from_bits(all.bits() & !0b0001);
+        assert_eq!(
+            hash_castling(0, all, without_wk),
+            ZOBRIST.castling[WHITE_KINGSIDE]
+        );
+        assert_eq!(hash_castling(0, all, all), 0);
+    }
+
+    #[test]
+    fn test_piece_index_layout() {
+        assert_eq!(piece_index(PieceType::Pawn, Color::White), 0);
+        assert_eq!(piece_index(PieceType::King, Color::White), 5);
+        assert_eq!(piece_index(PieceType::Pawn, Color::Black), 6);
+        assert_eq!(piece_index(PieceType::King, Color::Black), 11);
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Tests unusual positions and edge cases to ensure robustness.
 
+use engine::assert_boards_eq;
 use engine::board::Board;
 use engine::io::parse_fen;
 use engine::piece::{Color, Piece, PieceType};
@@ -67,7 +68,7 @@ fn test_all_promotion_types() {
 
         // Unmake and verify
         board.unmake_move(m, undo);
-        assert_eq!(board, original);
+        assert_boards_eq!(board, original);
     }
 }
 
@@ -255,3 +256,95 @@ fn test_double_check() {
         );
     }
 }
+
+#[test]
+fn test_degenerate_positions_report_errors() {
+    use engine::types::{EngineOptions, SearchLimit};
+    use engine::EngineImpl;
+
+    let mut engine = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 1,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(3),
+        use_tablebases: None,
+        chess960: None,
+    });
+
+    let cases = [
+        ("8/8/8/8/8/8/8/8 w - - 0 1", "no white king"),
+        ("4k3/8/8/8/8/8/8/R7 w - - 0 1", "no white king"),
+        ("8/8/8/8/8/8/8/R3K3 b - - 0 1", "no black king"),
+        (
+            "4k3/8/8/8/8/8/8/4K1KR w - - 0 1",
+            "more than one white king",
+        ),
+        // Black to move could capture the white king
+        (
+            "4k3/8/8/8/8/8/8/r3K3 b - - 0 1",
+            "side not to move is in check",
+        ),
+    ];
+    for (fen, reason) in cases {
+        let board = parse_fen(fen).unwrap();
+        assert!(!board.is_searchable(), "{}", fen);
+
+        engine.position(fen, &[]);
+        let best = engine.analyze(SearchLimit::Depth { depth: 4 }, |_| {});
+        assert_eq!(best.best, "0000", "{}", fen);
+        let error = best.error.unwrap_or_default();
+        assert!(error.contains(reason), "{}: {}", fen, error);
+
+        // The game analysis rejects them too
+        let analysis = engine.analyze_game(fen, &[], SearchLimit::Depth { depth: 2 }, |_, _| {});
+        assert!(analysis.is_err(), "{}", fen);
+    }
+
+    // Invalid FENs say why as well, and normal positions carry no error
+    engine.position("not a fen", &[]);
+    assert!(engine
+        .analyze(SearchLimit::Depth { depth: 2 }, |_| {})
+        .error
+        .is_some());
+    engine.position("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", &[]);
+    let best = engine.analyze(SearchLimit::Depth { depth: 2 }, |_| {});
+    assert!(best.error.is_none());
+    assert_ne!(best.best, "0000");
+
+    // So do illegal moves after the position
+    engine.position("startpos", &["e2e4".to_string(), "e2e4".to_string()]);
+    let error = engine
+        .analyze(SearchLimit::Depth { depth: 2 }, |_| {})
+        .error;
+    assert_eq!(error.as_deref(), Some("Illegal move at index 1: e2e4"));
+}
+
+#[test]
+fn test_searcher_returns_null_move_without_kings() {
+    use engine::search::Searcher;
+
+    let mut searcher = Searcher::with_tt_size(1);
+    let mut board = Board::empty();
+    board.set_piece(Square::E4, Piece::new(PieceType::Rook, Color::White));
+    assert!(board.generate_legal_moves().is_empty());
+
+    let result = searcher.search(&board, 3);
+    assert!(result.best_move.is_null());
+    assert_eq!(result.depth, 0);
+}
+
+#[test]
+fn test_mated_position_reports_null_move() {
+    use engine::types::SearchLimit;
+    use engine::EngineImpl;
+
+    let mut engine = EngineImpl::default();
+    engine.position(
+        "r1bqkb1r/pppp1Qpp/2n2n2/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 4",
+        &[],
+    );
+    let best = engine.analyze(SearchLimit::Depth { depth: 3 }, |_| {});
+    assert_eq!(best.best, "0000");
+    assert!(best.error.is_none());
+}
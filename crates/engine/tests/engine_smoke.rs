@@ -1,5 +1,10 @@
 use engine::{
-    types::{EngineOptions, SearchLimit},
+    board::AttackType,
+    tt::TranspositionTable,
+    types::{
+        CastleSide, CheckInfo, EngineOptions, HangingPiece, KingThreat, MoveInfo, PinInfo,
+        RootMoveFilter, Score, ScorePov, SearchLimit,
+    },
     EngineImpl,
 };
 
@@ -12,6 +17,7 @@ fn smoke_analyze() {
         skill_level: None,
         multi_pv: Some(1),
         use_tablebases: None,
+        chess960: None,
     });
     eng.position("startpos", &[]);
     let mut infos = vec![];
@@ -38,3 +44,678 @@ fn smoke_analyze() {
         assert!(!info.pv.is_empty(), "PV should not be empty");
     }
 }
+
+#[test]
+fn analyze_reports_search_statistics() {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 16,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    // Out-of-book position so a real search runs
+    eng.position(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[],
+    );
+    let best = eng.analyze(SearchLimit::Depth { depth: 6 }, |_| {});
+
+    assert_eq!(best.depth, Some(6));
+    assert!(best.nodes.unwrap() > 0, "Nodes should be > 0");
+    assert!(best.time_ms.unwrap() > 0, "Time should be > 0");
+    assert!(best.nps.is_some());
+    assert!(best.score.is_some());
+    assert_eq!(best.pv.first(), Some(&best.best), "PV should start with best move");
+}
+
+#[test]
+fn analyze_search_moves_restricts_root() {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 16,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    eng.position(
+        "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+        &[],
+    );
+
+    // Ba6?? just hangs the bishop to bxa6
+    let search_moves = vec!["b5a6".to_string()];
+    let best =
+        eng.analyze_with_search_moves(SearchLimit::Depth { depth: 5 }, Some(&search_moves), |_| {});
+
+    assert_eq!(best.best, "b5a6");
+    match best.score {
+        Some(Score::Cp { value }) => {
+            assert!(value < -150, "Expected a losing score, got {}", value)
+        }
+        other => panic!("Expected centipawn score, got {:?}", other),
+    }
+}
+
+#[test]
+fn analyze_excluded_best_move_plays_second_line() {
+    // Nxd5 wins the queen; Bxb4 winning the rook is the runner-up
+    let fen = "6k1/5ppp/8/3q4/1r6/B1N4P/5PP1/6K1 w - - 0 1";
+    let engine = |multi_pv| {
+        let mut eng = EngineImpl::new_with(EngineOptions {
+            hash_size_mb: 16,
+            threads: 1,
+            contempt: None,
+            skill_level: None,
+            multi_pv: Some(multi_pv),
+            use_tablebases: None,
+            chess960: None,
+        });
+        eng.position(fen, &[]);
+        eng
+    };
+    let cp = |score: Option<&Score>| match score {
+        Some(Score::Cp { value }) => *value,
+        other => panic!("Expected centipawn score, got {:?}", other),
+    };
+
+    let mut infos = vec![];
+    let best = engine(2).analyze(SearchLimit::Depth { depth: 5 }, |i| infos.push(i));
+    assert_eq!(best.best, "c3d5");
+    let second = infos
+        .iter()
+        .rev()
+        .find(|i| i.multipv == Some(2))
+        .expect("second line");
+
+    let filter = RootMoveFilter {
+        exclude_moves: vec![best.best.clone()],
+        ..Default::default()
+    };
+    let excluded = engine(1).analyze_with_filter(SearchLimit::Depth { depth: 5 }, &filter, |_| {});
+    assert_eq!(excluded.best, second.pv[0]);
+    assert!(cp(excluded.score.as_ref()) < cp(best.score.as_ref()) - 300);
+    assert!((cp(excluded.score.as_ref()) - cp(Some(&second.score))).abs() <= 50);
+
+    // Excluding every legal move is an error, not a silent full search
+    let mut eng = engine(1);
+    eng.position("7k/8/8/8/8/8/8/K7 w - - 0 1", &[]);
+    let filter = RootMoveFilter {
+        exclude_moves: vec!["a1a2".into(), "a1b1".into(), "a1b2".into()],
+        ..Default::default()
+    };
+    let best = eng.analyze_with_filter(SearchLimit::Depth { depth: 3 }, &filter, |_| {});
+    assert_eq!(best.best, "0000");
+    assert!(best.error.is_some());
+}
+
+#[test]
+fn split_analysis_returns_legal_moves_in_order() {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 4,
+        threads: 4,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        "7k/8/5K2/8/8/8/8/R7 w - - 0 1",
+        "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1",
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+    ];
+
+    let results = eng.split_analysis(&fens, 3);
+
+    assert_eq!(results.len(), fens.len());
+    for (fen, best) in fens.iter().zip(&results) {
+        assert!(
+            eng.is_move_legal(fen, &best.best),
+            "{} is not legal in {}",
+            best.best,
+            fen
+        );
+    }
+}
+
+fn best_move_with_skill(fen: &str, skill_level: Option<u32>) -> String {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 4,
+        threads: 1,
+        contempt: None,
+        skill_level,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    eng.position(fen, &[]);
+    let best = eng.analyze(SearchLimit::Depth { depth: 3 }, |_| {});
+    assert_eq!(best.pv.first(), Some(&best.best), "PV should start with best move");
+    best.best
+}
+
+const SKILL_FENS: [&str; 6] = [
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 1 5",
+    "r2q1rk1/ppp2ppp/2np1n2/2b1p1B1/2B1P1b1/2NP1N2/PPP2PPP/R2Q1RK1 b - - 3 8",
+];
+
+#[test]
+fn skill_level_20_plays_best_move() {
+    for fen in SKILL_FENS {
+        assert_eq!(best_move_with_skill(fen, Some(20)), best_move_with_skill(fen, None));
+    }
+}
+
+#[test]
+fn skill_level_0_plays_varied_legal_moves() {
+    let eng = EngineImpl::default();
+    let mut differs_from_best = 0;
+
+    for fen in SKILL_FENS {
+        let weak = best_move_with_skill(fen, Some(0));
+        assert!(eng.is_move_legal(fen, &weak), "{} is not legal in {}", weak, fen);
+
+        // Same position, same choice
+        assert_eq!(best_move_with_skill(fen, Some(0)), weak);
+
+        if weak != best_move_with_skill(fen, None) {
+            differs_from_best += 1;
+        }
+    }
+
+    assert!(
+        differs_from_best >= SKILL_FENS.len() / 2,
+        "Skill 0 should mostly deviate from the best move ({} of {})",
+        differs_from_best,
+        SKILL_FENS.len()
+    );
+}
+
+fn detailed_move(eng: &EngineImpl, fen: &str, uci: &str) -> MoveInfo {
+    eng.legal_moves_detailed(fen)
+        .into_iter()
+        .find(|info| info.uci == uci)
+        .unwrap_or_else(|| panic!("{} should be legal", uci))
+}
+
+#[test]
+fn legal_moves_detailed_castling() {
+    let eng = EngineImpl::default();
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+    let short = detailed_move(&eng, fen, "e1g1");
+    assert!(short.is_castle);
+    assert_eq!(short.castle_side, Some(CastleSide::Kingside));
+    assert_eq!(short.san, "O-O");
+    // Frontends see the king's landing square, not the rook's
+    assert_eq!((short.from.as_str(), short.to.as_str()), ("e1", "g1"));
+
+    let long = detailed_move(&eng, fen, "e1c1");
+    assert_eq!(long.castle_side, Some(CastleSide::Queenside));
+    assert_eq!(long.san, "O-O-O");
+
+    let rook = detailed_move(&eng, fen, "a1a8");
+    assert!(!rook.is_castle && rook.is_capture && rook.gives_check);
+    assert!(!rook.is_checkmate);
+    assert_eq!(rook.san, "Rxa8+");
+}
+
+#[test]
+fn legal_moves_detailed_promotion_and_en_passant() {
+    let eng = EngineImpl::default();
+
+    let fen = "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+    let promotions: Vec<_> = eng
+        .legal_moves_detailed(fen)
+        .into_iter()
+        .filter(|info| info.from == "a7")
+        .collect();
+    assert_eq!(promotions.len(), 8);
+    let capture_queen = detailed_move(&eng, fen, "a7b8q");
+    assert_eq!(capture_queen.promotion, Some('q'));
+    assert!(capture_queen.is_capture && capture_queen.gives_check);
+    assert_eq!(capture_queen.san, "axb8=Q+");
+
+    let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+    let ep = detailed_move(&eng, fen, "e5d6");
+    assert!(ep.is_en_passant && ep.is_capture);
+    assert_eq!(ep.promotion, None);
+    assert_eq!(ep.san, "exd6");
+
+    let json = serde_json::to_value(&ep).unwrap();
+    assert_eq!(json["isEnPassant"], true);
+    assert!(json.get("promotion").is_none());
+}
+
+#[test]
+fn legal_moves_as_objects_flags_checks_and_mates() {
+    let eng = EngineImpl::default();
+    // Castling, en passant, promotions and a back-rank mate all available
+    let fen = "6k1/1P3ppp/8/3pP3/8/8/8/R3K2R w KQ d6 0 1";
+    let moves = eng.legal_moves_as_objects(fen);
+    assert_eq!(moves, eng.legal_moves_detailed(fen));
+    let find = |uci: &str| moves.iter().find(|info| info.uci == uci).unwrap();
+
+    let castle = find("e1g1");
+    assert!(castle.is_castle && !castle.gives_check && !castle.is_checkmate);
+    let ep = find("e5d6");
+    assert!(ep.is_en_passant && !ep.gives_check);
+
+    let mate = find("a1a8");
+    assert!(mate.gives_check && mate.is_checkmate);
+    assert_eq!(mate.san, "Ra8#");
+    let queen = find("b7b8q");
+    assert!(queen.is_checkmate && queen.promotion == Some('q'));
+    let knight = find("b7b8n");
+    assert!(!knight.gives_check && !knight.is_checkmate);
+
+    let json = serde_json::to_value(mate).unwrap();
+    assert_eq!(json["isCheckmate"], true);
+    assert_eq!(json["givesCheck"], true);
+}
+
+#[test]
+fn legal_moves_by_square_groups_startpos() {
+    let eng = EngineImpl::default();
+    let by_square = eng.legal_moves_by_square(engine::io::STARTPOS_FEN);
+
+    assert_eq!(by_square.len(), 10);
+    let e2: Vec<_> = by_square["e2"]
+        .iter()
+        .map(|info| info.san.as_str())
+        .collect();
+    assert_eq!(e2.len(), 2);
+    assert!(e2.contains(&"e3") && e2.contains(&"e4"));
+    assert_eq!(by_square["g1"].len(), 2);
+    assert!(eng.legal_moves_by_square("not a fen").is_empty());
+}
+
+#[test]
+fn small_hash_engine_plays_legal_moves() {
+    let fen = "r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 8,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    eng.set_hash_size(1);
+    assert_eq!(eng.tt_bytes(), 0);
+
+    eng.position(fen, &[]);
+    let best = eng.analyze(SearchLimit::Depth { depth: 8 }, |_| {});
+
+    assert!(eng.is_move_legal(fen, &best.best), "{}", best.best);
+    assert!(matches!(best.score, Some(Score::Cp { value }) if value.abs() < 200));
+    assert!(eng.tt_bytes() > 0 && eng.tt_bytes() <= 1024 * 1024);
+    assert!(eng.pawn_hash_bytes() > 0);
+}
+
+#[test]
+fn hash_option_resizes_table() {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 32,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    assert_eq!(eng.tt_size_mb(), TranspositionTable::new(32).size_mb());
+
+    eng.set_option("Hash", "128");
+    let size_mb = eng.tt_size_mb();
+    assert_eq!(size_mb, TranspositionTable::new(128).size_mb());
+    assert!(size_mb > 64 && size_mb <= 128, "{} MB", size_mb);
+
+    eng.position("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", &[]);
+    let best = eng.analyze(SearchLimit::Depth { depth: 5 }, |_| {});
+    assert!(best.nodes.unwrap_or(0) > 0);
+    assert_eq!(eng.tt_bytes(), size_mb * 1024 * 1024);
+
+    // Unparsable values leave the table alone
+    eng.set_option("Hash", "lots");
+    assert_eq!(eng.tt_size_mb(), size_mb);
+}
+
+#[test]
+fn white_relative_scores_ignore_side_to_move() {
+    let mut eng = EngineImpl::default();
+    // Black is a queen up with either side to move
+    for fen in [
+        "3qk3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "3qk3/8/8/8/8/8/8/4K3 b - - 0 1",
+    ] {
+        eng.set_option("ScorePov", "SideToMove");
+        eng.position(fen, &[]);
+        let best = eng.analyze(SearchLimit::Depth { depth: 4 }, |_| {});
+        let Some(Score::Cp { value: relative }) = best.score else {
+            panic!("{}: {:?}", fen, best.score);
+        };
+        assert_eq!(relative > 0, fen.contains(" b "), "{}: {}", fen, relative);
+
+        eng.set_option("ScorePov", "White");
+        eng.set_option("UCI_ShowWDL", "true");
+        let mut infos = vec![];
+        let best = eng.analyze(SearchLimit::Depth { depth: 4 }, |i| infos.push(i));
+        assert!(matches!(best.score, Some(Score::Cp { value }) if value < -500));
+        for info in &infos {
+            assert_eq!(info.score_pov, ScorePov::White);
+            assert!(matches!(info.score, Score::Cp { value } if value < -500));
+            let (win, _, loss) = info.wdl.unwrap();
+            assert!(loss > 900 && win < 10, "{:?}", info.wdl);
+        }
+        eng.set_option("UCI_ShowWDL", "false");
+    }
+}
+
+#[test]
+fn chess960_option_writes_castling_as_king_takes_rook() {
+    let mut eng = EngineImpl::default();
+    eng.position("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1", &[]);
+    let castle = ["e1g1".to_string()];
+
+    let best =
+        eng.analyze_with_search_moves(SearchLimit::Depth { depth: 3 }, Some(&castle), |_| {});
+    assert_eq!(best.best, "e1g1");
+
+    eng.set_option("UCI_Chess960", "true");
+    assert_eq!(eng.opts.chess960, Some(true));
+    let mut infos = vec![];
+    let best = eng.analyze_with_search_moves(SearchLimit::Depth { depth: 3 }, Some(&castle), |i| {
+        infos.push(i)
+    });
+    assert_eq!(best.best, "e1h1");
+    assert_eq!(best.pv[0], "e1h1");
+    assert!(infos.iter().all(|info| info.pv[0] == "e1h1"), "{:?}", infos);
+}
+
+#[test]
+fn move_classification_finds_known_blunders() {
+    use engine::analysis::MoveClassification;
+
+    let mut eng = EngineImpl::default();
+
+    // 1. e4 e5 2. Nf3: Qg5?? hangs the queen to the knight
+    let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+    assert_eq!(
+        eng.move_classification(fen, "d8g5", 4),
+        Ok(MoveClassification::Blunder)
+    );
+    let develop = eng.move_classification(fen, "b8c6", 4).unwrap();
+    assert!(
+        matches!(develop, MoveClassification::Best | MoveClassification::Good),
+        "{:?}",
+        develop
+    );
+
+    // 1. e4 e5: Ba6?? hangs the bishop to the b7 pawn
+    let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+    assert_eq!(
+        eng.move_classification(fen, "f1a6", 4),
+        Ok(MoveClassification::Blunder)
+    );
+
+    // Missing the back-rank mate while staying a rook up is only a mistake
+    let fen = "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1";
+    assert_eq!(
+        eng.move_classification(fen, "a1a8", 4),
+        Ok(MoveClassification::Best)
+    );
+    assert_eq!(
+        eng.move_classification(fen, "h2h3", 4),
+        Ok(MoveClassification::Mistake)
+    );
+
+    assert!(eng.move_classification(fen, "e2e4", 4).is_err());
+    assert!(eng.move_classification("not a fen", "e2e4", 4).is_err());
+}
+
+#[test]
+fn position_hints_lists_hanging_pieces() {
+    let eng = EngineImpl::default();
+    // White's d4 knight is attacked by a pawn, black's a6 rook by the bishop
+    let hints = eng
+        .position_hints("4k3/8/r7/4p3/3N4/8/8/3RKB2 w - - 0 1")
+        .unwrap();
+
+    assert_eq!(
+        hints.white_hanging,
+        vec![HangingPiece {
+            square: "d4".into(),
+            piece: 'N',
+            value: 220,
+        }]
+    );
+    assert_eq!(hints.black_hanging.len(), 1);
+    assert_eq!(hints.black_hanging[0].square, "a6");
+    assert_eq!(hints.black_hanging[0].value, 500);
+
+    let safe = eng.position_hints(engine::io::STARTPOS_FEN).unwrap();
+    assert!(safe.white_hanging.is_empty() && safe.black_hanging.is_empty());
+    assert!(eng.position_hints("not a fen").is_err());
+}
+
+#[test]
+fn attacked_squares_lists_controlled_squares() {
+    let eng = EngineImpl::default();
+    let attacked = eng
+        .attacked_squares("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "white")
+        .unwrap();
+
+    // Rook on a1 along the file and rank up to the king, king around e1
+    assert!(attacked.contains(&"a8".to_string()));
+    assert!(attacked.contains(&"d1".to_string()));
+    assert!(!attacked.contains(&"f3".to_string()));
+    assert_eq!(attacked.len(), 7 + 3 + 5);
+
+    assert!(eng.attacked_squares(engine::io::STARTPOS_FEN, "x").is_err());
+    assert!(eng.attacked_squares("not a fen", "w").is_err());
+}
+
+#[test]
+fn check_info_reports_checkers() {
+    let eng = EngineImpl::default();
+
+    assert_eq!(
+        eng.check_info(engine::io::STARTPOS_FEN).unwrap(),
+        CheckInfo {
+            in_check: false,
+            king: Some("e1".into()),
+            checkers: vec![],
+        }
+    );
+
+    // Double check from the d6 knight and the e1 rook
+    let info = eng.check_info("4k3/8/3N4/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    assert!(info.in_check);
+    assert_eq!(info.king.as_deref(), Some("e8"));
+    assert_eq!(info.checkers, vec!["e1", "d6"]);
+}
+
+#[test]
+fn explain_check_reports_direct_and_discovered_threats() {
+    let eng = EngineImpl::default();
+
+    assert!(eng
+        .explain_check(engine::io::STARTPOS_FEN)
+        .unwrap()
+        .is_empty());
+
+    // The d6 knight checks; the e1 rook is behind the e2 pawn
+    let threats = eng
+        .explain_check("4k3/8/3N4/8/8/8/4P3/4R1K1 b - - 0 1")
+        .unwrap();
+    assert_eq!(
+        threats,
+        vec![
+            KingThreat {
+                square: "e1".into(),
+                piece: 'R',
+                attack_type: AttackType::Discovery,
+            },
+            KingThreat {
+                square: "d6".into(),
+                piece: 'N',
+                attack_type: AttackType::Direct,
+            },
+        ]
+    );
+    assert_eq!(
+        serde_json::to_value(&threats[0]).unwrap()["attackType"],
+        "discovery"
+    );
+
+    assert!(eng.explain_check("not a fen").is_err());
+}
+
+#[test]
+fn pinned_pieces_reports_pinner_and_ray() {
+    let eng = EngineImpl::default();
+
+    // Knight pinned on the file, bishop on the diagonal
+    let pins = eng
+        .pinned_pieces("k3r3/8/8/b7/8/8/3BN3/4K3 w - - 0 1")
+        .unwrap();
+    assert_eq!(
+        pins,
+        vec![
+            PinInfo {
+                square: "d2".into(),
+                pinner: "a5".into(),
+                ray: vec!["d2".into(), "c3".into(), "b4".into(), "a5".into()],
+            },
+            PinInfo {
+                square: "e2".into(),
+                pinner: "e8".into(),
+                ray: ["e2", "e3", "e4", "e5", "e6", "e7", "e8"]
+                    .map(String::from)
+                    .to_vec(),
+            },
+        ]
+    );
+
+    let none = eng.pinned_pieces(engine::io::STARTPOS_FEN).unwrap();
+    assert!(none.is_empty());
+}
+
+#[test]
+fn expand_pv_replays_castling_and_promotion() {
+    let eng = EngineImpl::default();
+    let fen = "4k3/1P6/8/8/8/8/8/4K2R w K - 0 1";
+    let pv: Vec<String> = ["e1g1", "e8e7", "b7b8q"].map(String::from).to_vec();
+
+    let nodes = eng.expand_pv(fen, &pv).unwrap();
+    let sans: Vec<&str> = nodes.iter().map(|n| n.san.as_str()).collect();
+    assert_eq!(sans, vec!["O-O", "Ke7", "b8=Q"]);
+    assert_eq!(nodes[0].fen_after, "4k3/1P6/8/8/8/8/8/5RK1 b - - 1 1");
+
+    for (i, node) in nodes.iter().enumerate() {
+        assert_eq!(node.uci, pv[i]);
+        assert!(engine::io::parse_fen(&node.fen_after).is_ok());
+        let side = node.fen_after.split(' ').nth(1).unwrap();
+        assert_eq!(side, if i % 2 == 0 { "b" } else { "w" });
+    }
+
+    assert!(eng.expand_pv(fen, &["e1c1".to_string()]).is_err());
+    assert!(eng.expand_pv("not a fen", &pv).is_err());
+}
+
+#[test]
+fn analyze_batch_matches_single_analyses() {
+    let fens: Vec<String> = include_str!("../positions/wacnew.epd")
+        .lines()
+        .take(50)
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().take(4).collect();
+            format!("{} 0 1", fields.join(" "))
+        })
+        .collect();
+    let fens: Vec<&str> = fens.iter().map(String::as_str).collect();
+    let limit = SearchLimit::Depth { depth: 6 };
+
+    let start = std::time::Instant::now();
+    let mut progress = Vec::new();
+    let batch = EngineImpl::default().analyze_batch(&fens, limit.clone(), |done, total| {
+        progress.push((done, total))
+    });
+    let batch_time = start.elapsed();
+
+    // The same positions with a fresh engine each
+    let start = std::time::Instant::now();
+    let singles: Vec<_> = fens
+        .iter()
+        .map(|fen| {
+            let mut eng = EngineImpl::default();
+            eng.position(fen, &[]);
+            eng.analyze(limit.clone(), |_| {})
+        })
+        .collect();
+    let singles_time = start.elapsed();
+
+    assert_eq!(batch.len(), 50);
+    let expected: Vec<_> = (1..=50).map(|done| (done, 50)).collect();
+    assert_eq!(progress, expected);
+    for (evaluation, single) in batch.iter().zip(&singles) {
+        assert_eq!(evaluation.best_move, single.best, "{}", evaluation.fen);
+        assert_eq!(evaluation.depth, single.depth);
+        match (&evaluation.score, &single.score) {
+            (Some(Score::Cp { value: a }), Some(Score::Cp { value: b })) => {
+                assert!((a - b).abs() <= 10, "{}: {} vs {}", evaluation.fen, a, b)
+            }
+            (Some(Score::Mate { plies: a }), Some(Score::Mate { plies: b })) => assert_eq!(a, b),
+            (a, b) => panic!("{}: {:?} vs {:?}", evaluation.fen, a, b),
+        }
+    }
+    assert!(
+        batch_time < singles_time,
+        "batch {:?}, single analyses {:?}",
+        batch_time,
+        singles_time
+    );
+}
+
+#[test]
+fn analyze_batch_reports_bad_fens_and_keeps_position() {
+    let mut eng = EngineImpl::default();
+    eng.position(engine::io::STARTPOS_FEN, &["e2e4".to_string()]);
+
+    let fens = [
+        "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1",
+        "not a fen",
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+    ];
+    let batch = eng.analyze_batch(&fens, SearchLimit::Depth { depth: 3 }, |_, _| {});
+
+    assert_eq!(batch.len(), 3);
+    assert_eq!(batch[0].best_move, "a1a8");
+    assert!(batch[0].error.is_none());
+    assert_eq!(batch[1].fen, "not a fen");
+    assert_eq!(batch[1].best_move, "0000");
+    assert!(batch[1].error.is_some());
+    assert!(batch[2].error.is_none());
+
+    assert_eq!(eng.current_moves, vec!["e2e4"]);
+    assert_eq!(eng.current_fen, engine::io::STARTPOS_FEN);
+}
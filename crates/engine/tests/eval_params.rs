@@ -0,0 +1,51 @@
+//! Loading evaluation parameters at runtime.
+//!
+//! The parameters are process-wide, so everything touching them lives in a
+//! single test.
+
+use engine::eval::Evaluator;
+use engine::io::parse_fen;
+use engine::tune::EvalParams;
+use engine::uci::UciHandler;
+
+#[test]
+fn eval_params_file_changes_evaluation_in_every_thread() {
+    // Doubled c-pawns for white
+    let board = parse_fen("4k3/8/8/8/8/2P5/2P5/4K3 w - - 0 1").unwrap();
+    let mut evaluator = Evaluator::new();
+    let default_score = evaluator.evaluate(&board);
+
+    let mut params = EvalParams::from_current_eval();
+    params.doubled_pawn_mg = -2000;
+    params.doubled_pawn_eg = -2000;
+    let path = std::env::temp_dir().join(format!("eval_params_{}.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&params).unwrap()).unwrap();
+
+    let mut handler = UciHandler::new();
+    let response = handler
+        .handle_command(&format!(
+            "setoption name EvalParamsFile value {}",
+            path.display()
+        ))
+        .unwrap();
+    std::fs::remove_file(&path).ok();
+    assert!(
+        response.starts_with("info string eval params loaded"),
+        "{}",
+        response
+    );
+
+    // The existing evaluator drops its cached pawn scores
+    let loaded_score = evaluator.evaluate(&board);
+    assert!(loaded_score < default_score - 100);
+
+    // Other threads see the same parameters
+    let board_clone = board.clone();
+    let thread_score = std::thread::spawn(move || Evaluator::new().evaluate(&board_clone))
+        .join()
+        .unwrap();
+    assert_eq!(thread_score, loaded_score);
+
+    handler.handle_command("setoption name EvalParamsFile value <empty>");
+    assert_eq!(evaluator.evaluate(&board), default_score);
+}
@@ -10,6 +10,7 @@ fn test_mate_in_1() {
         skill_level: None,
         multi_pv: None,
         use_tablebases: None,
+        chess960: None,
     };
 
     let mut eng = EngineImpl::new_with(opts);
@@ -55,6 +56,7 @@ fn test_tactical_position() {
         skill_level: None,
         multi_pv: None,
         use_tablebases: None,
+        chess960: None,
     };
 
     let mut eng = EngineImpl::new_with(opts);
@@ -76,3 +78,40 @@ fn test_tactical_position() {
         result.best
     );
 }
+
+#[test]
+fn test_go_mate_stops_at_proven_mate() {
+    let opts = EngineOptions {
+        hash_size_mb: 16,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: None,
+        use_tablebases: None,
+        chess960: None,
+    };
+
+    let mut eng = EngineImpl::new_with(opts);
+
+    // Mate in 2: 1.Kg6 Kg8 2.Ra8# (or 1.Kf7 Kh7 2.Rh1#)
+    let fen = "7k/8/5K2/8/8/8/8/R7 w - - 0 1";
+    eng.position(fen, &[]);
+
+    let start = std::time::Instant::now();
+    let result = eng.analyze(SearchLimit::Mate { moves: 2 }, |_| {});
+
+    // Mate in 2 moves is 3 plies
+    assert!(
+        matches!(result.score, Some(Score::Mate { plies: 3 })),
+        "got {:?}",
+        result.score
+    );
+    assert!(
+        result.best == "f6g6" || result.best == "f6f7",
+        "got {}",
+        result.best
+    );
+    // Stops as soon as the mate is proven instead of deepening to MAX_DEPTH
+    assert!(result.depth.unwrap() < 10);
+    assert!(start.elapsed().as_secs() < 5);
+}
@@ -0,0 +1,101 @@
+//! Opening move variety: near-best root moves are picked at random in the
+//! first moves of a game, reproducibly for a fixed seed.
+
+use std::collections::HashSet;
+
+use engine::{
+    board::Board,
+    search::{Searcher, MAX_MOVE_VARIETY},
+    time::TimeControl,
+    types::{EngineOptions, SearchLimit},
+    EngineImpl,
+};
+
+const DEPTH: u32 = 8;
+const RUNS: usize = 100;
+
+/// First moves chosen over `RUNS` searches from the start position.
+fn first_moves(variety: u32, seed: u64) -> Vec<String> {
+    // One searcher for all runs: the transposition table makes repeats cheap.
+    // The start position is in the book, which variety leaves alone.
+    let mut searcher = Searcher::with_tt_size(16);
+    searcher.set_use_book(false);
+    searcher.set_move_variety(variety);
+    searcher.set_move_variety_seed(seed);
+    let board = Board::startpos();
+    (0..RUNS)
+        .map(|_| {
+            let result = searcher.search_multi_pv_with_limit_callback(
+                &board,
+                DEPTH,
+                1,
+                TimeControl::Depth { depth: DEPTH },
+                |_| {},
+            );
+            result.best_move.to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn variety_plays_different_first_moves() {
+    let moves = first_moves(30, 1);
+    let distinct: HashSet<_> = moves.iter().collect();
+    assert!(distinct.len() >= 3, "only {:?}", distinct);
+}
+
+#[test]
+fn fixed_seed_replays_the_same_moves() {
+    assert_eq!(first_moves(30, 9), first_moves(30, 9));
+}
+
+#[test]
+fn zero_variety_is_deterministic() {
+    let moves = first_moves(0, 1);
+    assert!(moves.iter().all(|m| *m == moves[0]), "{:?}", moves);
+}
+
+#[test]
+fn variety_plays_book_moves() {
+    let mut searcher = Searcher::with_tt_size(16);
+    let board = Board::startpos();
+    let book = searcher.search_with_limit(&board, 4, TimeControl::Depth { depth: 4 });
+    assert_eq!(book.depth, 0);
+
+    searcher.set_move_variety(MAX_MOVE_VARIETY);
+    for seed in 0..5 {
+        searcher.set_move_variety_seed(seed);
+        let result = searcher.search_multi_pv_with_limit_callback(
+            &board,
+            4,
+            1,
+            TimeControl::Depth { depth: 4 },
+            |_| {},
+        );
+        assert_eq!(result.best_move, book.best_move);
+    }
+}
+
+#[test]
+fn variety_stops_after_the_opening() {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 16,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    eng.set_option("MoveVariety", "100");
+    // Quiet middlegame position at move 20: always the same best move
+    eng.position(
+        "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 20",
+        &[],
+    );
+    let first = eng.analyze(SearchLimit::Depth { depth: 6 }, |_| {}).best;
+    for _ in 0..5 {
+        let best = eng.analyze(SearchLimit::Depth { depth: 6 }, |_| {}).best;
+        assert_eq!(best, first);
+    }
+}
@@ -0,0 +1,36 @@
+//! Pawn hash table correctness.
+//!
+//! A long-lived evaluator answers most pawn-structure lookups from its pawn
+//! hash; it must score every position exactly like an evaluator that
+//! recomputes the structure each time.
+
+use engine::board::Board;
+use engine::eval::Evaluator;
+use engine::io::ToFen;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn cached_pawn_eval_matches_uncached_on_random_games() {
+    let mut rng = StdRng::seed_from_u64(0x5eed_2024);
+    let mut cached = Evaluator::new();
+    let mut positions = 0;
+
+    while positions < 4000 {
+        let mut board = Board::startpos();
+        for _ in 0..80 {
+            let moves = board.generate_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            board.make_move(*moves.get(rng.gen_range(0..moves.len())));
+
+            let expected = Evaluator::with_pawn_hash_bytes(0).evaluate(&board);
+            assert_eq!(cached.evaluate(&board), expected, "{}", board.to_fen());
+            positions += 1;
+        }
+    }
+
+    let stats = cached.pawn_hash_stats();
+    assert!(stats.hits > 0, "{:?}", stats);
+}
@@ -0,0 +1,21 @@
+//! Move generation regression check against the standard perft tables.
+//!
+//! Checks every standard position up to `PERFT_MAX_DEPTH` (default 4).
+//! Use a smaller value for quick CI runs or a larger one (up to 6) for a
+//! full check: `PERFT_MAX_DEPTH=6 cargo test --release --test perft_regression`.
+
+use engine::perft::verify_all_standard_positions;
+
+const DEFAULT_MAX_DEPTH: u32 = 4;
+
+#[test]
+fn perft_regression() {
+    let max_depth = std::env::var("PERFT_MAX_DEPTH")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_DEPTH);
+
+    let mismatches = verify_all_standard_positions(max_depth);
+    let report: Vec<String> = mismatches.iter().map(|m| m.to_string()).collect();
+    assert!(mismatches.is_empty(), "{}", report.join("\n"));
+}
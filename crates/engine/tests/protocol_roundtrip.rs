@@ -76,6 +76,19 @@ fn search_limit_infinite_roundtrip() {
     assert!(matches!(parsed, SearchLimit::Infinite));
 }
 
+#[test]
+fn search_limit_mate_roundtrip() {
+    let original = SearchLimit::Mate { moves: 3 };
+    let json = serde_json::to_string(&original).unwrap();
+    let parsed: SearchLimit = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, r#"{"kind":"mate","moves":3}"#);
+    match parsed {
+        SearchLimit::Mate { moves } => assert_eq!(moves, 3),
+        _ => panic!("Expected Mate limit"),
+    }
+}
+
 #[test]
 fn engine_options_roundtrip() {
     let original = EngineOptions {
@@ -85,6 +98,7 @@ fn engine_options_roundtrip() {
         skill_level: Some(15),
         multi_pv: Some(3),
         use_tablebases: Some(true),
+        chess960: Some(true),
     };
     let json = serde_json::to_string(&original).unwrap();
     let parsed: EngineOptions = serde_json::from_str(&json).unwrap();
@@ -94,6 +108,7 @@ fn engine_options_roundtrip() {
     assert!(json.contains("skillLevel"));
     assert!(json.contains("multiPV"));
     assert!(json.contains("useTablebases"));
+    assert!(json.contains("\"chess960\""));
 
     assert_eq!(parsed.hash_size_mb, 128);
     assert_eq!(parsed.threads, 4);
@@ -101,6 +116,7 @@ fn engine_options_roundtrip() {
     assert_eq!(parsed.skill_level, Some(15));
     assert_eq!(parsed.multi_pv, Some(3));
     assert_eq!(parsed.use_tablebases, Some(true));
+    assert_eq!(parsed.chess960, Some(true));
 }
 
 #[test]
@@ -112,6 +128,7 @@ fn engine_options_minimal_roundtrip() {
         skill_level: None,
         multi_pv: None,
         use_tablebases: None,
+        chess960: None,
     };
     let json = serde_json::to_string(&original).unwrap();
     let parsed: EngineOptions = serde_json::from_str(&json).unwrap();
@@ -128,6 +145,7 @@ fn analyze_request_roundtrip() {
         fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
         moves: Some(vec!["e2e4".to_string(), "e7e5".to_string()]),
         limit: SearchLimit::Depth { depth: 10 },
+        search_moves: None,
         options: Some(EngineOptions {
             hash_size_mb: 64,
             threads: 1,
@@ -135,6 +153,7 @@ fn analyze_request_roundtrip() {
             skill_level: None,
             multi_pv: Some(1),
             use_tablebases: None,
+            chess960: None,
         }),
         context: Some(AnalyzeRequestContext {
             allow_ponder: Some(true),
@@ -164,6 +183,7 @@ fn analyze_request_minimal_roundtrip() {
         fen: "startpos".to_string(),
         moves: None,
         limit: SearchLimit::Infinite,
+        search_moves: None,
         options: None,
         context: None,
     };
@@ -173,6 +193,19 @@ fn analyze_request_minimal_roundtrip() {
     assert_eq!(parsed.id, "minimal");
     assert_eq!(parsed.fen, "startpos");
     assert_eq!(parsed.moves, None);
+    assert!(!json.contains("searchMoves"));
+}
+
+#[test]
+fn analyze_request_search_moves_roundtrip() {
+    let json = r#"{"id":"sm","fen":"startpos","limit":{"kind":"mate","moves":2},"searchMoves":["e2e4","d2d4"]}"#;
+    let parsed: AnalyzeRequest = serde_json::from_str(json).unwrap();
+
+    assert!(matches!(parsed.limit, SearchLimit::Mate { moves: 2 }));
+    assert_eq!(
+        parsed.search_moves,
+        Some(vec!["e2e4".to_string(), "d2d4".to_string()])
+    );
 }
 
 #[test]
@@ -188,6 +221,12 @@ fn search_info_roundtrip() {
         pv: vec!["e2e4".to_string(), "e7e5".to_string()],
         hashfull: Some(500),
         tb_hits: Some(100),
+        multipv: None,
+        bound: None,
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        partial: false,
+        pv_terminated: None,
     };
     let json = serde_json::to_string(&original).unwrap();
     let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
@@ -208,6 +247,7 @@ fn best_move_roundtrip() {
         id: "move-1".to_string(),
         best: "e2e4".to_string(),
         ponder: Some("e7e5".to_string()),
+        ..Default::default()
     };
     let json = serde_json::to_string(&original).unwrap();
     let parsed: BestMove = serde_json::from_str(&json).unwrap();
@@ -217,6 +257,241 @@ fn best_move_roundtrip() {
     assert_eq!(parsed.ponder, Some("e7e5".to_string()));
 }
 
+#[test]
+fn search_info_multipv_roundtrip() {
+    let original = SearchInfo {
+        id: "info-2".to_string(),
+        depth: 8,
+        seldepth: None,
+        nodes: 50000,
+        nps: 250000,
+        time_ms: 200,
+        score: Score::Cp { value: -15 },
+        pv: vec!["d2d4".to_string()],
+        hashfull: None,
+        tb_hits: None,
+        multipv: Some(2),
+        bound: None,
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        partial: false,
+        pv_terminated: None,
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+
+    assert!(json.contains(r#""multiPV":2"#));
+    assert_eq!(parsed.multipv, Some(2));
+
+    // Single-PV infos omit the field entirely
+    let single = SearchInfo {
+        multipv: None,
+        ..original
+    };
+    let json = serde_json::to_string(&single).unwrap();
+    assert!(!json.contains("multiPV"));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.multipv, None);
+}
+
+#[test]
+fn search_info_bound_roundtrip() {
+    let original = SearchInfo {
+        id: "info-3".to_string(),
+        depth: 12,
+        seldepth: Some(18),
+        nodes: 900000,
+        nps: 1500000,
+        time_ms: 600,
+        score: Score::Cp { value: 240 },
+        pv: vec!["e4e5".to_string()],
+        hashfull: Some(30),
+        tb_hits: None,
+        multipv: None,
+        bound: Some(Bound::Lower),
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        partial: false,
+        pv_terminated: None,
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    assert!(json.contains(r#""bound":"lower""#));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.bound, Some(Bound::Lower));
+
+    // Exact scores omit the field
+    let exact = SearchInfo {
+        bound: None,
+        ..original
+    };
+    let json = serde_json::to_string(&exact).unwrap();
+    assert!(!json.contains("bound"));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.bound, None);
+}
+
+#[test]
+fn search_info_pov_and_wdl_roundtrip() {
+    let original = SearchInfo {
+        id: "info-4".to_string(),
+        depth: 9,
+        seldepth: Some(14),
+        nodes: 120000,
+        nps: 800000,
+        time_ms: 150,
+        score: Score::Cp { value: -130 },
+        pv: vec!["g8f6".to_string()],
+        hashfull: None,
+        tb_hits: None,
+        multipv: None,
+        bound: None,
+        score_pov: ScorePov::White,
+        wdl: Some((20, 380, 600)),
+        partial: false,
+        pv_terminated: None,
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    assert!(json.contains(r#""scorePov":"white""#));
+    assert!(json.contains(r#""wdl":[20,380,600]"#));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.score_pov, ScorePov::White);
+    assert_eq!(parsed.wdl, Some((20, 380, 600)));
+
+    // Side-to-move scores without WDL look exactly as before
+    let plain = SearchInfo {
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        ..original
+    };
+    let json = serde_json::to_string(&plain).unwrap();
+    assert!(!json.contains("scorePov"));
+    assert!(!json.contains("wdl"));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.score_pov, ScorePov::SideToMove);
+}
+
+#[test]
+fn search_info_partial_roundtrip() {
+    let original = SearchInfo {
+        id: "info-5".to_string(),
+        depth: 11,
+        seldepth: Some(15),
+        nodes: 2_500_000,
+        nps: 1_250_000,
+        time_ms: 2000,
+        score: Score::Cp { value: 18 },
+        pv: vec!["c2c4".to_string()],
+        hashfull: Some(120),
+        tb_hits: None,
+        multipv: None,
+        bound: None,
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        partial: true,
+        pv_terminated: None,
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    assert!(json.contains(r#""partial":true"#));
+    assert!(serde_json::from_str::<SearchInfo>(&json).unwrap().partial);
+
+    // Infos of completed iterations omit the flag
+    let complete = SearchInfo {
+        partial: false,
+        ..original
+    };
+    let json = serde_json::to_string(&complete).unwrap();
+    assert!(!json.contains("partial"));
+    assert!(!serde_json::from_str::<SearchInfo>(&json).unwrap().partial);
+}
+
+#[test]
+fn search_info_pv_terminated_roundtrip() {
+    let original = SearchInfo {
+        id: "info-6".to_string(),
+        depth: 9,
+        seldepth: Some(12),
+        nodes: 800_000,
+        nps: 1_000_000,
+        time_ms: 800,
+        score: Score::Cp { value: 0 },
+        pv: ["d7e8", "g8h7", "e8h5", "h7g8", "h5e8"]
+            .map(String::from)
+            .to_vec(),
+        hashfull: Some(40),
+        tb_hits: None,
+        multipv: None,
+        bound: None,
+        score_pov: ScorePov::SideToMove,
+        wdl: None,
+        partial: false,
+        pv_terminated: Some(PvEnd::Repetition),
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    assert!(json.contains(r#""pvTerminated":"repetition""#));
+    let parsed: SearchInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.pv_terminated, Some(PvEnd::Repetition));
+
+    for (end, name) in [
+        (PvEnd::TTMiss, "ttMiss"),
+        (PvEnd::MaxLen, "maxLen"),
+        (PvEnd::IllegalTTMove, "illegalTtMove"),
+    ] {
+        assert_eq!(serde_json::to_value(end).unwrap(), name);
+    }
+
+    // Older payloads have no reason
+    let plain = SearchInfo {
+        pv_terminated: None,
+        ..original
+    };
+    let json = serde_json::to_string(&plain).unwrap();
+    assert!(!json.contains("pvTerminated"));
+}
+
+#[test]
+fn best_move_with_stats_roundtrip() {
+    let original = BestMove {
+        id: "move-2".to_string(),
+        best: "g1f3".to_string(),
+        ponder: Some("d7d5".to_string()),
+        depth: Some(17),
+        seldepth: Some(23),
+        nodes: Some(4_100_000),
+        time_ms: Some(2300),
+        nps: Some(1_782_608),
+        score: Some(Score::Cp { value: 31 }),
+        pv: vec!["g1f3".to_string(), "d7d5".to_string()],
+        hashfull: Some(412),
+        tb_hits: None,
+        error: None,
+    };
+    let json = serde_json::to_string(&original).unwrap();
+    let parsed: BestMove = serde_json::from_str(&json).unwrap();
+
+    assert!(json.contains("timeMs"));
+    assert!(!json.contains("tbHits"));
+
+    assert_eq!(parsed.depth, Some(17));
+    assert_eq!(parsed.nodes, Some(4_100_000));
+    assert_eq!(parsed.time_ms, Some(2300));
+    assert_eq!(parsed.pv, vec!["g1f3", "d7d5"]);
+    assert!(matches!(parsed.score, Some(Score::Cp { value: 31 })));
+}
+
+#[test]
+fn best_move_legacy_payload() {
+    // Payloads from older engines carry no statistics
+    let json = r#"{"id":"old","best":"e2e4"}"#;
+    let parsed: BestMove = serde_json::from_str(json).unwrap();
+
+    assert_eq!(parsed.best, "e2e4");
+    assert_eq!(parsed.depth, None);
+    assert!(parsed.pv.is_empty());
+
+    let reserialized = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(reserialized, json);
+}
+
 #[test]
 fn engine_event_search_info_roundtrip() {
     let original = EngineEvent::SearchInfo {
@@ -231,6 +506,12 @@ fn engine_event_search_info_roundtrip() {
             pv: vec!["e2e4".to_string()],
             hashfull: None,
             tb_hits: None,
+            multipv: None,
+            bound: None,
+            score_pov: ScorePov::SideToMove,
+            wdl: None,
+            partial: false,
+            pv_terminated: None,
         },
     };
     let json = serde_json::to_string(&original).unwrap();
@@ -256,6 +537,7 @@ fn engine_event_best_move_roundtrip() {
             id: "evt-2".to_string(),
             best: "d2d4".to_string(),
             ponder: Some("d7d5".to_string()),
+            ..Default::default()
         },
     };
     let json = serde_json::to_string(&original).unwrap();
@@ -0,0 +1,47 @@
+//! The stable public API: the prelude and root re-exports.
+//!
+//! Every item is imported by name, so this file stops compiling if a prelude
+//! item is removed or renamed. That is a breaking change; it should only
+//! happen together with a major version bump. Items may move between
+//! modules freely as long as they still resolve here.
+
+use engine::prelude::{
+    parse_fen, search_params, BestMove, Board, Color, EngineImpl, EngineOptions, Move, MoveFlags,
+    MoveList, Piece, PieceType, SearchInfo, SearchLimit, SearchParams, SearchResult, Searcher,
+    Square, StopHandle, TimeControl, ToFen,
+};
+
+#[test]
+fn test_prelude_types_resolve() {
+    // One value (or signature) per type, so each import is used
+    let board: Board = Board::startpos();
+    let moves: MoveList = board.generate_legal_moves();
+    let first: Move = moves[0];
+    let _: MoveFlags = MoveFlags::QUIET;
+    let _: Square = first.from();
+    let _: Option<Piece> = board.piece_at(first.from());
+    let _: PieceType = PieceType::Knight;
+    let _: Color = board.side_to_move();
+    let _: fn() -> Searcher = Searcher::new;
+    let _: fn(&mut Searcher, &Board, u32) -> SearchResult = Searcher::search;
+    let _: StopHandle = StopHandle::new();
+    let _: TimeControl = TimeControl::Infinite;
+    let _: fn(EngineOptions) -> EngineImpl = EngineImpl::new_with;
+    let _: BestMove = BestMove::default();
+    let _: Option<SearchInfo> = None;
+    let _: SearchLimit = SearchLimit::Depth { depth: 1 };
+    let _: SearchParams = search_params::get_search_params();
+}
+
+#[test]
+fn test_prelude_functions_resolve() {
+    let _: fn(&str) -> Result<Board, engine::io::FenError> = parse_fen;
+    let _: fn(&Board) -> String = <Board as ToFen>::to_fen;
+}
+
+#[test]
+fn test_root_reexports_match_prelude() {
+    // Only compiles if the root names are the prelude's types
+    let _: fn(engine::Move) -> Move = |m| m;
+    let _: fn(engine::EngineImpl) -> EngineImpl = |e| e;
+}
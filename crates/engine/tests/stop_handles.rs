@@ -0,0 +1,166 @@
+//! Stress tests for per-search cancellation: rapid analyze/stop cycles from
+//! several threads must never let a search overrun its budget, and a stop
+//! aimed at one search must never cancel a later one.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use engine::{
+    search::StopHandle,
+    types::{EngineOptions, SearchLimit},
+    EngineImpl,
+};
+
+/// Out of book, with plenty of play so deep searches take a while.
+const FEN: &str = "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 9";
+
+/// Generous slack for slow CI machines running many threads at once.
+const SLACK: Duration = Duration::from_millis(750);
+
+fn engine() -> EngineImpl {
+    let mut eng = EngineImpl::new_with(EngineOptions {
+        hash_size_mb: 1,
+        threads: 1,
+        contempt: None,
+        skill_level: None,
+        multi_pv: Some(1),
+        use_tablebases: None,
+        chess960: None,
+    });
+    eng.position(FEN, &[]);
+    eng
+}
+
+#[test]
+fn stop_before_search_starts_cancels_it() {
+    let mut eng = engine();
+    let handle = eng.begin_analyze();
+    handle.stop();
+
+    let start = Instant::now();
+    let best = eng.analyze(SearchLimit::Infinite, |_| {});
+    assert!(start.elapsed() < SLACK, "pre-stopped search ran anyway");
+    // Nothing was searched, but there is still a move to play
+    assert!(eng.is_move_legal(FEN, &best.best), "{}", best.best);
+
+    // The next search has a fresh handle and runs to completion
+    let best = eng.analyze(SearchLimit::Depth { depth: 3 }, |_| {});
+    assert_eq!(best.depth, Some(3));
+}
+
+#[test]
+fn stopped_search_reports_best_move_so_far() {
+    let mut eng = engine();
+    let handle = eng.begin_analyze();
+    let stopper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        handle.stop();
+    });
+
+    let mut infos = Vec::new();
+    let best = eng.analyze(SearchLimit::Depth { depth: 30 }, |info| infos.push(info));
+    stopper.join().unwrap();
+
+    assert!(eng.is_move_legal(FEN, &best.best), "{}", best.best);
+    let (last, completed) = infos.split_last().unwrap();
+    assert!(last.partial);
+    assert!(completed.iter().all(|info| !info.partial));
+    // The partial line repeats the last completed iteration
+    let previous = completed.iter().rfind(|info| info.bound.is_none()).unwrap();
+    assert_eq!(last.depth, previous.depth);
+    assert_eq!(last.pv, previous.pv);
+    assert_eq!(format!("{:?}", last.score), format!("{:?}", previous.score));
+    assert!(last.nodes >= previous.nodes);
+
+    assert_eq!(best.depth, Some(last.depth));
+    assert_eq!(
+        format!("{:?}", best.score),
+        format!("{:?}", Some(&last.score))
+    );
+    assert_eq!(last.pv.first(), Some(&best.best));
+}
+
+#[test]
+fn rapid_analyze_stop_cycles_from_many_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|t| {
+            thread::spawn(move || {
+                let mut eng = engine();
+                for i in 0..20 {
+                    // An infinite search stopped from another thread
+                    let handle = eng.begin_analyze();
+                    let stopper = {
+                        let handle = handle.clone();
+                        let delay = Duration::from_millis(((t + i) % 5) as u64);
+                        thread::spawn(move || {
+                            thread::sleep(delay);
+                            handle.stop();
+                        })
+                    };
+                    let start = Instant::now();
+                    eng.analyze(SearchLimit::Infinite, |_| {});
+                    stopper.join().unwrap();
+                    assert!(start.elapsed() < SLACK, "stopped search overran");
+
+                    // A late stop of the old handle must not touch this search
+                    let late = thread::spawn(move || handle.stop());
+                    let best = eng.analyze(SearchLimit::Depth { depth: 3 }, |_| {});
+                    late.join().unwrap();
+                    assert_eq!(best.depth, Some(3), "fresh search was cancelled");
+                }
+            })
+        })
+        .collect();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn shared_engine_stops_only_the_targeted_search() {
+    // Mirrors the UCI server: one engine behind a mutex, handles created
+    // before the search thread gets the lock, stops sent without locking
+    let eng = Arc::new(Mutex::new(engine()));
+
+    let threads: Vec<_> = (0..4)
+        .map(|t| {
+            let eng = Arc::clone(&eng);
+            thread::spawn(move || {
+                for i in 0..10 {
+                    let stop = StopHandle::new();
+                    let cancel = (t + i) % 2 == 0;
+                    if cancel {
+                        stop.stop();
+                    }
+
+                    let mut eng = eng.lock().unwrap();
+                    eng.use_stop_handle(stop.clone());
+                    let start = Instant::now();
+                    let best = if cancel {
+                        eng.analyze(SearchLimit::Infinite, |_| {})
+                    } else {
+                        eng.analyze(SearchLimit::Time { move_time_ms: 150 }, |_| {})
+                    };
+                    drop(eng);
+
+                    let budget = Duration::from_millis(if cancel { 0 } else { 150 });
+                    assert!(start.elapsed() < budget + SLACK, "search overran");
+                    if !cancel {
+                        assert!(
+                            best.depth.unwrap_or(0) >= 2,
+                            "timed search was cancelled early"
+                        );
+                    }
+                    // Stopping after the fact affects nothing that follows
+                    stop.stop();
+                }
+            })
+        })
+        .collect();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+}
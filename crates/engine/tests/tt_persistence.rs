@@ -0,0 +1,46 @@
+//! Saving and loading the transposition table.
+
+use engine::io::parse_fen;
+use engine::search::Searcher;
+
+#[test]
+fn loaded_table_makes_re_search_cheap() {
+    let board = parse_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+
+    let mut searcher = Searcher::with_tt_size(16);
+    let original = searcher.search(&board, 12);
+
+    let mut saved = Vec::new();
+    searcher.save_tt(&mut saved).unwrap();
+
+    // A fresh searcher only knows the position through the loaded table
+    let mut fresh = Searcher::with_tt_size(1);
+    fresh.load_tt(saved.as_slice()).unwrap();
+    let resumed = fresh.search(&board, 12);
+
+    assert_eq!(resumed.depth, 12);
+    assert_eq!(resumed.best_move, original.best_move);
+    assert!(
+        resumed.nodes * 100 < original.nodes,
+        "Re-search should mostly hit the TT: {} vs {} nodes",
+        resumed.nodes,
+        original.nodes
+    );
+}
+
+#[test]
+fn load_rejects_garbage_and_keeps_current_table() {
+    let board = parse_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+    let mut searcher = Searcher::with_tt_size(1);
+    searcher.search(&board, 6);
+
+    assert!(searcher.load_tt(&b"not a hash file"[..]).is_err());
+
+    // The previous table still answers the search
+    let result = searcher.search(&board, 6);
+    assert!(
+        result.nodes < 1000,
+        "Expected TT hits, searched {} nodes",
+        result.nodes
+    );
+}
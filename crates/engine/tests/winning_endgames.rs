@@ -0,0 +1,122 @@
+//! Self-play in basic won endgames: the stronger side must actually deliver
+//! mate instead of shuffling until the 50-move rule.
+
+use engine::{
+    board::Board,
+    io::{parse_fen, ToFen},
+    piece::Color,
+    search::Searcher,
+};
+
+const DEPTH: u32 = 8;
+const PLACEMENTS: usize = 20;
+
+/// SplitMix64, so the placements are the same on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31)) % n
+    }
+}
+
+/// A random legal position with White's king and `piece` against the bare
+/// black king, White to move.
+fn random_position(rng: &mut Rng, piece: char) -> Board {
+    loop {
+        let mut squares = [None; 64];
+        let wk = rng.below(64) as usize;
+        let bk = rng.below(64) as usize;
+        let extra = rng.below(64) as usize;
+        if wk == bk || extra == wk || extra == bk {
+            continue;
+        }
+        squares[wk] = Some('K');
+        squares[bk] = Some('k');
+        squares[extra] = Some(piece);
+
+        let ranks: Vec<String> = (0..8)
+            .rev()
+            .map(|rank| {
+                let mut row = String::new();
+                let mut empty = 0;
+                for file in 0..8 {
+                    match squares[rank * 8 + file] {
+                        Some(c) => {
+                            if empty > 0 {
+                                row.push_str(&empty.to_string());
+                                empty = 0;
+                            }
+                            row.push(c);
+                        }
+                        None => empty += 1,
+                    }
+                }
+                if empty > 0 {
+                    row.push_str(&empty.to_string());
+                }
+                row
+            })
+            .collect();
+        let fen = format!("{} w - - 0 1", ranks.join("/"));
+
+        // Kings apart and Black not already in check
+        let Ok(board) = parse_fen(&fen) else { continue };
+        let mut black_to_move = board.clone();
+        black_to_move.set_side_to_move(Color::Black);
+        let kings_adjacent = (wk % 8).abs_diff(bk % 8) <= 1 && (wk / 8).abs_diff(bk / 8) <= 1;
+        if !kings_adjacent && !black_to_move.is_in_check() {
+            return board;
+        }
+    }
+}
+
+/// Plays the position out against itself, telling the searcher about the
+/// positions played so far; returns the number of moves White needed to
+/// mate, or `None` on a draw or after 100 moves.
+fn moves_to_mate(searcher: &mut Searcher, mut board: Board) -> Option<u32> {
+    let mut history = Vec::new();
+    for ply in 0..200u32 {
+        let moves = board.generate_legal_moves();
+        if moves.is_empty() {
+            return board.is_in_check().then_some(ply.div_ceil(2));
+        }
+        if board.halfmove_clock() >= 100 || board.occupied().count() < 3 {
+            return None;
+        }
+        searcher.set_game_history(history.clone());
+        let result = searcher.search(&board, DEPTH);
+        history.push(board.hash());
+        board.make_move(result.best_move);
+    }
+    None
+}
+
+fn self_play(piece: char, max_moves: u32) {
+    let mut rng = Rng(piece as u64);
+    let mut searcher = Searcher::with_tt_size(16);
+    searcher.set_use_book(false);
+    for _ in 0..PLACEMENTS {
+        let board = random_position(&mut rng, piece);
+        let fen = board.to_fen();
+        searcher.reset_for_new_game();
+        match moves_to_mate(&mut searcher, board) {
+            Some(moves) => assert!(moves <= max_moves, "{}: mate took {} moves", fen, moves),
+            None => panic!("{}: no mate", fen),
+        }
+    }
+}
+
+#[test]
+fn queen_mates_bare_king() {
+    self_play('Q', 35);
+}
+
+#[test]
+fn rook_mates_bare_king() {
+    self_play('R', 35);
+}
@@ -10,6 +10,7 @@ export const SearchLimit = z.union([
   z.object({ kind: z.literal('nodes'), nodes: z.number().int().min(1) }),
   z.object({ kind: z.literal('time'), moveTimeMs: z.number().int().min(1) }),
   z.object({ kind: z.literal('infinite') }),
+  z.object({ kind: z.literal('mate'), moves: z.number().int().min(1) }),
 ]);
 
 export const EngineOptions = z.object({
@@ -19,6 +20,7 @@ export const EngineOptions = z.object({
   skillLevel: z.number().int().min(0).max(20).optional(),
   multiPV: z.number().int().min(1).optional(),
   useTablebases: z.boolean().optional(),
+  chess960: z.boolean().optional(),
 });
 
 export const AnalyzeRequest = z.object({
@@ -26,6 +28,8 @@ export const AnalyzeRequest = z.object({
   fen: z.string(),
   moves: z.array(z.string()).optional(),
   limit: SearchLimit,
+  searchMoves: z.array(z.string()).optional(),
+  excludeMoves: z.array(z.string()).optional(),
   options: EngineOptions.partial().optional(),
   context: z.object({ allowPonder: z.boolean().optional() }).optional(),
 });
@@ -41,12 +45,34 @@ export const SearchInfo = z.object({
   pv: z.array(z.string()),
   hashfull: z.number().int().min(0).max(1000).optional(),
   tbHits: z.number().int().min(0).optional(),
+  multiPV: z.number().int().min(1).optional(),
+  bound: z.enum(['lower', 'upper']).optional(),
+  scorePov: z.enum(['sideToMove', 'white']).optional(),
+  wdl: z
+    .tuple([
+      z.number().int().min(0).max(1000),
+      z.number().int().min(0).max(1000),
+      z.number().int().min(0).max(1000),
+    ])
+    .optional(),
+  partial: z.boolean().optional(),
+  pvTerminated: z.enum(['repetition', 'ttMiss', 'maxLen', 'illegalTtMove']).optional(),
 });
 
 export const BestMove = z.object({
   id: z.string(),
   best: z.string(),
   ponder: z.string().optional(),
+  depth: z.number().int().min(0).optional(),
+  seldepth: z.number().int().min(0).optional(),
+  nodes: z.number().int().min(0).optional(),
+  timeMs: z.number().int().min(0).optional(),
+  nps: z.number().int().min(0).optional(),
+  score: Score.optional(),
+  pv: z.array(z.string()).optional(),
+  hashfull: z.number().int().min(0).max(1000).optional(),
+  tbHits: z.number().int().min(0).optional(),
+  error: z.string().optional(),
 });
 
 export const EngineEvent = z.union([
@@ -6,7 +6,8 @@ export type SearchLimit =
   | { kind: 'depth'; depth: number }
   | { kind: 'nodes'; nodes: number }
   | { kind: 'time'; moveTimeMs: number }
-  | { kind: 'infinite' };
+  | { kind: 'infinite' }
+  | { kind: 'mate'; moves: number };
 
 export type Score = { kind: 'cp'; value: number } | { kind: 'mate'; plies: number };
 
@@ -17,6 +18,8 @@ export interface EngineOptions {
   skillLevel?: number;
   multiPV?: number;
   useTablebases?: boolean;
+  /** Castling as the king taking its own rook (`e1h1`) in best moves and PVs */
+  chess960?: boolean;
 }
 
 export interface AnalyzeRequest {
@@ -24,6 +27,9 @@ export interface AnalyzeRequest {
   fen: string;
   moves?: UciMove[];
   limit: SearchLimit;
+  searchMoves?: UciMove[];
+  /** Root moves the engine must not play, e.g. to vary rematches */
+  excludeMoves?: UciMove[];
   options?: Partial<EngineOptions>;
   context?: { allowPonder?: boolean };
 }
@@ -39,12 +45,37 @@ export interface SearchInfo {
   pv: UciMove[];
   hashfull?: number;
   tbHits?: number;
+  multiPV?: number;
+  /** Set when an aspiration search failed: the score is only a bound */
+  bound?: 'lower' | 'upper';
+  /** Point of view of `score` and `wdl`; absent means the side to move */
+  scorePov?: 'sideToMove' | 'white';
+  /** Win/draw/loss estimate in per mille, when enabled */
+  wdl?: [number, number, number];
+  /**
+   * Set on the last info of a stopped search: depth, score and pv are those
+   * of the last completed iteration, nodes and time are up to date
+   */
+  partial?: boolean;
+  /** Why the pv ends, for debugging short lines */
+  pvTerminated?: 'repetition' | 'ttMiss' | 'maxLen' | 'illegalTtMove';
 }
 
 export interface BestMove {
   id: string;
   best: UciMove;
   ponder?: UciMove;
+  depth?: number;
+  seldepth?: number;
+  nodes?: number;
+  timeMs?: number;
+  nps?: number;
+  score?: Score;
+  pv?: UciMove[];
+  hashfull?: number;
+  tbHits?: number;
+  /** Why no search was run (invalid FEN, unsearchable position); `best` is then "0000" */
+  error?: string;
 }
 
 export type EngineEvent =
@@ -46,6 +46,11 @@ describe('Protocol Schema Validation', () => {
       expect(Schema.SearchLimit.safeParse(valid).success).toBe(true);
     });
 
+    it('validates mate limit', () => {
+      const valid: Types.SearchLimit = { kind: 'mate', moves: 3 };
+      expect(Schema.SearchLimit.safeParse(valid).success).toBe(true);
+    });
+
     it('rejects depth < 1', () => {
       const invalid = { kind: 'depth', depth: 0 };
       expect(Schema.SearchLimit.safeParse(invalid).success).toBe(false);
@@ -66,6 +71,7 @@ describe('Protocol Schema Validation', () => {
         skillLevel: 15,
         multiPV: 3,
         useTablebases: true,
+        chess960: true,
       };
       expect(Schema.EngineOptions.safeParse(valid).success).toBe(true);
     });
@@ -118,6 +124,31 @@ describe('Protocol Schema Validation', () => {
       expect(Schema.AnalyzeRequest.safeParse(valid).success).toBe(true);
     });
 
+    it('validates request with root move filters', () => {
+      const valid: Types.AnalyzeRequest = {
+        id: 'test-789',
+        fen: 'startpos',
+        limit: { kind: 'depth', depth: 8 },
+        searchMoves: ['e2e4', 'd2d4', 'c2c4'],
+        excludeMoves: ['e2e4'],
+      };
+      const result = Schema.AnalyzeRequest.safeParse(valid);
+      expect(result.success).toBe(true);
+      if (result.success) {
+        expect(result.data.excludeMoves).toEqual(['e2e4']);
+      }
+    });
+
+    it('rejects non-array excludeMoves', () => {
+      const invalid = {
+        id: 'test',
+        fen: 'startpos',
+        limit: { kind: 'depth', depth: 5 },
+        excludeMoves: 'e2e4',
+      };
+      expect(Schema.AnalyzeRequest.safeParse(invalid).success).toBe(false);
+    });
+
     it('rejects request missing id', () => {
       const invalid = {
         fen: 'startpos',
@@ -192,6 +223,22 @@ describe('Protocol Schema Validation', () => {
       };
       expect(Schema.SearchInfo.safeParse(invalid).success).toBe(false);
     });
+
+    it('validates white-relative score with wdl', () => {
+      const valid: Types.SearchInfo = {
+        id: 'search-5',
+        depth: 9,
+        nodes: 120000,
+        nps: 800000,
+        timeMs: 150,
+        score: { kind: 'cp', value: -130 },
+        pv: ['g8f6'],
+        scorePov: 'white',
+        wdl: [20, 380, 600],
+      };
+      expect(Schema.SearchInfo.safeParse(valid).success).toBe(true);
+      expect(Schema.SearchInfo.safeParse({ ...valid, wdl: [20, 380] }).success).toBe(false);
+    });
   });
 
   describe('BestMove', () => {
@@ -212,6 +259,15 @@ describe('Protocol Schema Validation', () => {
       expect(Schema.BestMove.safeParse(valid).success).toBe(true);
     });
 
+    it('validates best move with error', () => {
+      const valid: Types.BestMove = {
+        id: 'move-4',
+        best: '0000',
+        error: 'Invalid FEN string',
+      };
+      expect(Schema.BestMove.safeParse(valid).success).toBe(true);
+    });
+
     it('rejects missing best field', () => {
       const invalid = {
         id: 'move-3',
@@ -1,34 +1,315 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Path, State,
     },
+    http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
 use engine::{
-    types::{BestMove, EngineOptions, Score, SearchInfo},
+    adjudication::{Adjudication, AdjudicationOptions, Adjudicator},
+    analysis::MoveAssessment,
+    search::StopHandle,
+    types::{BatchEvaluation, BestMove, EngineEvent, EngineOptions, RootMoveFilter, SearchLimit},
+    version::EngineAbout,
     EngineImpl,
 };
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 use uuid::Uuid;
 
+/// Idle engines kept around for reuse by later requests.
+const MAX_IDLE_ENGINES: usize = 4;
+
+/// How often sessions are checked for the idle timeout.
+const REAP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long shutdown waits for stopped analyses to finish.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// How long `/stop` waits for the stopped analysis to report its best move.
+const STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Server-side caps on analyses, applied whatever the client asks for.
+///
+/// Each can be overridden with an environment variable, see
+/// [`Limits::from_env`].
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    /// Analyses running at once; more are rejected with 429
+    max_concurrent: usize,
+    /// Longest any single analysis may search
+    max_time: Duration,
+    /// Most nodes any single analysis may search
+    max_nodes: u64,
+    /// Sessions untouched by any client for this long are stopped and removed
+    idle_timeout: Duration,
+    /// Most positions one /analyze-batch request may list
+    max_batch_positions: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 8,
+            max_time: Duration::from_secs(30),
+            max_nodes: 50_000_000,
+            idle_timeout: Duration::from_secs(60),
+            max_batch_positions: 256,
+        }
+    }
+}
+
+impl Limits {
+    /// Defaults, overridden by `ENGINE_SERVER_MAX_ANALYSES`,
+    /// `ENGINE_SERVER_MAX_TIME_MS`, `ENGINE_SERVER_MAX_NODES`,
+    /// `ENGINE_SERVER_IDLE_TIMEOUT_MS` and `ENGINE_SERVER_MAX_BATCH` where
+    /// set.
+    fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok()?.parse().ok()
+        }
+        let default = Self::default();
+        Self {
+            max_concurrent: var("ENGINE_SERVER_MAX_ANALYSES").unwrap_or(default.max_concurrent),
+            max_time: var("ENGINE_SERVER_MAX_TIME_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.max_time),
+            max_nodes: var("ENGINE_SERVER_MAX_NODES").unwrap_or(default.max_nodes),
+            idle_timeout: var("ENGINE_SERVER_IDLE_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.idle_timeout),
+            max_batch_positions: var("ENGINE_SERVER_MAX_BATCH")
+                .unwrap_or(default.max_batch_positions),
+        }
+    }
+
+    /// Time and node budget for a search with the client's `limit`, capped
+    /// by the server ceilings.
+    fn budget(&self, limit: Option<&SearchLimit>) -> (Duration, u64) {
+        match limit {
+            Some(SearchLimit::Time { move_time_ms }) => (
+                Duration::from_millis(*move_time_ms).min(self.max_time),
+                self.max_nodes,
+            ),
+            Some(SearchLimit::Nodes { nodes }) => (self.max_time, (*nodes).min(self.max_nodes)),
+            _ => (self.max_time, self.max_nodes),
+        }
+    }
+}
+
+/// A running analysis: its output stream and the handle that cancels it.
+struct Session {
+    tx: broadcast::Sender<String>,
+    stop: StopHandle,
+    /// Last time a client started or subscribed to the session
+    last_active: Instant,
+    /// The final result, once the analysis has sent it
+    best_move: Option<BestMove>,
+}
+
+/// A game against the engine: the client plays its moves and asks the
+/// engine for each of the engine's.
+struct Game {
+    /// Decides when the engine resigns or agrees a draw; `None` never does
+    adjudicator: Option<Adjudicator>,
+    /// Last time the client asked for a move
+    last_active: Instant,
+}
+
 #[derive(Clone)]
 struct AppState {
-    sessions: Arc<Mutex<HashMap<String, broadcast::Sender<String>>>>,
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+    games: Arc<Mutex<HashMap<String, Game>>>,
+    /// Engines whose tables are already allocated; reset between requests
+    engines: Arc<Mutex<Vec<EngineImpl>>>,
+    limits: Limits,
+    /// One permit per running analysis, `limits.max_concurrent` in total
+    permits: Arc<Semaphore>,
+}
+
+impl AppState {
+    fn new(limits: Limits) -> Self {
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            games: Arc::new(Mutex::new(HashMap::new())),
+            engines: Arc::new(Mutex::new(Vec::new())),
+            limits,
+            permits: Arc::new(Semaphore::new(limits.max_concurrent)),
+        }
+    }
+
+    /// Take an idle engine, or create one if none is available.
+    fn checkout_engine(&self) -> EngineImpl {
+        self.engines.lock().pop().unwrap_or_else(|| {
+            EngineImpl::new_with(EngineOptions {
+                hash_size_mb: 64,
+                threads: 1,
+                contempt: None,
+                skill_level: None,
+                multi_pv: Some(1),
+                use_tablebases: None,
+                chess960: None,
+            })
+        })
+    }
+
+    /// Reset an engine and keep it for the next request.
+    fn return_engine(&self, mut eng: EngineImpl) {
+        eng.new_game();
+        let mut engines = self.engines.lock();
+        if engines.len() < MAX_IDLE_ENGINES {
+            engines.push(eng);
+        }
+    }
+
+    /// Take one of the `limits.max_concurrent` analysis slots, held until the
+    /// returned permit is dropped.
+    fn acquire_permit(&self) -> Result<OwnedSemaphorePermit, (StatusCode, String)> {
+        self.permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|e| match e {
+                TryAcquireError::NoPermits => (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    format!(
+                        "at most {} analyses may run at once",
+                        self.limits.max_concurrent
+                    ),
+                ),
+                TryAcquireError::Closed => (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "server is shutting down".to_string(),
+                ),
+            })
+    }
+
+    /// The client's `limit` for one search, with the time and node ceilings
+    /// applied. An infinite search becomes a search of `limits.max_nodes`;
+    /// depth and mate searches are left to the time ceiling of
+    /// [`Self::run_search`].
+    fn capped_limit(&self, limit: SearchLimit) -> SearchLimit {
+        let (max_time, max_nodes) = self.limits.budget(Some(&limit));
+        match limit {
+            SearchLimit::Time { .. } => SearchLimit::Time {
+                move_time_ms: max_time.as_millis() as u64,
+            },
+            SearchLimit::Nodes { .. } | SearchLimit::Infinite => {
+                SearchLimit::Nodes { nodes: max_nodes }
+            }
+            limit => limit,
+        }
+    }
+
+    /// Run `search` on a pooled engine, off the async workers, holding
+    /// `permit` until it is done. The engine's next search is stopped once
+    /// `limits.max_time` has passed, so the whole request, however many
+    /// searches it makes, stays within the time ceiling.
+    async fn run_search<T: Send + 'static>(
+        &self,
+        permit: OwnedSemaphorePermit,
+        search: impl FnOnce(&mut EngineImpl) -> T + Send + 'static,
+    ) -> Result<T, (StatusCode, String)> {
+        self.run_search_until(permit, StopHandle::new(), search)
+            .await
+    }
+
+    /// [`Self::run_search`], stopped early by `stop` as well as by the time
+    /// ceiling.
+    async fn run_search_until<T: Send + 'static>(
+        &self,
+        permit: OwnedSemaphorePermit,
+        stop: StopHandle,
+        search: impl FnOnce(&mut EngineImpl) -> T + Send + 'static,
+    ) -> Result<T, (StatusCode, String)> {
+        let deadline = {
+            let stop = stop.clone();
+            let max_time = self.limits.max_time;
+            tokio::spawn(async move {
+                tokio::time::sleep(max_time).await;
+                stop.stop();
+            })
+        };
+        let state = self.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let mut eng = state.checkout_engine();
+            eng.use_stop_handle(stop);
+            let result = search(&mut eng);
+            state.return_engine(eng);
+            result
+        })
+        .await;
+        deadline.abort();
+        result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+    }
+
+    /// Remove the session `id` once its analysis is over, unless a newer
+    /// analysis has taken over the id.
+    fn finish_session(&self, id: &str, stop: &StopHandle) {
+        let mut sessions = self.sessions.lock();
+        if sessions.get(id).is_some_and(|s| s.stop.same_as(stop)) {
+            sessions.remove(id);
+        }
+    }
+
+    /// Stop and remove sessions no client has touched within the idle
+    /// timeout, and forget games abandoned for as long.
+    fn reap_idle_sessions(&self) {
+        let timeout = self.limits.idle_timeout;
+        self.sessions.lock().retain(|_, session| {
+            let idle = session.last_active.elapsed() >= timeout;
+            if idle {
+                session.stop.stop();
+            }
+            !idle
+        });
+        self.games
+            .lock()
+            .retain(|_, game| game.last_active.elapsed() < timeout);
+    }
+
+    /// Stop every running analysis and wait (up to [`SHUTDOWN_GRACE`]) for
+    /// them to finish. Returns whether they all did.
+    async fn shutdown(&self) -> bool {
+        // Closing the semaphore rejects analyses started from now on
+        self.permits.close();
+        for (_, session) in self.sessions.lock().drain() {
+            session.stop.stop();
+        }
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        // Each running analysis holds a permit until its task ends
+        while self.permits.available_permits() < self.limits.max_concurrent {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        true
+    }
 }
 
 #[derive(Deserialize)]
 struct AnalyzeRequestBody {
     id: Option<String>,
     fen: String,
-    #[allow(dead_code)]
+    /// UCI moves played from `fen`
+    #[serde(default)]
+    moves: Vec<String>,
+    /// A [`SearchLimit`]; anything else searches up to the server ceilings
     limit: Option<serde_json::Value>,
+    /// UCI root moves the engine must not play, e.g. to vary rematches
+    #[serde(default, rename = "excludeMoves")]
+    exclude_moves: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -36,80 +317,302 @@ struct AnalyzeResponse {
     id: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AnalyzeGameBody {
+    fen: String,
+    moves: Vec<String>,
+    limit_per_move: SearchLimit,
+}
+
+#[derive(Deserialize)]
+struct AnalyzeBatchBody {
+    fens: Vec<String>,
+    limit: SearchLimit,
+}
+
+#[derive(Deserialize)]
+struct NewGameBody {
+    /// Resign and draw thresholds; without them the engine plays every game
+    /// out
+    #[serde(default)]
+    adjudication: Option<AdjudicationOptions>,
+}
+
+#[derive(Serialize)]
+struct NewGameResponse {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct GameMoveBody {
+    fen: String,
+    /// UCI moves played from `fen`, the engine to move after them
+    #[serde(default)]
+    moves: Vec<String>,
+    limit: SearchLimit,
+}
+
+#[derive(Serialize)]
+struct GameMoveResponse {
+    #[serde(flatten)]
+    best_move: BestMove,
+    /// Set when the engine resigns or agrees a draw instead of playing on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjudication: Option<Adjudication>,
+}
+
 #[tokio::main]
 async fn main() {
-    let state = AppState {
-        sessions: Arc::new(Mutex::new(HashMap::new())),
-    };
+    let state = AppState::new(Limits::from_env());
     let app = Router::new()
         .route("/health", get(|| async { "ok" }))
+        .route("/about", get(about))
         .route("/analyze", post(start_analyze))
         .route("/stop", post(stop_analyze))
+        .route("/analyze-game", post(analyze_game))
+        .route("/analyze-batch", post(analyze_batch))
+        .route("/games", post(new_game))
+        .route("/games/:id/move", post(game_move))
         .route("/streams/:id", get(ws_stream))
-        .with_state(state);
+        .with_state(state.clone());
+
+    let reaper = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REAP_INTERVAL);
+        loop {
+            interval.tick().await;
+            reaper.reap_idle_sessions();
+        }
+    });
+
     let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8080));
     println!("engine-server listening on http://{addr}");
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
+
+    if !state.shutdown().await {
+        eprintln!("engine-server: analyses still running after {SHUTDOWN_GRACE:?}");
+    }
+}
+
+/// Resolve on Ctrl-C or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
 
 async fn start_analyze(
     State(state): State<AppState>,
     Json(body): Json<AnalyzeRequestBody>,
-) -> impl IntoResponse {
+) -> Result<Json<AnalyzeResponse>, (StatusCode, String)> {
+    // Reject bad positions, and exclusions that leave nothing to play,
+    // before taking a slot
+    let filter = RootMoveFilter {
+        exclude_moves: body.exclude_moves.clone(),
+        ..Default::default()
+    };
+    engine::io::setup_position(&body.fen, &body.moves)
+        .map_err(|e| e.to_string())
+        .and_then(|board| EngineImpl::filter_root_moves(&board, &filter))
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let permit = state.acquire_permit()?;
+
     let id = body.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let limit: Option<SearchLimit> = body.limit.and_then(|v| serde_json::from_value(v).ok());
+    let limit = state.capped_limit(limit.unwrap_or(SearchLimit::Infinite));
     let (tx, _rx) = broadcast::channel::<String>(16);
-    state.sessions.lock().insert(id.clone(), tx.clone());
+    let stop = StopHandle::new();
+    let session = Session {
+        tx: tx.clone(),
+        stop: stop.clone(),
+        last_active: Instant::now(),
+        best_move: None,
+    };
+    // A new analysis under a reused id replaces (and cancels) the old one
+    if let Some(old) = state.sessions.lock().insert(id.clone(), session) {
+        old.stop.stop();
+    }
 
-    // Spawn a task that simulates iterative deepening and sends SearchInfo JSON lines
+    // Run the search in the background, streaming SearchInfo JSON lines;
+    // /stop and the time ceiling both stop it through the session's handle
     let id_for_task = id.clone();
     tokio::spawn(async move {
-        let mut eng = EngineImpl::new_with(EngineOptions {
-            hash_size_mb: 64,
-            threads: 1,
-            contempt: None,
-            skill_level: None,
-            multi_pv: Some(1),
-            use_tablebases: None,
-        });
-        eng.position(&body.fen, &[]);
-        for depth in 1..=6u32 {
-            let info = SearchInfo {
-                id: id_for_task.clone(),
-                depth,
-                seldepth: Some(depth + 1),
-                nodes: depth as u64 * 10_000,
-                nps: 1_000_000,
-                time_ms: depth as u64 * 80,
-                score: Score::Cp {
-                    value: depth as i32 * 12,
-                },
-                pv: vec!["e2e4".into(), "e7e5".into()],
-                hashfull: Some(20 * depth),
-                tb_hits: None,
-            };
-            let line = serde_json::to_string(&serde_json::json!({
-                "type": "searchInfo",
-                "payload": info
-            }))
-            .unwrap();
-            let _ = tx.send(line);
-            tokio::time::sleep(Duration::from_millis(150)).await;
-        }
-        let best = BestMove {
-            id: id_for_task.clone(),
-            best: "e2e4".into(),
-            ponder: Some("e7e5".into()),
+        let search_id = id_for_task.clone();
+        let search_tx = tx.clone();
+        let result = state
+            .run_search_until(permit, stop.clone(), move |eng| {
+                eng.position(&body.fen, &body.moves);
+                eng.analyze_with_filter(limit, &filter, |mut info| {
+                    info.id = search_id.clone();
+                    let line = serde_json::to_string(&serde_json::json!({
+                        "type": "searchInfo",
+                        "payload": info
+                    }))
+                    .unwrap();
+                    let _ = search_tx.send(line);
+                })
+            })
+            .await;
+        let Ok(mut best) = result else {
+            state.finish_session(&id_for_task, &stop);
+            return;
         };
+        best.id = id_for_task.clone();
         let line = serde_json::to_string(&serde_json::json!({
             "type": "bestMove",
             "payload": best
         }))
         .unwrap();
+        // Stored under the lock, so a /stop either finds it or is
+        // subscribed in time to receive the line
+        if let Some(session) = state
+            .sessions
+            .lock()
+            .get_mut(&id_for_task)
+            .filter(|s| s.stop.same_as(&stop))
+        {
+            session.best_move = Some(best);
+        }
         let _ = tx.send(line);
+        state.finish_session(&id_for_task, &stop);
     });
 
-    Json(AnalyzeResponse { id })
+    Ok(Json(AnalyzeResponse { id }))
+}
+
+async fn about() -> Result<Json<EngineAbout>, (StatusCode, String)> {
+    // The first call runs the bench searches for the signature
+    tokio::task::spawn_blocking(EngineAbout::current)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Classify every move of a game. Each move's search is capped by the
+/// server ceilings, and the analysis as a whole stops at the time ceiling,
+/// returning the moves assessed by then.
+async fn analyze_game(
+    State(state): State<AppState>,
+    Json(body): Json<AnalyzeGameBody>,
+) -> Result<Json<Vec<MoveAssessment>>, (StatusCode, String)> {
+    let permit = state.acquire_permit()?;
+    let limit = state.capped_limit(body.limit_per_move);
+    state
+        .run_search(permit, move |eng| {
+            eng.analyze_game(&body.fen, &body.moves, limit, |_, _| {})
+        })
+        .await?
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+/// Analyze each of the listed positions, at most
+/// `limits.max_batch_positions` of them, on one engine.
+///
+/// Takes one analysis slot for the whole batch; each position's limit is
+/// capped like any other search, and the batch as a whole stops at the
+/// time ceiling, returning the positions finished by then.
+async fn analyze_batch(
+    State(state): State<AppState>,
+    Json(body): Json<AnalyzeBatchBody>,
+) -> Result<Json<Vec<BatchEvaluation>>, (StatusCode, String)> {
+    if body.fens.len() > state.limits.max_batch_positions {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "{} positions, at most {} allowed",
+                body.fens.len(),
+                state.limits.max_batch_positions
+            ),
+        ));
+    }
+    let permit = state.acquire_permit()?;
+    let limit = state.capped_limit(body.limit);
+    state
+        .run_search(permit, move |eng| {
+            let fens: Vec<&str> = body.fens.iter().map(String::as_str).collect();
+            eng.analyze_batch(&fens, limit, |_, _| {})
+        })
+        .await
+        .map(Json)
+}
+
+async fn new_game(
+    State(state): State<AppState>,
+    Json(body): Json<NewGameBody>,
+) -> Json<NewGameResponse> {
+    let id = Uuid::new_v4().to_string();
+    let game = Game {
+        adjudicator: body.adjudication.map(Adjudicator::new),
+        last_active: Instant::now(),
+    };
+    state.games.lock().insert(id.clone(), game);
+    Json(NewGameResponse { id })
+}
+
+/// Search the engine's move in game `id`, and let the game's adjudicator
+/// decide whether the engine resigns or agrees a draw instead.
+///
+/// The adjudicator counts one score per request, so each engine move should
+/// be asked for once. The search takes an analysis slot and stays within the
+/// server's time and node ceilings.
+async fn game_move(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<GameMoveBody>,
+) -> Result<Json<GameMoveResponse>, (StatusCode, String)> {
+    let not_found = || (StatusCode::NOT_FOUND, format!("no game {id}"));
+    let board = engine::io::setup_position(&body.fen, &body.moves)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    if !state.games.lock().contains_key(&id) {
+        return Err(not_found());
+    }
+
+    let permit = state.acquire_permit()?;
+    let limit = state.capped_limit(body.limit);
+    let best_move = state
+        .run_search(permit, move |eng| {
+            eng.position(&body.fen, &body.moves);
+            eng.analyze(limit, |_| {})
+        })
+        .await?;
+
+    // The game may have been reaped while the engine was thinking
+    let mut games = state.games.lock();
+    let game = games.get_mut(&id).ok_or_else(not_found)?;
+    game.last_active = Instant::now();
+    let adjudication = match (game.adjudicator.as_mut(), &best_move.score) {
+        (Some(adjudicator), Some(score)) => {
+            adjudicator.update_score(score, board.fullmove_number())
+        }
+        _ => None,
+    };
+    Ok(Json(GameMoveResponse {
+        best_move,
+        adjudication,
+    }))
 }
 
 #[derive(Deserialize)]
@@ -117,23 +620,48 @@ struct StopBody {
     id: String,
 }
 
+/// Stop an analysis and return its best move so far, which stream
+/// subscribers also receive as the usual final `bestMove` message. An
+/// analysis that already finished returns its final best move.
 async fn stop_analyze(
     State(state): State<AppState>,
     Json(body): Json<StopBody>,
-) -> impl IntoResponse {
-    let mut sessions = state.sessions.lock();
-    if let Some(tx) = sessions.remove(&body.id) {
-        let line = serde_json::to_string(&serde_json::json!({
-            "type": "error",
-            "payload": {
-                "id": body.id,
-                "message": "stopped"
+) -> Result<Json<BestMove>, (StatusCode, String)> {
+    let (mut rx, stop) = {
+        let sessions = state.sessions.lock();
+        let session = sessions.get(&body.id).ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("no running analysis {}", body.id),
+            )
+        })?;
+        if let Some(best) = &session.best_move {
+            return Ok(Json(best.clone()));
+        }
+        (session.tx.subscribe(), session.stop.clone())
+    };
+    stop.stop();
+
+    let best_move = async {
+        loop {
+            match rx.recv().await {
+                Ok(line) => {
+                    if let Ok(EngineEvent::BestMove { payload }) = serde_json::from_str(&line) {
+                        return Some(payload);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
             }
-        }))
-        .unwrap();
-        let _ = tx.send(line);
+        }
+    };
+    match tokio::time::timeout(STOP_TIMEOUT, best_move).await {
+        Ok(Some(best)) => Ok(Json(best)),
+        _ => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "analysis ended without a best move".to_string(),
+        )),
     }
-    "ok"
 }
 
 async fn ws_stream(
@@ -146,8 +674,11 @@ async fn ws_stream(
 
 async fn handle_ws(state: AppState, id: String, mut socket: WebSocket) {
     let rx = {
-        let sessions = state.sessions.lock();
-        sessions.get(&id).map(|tx| tx.subscribe())
+        let mut sessions = state.sessions.lock();
+        sessions.get_mut(&id).map(|session| {
+            session.last_active = Instant::now();
+            session.tx.subscribe()
+        })
     };
 
     let mut rx = match rx {
@@ -169,3 +700,412 @@ async fn handle_ws(state: AppState, id: String, mut socket: WebSocket) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A middlegame out of the opening book, so analyses really search
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    /// Start an analysis of [`KIWIPETE`], returning its id.
+    async fn analyze(state: &AppState, limit: serde_json::Value) -> Result<String, StatusCode> {
+        let body = AnalyzeRequestBody {
+            id: None,
+            fen: KIWIPETE.to_string(),
+            moves: Vec::new(),
+            limit: Some(limit),
+            exclude_moves: Vec::new(),
+        };
+        start_analyze(State(state.clone()), Json(body))
+            .await
+            .map(|Json(response)| response.id)
+            .map_err(|(status, _)| status)
+    }
+
+    fn limits(max_concurrent: usize) -> Limits {
+        Limits {
+            max_concurrent,
+            ..Limits::default()
+        }
+    }
+
+    /// Poll until `done` holds, for at most `timeout`.
+    async fn wait_for(timeout: Duration, done: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + timeout;
+        while !done() {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        true
+    }
+
+    #[test]
+    fn budget_caps_client_limits() {
+        let limits = Limits {
+            max_time: Duration::from_secs(10),
+            max_nodes: 1_000,
+            ..Limits::default()
+        };
+        let time = |ms| SearchLimit::Time { move_time_ms: ms };
+        assert_eq!(
+            limits.budget(Some(&time(500))),
+            (Duration::from_millis(500), 1_000)
+        );
+        assert_eq!(
+            limits.budget(Some(&time(60_000))),
+            (Duration::from_secs(10), 1_000)
+        );
+        assert_eq!(
+            limits.budget(Some(&SearchLimit::Nodes { nodes: 10 })),
+            (Duration::from_secs(10), 10)
+        );
+        assert_eq!(
+            limits.budget(Some(&SearchLimit::Infinite)),
+            (Duration::from_secs(10), 1_000)
+        );
+        assert_eq!(limits.budget(None), (Duration::from_secs(10), 1_000));
+    }
+
+    #[tokio::test]
+    async fn rapid_analyze_requests_are_capped_and_cleaned_up() {
+        let state = AppState::new(Limits {
+            max_time: Duration::from_millis(500),
+            max_nodes: 100_000,
+            ..limits(4)
+        });
+        let mut accepted = 0;
+        let mut rejected = 0;
+        for _ in 0..50 {
+            match analyze(&state, serde_json::json!({ "kind": "infinite" })).await {
+                Ok(_) => accepted += 1,
+                Err(status) => {
+                    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+                    rejected += 1;
+                }
+            }
+            assert!(state.sessions.lock().len() <= 4);
+        }
+        assert_eq!((accepted, rejected), (4, 46));
+
+        // Finished analyses give back their slot and remove their session
+        let finished = wait_for(Duration::from_secs(3), || {
+            state.sessions.lock().is_empty() && state.permits.available_permits() == 4
+        })
+        .await;
+        assert!(finished);
+        assert!(state.engines.lock().len() <= MAX_IDLE_ENGINES);
+        assert!(analyze(&state, serde_json::json!(null)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn excluding_every_move_is_rejected() {
+        let state = AppState::new(limits(1));
+        let body = |exclude_moves: &[&str]| AnalyzeRequestBody {
+            id: None,
+            fen: "7k/8/8/8/8/8/8/K7 w - - 0 1".to_string(),
+            moves: Vec::new(),
+            limit: None,
+            exclude_moves: exclude_moves.iter().map(|m| m.to_string()).collect(),
+        };
+
+        let rejected = start_analyze(State(state.clone()), Json(body(&["a1a2", "a1b1", "a1b2"])))
+            .await
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(rejected.0, StatusCode::BAD_REQUEST);
+        assert_eq!(state.permits.available_permits(), 1);
+
+        assert!(
+            start_analyze(State(state.clone()), Json(body(&["a1a2", "a1b1"])))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_returns_best_move_so_far() {
+        let state = AppState::new(limits(1));
+        let id = analyze(&state, serde_json::json!({ "kind": "infinite" }))
+            .await
+            .unwrap();
+        let mut rx = state.sessions.lock()[&id].tx.subscribe();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let Json(best) = stop_analyze(State(state.clone()), Json(StopBody { id: id.clone() }))
+            .await
+            .unwrap();
+        let board = engine::io::parse_fen(KIWIPETE).unwrap();
+        assert!(board
+            .generate_legal_moves()
+            .iter()
+            .any(|m| m.to_uci() == best.best));
+        assert!(best.depth.is_some());
+
+        // The stream ends with the last line repeated as partial, then the
+        // same best move
+        let mut events = Vec::new();
+        while let Ok(line) = rx.recv().await {
+            events.push(serde_json::from_str::<serde_json::Value>(&line).unwrap());
+        }
+        let [.., info, last] = events.as_slice() else {
+            panic!("too few events: {events:?}");
+        };
+        assert_eq!(info["type"], "searchInfo");
+        assert_eq!(info["payload"]["partial"], true);
+        assert_eq!(
+            info["payload"]["score"],
+            serde_json::to_value(&best.score).unwrap()
+        );
+        assert_eq!(last["type"], "bestMove");
+
+        let missing = stop_analyze(State(state.clone()), Json(StopBody { id }))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn stop_after_best_move_returns_it() {
+        let state = AppState::new(limits(1));
+        let (tx, _rx) = broadcast::channel(16);
+        let best = BestMove {
+            id: "done".to_string(),
+            best: "d2d4".to_string(),
+            ..Default::default()
+        };
+        // A finished analysis whose session isn't removed yet
+        let session = Session {
+            tx,
+            stop: StopHandle::new(),
+            last_active: Instant::now(),
+            best_move: Some(best.clone()),
+        };
+        state.sessions.lock().insert("done".to_string(), session);
+
+        let started = Instant::now();
+        let Json(stopped) = stop_analyze(
+            State(state.clone()),
+            Json(StopBody {
+                id: "done".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stopped.best, best.best);
+        assert!(started.elapsed() < STOP_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn idle_sessions_are_stopped_and_removed() {
+        let state = AppState::new(Limits {
+            idle_timeout: Duration::ZERO,
+            ..limits(2)
+        });
+        let id = analyze(&state, serde_json::json!({ "kind": "infinite" }))
+            .await
+            .unwrap();
+        let stop = state.sessions.lock()[&id].stop.clone();
+
+        state.reap_idle_sessions();
+        assert!(state.sessions.lock().is_empty());
+        assert!(stop.is_stopped());
+    }
+
+    #[tokio::test]
+    async fn game_analysis_is_capped() {
+        let state = AppState::new(Limits {
+            max_time: Duration::from_millis(300),
+            ..limits(1)
+        });
+        let body = || AnalyzeGameBody {
+            fen: "startpos".to_string(),
+            moves: [
+                "e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5a4", "g8f6",
+            ]
+            .map(String::from)
+            .to_vec(),
+            limit_per_move: SearchLimit::Depth { depth: 30 },
+        };
+
+        // The time ceiling ends the analysis early, keeping finished moves
+        let started = Instant::now();
+        let Json(assessed) = analyze_game(State(state.clone()), Json(body()))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(assessed.len() < 8);
+        assert_eq!(state.permits.available_permits(), 1);
+
+        // No slot, no analysis
+        let _busy = state.acquire_permit().unwrap();
+        let rejected = analyze_game(State(state.clone()), Json(body()))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.0, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn batch_analysis_is_capped() {
+        let state = AppState::new(Limits {
+            max_time: Duration::from_millis(300),
+            max_batch_positions: 3,
+            ..limits(1)
+        });
+        let body = |count| AnalyzeBatchBody {
+            fens: vec![
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+                    .to_string();
+                count
+            ],
+            limit: SearchLimit::Depth { depth: 30 },
+        };
+
+        // The time ceiling ends the batch early, keeping finished positions
+        let started = Instant::now();
+        let Json(evaluated) = analyze_batch(State(state.clone()), Json(body(3)))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(evaluated.len() < 3);
+        assert_eq!(state.permits.available_permits(), 1);
+
+        let too_many = analyze_batch(State(state.clone()), Json(body(4)))
+            .await
+            .unwrap_err();
+        assert_eq!(too_many.0, StatusCode::PAYLOAD_TOO_LARGE);
+
+        // No slot, no analysis
+        let _busy = state.acquire_permit().unwrap();
+        let rejected = analyze_batch(State(state.clone()), Json(body(1)))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.0, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    /// Ask for the engine's move in game `id` from `fen`.
+    async fn play(
+        state: &AppState,
+        id: &str,
+        fen: &str,
+    ) -> Result<Option<Adjudication>, StatusCode> {
+        let body = GameMoveBody {
+            fen: fen.to_string(),
+            moves: Vec::new(),
+            limit: SearchLimit::Depth { depth: 4 },
+        };
+        game_move(State(state.clone()), Path(id.to_string()), Json(body))
+            .await
+            .map(|Json(response)| response.adjudication)
+            .map_err(|(status, _)| status)
+    }
+
+    #[tokio::test]
+    async fn engine_resigns_only_once_lost_for_long_enough() {
+        // Black is a queen down with nothing to show for it
+        const LOST: &str = "4k3/8/8/8/8/8/8/3QK3 b - - 0 1";
+        const LEVEL: &str = "4k3/4p3/8/8/8/8/4P3/4K3 b - - 0 1";
+        let state = AppState::new(limits(1));
+        let start = |adjudication| {
+            let body = NewGameBody { adjudication };
+            new_game(State(state.clone()), Json(body))
+        };
+
+        let Json(game) = start(Some(AdjudicationOptions {
+            resign_score: 500,
+            resign_moves: 3,
+            ..AdjudicationOptions::default()
+        }))
+        .await;
+        assert_eq!(play(&state, &game.id, LOST).await, Ok(None));
+        assert_eq!(play(&state, &game.id, LOST).await, Ok(None));
+        // Holding on for a move restarts the count
+        assert_eq!(play(&state, &game.id, LEVEL).await, Ok(None));
+        assert_eq!(play(&state, &game.id, LOST).await, Ok(None));
+        assert_eq!(play(&state, &game.id, LOST).await, Ok(None));
+        assert_eq!(
+            play(&state, &game.id, LOST).await,
+            Ok(Some(Adjudication::Resign))
+        );
+
+        // Without adjudication the engine plays on
+        let Json(game) = start(None).await;
+        for _ in 0..5 {
+            assert_eq!(play(&state, &game.id, LOST).await, Ok(None));
+        }
+
+        assert_eq!(
+            play(&state, "no-such-game", LOST).await,
+            Err(StatusCode::NOT_FOUND)
+        );
+    }
+
+    #[tokio::test]
+    async fn game_moves_are_capped() {
+        let state = AppState::new(Limits {
+            max_time: Duration::from_millis(300),
+            ..limits(1)
+        });
+        let Json(game) = new_game(
+            State(state.clone()),
+            Json(NewGameBody { adjudication: None }),
+        )
+        .await;
+        let body = || GameMoveBody {
+            fen: "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1".to_string(),
+            moves: Vec::new(),
+            limit: SearchLimit::Depth { depth: 30 },
+        };
+        let play = || game_move(State(state.clone()), Path(game.id.clone()), Json(body()));
+
+        // The time ceiling ends the search, which still returns a move
+        let started = Instant::now();
+        let Json(response) = play().await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(response.best_move.depth.unwrap() < 30);
+        assert_eq!(state.permits.available_permits(), 1);
+
+        // No slot, no search
+        let _busy = state.acquire_permit().unwrap();
+        let rejected = play().await.map(|_| ()).unwrap_err();
+        assert_eq!(rejected.0, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn shutdown_aborts_running_analyses() {
+        let state = AppState::new(limits(4));
+        let mut streams = Vec::new();
+        for _ in 0..4 {
+            let id = analyze(&state, serde_json::json!({ "kind": "infinite" }))
+                .await
+                .unwrap();
+            streams.push(state.sessions.lock()[&id].tx.subscribe());
+        }
+
+        let started = Instant::now();
+        assert!(state.shutdown().await);
+        assert!(started.elapsed() < SHUTDOWN_GRACE);
+        assert!(state.sessions.lock().is_empty());
+
+        // Every analysis ended early, before its last iteration
+        for mut rx in streams {
+            let mut best = None;
+            while let Ok(line) = rx.recv().await {
+                let msg: serde_json::Value = serde_json::from_str(&line).unwrap();
+                if msg["type"] == "bestMove" {
+                    best = Some(msg["payload"].clone());
+                }
+            }
+            let depth = best.unwrap()["depth"].as_u64().unwrap_or(0);
+            assert!(depth < 6, "analysis ran to depth {depth}");
+        }
+
+        // No new analyses once shut down
+        assert_eq!(
+            analyze(&state, serde_json::json!(null)).await,
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
+}